@@ -0,0 +1,12 @@
+//! Converts a text depth report into the packed binary format read by
+//! `d1 --binary`: `pack [--input <path>] > depths.bin`
+
+use std::io;
+
+use d1::*;
+
+fn main() {
+    let stdout = io::stdout();
+    let depths = aoc_core::or_exit(get_depths(aoc_core::input()));
+    write_depths_binary(depths.into_iter(), stdout.lock()).unwrap();
+}
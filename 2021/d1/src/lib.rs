@@ -0,0 +1,112 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use itertools::Itertools;
+
+use aoc_core::{AocError, Solver};
+
+/// A depth per line
+pub fn get_depths<R: BufRead>(reader: R) -> Result<Vec<u32>, AocError> {
+    aoc_input::parse_lines(reader)
+}
+
+/// Depths packed as little-endian u32s, for inputs too big to parse as text.
+/// A trailing partial value is ignored.
+pub fn get_depths_binary<R: Read>(rdr: R) -> impl Iterator<Item = u32> {
+    let mut reader = BufReader::with_capacity(64 * 1024, rdr);
+    std::iter::from_fn(move || {
+        let mut buf = [0u8; 4];
+        match reader.read_exact(&mut buf) {
+            Ok(()) => Some(u32::from_le_bytes(buf)),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => None,
+            Err(e) => panic!("reading depths: {}", e),
+        }
+    })
+}
+
+/// Writes depths in the format read by `get_depths_binary`
+pub fn write_depths_binary<W: Write>(depths: impl Iterator<Item = u32>, w: W) -> io::Result<()> {
+    let mut writer = io::BufWriter::new(w);
+    for d in depths {
+        writer.write_all(&d.to_le_bytes())?;
+    }
+    writer.flush()
+}
+
+/// Number of depths that increase over the previous one.
+pub fn count_increases(depths: impl Iterator<Item = u32>) -> u32 {
+    depths.tuple_windows()
+        .map(|(a, b)| if b > a { 1 } else { 0 })
+        .sum()
+}
+
+/// Sums of each sliding window of three depths.
+pub fn window_sums(depths: impl Iterator<Item = u32>) -> impl Iterator<Item = u32> {
+    depths.tuple_windows::<(_,_,_)>()
+        .map(|(a, b, c)| a + b + c)
+}
+
+/// Both parts' counts in one pass over `reader`, keeping only the last
+/// three depths. Windows sharing two depths only differ by the ones they
+/// don't share, so a window sum goes up when a depth beats the one three
+/// before it.
+pub fn stream<R: BufRead>(reader: R) -> Result<[Option<String>; 2], AocError> {
+    let (mut increases, mut window_increases) = (0u64, 0u64);
+    let mut last = [None; 3];
+    for depth in aoc_input::stream_lines::<u32, _>(reader) {
+        let depth = Some(depth?);
+        increases += (depth > last[2] && last[2].is_some()) as u64;
+        window_increases += (depth > last[0] && last[0].is_some()) as u64;
+        last = [last[1], last[2], depth];
+    }
+    Ok([Some(increases.to_string()), Some(window_increases.to_string())])
+}
+
+pub struct Puzzle {
+    depths: Vec<u32>,
+}
+
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        Ok(Puzzle { depths: get_depths(input.as_bytes())? })
+    }
+
+    fn part1(&self) -> String {
+        count_increases(self.depths.iter().copied()).to_string()
+    }
+
+    fn part2(&self) -> String {
+        count_increases(window_sums(self.depths.iter().copied())).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_round_trip() {
+        let depths = get_depths(include_str!("../test_input").as_bytes()).unwrap();
+        let mut packed = vec!();
+        write_depths_binary(depths.iter().copied(), &mut packed).unwrap();
+        assert_eq!(packed.len(), depths.len() * 4);
+
+        packed.push(0xff);
+        let unpacked: Vec<u32> = get_depths_binary(packed.as_slice()).collect();
+        assert_eq!(unpacked, depths);
+    }
+
+    /// The answers given in the puzzle text for its example
+    #[test]
+    fn example_answers() {
+        let puzzle = Puzzle::parse(include_str!("../test_input"));
+        assert_eq!(puzzle.part1(), "7");
+        assert_eq!(puzzle.part2(), "5");
+    }
+
+    #[test]
+    fn streamed_answers() {
+        let answers = stream(include_str!("../test_input").as_bytes()).unwrap();
+        assert_eq!(answers, [Some("7".to_string()), Some("5".to_string())]);
+        assert_eq!(stream("3\n".as_bytes()).unwrap(), [Some("0".to_string()), Some("0".to_string())]);
+        assert!(stream("3\n-1\n".as_bytes()).is_err());
+    }
+}
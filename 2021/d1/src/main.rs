@@ -0,0 +1,35 @@
+use std::time::Instant;
+
+use aoc_core::InputSource;
+use d1::*;
+
+/// Counts both parts over a packed binary input, timing each pass
+fn bench_binary(input: &InputSource) {
+    let depths: Vec<u32> = get_depths_binary(aoc_core::open_input(input)).collect();
+    println!("{} depths", depths.len());
+
+    let t = Instant::now();
+    let c = count_increases(depths.iter().copied());
+    println!("increases: {} ({:?})", c, t.elapsed());
+
+    let t = Instant::now();
+    let c = count_increases(window_sums(depths.iter().copied()));
+    println!("window increases: {} ({:?})", c, t.elapsed());
+}
+
+fn main() {
+    // --binary reads depths packed by the `pack` tool instead of text
+    let (input, args) = aoc_core::input_arg();
+    if args.iter().any(|a| a == "--binary") {
+        return bench_binary(&input);
+    }
+
+    let depths = aoc_core::or_exit(get_depths(aoc_core::open_input(&input)));
+    /*
+    let c = count_increases(depths.into_iter());
+    */
+    let c = count_increases(window_sums(depths.into_iter())
+        .inspect(|s| println!("{}", s)));
+
+    println!("{}", c);
+}
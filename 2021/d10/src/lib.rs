@@ -0,0 +1,289 @@
+use std::fmt;
+use std::iter::Iterator;
+
+use serde::Serialize;
+
+use aoc_core::{AocError, Solver};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Bracket {
+    Paren,
+    Square,
+    Brace,
+    Angle,
+    Any,
+}
+
+impl Bracket {
+    pub fn score(&self) -> usize {
+        match self {
+            Bracket::Paren => 3,
+            Bracket::Square => 57,
+            Bracket::Brace => 1197,
+            Bracket::Angle => 25137,
+            _ => 0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd)]
+pub enum BracketType {
+    Open,
+    Close,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Token {
+    b: Bracket,
+    t: BracketType,
+}
+
+const TOK_OPEN_PAREN: Token = Token::open(Bracket::Paren);
+const TOK_CLOSE_PAREN: Token = Token::close(Bracket::Paren);
+const TOK_OPEN_SQUARE: Token = Token::open(Bracket::Square);
+const TOK_CLOSE_SQUARE: Token = Token::close(Bracket::Square);
+const TOK_OPEN_BRACE: Token = Token::open(Bracket::Brace);
+const TOK_CLOSE_BRACE: Token = Token::close(Bracket::Brace);
+const TOK_OPEN_ANGLE: Token = Token::open(Bracket::Angle);
+const TOK_CLOSE_ANGLE: Token = Token::close(Bracket::Angle);
+
+impl Token {
+    pub const fn open(b: Bracket) -> Token {
+        Token{ b, t: BracketType::Open }
+    }
+
+    pub const fn close(b: Bracket) -> Token {
+        Token{ b, t: BracketType::Close }
+    }
+
+    pub fn from_char(c: char) -> Option<Token> {
+        Some(match c {
+            '(' => TOK_OPEN_PAREN,
+            ')' => TOK_CLOSE_PAREN,
+            '[' => TOK_OPEN_SQUARE,
+            ']' => TOK_CLOSE_SQUARE,
+            '{' => TOK_OPEN_BRACE,
+            '}' => TOK_CLOSE_BRACE,
+            '<' => TOK_OPEN_ANGLE,
+            '>' => TOK_CLOSE_ANGLE,
+            _ => return None,
+        })
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.t == BracketType::Open
+    }
+
+    pub fn is_close(&self) -> bool {
+        self.t == BracketType::Close
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", match *self {
+            TOK_OPEN_PAREN => '(',
+            TOK_CLOSE_PAREN => ')',
+            TOK_OPEN_SQUARE => '[',
+            TOK_CLOSE_SQUARE => ']',
+            TOK_OPEN_BRACE => '{',
+            TOK_CLOSE_BRACE => '}',
+            TOK_OPEN_ANGLE => '<',
+            TOK_CLOSE_ANGLE => '>',
+            _ => '?',
+        })
+    }
+}
+
+pub struct ParseErr {
+    expected: Bracket,
+    found: Option<Bracket>,
+    pos: usize,
+}
+
+impl ParseErr {
+    pub fn score(&self) -> usize {
+        match self.found {
+            Some(b) => b.score(),
+            _ => 0,
+        }
+    }
+}
+
+pub fn score_completion(l: &[Token]) -> usize {
+    l.iter()
+        .map(|t| match t.b {
+            Bracket::Paren => 1,
+            Bracket::Square => 2,
+            Bracket::Brace => 3,
+            Bracket::Angle => 4,
+            _ => 0,
+        })
+        .fold(0, |total, score| total * 5 + score)
+}
+
+pub fn parse_line(s: &str, completion: &mut Vec<Token>) -> Result<(), ParseErr> {
+    let mut stack = Vec::with_capacity(10);
+
+    for (i, c) in s.trim_end().chars().enumerate() {
+        let token = Token::from_char(c).unwrap();
+        if token.is_open() {
+            stack.push(token);
+        }
+        else {
+            match stack.pop() {
+                Some(opener) if opener.b == token.b => (),
+                Some(opener) => return Err(ParseErr{ expected: opener.b, found: Some(token.b), pos: i}),
+                None => return Err(ParseErr{ expected: Bracket::Any, found: None, pos: i}),
+            }
+        }
+    }
+
+    for &t in stack.iter().rev() {
+        completion.push(Token::close(t.b));
+    }
+
+    Ok(())
+}
+
+/// Outcome of checking a single line
+#[derive(Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum LineStatus {
+    Ok,
+    Corrupted { pos: usize, expected: String, found: String, score: usize },
+    Incomplete { completion: String, score: usize },
+}
+
+#[derive(Serialize)]
+pub struct LineResult {
+    pub line: usize,
+    pub input: String,
+    #[serde(flatten)]
+    pub status: LineStatus,
+}
+
+#[derive(Serialize)]
+pub struct Report {
+    pub syntax_error_score: usize,
+    pub completion_score: Option<usize>,
+    pub per_line: Vec<LineResult>,
+}
+
+impl Report {
+    pub fn from_lines(lines: impl Iterator<Item = String>) -> Report {
+        let mut per_line = Vec::new();
+        let mut syntax_error_score = 0;
+        let mut completion_scores = Vec::new();
+
+        for (i, s) in lines.enumerate() {
+            let mut completion = vec!();
+            let status = match parse_line(&s, &mut completion) {
+                Ok(_) if completion.is_empty() => LineStatus::Ok,
+                Ok(_) => {
+                    let score = score_completion(&completion);
+                    completion_scores.push(score);
+                    LineStatus::Incomplete {
+                        completion: completion.iter().map(|t| t.to_string()).collect(),
+                        score,
+                    }
+                },
+                Err(e) => {
+                    syntax_error_score += e.score();
+                    LineStatus::Corrupted {
+                        pos: e.pos,
+                        expected: Token::close(e.expected).to_string(),
+                        found: Token::close(e.found.unwrap_or(Bracket::Any)).to_string(),
+                        score: e.score(),
+                    }
+                },
+            };
+            per_line.push(LineResult { line: i + 1, input: s.trim_end().to_owned(), status });
+        }
+
+        completion_scores.sort();
+        let completion_score = completion_scores.get(completion_scores.len() / 2).copied();
+
+        Report { syntax_error_score, completion_score, per_line }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for l in &self.per_line {
+            writeln!(f, "{}", l.input)?;
+            match &l.status {
+                LineStatus::Ok => writeln!(f, " OK!")?,
+                LineStatus::Incomplete { completion, .. } => writeln!(f, " PARTIAL: missing {}", completion)?,
+                LineStatus::Corrupted { pos, expected, found, .. } =>
+                    writeln!(f, " {}: Expected {} but found {}", pos, expected, found)?,
+            }
+        }
+
+        writeln!(f, "syntax score: {}", self.syntax_error_score)?;
+        match self.completion_score {
+            Some(score) => write!(f, "completion score: {}", score),
+            None => write!(f, "completion score: -"),
+        }
+    }
+}
+
+pub struct Puzzle {
+    report: Report,
+}
+
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        Ok(Puzzle { report: Report::from_lines(input.lines().map(String::from)) })
+    }
+
+    fn part1(&self) -> String {
+        self.report.syntax_error_score.to_string()
+    }
+
+    fn part2(&self) -> String {
+        self.report.completion_score.map_or_else(|| "-".to_string(), |s| s.to_string())
+    }
+
+    /// The scores and how each line ended
+    fn report(&self) -> Option<String> {
+        serde_json::to_string(&self.report).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test_input");
+
+    #[test]
+    fn example_report() {
+        let report = Report::from_lines(EXAMPLE.lines().map(String::from));
+
+        assert_eq!(report.syntax_error_score, 26397);
+        assert_eq!(report.completion_score, Some(288957));
+        assert_eq!(report.per_line.len(), 10);
+        assert!(matches!(report.per_line[2].status,
+            LineStatus::Corrupted { pos: 12, ref expected, ref found, score: 1197 } if expected == "]" && found == "}"));
+    }
+
+    #[test]
+    fn json_line_results() {
+        let report = Report::from_lines(["[({(<(())[]>[[{[]{<()<>>".to_owned(), "()".to_owned()].into_iter());
+        let json = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(json["completion_score"], 288957);
+        assert_eq!(json["per_line"][0]["status"], "incomplete");
+        assert_eq!(json["per_line"][0]["completion"], "}}]])})]");
+        assert_eq!(json["per_line"][1]["status"], "ok");
+    }
+
+    /// The answers given in the puzzle text for its example
+    #[test]
+    fn example_answers() {
+        let puzzle = Puzzle::parse(include_str!("../test_input"));
+        assert_eq!(puzzle.part1(), "26397");
+        assert_eq!(puzzle.part2(), "288957");
+    }
+}
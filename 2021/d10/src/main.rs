@@ -0,0 +1,25 @@
+use std::io::BufRead;
+
+use d10::*;
+
+fn main() {
+    let (input, args) = aoc_core::input_arg();
+    let json = match args.first().map(String::as_str) {
+        Some("--format") => match args.get(1).map(String::as_str) {
+            Some("json") => true,
+            Some("text") => false,
+            f => panic!("unknown format: {:?}", f),
+        },
+        None => false,
+        Some(arg) => panic!("unknown argument: {}", arg),
+    };
+
+    let input = aoc_core::open_input(&input);
+    let report = Report::from_lines(input.lines().map(|l| l.unwrap()));
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap());
+    } else {
+        println!("{}", report);
+    }
+}
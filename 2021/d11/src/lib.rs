@@ -0,0 +1,377 @@
+use std::cmp;
+use std::fmt;
+
+use aoc_core::color::{paint, Color};
+use aoc_core::{AocError, Solver};
+use aoc_grid::Grid;
+
+pub use aoc_grid::Pos;
+
+#[derive(PartialEq, Eq, Clone, Copy, Default)]
+pub struct Octopus(u8, bool);
+
+impl Octopus {
+    pub fn with_energy(e: u8) -> Octopus {
+        Octopus(e, false)
+    }
+
+    pub const fn energy(&self) -> u8 { self.0 }
+    pub const fn is_stepping(&self) -> bool { self.1 }
+    pub const fn will_flash(&self) -> bool { self.0 >= 10 }
+    pub const fn flashed(&self) -> bool { self.0 == 0 }
+
+    pub fn inc_energy(&mut self) -> bool {
+        self.1 = true;
+        self.0 = cmp::min(self.0 + 1, 11);
+        self.0 == 10
+    }
+
+    pub fn finish_step(&mut self) -> bool {
+        self.1 = false;
+        if self.0 > 9 {
+            self.0 = 0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl fmt::Debug for Octopus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Octopuses that flashed on the last step, back at 0, are highlighted
+/// when colour is on
+impl fmt::Display for Octopus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            0 => fmt::Display::fmt(&paint(0, Color::Yellow).bold(), f),
+            e => fmt::Display::fmt(&e, f),
+        }
+    }
+}
+
+/// Hooks for observing a running simulation. Every method defaults to doing
+/// nothing, so observers only implement what they care about.
+pub trait StepObserver {
+    /// An octopus at `pos` flashed during the current step
+    fn on_flash(&mut self, _pos: Pos) {}
+
+    /// Octopuses at `a` and `b`, next to each other, both flashed during
+    /// the current step
+    fn on_linked(&mut self, _a: Pos, _b: Pos) {}
+
+    /// The octopus at `pos` ended the current step with `energy`
+    fn on_energy(&mut self, _pos: Pos, _energy: u8) {}
+
+    /// Step number `step` (starting at 1) finished with `flashes` flashes
+    fn on_step(&mut self, _step: u32, _flashes: u32) {}
+}
+
+impl StepObserver for () {}
+
+#[derive(Clone)]
+pub struct OctoMap(Grid<Octopus>, u32);
+
+impl OctoMap {
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(lines: impl Iterator<Item = String>) -> OctoMap {
+        let mut map = Vec::with_capacity(100);
+        let mut width = 0;
+        for l in lines {
+            let s = l.trim_end();
+            map.extend(s.chars().map(|c| Octopus::with_energy(c.to_digit(10).unwrap() as u8)));
+            width = s.len();
+        }
+        OctoMap(Grid::from_vec(width, map), 0)
+    }
+
+    pub fn height(&self) -> usize {
+        self.0.height()
+    }
+
+    pub fn width(&self) -> usize {
+        self.0.width()
+    }
+
+    /// Number of steps simulated so far
+    pub fn steps(&self) -> u32 {
+        self.1
+    }
+
+    pub fn step(&mut self) -> u32 {
+        self.step_with(&mut ())
+    }
+
+    pub fn step_with(&mut self, observer: &mut impl StepObserver) -> u32 {
+        let mut will_flash = vec!();
+
+        for p in self.0.positions() {
+            let o = &mut self.0[p];
+            if o.inc_energy() {
+                will_flash.push(p);
+                observer.on_flash(p);
+            }
+        }
+
+        while let Some(center) = will_flash.pop() {
+            for adj_pos in self.0.neighbors8(center) {
+                let adj = &mut self.0[adj_pos];
+                if adj.inc_energy() {
+                    will_flash.push(adj_pos);
+                    observer.on_flash(adj_pos);
+                }
+                if adj.will_flash() {
+                    observer.on_linked(center, adj_pos);
+                }
+            }
+        }
+
+        let mut flashed = 0;
+        let width = self.0.width();
+        for (i, o) in self.0.cells_mut().iter_mut().enumerate() {
+            if o.finish_step() {
+                flashed += 1;
+            }
+            observer.on_energy(Pos::new(i % width, i / width), o.energy());
+        }
+
+        self.1 += 1;
+        observer.on_step(self.1, flashed);
+        flashed
+    }
+}
+
+/// Energy levels and flash clusters after one step
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StepStats {
+    pub step: u32,
+    pub flashes: u32,
+    /// Number of octopuses at each energy level, 0 to 9
+    pub energy: [u32; 10],
+    /// Most octopuses in one group of adjacent flashes
+    pub largest_cluster: usize,
+}
+
+/// Observer collecting `StepStats` for every step, joining adjacent flashes
+/// into clusters with a union-find as they happen
+pub struct StatsRecorder {
+    width: usize,
+    /// Union-find parent of each octopus, by index; only meaningful for
+    /// ones that flashed this step
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    flashed: Vec<usize>,
+    energy: [u32; 10],
+    pub steps: Vec<StepStats>,
+}
+
+impl StatsRecorder {
+    pub fn new(map: &OctoMap) -> StatsRecorder {
+        let cells = map.width() * map.height();
+        StatsRecorder {
+            width: map.width(),
+            parent: (0..cells).collect(),
+            size: vec![1; cells],
+            flashed: vec!(),
+            energy: [0; 10],
+            steps: vec!(),
+        }
+    }
+
+    fn index(&self, p: Pos) -> usize {
+        p.y * self.width + p.x
+    }
+
+    fn root(&mut self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            self.parent[i] = self.parent[self.parent[i]];
+            i = self.parent[i];
+        }
+        i
+    }
+}
+
+impl StepObserver for StatsRecorder {
+    fn on_flash(&mut self, pos: Pos) {
+        let i = self.index(pos);
+        self.parent[i] = i;
+        self.size[i] = 1;
+        self.flashed.push(i);
+    }
+
+    fn on_linked(&mut self, a: Pos, b: Pos) {
+        let (a, b) = (self.index(a), self.index(b));
+        let (a, b) = (self.root(a), self.root(b));
+        if a != b {
+            let (big, small) = if self.size[a] >= self.size[b] { (a, b) } else { (b, a) };
+            self.parent[small] = big;
+            self.size[big] += self.size[small];
+        }
+    }
+
+    fn on_energy(&mut self, _pos: Pos, energy: u8) {
+        self.energy[energy as usize] += 1;
+    }
+
+    fn on_step(&mut self, step: u32, flashes: u32) {
+        let flashed = std::mem::take(&mut self.flashed);
+        let largest_cluster = flashed.iter().map(|&i| {
+            let r = self.root(i);
+            self.size[r]
+        }).max().unwrap_or(0);
+        let energy = std::mem::take(&mut self.energy);
+        self.steps.push(StepStats { step, flashes, energy, largest_cluster });
+    }
+}
+
+impl std::ops::Index<Pos> for OctoMap {
+    type Output = Octopus;
+    fn index(&self, index: Pos) -> &Self::Output {
+        &self.0[index]
+    }
+}
+impl std::ops::IndexMut<Pos> for OctoMap {
+    fn index_mut(&mut self, index: Pos) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+impl fmt::Display for OctoMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+pub struct Puzzle {
+    map: OctoMap,
+}
+
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        Ok(Puzzle { map: OctoMap::from_str(input.lines().map(String::from)) })
+    }
+
+    fn part1(&self) -> String {
+        let mut map = self.map.clone();
+        (0..100).map(|_| map.step()).sum::<u32>().to_string()
+    }
+
+    fn part2(&self) -> String {
+        let mut map = self.map.clone();
+        let all = (map.width() * map.height()) as u32;
+        while map.step() != all {}
+        map.steps().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test_input");
+
+    #[derive(Default)]
+    struct Recorder {
+        flashes: Vec<Pos>,
+        steps: Vec<(u32, u32)>,
+    }
+
+    impl StepObserver for Recorder {
+        fn on_flash(&mut self, pos: Pos) {
+            self.flashes.push(pos);
+        }
+
+        fn on_step(&mut self, step: u32, flashes: u32) {
+            self.steps.push((step, flashes));
+        }
+    }
+
+    #[test]
+    fn steps_draw_as_golden() {
+        let mut map = OctoMap::from_str(EXAMPLE.lines().map(String::from));
+        let mut drawn = format!("before any steps:\n{}", map);
+        for step in 1..=10 {
+            map.step();
+            if [1, 2, 10].contains(&step) {
+                drawn += &format!("\nafter step {}:\n{}", step, map);
+            }
+        }
+        aoc_core::golden::check(env!("CARGO_MANIFEST_DIR"), "steps", &drawn);
+    }
+
+    #[test]
+    fn observer_sees_every_flash() {
+        let mut map = OctoMap::from_str(EXAMPLE.lines().map(String::from));
+        let mut recorder = Recorder::default();
+        for _ in 0..10 {
+            map.step_with(&mut recorder);
+        }
+
+        assert_eq!(map.steps(), 10);
+        assert_eq!(recorder.flashes.len(), 204);
+        assert_eq!(recorder.steps.len(), 10);
+        assert_eq!(recorder.steps[0], (1, 0));
+        assert_eq!(recorder.steps[1], (2, 35));
+        assert_eq!(recorder.steps.iter().map(|s| s.1).sum::<u32>(), 204);
+    }
+
+    /// Largest 8-connected group of octopuses that just flashed, found by
+    /// flood fill after the fact
+    fn largest_flashed_group(map: &OctoMap) -> usize {
+        let mut seen = std::collections::HashSet::new();
+        let mut largest = 0;
+        for p in map.0.positions().filter(|&p| map[p].flashed()) {
+            if !seen.insert(p) {
+                continue;
+            }
+            let mut group = 1;
+            let mut todo = vec!(p);
+            while let Some(q) = todo.pop() {
+                for n in map.0.neighbors8(q) {
+                    if map[n].flashed() && seen.insert(n) {
+                        group += 1;
+                        todo.push(n);
+                    }
+                }
+            }
+            largest = largest.max(group);
+        }
+        largest
+    }
+
+    #[test]
+    fn stats_per_step() {
+        let mut map = OctoMap::from_str(EXAMPLE.lines().map(String::from));
+        let mut stats = StatsRecorder::new(&map);
+        loop {
+            let flashes = map.step_with(&mut stats);
+            assert_eq!(stats.steps.last().unwrap().largest_cluster, largest_flashed_group(&map));
+            if flashes == 100 {
+                break;
+            }
+        }
+
+        assert_eq!(stats.steps.len(), 195);
+        assert!(stats.steps.iter().all(|s| s.energy.iter().sum::<u32>() == 100));
+        assert!(stats.steps.iter().all(|s| s.largest_cluster <= s.flashes as usize));
+        assert!(stats.steps.iter().all(|s| s.energy[0] == s.flashes));
+
+        assert_eq!(stats.steps[0].largest_cluster, 0);
+        assert_eq!(stats.steps[1].flashes, 35);
+
+        let last = stats.steps.last().unwrap();
+        assert_eq!((last.largest_cluster, last.energy[0]), (100, 100));
+    }
+
+    /// The answers given in the puzzle text for its example
+    #[test]
+    fn example_answers() {
+        let puzzle = Puzzle::parse(include_str!("../test_input"));
+        assert_eq!(puzzle.part1(), "1656");
+        assert_eq!(puzzle.part2(), "195");
+    }
+}
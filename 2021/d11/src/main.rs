@@ -0,0 +1,52 @@
+use std::io::BufRead;
+
+use d11::*;
+
+/// One CSV row per step until the octopuses synchronize, for plotting
+fn print_stats(map: &mut OctoMap) {
+    let mut stats = StatsRecorder::new(map);
+    while map.step_with(&mut stats) as usize != map.width() * map.height() {}
+
+    println!("step,flashes,largest_cluster,{}", (0..10).map(|e| format!("energy{}", e)).collect::<Vec<_>>().join(","));
+    for s in &stats.steps {
+        let energy: Vec<String> = s.energy.iter().map(u32::to_string).collect();
+        println!("{},{},{},{}", s.step, s.flashes, s.largest_cluster, energy.join(","));
+    }
+}
+
+fn main() {
+    // --stats prints energy levels and flash cluster sizes per step as CSV;
+    // --color highlights the octopuses that flashed each step
+    let mut stats = false;
+    let (input, args) = aoc_core::input_arg();
+    for arg in args {
+        match arg.as_str() {
+            "--stats" => stats = true,
+            "--color" => aoc_core::color::enable(true),
+            _ => panic!("unknown argument: {}", arg),
+        }
+    }
+
+    let input = aoc_core::open_input(&input);
+    let mut map = OctoMap::from_str(input.lines().map(|l| l.unwrap()));
+
+    if stats {
+        print_stats(&mut map);
+        return;
+    }
+
+    println!("{}", &map);
+
+    let mut flashes = 0;
+    for step in 1..=1000 {
+        let f = map.step();
+        flashes += f;
+        println!("step {} ({} flahes this step; {} total)", step, f, flashes);
+        println!("{}", &map);
+
+        if f == 100 {
+            println!("synchronized at step {}", step);
+            break;
+        }
+    }
+}
@@ -0,0 +1,325 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use aoc_core::intern::{Interner, Symbol};
+use aoc_core::{AocError, Solver};
+
+mod policy;
+mod sample;
+pub use policy::VisitPolicy;
+use policy::Visit;
+pub use sample::PathCounter;
+
+/// A cave by name, borrowed from the map it's in
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Cave<'m>(&'m str);
+
+impl<'m> Cave<'m> {
+    pub fn from(s: &'m str) -> Cave<'m> {
+        Cave(s)
+    }
+
+    pub fn as_str(&self) -> &'m str {
+        self.0
+    }
+
+    pub fn is_start(&self) -> bool {
+        self.as_str() == "start"
+    }
+
+    pub fn is_end(&self) -> bool {
+        self.as_str() == "end"
+    }
+
+    pub fn is_small(&self) -> bool {
+        !self.as_str().chars().any(|c| c.is_ascii_uppercase())
+    }
+}
+
+impl Ord for Cave<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.0 {
+            "start" => Ordering::Less,
+            "end" => Ordering::Greater,
+            s => s.cmp(other.0),
+        }
+    }
+}
+
+impl PartialOrd for Cave<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Cave<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl fmt::Debug for Cave<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Caves and the passages between them. Each cave's name is interned once,
+/// and passages are kept by symbol, indexed by the symbol they lead from.
+pub struct CaveMap {
+    names: Interner,
+    links: Vec<Vec<Symbol>>,
+}
+
+impl CaveMap {
+    pub fn from_input(specs: impl Iterator<Item = String>) -> CaveMap {
+        let mut map = CaveMap { names: Interner::new(), links: vec!() };
+
+        for s in specs {
+            let mut splits = s.split('-');
+            let a = map.add(splits.next().unwrap());
+            let b = map.add(splits.next().unwrap());
+
+            map.links[a.index()].push(b);
+            map.links[b.index()].push(a);
+        }
+
+        map
+    }
+
+    fn add(&mut self, name: &str) -> Symbol {
+        let cave = self.names.intern(name);
+        if cave.index() == self.links.len() {
+            self.links.push(vec!());
+        }
+        cave
+    }
+
+    pub fn cave(&self, c: Symbol) -> Cave<'_> {
+        Cave(self.names.resolve(c))
+    }
+
+    pub fn next_from(&self, c: &Cave) -> Option<Vec<Cave<'_>>> {
+        let c = self.names.get(c.as_str())?;
+        Some(self.links[c.index()].iter().map(|&n| self.cave(n)).collect())
+    }
+
+    pub fn each_path<F>(&self, f: &F) -> usize
+        where F: Fn(&[Symbol])
+    {
+        let mut walker = PathWalker::new(self, VisitPolicy::default().with_revisit(true));
+        let mut count = 0;
+        while let Some(path) = walker.advance() {
+            f(path);
+            count += 1;
+        }
+        count
+    }
+
+    pub fn paths(&self) -> Paths<'_> {
+        self.paths_with(VisitPolicy::default().with_revisit(true))
+    }
+
+    /// Paths that keep to `policy`
+    pub fn paths_with(&self, policy: VisitPolicy) -> Paths<'_> {
+        Paths { walker: PathWalker::new(self, policy) }
+    }
+
+    /// Number of paths, optionally allowing a single small cave to be
+    /// visited twice
+    pub fn count_paths(&self, revisit: bool) -> usize {
+        self.count_paths_with(&VisitPolicy::default().with_revisit(revisit))
+    }
+
+    /// Number of paths that keep to `policy`
+    pub fn count_paths_with(&self, policy: &VisitPolicy) -> usize {
+        let mut walker = PathWalker::new(self, policy.clone());
+        let mut count = 0;
+        while walker.advance().is_some() {
+            count += 1;
+        }
+        count
+    }
+}
+
+struct Frame {
+    cave: Symbol,
+    next_branch: usize,
+    big_small: Option<Symbol>,
+}
+
+/// Depth-first path enumeration with an explicit stack instead of
+/// recursion, so long corridors can't overflow the call stack
+struct PathWalker<'m> {
+    map: &'m CaveMap,
+    path: Vec<Symbol>,
+    stack: Vec<Frame>,
+    policy: VisitPolicy,
+}
+
+impl<'m> PathWalker<'m> {
+    fn new(map: &'m CaveMap, policy: VisitPolicy) -> Self {
+        let start = map.names.get("start").expect("no start cave");
+        PathWalker {
+            map,
+            path: vec!(start),
+            stack: vec!(Frame { cave: start, next_branch: 0, big_small: None }),
+            policy,
+        }
+    }
+
+    /// Step until the next complete path is found
+    fn advance(&mut self) -> Option<&[Symbol]> {
+        if self.path.last().is_some_and(|&c| self.map.cave(c).is_end()) {
+            self.path.pop();
+        }
+
+        loop {
+            let frame = self.stack.last_mut()?;
+            let branches = &self.map.links[frame.cave.index()];
+            if frame.next_branch == branches.len() {
+                self.stack.pop();
+                self.path.pop();
+                continue;
+            }
+
+            let c = branches[frame.next_branch];
+            frame.next_branch += 1;
+
+            // only caves with a limit need counting, which without rules
+            // is just the small ones
+            let cave = self.map.cave(c);
+            let visits = match cave.is_small() || self.policy.has_limits() {
+                true => self.path.iter().filter(|&&p| p == c).count(),
+                false => 0,
+            };
+            let can_revisit = self.policy.allows_revisit() && frame.big_small.is_none();
+            let repeated_small = match self.policy.visit(&cave, visits, can_revisit) {
+                Visit::Allowed => frame.big_small,
+                Visit::Revisit => Some(c),
+                Visit::Refused => continue,
+            };
+
+            self.path.push(c);
+            if cave.is_end() {
+                return Some(&self.path);
+            }
+            self.stack.push(Frame { cave: c, next_branch: 0, big_small: repeated_small });
+        }
+    }
+}
+
+pub struct Paths<'m> {
+    walker: PathWalker<'m>,
+}
+
+impl<'m> Iterator for Paths<'m> {
+    type Item = Vec<Cave<'m>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let map = self.walker.map;
+        self.walker.advance().map(|path| path.iter().map(|&c| map.cave(c)).collect())
+    }
+}
+
+pub struct Puzzle {
+    map: CaveMap,
+    /// Limits from the `rules` option
+    policy: VisitPolicy,
+}
+
+impl Puzzle {
+    fn count_paths(&self, revisit: bool) -> usize {
+        self.map.count_paths_with(&self.policy.clone().with_revisit(revisit))
+    }
+}
+
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        Ok(Puzzle { map: CaveMap::from_input(input.lines().map(String::from)), policy: VisitPolicy::default() })
+    }
+
+    /// `rules`: visit limits for particular caves, in the form
+    /// `VisitPolicy::parse_rules` reads
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), AocError> {
+        match name {
+            "rules" => self.policy.add_rules(value),
+            _ => Err(AocError::invalid(format!("no option {}", name))),
+        }
+    }
+
+    fn part1(&self) -> String {
+        self.count_paths(false).to_string()
+    }
+
+    fn part2(&self) -> String {
+        self.count_paths(true).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMALL: &str = "start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end";
+
+    fn lines(s: &str) -> impl Iterator<Item = String> + '_ {
+        s.lines().map(String::from)
+    }
+
+    #[test]
+    fn small_example() {
+        let map = CaveMap::from_input(lines(SMALL));
+
+        assert_eq!(map.count_paths(false), 10);
+        assert_eq!(map.each_path(&|_| ()), 36);
+        assert_eq!(map.paths().count(), 36);
+        assert!(map.paths().all(|p| p.first().unwrap().is_start() && p.last().unwrap().is_end()));
+    }
+
+    #[test]
+    fn long_corridor_does_not_overflow() {
+        let n = 5_000;
+        let mut spec = vec!(String::from("start-c0"));
+        spec.extend((1..n).map(|i| format!("c{}-c{}", i - 1, i)));
+        spec.push(format!("c{}-end", n - 1));
+
+        let map = CaveMap::from_input(spec.into_iter());
+        let paths: Vec<_> = map.paths().collect();
+
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].len(), n + 2);
+    }
+
+    #[test]
+    fn rules_limit_visits() {
+        let mut puzzle = Puzzle::parse(SMALL);
+        puzzle.set_option("rules", "cave b may be visited twice").unwrap();
+        assert_eq!(puzzle.part1(), "30");
+        assert_eq!(puzzle.part2(), "54");
+        puzzle.set_option("rules", "cave b may not be visited").unwrap();
+        assert_eq!(puzzle.part1(), "2");
+        assert!(puzzle.set_option("rules", "b twice").is_err());
+        assert!(puzzle.set_option("days", "3").is_err());
+    }
+
+    /// The answers given in the puzzle text for its example
+    #[test]
+    fn example_answers() {
+        let puzzle = Puzzle::parse(include_str!("../input_simple"));
+        assert_eq!(puzzle.part1(), "10");
+        assert_eq!(puzzle.part2(), "36");
+        let puzzle = Puzzle::parse(include_str!("../input2"));
+        assert_eq!(puzzle.part1(), "19");
+        assert_eq!(puzzle.part2(), "103");
+        let puzzle = Puzzle::parse(include_str!("../input3"));
+        assert_eq!(puzzle.part1(), "226");
+        assert_eq!(puzzle.part2(), "3509");
+    }
+}
@@ -0,0 +1,78 @@
+use std::fs;
+use std::io::BufRead;
+use std::path::Path;
+
+use aoc_core::rng::Rng;
+use d12::*;
+
+fn main() {
+    // --sample <k> prints k random paths and the exact count instead of
+    // walking them all; --revisit allows one small cave twice, --seed picks
+    // the sample. --rules <path> reads visit limits for particular caves,
+    // from <input>.rules beside the input if not given
+    let mut sample = None;
+    let mut revisit = false;
+    let mut seed = 0;
+    let mut rules = None;
+    let (input, args) = aoc_core::input_arg();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--sample" => sample = args.next().and_then(|k| k.parse::<u64>().ok()),
+            "--revisit" => revisit = true,
+            "--seed" => seed = args.next().and_then(|s| s.parse::<u64>().ok()).expect("--seed needs a number"),
+            "--rules" => rules = Some(args.next().expect("--rules needs a path")),
+            _ => panic!("unknown argument: {}", arg),
+        }
+    }
+
+    let rules = rules.or_else(|| {
+        let sidecar = format!("{}.rules", input.path()?.display());
+        Path::new(&sidecar).exists().then_some(sidecar)
+    });
+    let policy = match &rules {
+        Some(path) => {
+            let text = fs::read_to_string(path).unwrap_or_else(|e| panic!("{}: {}", path, e));
+            aoc_core::or_exit(VisitPolicy::parse_rules(&text))
+        },
+        None => VisitPolicy::default(),
+    };
+
+    let input = aoc_core::open_input(&input);
+    let map = CaveMap::from_input(input.lines().map(|l| l.unwrap()));
+
+    if let Some(k) = sample {
+        let mut counter = PathCounter::with_policy(&map, &policy);
+        for path in counter.sample(revisit, k, &mut Rng::seed_from(seed)) {
+            let names: Vec<&str> = path.iter().map(|c| c.as_str()).collect();
+            println!("{}", names.join(","));
+        }
+        println!("{} paths", counter.count(revisit));
+        return;
+    }
+
+    if policy.has_limits() {
+        // the full walk below has always allowed the revisit
+        println!("{} paths", map.count_paths_with(&policy.with_revisit(true)));
+        return;
+    }
+
+    /*
+    for (k, v) in map.index.iter() {
+        for c in v {
+            println!("{} -> {}", k.as_str(), c.as_str());
+        }
+    }
+    */
+
+    let count = map.each_path(&|_path| {
+        /*
+        for c in path {
+            print!("->{}", c);
+        }
+        println!();
+        */
+    });
+
+    println!("{} paths", count);
+}
@@ -0,0 +1,157 @@
+//! Which caves a path may go through, and how often. By default small caves
+//! take one visit and big caves any number, with part 2's single revisit of
+//! a small cave on top. Rules can give particular caves their own limits,
+//! one per line:
+//!
+//! ```text
+//! # comments and blank lines are skipped
+//! cave b may be visited 3 times
+//! cave A may be visited twice
+//! cave c may not be visited
+//! ```
+
+use std::collections::BTreeMap;
+
+use aoc_core::AocError;
+
+use crate::Cave;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Visit {
+    Allowed,
+    /// Allowed by using up the path's revisit
+    Revisit,
+    Refused,
+}
+
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct VisitPolicy {
+    revisit: bool,
+    /// Visits allowed to caves with rules of their own
+    limits: BTreeMap<String, usize>,
+}
+
+impl VisitPolicy {
+    /// Allowing one small cave (not start or end) a second visit, the way
+    /// part 2 does. Caves with rules of their own don't take the revisit.
+    pub fn with_revisit(mut self, revisit: bool) -> VisitPolicy {
+        self.revisit = revisit;
+        self
+    }
+
+    pub fn with_limit(mut self, cave: &str, visits: usize) -> VisitPolicy {
+        self.limits.insert(cave.to_string(), visits);
+        self
+    }
+
+    /// A policy with the limits in `rules`
+    pub fn parse_rules(rules: &str) -> Result<VisitPolicy, AocError> {
+        let mut policy = VisitPolicy::default();
+        policy.add_rules(rules)?;
+        Ok(policy)
+    }
+
+    /// Adds the limits in `rules`, replacing any already set for the same
+    /// caves
+    pub fn add_rules(&mut self, rules: &str) -> Result<(), AocError> {
+        let rules = aoc_core::parse_lines(rules, |line| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return Ok(None);
+            }
+            parse_rule(line).map(Some)
+        })?;
+        self.limits.extend(rules.into_iter().flatten());
+        Ok(())
+    }
+
+    pub fn allows_revisit(&self) -> bool {
+        self.revisit
+    }
+
+    /// Whether any cave has a rule of its own
+    pub fn has_limits(&self) -> bool {
+        !self.limits.is_empty()
+    }
+
+    /// Visits a path may make to `cave` without the revisit, or `None` if
+    /// there's no limit
+    pub fn limit(&self, cave: &Cave) -> Option<usize> {
+        match self.limits.get(cave.as_str()) {
+            Some(&n) => Some(n),
+            None if cave.is_small() => Some(1),
+            None => None,
+        }
+    }
+
+    /// Whether a path that has been to `cave` `visits` times already may go
+    /// there again, given whether its revisit is still unused
+    pub(crate) fn visit(&self, cave: &Cave, visits: usize, can_revisit: bool) -> Visit {
+        let limit = match self.limits.get(cave.as_str()) {
+            Some(&n) if visits < n => return Visit::Allowed,
+            Some(_) => return Visit::Refused,
+            None if cave.is_small() => 1,
+            None => return Visit::Allowed,
+        };
+        if visits < limit {
+            Visit::Allowed
+        } else if can_revisit && visits == 1 && !cave.is_start() && !cave.is_end() {
+            Visit::Revisit
+        } else {
+            Visit::Refused
+        }
+    }
+}
+
+/// `cave b may be visited 3 times`, `once`, `twice`, or `cave b may not be
+/// visited`
+fn parse_rule(line: &str) -> Result<(String, usize), AocError> {
+    let bad = || AocError::invalid(format!("expected a rule like \"cave b may be visited 3 times\": {}", line));
+    let (cave, rest) = line.strip_prefix("cave ")
+        .and_then(|s| s.split_once(" may "))
+        .ok_or_else(bad)?;
+    let visits = match rest.strip_prefix("be visited ") {
+        None if rest == "not be visited" => 0,
+        None => return Err(bad()),
+        Some("once") => 1,
+        Some("twice") => 2,
+        Some("1 time") => 1,
+        Some(times) => times.strip_suffix(" times").ok_or_else(bad)?.parse()?,
+    };
+    Ok((cave.to_string(), visits))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_rules() {
+        let policy = VisitPolicy::parse_rules("# variant\ncave b may be visited 3 times\n\ncave A may be visited once\ncave c may not be visited\n").unwrap();
+        assert_eq!(policy, VisitPolicy::default().with_limit("b", 3).with_limit("A", 1).with_limit("c", 0));
+        assert_eq!(policy.limit(&Cave::from("b")), Some(3));
+        assert_eq!(policy.limit(&Cave::from("d")), Some(1));
+        assert_eq!(policy.limit(&Cave::from("B")), None);
+
+        assert_eq!(VisitPolicy::parse_rules("cave b may be visited twice").unwrap().limit(&Cave::from("b")), Some(2));
+        assert_eq!(VisitPolicy::parse_rules("cave b may be visited 1 time").unwrap().limit(&Cave::from("b")), Some(1));
+        assert!(VisitPolicy::parse_rules("cave b may be visited lots").is_err());
+        assert_eq!(
+            VisitPolicy::parse_rules("cave b may be visited 2 times\nb: 3").unwrap_err().to_string(),
+            "line 2: expected a rule like \"cave b may be visited 3 times\": b: 3",
+        );
+    }
+
+    #[test]
+    fn revisit_is_for_small_caves_without_rules() {
+        let policy = VisitPolicy::default().with_revisit(true).with_limit("b", 2);
+        let (b, c) = (Cave::from("b"), Cave::from("c"));
+        assert_eq!(policy.visit(&c, 0, true), Visit::Allowed);
+        assert_eq!(policy.visit(&c, 1, true), Visit::Revisit);
+        assert_eq!(policy.visit(&c, 1, false), Visit::Refused);
+        assert_eq!(policy.visit(&b, 1, false), Visit::Allowed);
+        assert_eq!(policy.visit(&b, 2, true), Visit::Refused);
+        assert_eq!(policy.visit(&Cave::from("start"), 1, true), Visit::Refused);
+        assert_eq!(policy.visit(&Cave::from("A"), 100, false), Visit::Allowed);
+    }
+}
@@ -0,0 +1,224 @@
+//! Counting and sampling paths without walking every one. The number of
+//! ways to finish a path depends only on where it is, how often it has been
+//! through each cave with a limit and whether its revisit is used up, so
+//! those counts are memoized. A path can then be rebuilt from its rank in the walker's order,
+//! and sampling picks distinct ranks rather than reservoir sampling a full
+//! enumeration.
+
+use std::collections::{BTreeSet, HashMap};
+
+use aoc_core::intern::Symbol;
+
+use aoc_core::rng::Rng;
+
+use crate::{Cave, CaveMap, Visit, VisitPolicy};
+
+/// Visits so far to each cave with a limit, packed into as many bits as
+/// its limit needs
+type Visited = u128;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct State {
+    cave: usize,
+    visited: Visited,
+    can_revisit: bool,
+}
+
+/// Memoized path counts over a cave map, with caves numbered by their
+/// symbols in it
+pub struct PathCounter<'m> {
+    caves: Vec<Cave<'m>>,
+    links: Vec<Vec<usize>>,
+    policy: VisitPolicy,
+    /// Lowest bit of each cave's visit count, or 0 if it has no limit
+    bits: Vec<Visited>,
+    /// Bits of each cave's visit count
+    masks: Vec<Visited>,
+    start: usize,
+    memo: HashMap<State, u64>,
+}
+
+impl<'m> PathCounter<'m> {
+    /// Panics if the map has more small caves than fit in the visited set
+    pub fn new(map: &'m CaveMap) -> PathCounter<'m> {
+        PathCounter::with_policy(map, &VisitPolicy::default())
+    }
+
+    /// Counting paths under the limits of `policy`. Whether the revisit is
+    /// allowed is still chosen per call.
+    pub fn with_policy(map: &'m CaveMap, policy: &VisitPolicy) -> PathCounter<'m> {
+        let caves: Vec<Cave> = map.names.iter().map(|(_, name)| Cave::from(name)).collect();
+        let links = map.links.iter().map(|l| l.iter().map(|n| n.index()).collect()).collect();
+
+        let mut bits = vec!();
+        let mut masks = vec!();
+        let mut used = 0;
+        for c in &caves {
+            let width = policy.limit(c).map_or(0, |n| usize::BITS - n.leading_zeros());
+            if width == 0 {
+                bits.push(0);
+                masks.push(0);
+                continue;
+            }
+            assert!(used + width <= Visited::BITS, "too many small caves to count paths");
+            bits.push(1 << used);
+            masks.push(((1 << width) - 1) << used);
+            used += width;
+        }
+
+        let start = map.names.get("start").map(Symbol::index).expect("no start cave");
+        PathCounter { caves, links, policy: policy.clone(), bits, masks, start, memo: HashMap::new() }
+    }
+
+    fn start_state(&self, revisit: bool) -> State {
+        State { cave: self.start, visited: self.bits[self.start], can_revisit: revisit }
+    }
+
+    /// Where stepping to `next` leads: `Some(None)` for the end, `None` if
+    /// it isn't allowed. Mirrors the rules `PathWalker` applies.
+    fn step(&self, from: State, next: usize) -> Option<Option<State>> {
+        let cave = self.caves[next];
+        if cave.is_end() {
+            return (self.policy.visit(&cave, 0, false) != Visit::Refused).then_some(None);
+        }
+        let visits = (from.visited & self.masks[next]).checked_div(self.bits[next]).unwrap_or(0);
+        match self.policy.visit(&cave, visits as usize, from.can_revisit) {
+            Visit::Allowed => Some(Some(State { cave: next, visited: from.visited + self.bits[next], can_revisit: from.can_revisit })),
+            Visit::Revisit => Some(Some(State { cave: next, visited: from.visited, can_revisit: false })),
+            Visit::Refused => None,
+        }
+    }
+
+    /// Paths from `state` to the end
+    fn completions(&mut self, state: State) -> u64 {
+        if let Some(&n) = self.memo.get(&state) {
+            return n;
+        }
+        let mut total: u64 = 0;
+        for i in 0..self.links[state.cave].len() {
+            let n = match self.step(state, self.links[state.cave][i]) {
+                Some(None) => 1,
+                Some(Some(next)) => self.completions(next),
+                None => 0,
+            };
+            total = total.checked_add(n).expect("too many paths to count");
+        }
+        self.memo.insert(state, total);
+        total
+    }
+
+    /// Number of paths, optionally allowing a single small cave to be
+    /// visited twice, as `CaveMap::count_paths` gives
+    pub fn count(&mut self, revisit: bool) -> u64 {
+        self.completions(self.start_state(revisit))
+    }
+
+    /// The path `rank` places along in the order the walker finds them.
+    /// `rank` must be less than `count(revisit)`.
+    pub fn path(&mut self, revisit: bool, mut rank: u64) -> Vec<Cave<'m>> {
+        let mut state = self.start_state(revisit);
+        let mut path = vec!(self.caves[self.start]);
+        loop {
+            let mut chosen = None;
+            for i in 0..self.links[state.cave].len() {
+                let next = self.links[state.cave][i];
+                let (n, to) = match self.step(state, next) {
+                    Some(None) => (1, None),
+                    Some(Some(to)) => (self.completions(to), Some(to)),
+                    None => continue,
+                };
+                if rank < n {
+                    chosen = Some((next, to));
+                    break;
+                }
+                rank -= n;
+            }
+
+            let (next, to) = chosen.expect("rank past the last path");
+            path.push(self.caves[next]);
+            match to {
+                Some(to) => state = to,
+                None => return path,
+            }
+        }
+    }
+
+    /// `k` distinct paths chosen uniformly at random, or every path if there
+    /// are no more than `k`, in the walker's order
+    pub fn sample(&mut self, revisit: bool, k: u64, rng: &mut Rng) -> Vec<Vec<Cave<'m>>> {
+        let count = self.count(revisit);
+        let ranks: BTreeSet<u64> = if k >= count {
+            (0..count).collect()
+        } else {
+            // Floyd's algorithm: k distinct ranks from k draws
+            let mut ranks = BTreeSet::new();
+            for j in count - k..count {
+                let t = rng.below(j + 1);
+                if !ranks.insert(t) {
+                    ranks.insert(j);
+                }
+            }
+            ranks
+        };
+        ranks.into_iter().map(|r| self.path(revisit, r)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLES: [(&str, u64, u64); 3] = [
+        (include_str!("../input_simple"), 10, 36),
+        (include_str!("../input2"), 19, 103),
+        (include_str!("../input3"), 226, 3509),
+    ];
+
+    #[test]
+    fn counts_match_walker() {
+        for (input, no_revisit, revisit) in EXAMPLES {
+            let map = CaveMap::from_input(input.lines().map(String::from));
+            let mut counter = PathCounter::new(&map);
+            assert_eq!(counter.count(false), no_revisit);
+            assert_eq!(counter.count(true), revisit);
+            assert_eq!(counter.count(true), map.count_paths(true) as u64);
+        }
+    }
+
+    #[test]
+    fn counts_with_limits_match_walker() {
+        let policy = VisitPolicy::parse_rules("cave dc may be visited 3 times\ncave HN may be visited twice\ncave kj may not be visited").unwrap();
+        let map = CaveMap::from_input(EXAMPLES[1].0.lines().map(String::from));
+        let mut counter = PathCounter::with_policy(&map, &policy);
+        for revisit in [false, true] {
+            let policy = policy.clone().with_revisit(revisit);
+            assert_eq!(counter.count(revisit), map.count_paths_with(&policy) as u64);
+            for (rank, path) in map.paths_with(policy).enumerate() {
+                assert_eq!(counter.path(revisit, rank as u64), path);
+            }
+        }
+    }
+
+    #[test]
+    fn ranks_follow_walker_order() {
+        let map = CaveMap::from_input(EXAMPLES[1].0.lines().map(String::from));
+        let mut counter = PathCounter::new(&map);
+        for (rank, path) in map.paths().enumerate() {
+            assert_eq!(counter.path(true, rank as u64), path);
+        }
+    }
+
+    #[test]
+    fn samples_are_distinct_paths() {
+        let map = CaveMap::from_input(EXAMPLES[2].0.lines().map(String::from));
+        let mut counter = PathCounter::new(&map);
+        let all: Vec<_> = map.paths().collect();
+
+        let samples = counter.sample(true, 20, &mut Rng::seed_from(12));
+        assert_eq!(samples.len(), 20);
+        assert!(samples.windows(2).all(|w| w[0] != w[1]));
+        assert!(samples.iter().all(|s| all.contains(s)));
+
+        assert_eq!(counter.sample(false, 1000, &mut Rng::seed_from(12)).len(), 226);
+    }
+}
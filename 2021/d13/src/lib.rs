@@ -0,0 +1,399 @@
+use std::collections::HashSet;
+use std::cmp;
+use std::fmt;
+
+use aoc_core::color::{paint, Color};
+use aoc_core::{AocError, Solver};
+use aoc_geometry::Point2;
+
+/// Dot marked on the paper, written `x,y` in the input
+pub type Dot = Point2<i32>;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Fold {
+    Horizontal(i32),
+    Vertical(i32),
+}
+
+impl Fold {
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Fold> {
+        let mut it = s.split('=');
+        let axis = it.next()?.chars().last()?;
+        let n = it.next()?.parse::<i32>().ok()?;
+
+        match axis {
+            'x' => Some(Fold::Horizontal(n)),
+            'y' => Some(Fold::Vertical(n)),
+            _ => None
+        }
+    }
+
+    /// Where `dot` ends up after this fold, and whether it moved
+    pub fn apply(&self, dot: Dot) -> (Dot, bool) {
+        match self {
+            Fold::Horizontal(x) if dot.x > *x => (Dot::new(x - (dot.x - x), dot.y), true),
+            Fold::Vertical(y) if dot.y > *y => (Dot::new(dot.x, y - (dot.y - y)), true),
+            _ => (dot, false),
+        }
+    }
+}
+
+impl fmt::Display for Fold {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let v = match self {
+            Fold::Horizontal(n) => ('x', n),
+            Fold::Vertical(n) => ('y', n),
+        };
+        write!(f, "fold along {}={}", v.0, v.1)
+    }
+}
+
+pub fn fold_paper(paper: &mut HashSet<Dot>, fold: &Fold) -> usize {
+    let mut moves = vec!();
+    for dot in paper.iter() {
+        if let (d, true) = fold.apply(*dot) {
+            moves.push((*dot, d));
+        }
+    }
+
+    for (from, to) in moves {
+        //println!("{} -> {}", to, from);
+        paper.insert(to);
+        paper.remove(&from);
+    }
+
+    paper.len()
+}
+
+/// Sheet of transparent paper with dots marked on it
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct Paper {
+    dots: HashSet<Dot>,
+}
+
+impl Paper {
+    pub fn dots(&self) -> &HashSet<Dot> { &self.dots }
+
+    pub fn len(&self) -> usize { self.dots.len() }
+
+    pub fn is_empty(&self) -> bool { self.dots.is_empty() }
+
+    /// Largest x and y coordinates of any dot
+    pub fn extent(&self) -> (i32, i32) {
+        self.dots.iter().fold((0, 0), |a, d| (cmp::max(a.0, d.x), cmp::max(a.1, d.y)))
+    }
+
+    pub fn fold(&mut self, fold: &Fold) -> usize {
+        fold_paper(&mut self.dots, fold)
+    }
+
+    pub fn folded(&self, fold: &Fold) -> Paper {
+        let mut paper = self.clone();
+        paper.fold(fold);
+        paper
+    }
+
+    /// Largest paper that folds into this one: every dot is mirrored across
+    /// the fold line as well as kept in place
+    pub fn unfold(&self, fold: &Fold) -> Paper {
+        let mirrored = self.dots.iter().filter_map(|d| match fold {
+            Fold::Horizontal(x) if d.x < *x => Some(Dot::new(x + (x - d.x), d.y)),
+            Fold::Vertical(y) if d.y < *y => Some(Dot::new(d.x, y + (y - d.y))),
+            _ => None,
+        });
+        self.dots.iter().copied().chain(mirrored).collect()
+    }
+
+    /// Search for up to `max_folds` folds that turn this paper into `target`.
+    /// Fold lines never pass through a dot and always leave every dot at a
+    /// non-negative coordinate, as in the puzzle.
+    pub fn infer_folds(&self, target: &Paper, max_folds: usize) -> Option<Vec<Fold>> {
+        let mut folds = Vec::with_capacity(max_folds);
+        if self.search_folds(target, target.extent(), max_folds, &mut folds) {
+            Some(folds)
+        } else {
+            None
+        }
+    }
+
+    fn search_folds(&self, target: &Paper, target_extent: (i32, i32), remaining: usize, folds: &mut Vec<Fold>) -> bool {
+        if self == target {
+            return true;
+        }
+
+        let (max_x, max_y) = self.extent();
+        if remaining == 0 || self.len() < target.len() || max_x < target_extent.0 || max_y < target_extent.1 {
+            return false;
+        }
+
+        let x_folds = (cmp::max(target_extent.0 + 1, (max_x + 1) / 2)..max_x)
+            .filter(|&x| !self.dots.iter().any(|d| d.x == x))
+            .map(Fold::Horizontal);
+        let y_folds = (cmp::max(target_extent.1 + 1, (max_y + 1) / 2)..max_y)
+            .filter(|&y| !self.dots.iter().any(|d| d.y == y))
+            .map(Fold::Vertical);
+        let candidates: Vec<Fold> = x_folds.chain(y_folds).collect();
+
+        for fold in candidates {
+            folds.push(fold);
+            if self.folded(&fold).search_folds(target, target_extent, remaining - 1, folds) {
+                return true;
+            }
+            folds.pop();
+        }
+        false
+    }
+}
+
+impl From<HashSet<Dot>> for Paper {
+    fn from(dots: HashSet<Dot>) -> Self {
+        Paper { dots }
+    }
+}
+
+impl FromIterator<Dot> for Paper {
+    fn from_iter<I: IntoIterator<Item = Dot>>(iter: I) -> Self {
+        Paper { dots: iter.into_iter().collect() }
+    }
+}
+
+impl Paper {
+    /// Dots drawn as `#` on a `.` background
+    pub fn render(&self) -> String {
+        let (width, height) = self.extent();
+
+        let (width, height) = ((width + 1) as usize, (height + 1) as usize);
+
+        let mut grid = vec![false; width * height];
+        for d in self.dots().iter() {
+            let i = (d.y * width as i32 + d.x) as usize;
+            grid[i] = true;
+        }
+
+        let mut s = String::with_capacity((width + 1) * height);
+        for y in 0..height {
+            for x in 0..width {
+                s.push(if grid[y * width + x] { '#' } else { '.' });
+            }
+            s.push('\n');
+        }
+        s
+    }
+}
+
+/// The paper as `render` draws it, with the dots picked out when colour is
+/// on so the folded letters are easier to read
+impl fmt::Display for Paper {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for c in self.render().chars() {
+            match c {
+                '#' => write!(f, "{}", paint(c, Color::Yellow).bold())?,
+                c => write!(f, "{}", c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How one paper's dots differ from another's
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct DotDiff {
+    /// Dots on both
+    pub kept: Vec<Dot>,
+    /// Dots only on the second paper
+    pub added: Vec<Dot>,
+    /// Dots only on the first paper
+    pub removed: Vec<Dot>,
+    /// Dots only on the first paper paired with one only on the second in
+    /// the same row or column, closest pairs first. These are also in
+    /// `removed` and `added`.
+    pub moved: Vec<(Dot, Dot)>,
+}
+
+impl DotDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+
+    /// Both papers drawn over each other: `#` for a dot on both, `+` for one
+    /// only on the second, `-` for one only on the first
+    pub fn render(&self) -> String {
+        let all = || self.kept.iter().chain(&self.added).chain(&self.removed);
+        let width = all().map(|d| d.x + 1).max().unwrap_or(0) as usize;
+        let height = all().map(|d| d.y + 1).max().unwrap_or(0) as usize;
+
+        let mut grid = vec![b'.'; width * height];
+        for (dots, mark) in [(&self.kept, b'#'), (&self.added, b'+'), (&self.removed, b'-')] {
+            for d in dots {
+                grid[d.y as usize * width + d.x as usize] = mark;
+            }
+        }
+        let mut s = String::with_capacity((width + 1) * height);
+        for row in grid.chunks(width.max(1)) {
+            s.push_str(std::str::from_utf8(row).unwrap());
+            s.push('\n');
+        }
+        s
+    }
+}
+
+/// Moves, then whatever else was removed or added, with dots written `x,y`
+/// as in the input, then the drawing
+impl fmt::Display for DotDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "no differences in {} dots", self.kept.len());
+        }
+        for (from, to) in &self.moved {
+            writeln!(f, "moved {},{} -> {},{}", from.x, from.y, to.x, to.y)?;
+        }
+        for d in self.removed.iter().filter(|d| !self.moved.iter().any(|(from, _)| from == *d)) {
+            writeln!(f, "removed {},{}", d.x, d.y)?;
+        }
+        for d in self.added.iter().filter(|d| !self.moved.iter().any(|(_, to)| to == *d)) {
+            writeln!(f, "added {},{}", d.x, d.y)?;
+        }
+        write!(f, "{}", self.render())
+    }
+}
+
+impl Paper {
+    /// What changed going from this paper to `other`, each list in reading
+    /// order
+    pub fn diff(&self, other: &Paper) -> DotDiff {
+        let sorted = |dots: Vec<&Dot>| {
+            let mut dots: Vec<Dot> = dots.into_iter().copied().collect();
+            dots.sort_by_key(|d| (d.y, d.x));
+            dots
+        };
+        let kept = sorted(self.dots.intersection(&other.dots).collect());
+        let added = sorted(other.dots.difference(&self.dots).collect());
+        let removed = sorted(self.dots.difference(&other.dots).collect());
+
+        let mut pairs: Vec<(i32, Dot, Dot)> = removed.iter()
+            .flat_map(|&from| added.iter().map(move |&to| (from.manhattan(to), from, to)))
+            .filter(|(_, from, to)| from.x == to.x || from.y == to.y)
+            .collect();
+        pairs.sort_by_key(|&(distance, from, to)| (distance, from.y, from.x, to.y, to.x));
+        let mut moved = vec!();
+        let (mut from_used, mut to_used) = (HashSet::new(), HashSet::new());
+        for (_, from, to) in pairs {
+            if !from_used.contains(&from) && !to_used.contains(&to) {
+                from_used.insert(from);
+                to_used.insert(to);
+                moved.push((from, to));
+            }
+        }
+
+        DotDiff { kept, added, removed, moved }
+    }
+}
+
+/// Dots followed by a blank line and the fold instructions
+pub fn parse_input(input: &str) -> Result<(Paper, Vec<Fold>), AocError> {
+    let mut blocks = aoc_input::split_blank_blocks(input.as_bytes())?.into_iter();
+    let paper = match blocks.next() {
+        Some(block) => block.parse_each(str::parse::<Dot>)?.into_iter().collect(),
+        None => Paper::default(),
+    };
+    let folds = match blocks.next() {
+        Some(block) => block.parse_each(|l| Fold::from_str(l).ok_or("expected fold along x=N or y=N"))?,
+        None => vec!(),
+    };
+    Ok((paper, folds))
+}
+
+pub struct Puzzle {
+    paper: Paper,
+    folds: Vec<Fold>,
+}
+
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        let (paper, folds) = parse_input(input)?;
+        Ok(Puzzle { paper, folds })
+    }
+
+    /// `-` with no folds to make
+    fn part1(&self) -> String {
+        self.folds.first().map_or_else(|| "-".to_string(), |f| self.paper.folded(f).len().to_string())
+    }
+
+    fn part2(&self) -> String {
+        self.folds.iter().fold(self.paper.clone(), |p, f| p.folded(f)).render()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../input0");
+
+    fn example() -> (Paper, Vec<Fold>) {
+        parse_input(EXAMPLE).unwrap()
+    }
+
+    #[test]
+    fn unfold_covers_original() {
+        let (paper, folds) = example();
+        let folded = paper.folded(&folds[0]);
+        let unfolded = folded.unfold(&folds[0]);
+
+        assert!(paper.dots().is_subset(unfolded.dots()));
+        assert_eq!(unfolded.folded(&folds[0]), folded);
+    }
+
+    #[test]
+    fn infer_example_folds() {
+        let (paper, folds) = example();
+        let target = folds.iter().fold(paper.clone(), |p, f| p.folded(f));
+        assert_eq!(target.len(), 16);
+
+        let inferred = paper.infer_folds(&target, 2).unwrap();
+        let result = inferred.iter().fold(paper.clone(), |p, f| p.folded(f));
+        let diff = target.diff(&result);
+        assert!(diff.is_empty(), "{}", diff);
+        assert!(paper.infer_folds(&target, 1).is_none());
+    }
+
+    #[test]
+    fn diff_of_a_fold_off_by_one() {
+        let (paper, folds) = example();
+        let right = paper.folded(&folds[0]);
+        assert!(right.diff(&right).is_empty());
+        assert_eq!(right.diff(&right).to_string(), "no differences in 17 dots\n");
+
+        // folding along y=6 instead puts each folded dot a row lower
+        let wrong: Paper = paper.dots().iter().map(|&d| Dot::new(d.x, if d.y > 7 { 14 - d.y + 1 } else { d.y })).collect();
+        let diff = right.diff(&wrong);
+        assert!(!diff.is_empty());
+        assert!(diff.moved.iter().all(|(from, to)| from.x == to.x));
+        assert_eq!(diff.kept.len() + diff.removed.len(), right.len());
+        assert_eq!(diff.kept.len() + diff.added.len(), wrong.len());
+
+        let small: Paper = [Dot::new(0, 0), Dot::new(2, 0)].into_iter().collect();
+        let other: Paper = [Dot::new(0, 0), Dot::new(2, 1), Dot::new(1, 2)].into_iter().collect();
+        let diff = small.diff(&other);
+        assert_eq!(diff.moved, [(Dot::new(2, 0), Dot::new(2, 1))]);
+        assert_eq!(diff.render(), "#.-\n..+\n.+.\n");
+        assert_eq!(diff.to_string(), "moved 2,0 -> 2,1\nadded 1,2\n#.-\n..+\n.+.\n");
+    }
+
+    /// The answers given in the puzzle text for its example
+    #[test]
+    fn example_answers() {
+        let puzzle = Puzzle::parse(include_str!("../input0"));
+        assert_eq!(puzzle.part1(), "17");
+        assert_eq!(puzzle.part2(), "#####\n#...#\n#...#\n#...#\n#####\n");
+    }
+
+    #[test]
+    fn malformed_lines() {
+        let err = Puzzle::try_parse("6,10\n0,14\n\nfold along y=7\nfold along z=3\n").err().unwrap();
+        assert_eq!(err.to_string(), "line 5: expected fold along x=N or y=N");
+        assert!(Puzzle::try_parse("6,10\n0;14\n").err().unwrap().to_string().starts_with("line 2: "));
+        assert_eq!(Puzzle::parse("6,10\n0,14\n").part1(), "-");
+    }
+}
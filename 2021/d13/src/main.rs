@@ -0,0 +1,53 @@
+use std::io::Read;
+use std::path::PathBuf;
+
+use aoc_render::Raster;
+use d13::*;
+
+fn main() {
+    // --render <path> draws the paper after every fold, to a PNG of the
+    // result or a GIF of the lot; --color picks out the folded letters
+    let mut render: Option<PathBuf> = None;
+    let (path, args) = aoc_core::input_arg();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--render" => render = Some(args.next().expect("--render needs a path").into()),
+            "--color" => aoc_core::color::enable(true),
+            _ => panic!("unknown argument: {}", arg),
+        }
+    }
+
+    let mut input = String::new();
+    aoc_core::open_input(&path).read_to_string(&mut input).unwrap();
+
+    let (mut dots, folds) = parse_input(&input).unwrap();
+
+    /*
+    for d in &dots {
+        println!("{}", d);
+    }
+    */
+    //print!("{}", dots.render());
+    println!("starting  with {} dots", dots.len());
+    /*
+    for f in &folds {
+        println!("{}", f);
+    }
+    */
+
+    println!();
+
+    let mut frames = vec!(Raster::from_points(dots.dots().iter().copied()));
+    for f in &folds {
+        dots.fold(f);
+        println!("after {}: {} dots", f, dots.len());
+        frames.push(Raster::from_points(dots.dots().iter().copied()));
+    }
+
+    print!("{}", dots);
+
+    if let Some(path) = render {
+        aoc_render::write(&path, &frames, 4).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+    }
+}
@@ -0,0 +1,171 @@
+//! What a rule set does in the long run, without growing any polymer. Rules
+//! link elements: `AB -> C` makes `C` appear next to `A` and `B`. Pair
+//! counts evolve linearly, so after enough steps the mix of elements settles
+//! on the dominant eigenvector of the pair transition matrix.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::{Element, InsertionMap, Polymer};
+
+/// Every element named by a rule or the template
+fn elements(seed: &Polymer, map: &InsertionMap) -> BTreeSet<Element> {
+    map.iter()
+        .flat_map(|(&(a, b), &e)| [a, b, e])
+        .chain(seed.0.iter().copied())
+        .collect()
+}
+
+/// Pairs of known elements that no rule covers, so they never grow
+pub fn missing_rules(seed: &Polymer, map: &InsertionMap) -> Vec<(Element, Element)> {
+    let elements = elements(seed, map);
+    elements.iter()
+        .flat_map(|&a| elements.iter().map(move |&b| (a, b)))
+        .filter(|pair| !map.contains_key(pair))
+        .collect()
+}
+
+/// Elements no rule inserts: their counts never change from the template
+pub fn sources(seed: &Polymer, map: &InsertionMap) -> BTreeSet<Element> {
+    let inserted: BTreeSet<Element> = map.values().copied().collect();
+    elements(seed, map).into_iter().filter(|e| !inserted.contains(e)).collect()
+}
+
+/// Elements in no pair that has a rule: nothing is ever inserted beside them
+/// on their account
+pub fn sinks(seed: &Polymer, map: &InsertionMap) -> BTreeSet<Element> {
+    let linking: BTreeSet<Element> = map.keys().flat_map(|&(a, b)| [a, b]).collect();
+    elements(seed, map).into_iter().filter(|e| !linking.contains(e)).collect()
+}
+
+/// Sparse pair transition matrix over the pairs reachable from a template:
+/// each pair becomes the pairs listed for it in the next step
+pub struct PairMatrix {
+    pairs: Vec<(Element, Element)>,
+    next: Vec<Vec<usize>>,
+}
+
+impl PairMatrix {
+    pub fn new(seed: &Polymer, map: &InsertionMap) -> PairMatrix {
+        let mut index = HashMap::new();
+        let mut pairs = vec!();
+        let mut next = vec!();
+        let mut todo: Vec<(Element, Element)> = seed.0.windows(2).map(|w| (w[0], w[1])).collect();
+        while let Some(pair) = todo.pop() {
+            if index.contains_key(&pair) {
+                continue;
+            }
+            index.insert(pair, pairs.len());
+            pairs.push(pair);
+            next.push(match map.get(&pair) {
+                Some(&e) => vec!((pair.0, e), (e, pair.1)),
+                None => vec!(pair),
+            });
+            todo.extend(next.last().unwrap());
+        }
+
+        let next = next.into_iter()
+            .map(|to: Vec<(Element, Element)>| to.iter().map(|p| index[p]).collect())
+            .collect();
+        PairMatrix { pairs, next }
+    }
+
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    fn apply(&self, v: &[f64]) -> Vec<f64> {
+        let mut out = vec!(0.0; v.len());
+        for (i, to) in self.next.iter().enumerate() {
+            for &j in to {
+                out[j] += v[i];
+            }
+        }
+        out
+    }
+
+    /// Share of each element once the pair mix stops changing, found by
+    /// power iteration from the template's pairs. Gives up after `limit`
+    /// steps if the mix keeps oscillating.
+    pub fn steady_state(&self, seed: &Polymer, limit: usize) -> BTreeMap<Element, f64> {
+        let mut v = vec!(0.0; self.len());
+        for w in seed.0.windows(2) {
+            v[self.pairs.iter().position(|&p| p == (w[0], w[1])).unwrap()] += 1.0;
+        }
+        normalize(&mut v);
+
+        for _ in 0..limit {
+            let mut next = self.apply(&v);
+            normalize(&mut next);
+            let change = v.iter().zip(&next).map(|(a, b)| (a - b).abs()).fold(0.0, f64::max);
+            v = next;
+            if change < 1e-12 {
+                break;
+            }
+        }
+
+        // every element but the last starts exactly one pair
+        let mut shares = BTreeMap::new();
+        for (&(a, _), share) in self.pairs.iter().zip(v) {
+            *shares.entry(a).or_insert(0.0) += share;
+        }
+        shares
+    }
+}
+
+fn normalize(v: &mut [f64]) {
+    let total: f64 = v.iter().sum();
+    if total > 0.0 {
+        v.iter_mut().for_each(|x| *x /= total);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_input, ElementCount, PolymerData};
+
+    const EXAMPLE: &str = include_str!("../input_test");
+
+    #[test]
+    fn example_rules_cover_every_pair() {
+        let (seed, map) = parse_input(EXAMPLE);
+        assert!(missing_rules(&seed, &map).is_empty());
+        assert!(sources(&seed, &map).is_empty());
+        assert!(sinks(&seed, &map).is_empty());
+    }
+
+    #[test]
+    fn uncovered_pairs_and_lonely_elements() {
+        let map = InsertionMap::from([((b'A', b'B'), b'C'), ((b'C', b'B'), b'A')]);
+        let seed = Polymer::from("ABD");
+        assert_eq!(sources(&seed, &map), BTreeSet::from([b'B', b'D']));
+        assert_eq!(sinks(&seed, &map), BTreeSet::from([b'D']));
+        assert!(missing_rules(&seed, &map).contains(&(b'B', b'D')));
+        assert!(!missing_rules(&seed, &map).contains(&(b'A', b'B')));
+    }
+
+    #[test]
+    fn steady_state_matches_long_runs() {
+        let (seed, map) = parse_input(EXAMPLE);
+        let shares = PairMatrix::new(&seed, &map).steady_state(&seed, 1000);
+        assert!((shares.values().sum::<f64>() - 1.0).abs() < 1e-9);
+
+        let mut data = PolymerData::from(&seed);
+        for _ in 0..40 {
+            data = data.with_insertions(&map);
+        }
+        let tally = data.tally();
+        // by way of text, as bigint counts have no `as f64`
+        let as_f64 = |count: &ElementCount| count.to_string().parse::<f64>().unwrap();
+        let total: f64 = tally.values().map(as_f64).sum();
+        // C is dying out, but still a fifth of a percent after 40 steps
+        assert!(shares[&b'C'] < 1e-9);
+        for (e, count) in &tally {
+            assert!((shares[e] - as_f64(count) / total).abs() < 5e-3, "{}", *e as char);
+        }
+    }
+}
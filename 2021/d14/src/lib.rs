@@ -0,0 +1,103 @@
+extern crate alloc;
+
+use aoc_core::{option_value, AocError, Solver, Strategies, Strategy};
+
+mod analysis;
+mod polymer;
+pub use analysis::{missing_rules, sinks, sources, PairMatrix};
+pub use polymer::{parse_input, parse_map, score, Element, ElementCount, InsertionMap, Polymer, PolymerData};
+
+pub struct Puzzle {
+    seed: Polymer,
+    map: InsertionMap,
+    /// Insertion steps for each part
+    steps: [usize; 2],
+    /// Grow the polymer itself rather than counting its pairs
+    string: bool,
+}
+
+impl Puzzle {
+    fn score_after(&self, steps: usize) -> ElementCount {
+        if self.string {
+            let grown = (0..steps).fold(self.seed.clone(), |p, _| p.with_insertions(&self.map));
+            return score(&grown.tally());
+        }
+        let mut next = PolymerData::from(&self.seed);
+        for _ in 0..steps {
+            next = next.with_insertions(&self.map);
+        }
+        score(&next.tally())
+    }
+}
+
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        let (seed, map) = parse_input(input);
+        Ok(Puzzle { seed, map, steps: [10, 40], string: false })
+    }
+
+    fn strategies() -> Strategies {
+        Strategies {
+            algos: &[
+                Strategy { name: "pairs", about: "count the pairs in the polymer, step by step" },
+                Strategy { name: "string", about: "grow the polymer itself, doubling it every step (too big for part 2)" },
+            ],
+            impls: &[],
+        }
+    }
+
+    /// `part1_steps`, `part2_steps`, and `algo`: `pairs` or `string`
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), AocError> {
+        match name {
+            "part1_steps" => self.steps[0] = option_value(name, value)?,
+            "part2_steps" => self.steps[1] = option_value(name, value)?,
+            "algo" => self.string = match value {
+                "pairs" => false,
+                "string" => true,
+                _ => return Err(AocError::invalid(format!("bad value {:?} for option algo", value))),
+            },
+            _ => return Err(AocError::invalid(format!("no option {}", name))),
+        }
+        Ok(())
+    }
+
+    fn part1(&self) -> String {
+        self.score_after(self.steps[0]).to_string()
+    }
+
+    fn part2(&self) -> String {
+        self.score_after(self.steps[1]).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The answers given in the puzzle text for its example
+    #[test]
+    fn example_answers() {
+        let puzzle = Puzzle::parse(include_str!("../input_test"));
+        assert_eq!(puzzle.part1(), "1588");
+        assert_eq!(puzzle.part2(), "2188189693529");
+    }
+
+    #[test]
+    fn steps_are_an_option() {
+        let mut puzzle = Puzzle::parse(include_str!("../input_test"));
+        puzzle.set_option("part2_steps", "10").unwrap();
+        assert_eq!(puzzle.part2(), "1588");
+        assert!(puzzle.set_option("part1_steps", "-1").is_err());
+
+        puzzle.set_option("algo", "string").unwrap();
+        assert_eq!(puzzle.part2(), "1588");
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn scores_far_past_usize() {
+        let mut puzzle = Puzzle::parse(include_str!("../input_test"));
+        puzzle.set_option("part2_steps", "200").unwrap();
+        assert_eq!(puzzle.part2(), "3213876088504849459628933947091293102955756486973947218116623");
+    }
+}
@@ -0,0 +1,57 @@
+use std::io::BufRead;
+
+use d14::*;
+
+fn show(elements: impl IntoIterator<Item = Element>) -> String {
+    let s: String = elements.into_iter().map(|e| e as char).collect();
+    if s.is_empty() { "none".into() } else { s }
+}
+
+/// Print what the rules do in the long run
+fn analyze(seed: &Polymer, map: &InsertionMap) {
+    let missing = missing_rules(seed, map);
+    let missing: Vec<String> = missing.iter().map(|&(a, b)| show([a, b])).collect();
+    println!("pairs without a rule: {}", if missing.is_empty() { "none".into() } else { missing.join(" ") });
+    println!("sources (never inserted): {}", show(sources(seed, map)));
+    println!("sinks (never cause an insertion): {}", show(sinks(seed, map)));
+
+    let matrix = PairMatrix::new(seed, map);
+    println!("steady state over {} reachable pairs:", matrix.len());
+    for (e, share) in matrix.steady_state(seed, 10_000) {
+        println!("  {}: {:.6}", e as char, share);
+    }
+}
+
+fn main() {
+    // --analyze reports missing rules, sources, sinks and the steady-state
+    // element mix before the score
+    let mut analysis = false;
+    let (input, args) = aoc_core::input_arg();
+    for arg in args {
+        match arg.as_str() {
+            "--analyze" => analysis = true,
+            _ => panic!("unknown argument: {}", arg),
+        }
+    }
+
+    let input = aoc_core::open_input(&input);
+    let mut it = input.lines().map(|l| l.unwrap());
+
+    let seed = Polymer::from(it.next().unwrap().trim_end());
+    it.next();
+    let map = parse_map(it);
+
+    if analysis {
+        analyze(&seed, &map);
+    }
+
+    //let mut next = seed;
+    let mut next = PolymerData::from(&seed);
+    println!("0: {} {:?}", &seed, &next);
+    for _i in 1..=40 {
+        next = next.with_insertions(&map);
+        //println!("{}: {:?}", _i, next);
+    }
+
+    println!("score: {}", score(&next.tally()));
+}
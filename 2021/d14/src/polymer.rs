@@ -0,0 +1,166 @@
+//! Growing polymers, both as the string itself and as counts of the pairs
+//! in it. Only `core` and `alloc` are used, with pairs kept in `BTreeMap`s,
+//! so this works without `std`.
+#![deny(clippy::std_instead_of_core, clippy::std_instead_of_alloc)]
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str;
+
+pub type Element = u8;
+#[cfg(not(feature = "bigint"))]
+pub type ElementCount = usize;
+/// Without a limit, for running many more steps than the puzzle's 40
+#[cfg(feature = "bigint")]
+pub type ElementCount = num_bigint::BigUint;
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Polymer(pub(crate) Vec<Element>);
+
+impl Polymer {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn with_insertions(&self, map: &InsertionMap) -> Polymer {
+        let mut next = Vec::with_capacity(self.len() * 2);
+
+        for s in self.0.windows(2) {
+            let a = s[0];
+            let b = s[1];
+
+            next.push(a);
+
+            if let Some(&e) = map.get(&(a, b)) {
+                next.push(e);
+            }
+        }
+        next.push(*self.0.last().unwrap());
+
+        Polymer(next)
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from<S>(s: S) -> Polymer
+    where S: AsRef<str>
+    {
+        Polymer(s.as_ref().bytes().collect())
+    }
+
+    pub fn tally(&self) -> BTreeMap<Element, ElementCount> {
+        let mut map: BTreeMap<_, ElementCount> = BTreeMap::new();
+        for &e in &self.0 {
+            *map.entry(e).or_default() += ElementCount::from(1u8);
+        }
+        map
+    }
+}
+
+impl fmt::Display for Polymer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", str::from_utf8(self.0.as_slice()).unwrap())
+    }
+}
+
+pub struct PolymerData(BTreeMap<(Element, Element), ElementCount>);
+
+impl PolymerData {
+    #[allow(clippy::should_implement_trait)]
+    pub fn from(p: &Polymer) -> PolymerData {
+        let mut counts: BTreeMap<_, ElementCount> = BTreeMap::new();
+        counts.insert((0, p.0[0]), 1u8.into());
+        counts.insert((*p.0.last().unwrap(), 0), 1u8.into());
+
+        for s in p.0.windows(2) {
+            *counts.entry((s[0], s[1])).or_default() += ElementCount::from(1u8);
+        }
+
+        PolymerData(counts)
+    }
+
+    pub fn with_insertions(&self, map: &InsertionMap) -> PolymerData {
+        let mut next: BTreeMap<_, ElementCount> = BTreeMap::new();
+
+        for (&pair, v) in &self.0 {
+            match map.get(&pair) {
+                Some(&e) => {
+                    let (a, b) = pair;
+                    *next.entry((a, e)).or_default() += v;
+                    *next.entry((e, b)).or_default() += v;
+                },
+                None => { *next.entry(pair).or_default() += v; },
+            }
+        }
+
+        PolymerData(next)
+    }
+
+    pub fn tally(&self) -> BTreeMap<Element, ElementCount> {
+        let mut tally: BTreeMap<_, ElementCount> = BTreeMap::new();
+        for (pair, v) in &self.0 {
+            for e in [pair.0, pair.1] {
+                *tally.entry(e).or_default() += v;
+            }
+        }
+
+        tally.remove(&0);
+
+        for v in tally.values_mut() {
+            *v /= ElementCount::from(2u8);
+        }
+
+        tally
+    }
+}
+
+impl fmt::Debug for PolymerData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[")?;
+        for (pair, v) in &self.0 {
+            write!(f, "({}):{} ", str::from_utf8(&[pair.0, pair.1]).unwrap(), v)?;
+        }
+        write!(f, "]")?;
+        let tally = self.tally();
+        write!(f, "{{ ")?;
+        for (k, v) in tally {
+            write!(f, "{}:{} ", str::from_utf8(&[k]).unwrap(), v)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+pub type InsertionMap = BTreeMap<(Element, Element), Element>;
+
+pub fn parse_map(it: impl Iterator<Item = String>) -> InsertionMap {
+    let mut map = InsertionMap::new();
+
+    for s in it {
+        let bytes = s.as_bytes();
+        let a = bytes[0];
+        let b = bytes[1];
+        let insert = bytes[6];
+
+        map.insert((a, b), insert);
+    }
+
+    map
+}
+
+/// Difference between the most and least common element
+pub fn score(tally: &BTreeMap<Element, ElementCount>) -> ElementCount {
+    let none = ElementCount::default();
+    tally.values().max().unwrap_or(&none) - tally.values().min().unwrap_or(&none)
+}
+
+pub fn parse_input(input: &str) -> (Polymer, InsertionMap) {
+    let mut it = input.lines().map(String::from);
+    let seed = Polymer::from(it.next().unwrap().trim_end());
+    it.next();
+    (seed, parse_map(it))
+}
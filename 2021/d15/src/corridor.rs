@@ -0,0 +1,193 @@
+//! Cave search on a contracted graph: runs of cells with exactly two open
+//! neighbours are corridors with only one way through, so they become
+//! single weighted edges between the junctions at their ends. On maze-like
+//! caves that leaves far fewer nodes for Dijkstra to visit.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::{CaveMap, ChitonCave, Pos, Risk};
+
+struct Edge {
+    to: usize,
+    /// Risk of the corridor cells between the ends, not counting either end
+    inner: Risk,
+    /// The corridor cells, in order from this end
+    via: Vec<Pos>,
+}
+
+/// Junctions of a cave, joined by the corridors between them
+pub struct CorridorGraph {
+    nodes: Vec<Pos>,
+    /// Risk of entering each node
+    risks: Vec<Risk>,
+    edges: Vec<Vec<Edge>>,
+    entrance: usize,
+    exit: usize,
+}
+
+fn open_neighbours<C: ChitonCave>(cave: &C, p: Pos) -> impl Iterator<Item = Pos> + '_ {
+    cave.neighbours(p).filter(|&n| cave.risk(n).is_some())
+}
+
+/// Cells with exactly two ways in or out, other than the entrance and exit
+fn is_corridor<C: ChitonCave>(cave: &C, p: Pos) -> bool {
+    p != cave.entrance() && p != cave.exit() && open_neighbours(cave, p).count() == 2
+}
+
+impl CorridorGraph {
+    pub fn build<C: ChitonCave>(cave: &C) -> CorridorGraph {
+        let mut index = HashMap::new();
+        let mut nodes = vec!();
+        let mut risks = vec!();
+        for y in 0..cave.dim() {
+            for x in 0..cave.dim() {
+                let p = Pos::new(x, y);
+                if let Some(r) = cave.risk(p) {
+                    if !is_corridor(cave, p) {
+                        index.insert(p, nodes.len());
+                        nodes.push(p);
+                        risks.push(r);
+                    }
+                }
+            }
+        }
+
+        let mut edges: Vec<Vec<Edge>> = Vec::with_capacity(nodes.len());
+        for &start in &nodes {
+            let mut out = vec!();
+            for first in open_neighbours(cave, start) {
+                let (mut prev, mut at) = (start, first);
+                let mut inner = 0;
+                let mut via = vec!();
+                while is_corridor(cave, at) {
+                    inner += cave.risk(at).unwrap();
+                    via.push(at);
+                    let next = open_neighbours(cave, at).find(|&n| n != prev).unwrap();
+                    (prev, at) = (at, next);
+                }
+                if at != start {
+                    out.push(Edge { to: index[&at], inner, via });
+                }
+            }
+            edges.push(out);
+        }
+
+        CorridorGraph {
+            entrance: index[&cave.entrance()],
+            exit: index[&cave.exit()],
+            nodes,
+            risks,
+            edges,
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.iter().map(Vec::len).sum()
+    }
+
+    /// Lowest risk path from entrance to exit, expanded back into cells, as
+    /// `CaveMap::best_path` would find it
+    pub fn best_path(&self) -> Option<(Vec<Pos>, Risk)> {
+        let mut risk: Vec<Option<Risk>> = vec![None; self.nodes.len()];
+        // the node and edge each node was reached by
+        let mut came_from: Vec<Option<(usize, usize)>> = vec![None; self.nodes.len()];
+        let mut queue = BinaryHeap::from([Reverse((0, self.entrance))]);
+        risk[self.entrance] = Some(0);
+
+        while let Some(Reverse((r, node))) = queue.pop() {
+            if node == self.exit {
+                return Some((self.path_to(node, &came_from), r));
+            }
+            if risk[node].is_some_and(|best| r > best) {
+                continue;
+            }
+            for (i, edge) in self.edges[node].iter().enumerate() {
+                let next = r + edge.inner + self.risks[edge.to];
+                if risk[edge.to].is_none_or(|best| next < best) {
+                    risk[edge.to] = Some(next);
+                    came_from[edge.to] = Some((node, i));
+                    queue.push(Reverse((next, edge.to)));
+                }
+            }
+        }
+        None
+    }
+
+    fn path_to(&self, mut node: usize, came_from: &[Option<(usize, usize)>]) -> Vec<Pos> {
+        let mut path = vec!(self.nodes[node]);
+        while let Some((from, edge)) = came_from[node] {
+            path.extend(self.edges[from][edge].via.iter().rev());
+            path.push(self.nodes[from]);
+            node = from;
+        }
+        path.reverse();
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cave, ExtendedCave};
+
+    fn example() -> Cave {
+        Cave::from_reader(&mut include_str!("../input_test").lines())
+    }
+
+    /// The example with walls on every other column, each with one gap,
+    /// alternating between the top and bottom
+    fn maze() -> Cave {
+        let mut cave = example();
+        for x in (1..10).step_by(2) {
+            let gap = if x % 4 == 1 { 9 } else { 0 };
+            for y in (0..10).filter(|&y| y != gap) {
+                cave.block(Pos::new(x, y));
+            }
+        }
+        cave
+    }
+
+    fn assert_valid(cave: &impl ChitonCave, path: &[Pos], risk: Risk) {
+        assert_eq!(path.first(), Some(&cave.entrance()));
+        assert_eq!(path.last(), Some(&cave.exit()));
+        for pair in path.windows(2) {
+            assert_eq!(pair[0].manhattan(pair[1]), 1);
+        }
+        assert_eq!(path[1..].iter().map(|&p| cave.risk(p).unwrap()).sum::<Risk>(), risk);
+    }
+
+    #[test]
+    fn matches_grid_search() {
+        let cave = example();
+        let (path, risk) = CorridorGraph::build(&cave).best_path().unwrap();
+        assert_eq!(risk, 40);
+        assert_valid(&cave, &path, risk);
+
+        let extended = ExtendedCave::from_cave(&cave, 5);
+        assert_eq!(CorridorGraph::build(&extended).best_path().unwrap().1, 315);
+    }
+
+    #[test]
+    fn maze_contracts_to_few_nodes() {
+        let cave = maze();
+        let graph = CorridorGraph::build(&cave);
+        assert!(graph.node_count() < 30, "{} nodes", graph.node_count());
+
+        let (path, risk) = graph.best_path().unwrap();
+        assert_eq!(risk, cave.best_path().unwrap().1);
+        assert_valid(&cave, &path, risk);
+    }
+
+    #[test]
+    fn blocked_maze_has_no_path() {
+        let mut cave = maze();
+        cave.block(Pos::new(1, 9));
+        assert!(CorridorGraph::build(&cave).best_path().is_none());
+        assert!(cave.best_path().is_none());
+    }
+}
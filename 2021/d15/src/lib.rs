@@ -0,0 +1,332 @@
+use std::ops::{Deref};
+
+use aoc_core::rng::Rng;
+use aoc_core::{AocError, Solver, Strategies, Strategy};
+use aoc_grid::{Grid, Neighbors};
+
+pub use aoc_grid::Pos;
+
+mod corridor;
+pub use corridor::CorridorGraph;
+
+pub trait ChitonCave {
+    fn dim(&self) -> usize;
+
+    fn entrance(&self) -> Pos {
+        [0, 0].into()
+    }
+
+    fn exit(&self) -> Pos {
+        let d = self.dim();
+        [d-1, d-1].into()
+    }
+
+    /// Risk of entering `p`, or `None` if the cell is impassable
+    fn risk(&self, p: Pos) -> Option<Risk>;
+}
+
+/// How to look for the lowest risk path
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Search {
+    /// A*, with the distance to the exit as the heuristic
+    #[default]
+    AStar,
+    /// Dijkstra, cell by cell
+    Dijkstra,
+    /// Dijkstra over the junctions of a `CorridorGraph`
+    Corridor,
+}
+
+pub trait CaveMap {
+    fn best_path(&self) -> Option<(Vec<Pos>, Risk)> {
+        self.best_path_by(Search::AStar)
+    }
+
+    fn best_path_by(&self, search: Search) -> Option<(Vec<Pos>, Risk)>;
+    fn neighbours(&self, p: Pos) -> Neighbors;
+}
+
+impl<C: ChitonCave> CaveMap for C {
+    fn best_path_by(&self, search: Search) -> Option<(Vec<Pos>, Risk)> {
+        let successors = |&o: &Pos| self.neighbours(o).filter_map(|p| self.risk(p).map(|r| (p, r)));
+        match search {
+            Search::AStar => aoc_search::astar(
+                &self.entrance(),
+                successors,
+                |p| (self.dim() * 2 - p.x - p.y) as Risk,
+                |p| *p == self.exit()),
+            Search::Dijkstra => aoc_search::dijkstra(&self.entrance(), successors, |p| *p == self.exit()),
+            Search::Corridor => CorridorGraph::build(self).best_path(),
+        }
+    }
+
+    fn neighbours(&self, p: Pos) -> Neighbors {
+        Neighbors::orthogonal(p, self.dim(), self.dim())
+    }
+}
+
+pub struct Cave {
+    risks: Grid<Option<u8>>,
+}
+impl ChitonCave for Cave {
+    fn dim(&self) -> usize { self.risks.width() }
+
+    fn risk(&self, p: Pos) -> Option<Risk> {
+        self.risks[p].map(Risk::from)
+    }
+}
+impl Cave {
+    /// Change the risk of `p`, returning the previous value.
+    /// Risks must be at least 1 to keep the A* heuristic admissible.
+    pub fn set_risk(&mut self, p: Pos, risk: Option<Risk>) -> Option<Risk> {
+        if let Some(r) = risk {
+            assert!((1..=u8::MAX as Risk).contains(&r), "risk out of range: {}", r);
+        }
+        let old = std::mem::replace(&mut self.risks[p], risk.map(|r| r as u8));
+        old.map(Risk::from)
+    }
+
+    pub fn block(&mut self, p: Pos) -> Option<Risk> {
+        self.set_risk(p, None)
+    }
+
+    pub fn from_reader<I, L>(lines: &mut I) -> Cave
+    where I: Iterator<Item = L>, L: Deref<Target = str> {
+        let mut risks = Vec::with_capacity(100);
+        let mut dim = 0;
+
+        for l in lines {
+            let bytes = l.as_bytes();
+            dim = bytes.len();
+            risks.extend(bytes.iter().map(|b| Some(b - b'0')));
+        }
+
+        Cave { risks: Grid::from_vec(dim, risks) }
+    }
+}
+
+pub struct ExtendedCave<'a> {
+    cave: &'a Cave,
+    repeat: usize,
+}
+impl<'a> ExtendedCave<'a> {
+    pub fn from_cave(cave: &'a Cave, repeat: usize) -> ExtendedCave<'a> {
+        ExtendedCave { cave, repeat }
+    }
+}
+impl<'cave> ChitonCave for ExtendedCave<'cave> {
+    fn dim(&self) -> usize { self.cave.dim() * self.repeat }
+
+    fn risk(&self, p: Pos) -> Option<Risk> {
+        let d = self.cave.dim();
+        let dr = p.x / d + p.y / d;
+        let r = self.cave.risk([p.x % d, p.y % d].into())?;
+
+        Some((r - 1 + dr as Risk) % 9 + 1)
+    }
+}
+
+/// Editable cave that remembers its best path and only re-solves when an
+/// edit could actually change it
+pub struct WhatIfCave {
+    cave: Cave,
+    best: Option<Option<(Vec<Pos>, Risk)>>,
+}
+impl WhatIfCave {
+    pub fn new(cave: Cave) -> WhatIfCave {
+        WhatIfCave { cave, best: None }
+    }
+
+    pub fn cave(&self) -> &Cave { &self.cave }
+
+    pub fn into_cave(self) -> Cave { self.cave }
+
+    pub fn best_path(&mut self) -> Option<&(Vec<Pos>, Risk)> {
+        if self.best.is_none() {
+            self.best = Some(self.cave.best_path());
+        }
+        self.best.as_ref().unwrap().as_ref()
+    }
+
+    pub fn set_risk(&mut self, p: Pos, risk: Option<Risk>) {
+        let old = self.cave.set_risk(p, risk);
+        if old != risk {
+            self.invalidate(p, old, risk);
+        }
+    }
+
+    pub fn block(&mut self, p: Pos) {
+        self.set_risk(p, None)
+    }
+
+    /// Randomly perturb every cell by up to `amplitude` in either direction,
+    /// keeping risks within 1..=9 and leaving blocked cells blocked
+    pub fn add_noise(&mut self, seed: u64, amplitude: Risk) {
+        let mut rng = Rng::seed_from(seed);
+        let amplitude = amplitude as i64;
+        for y in 0..self.cave.dim() {
+            for x in 0..self.cave.dim() {
+                let p = Pos::from([x, y]);
+                let r = match self.cave.risk(p) {
+                    Some(r) => r as i64,
+                    None => continue,
+                };
+                let delta = rng.range(-amplitude..amplitude + 1);
+                self.set_risk(p, Some((r + delta).clamp(1, 9) as Risk));
+            }
+        }
+    }
+
+    fn invalidate(&mut self, p: Pos, old: Option<Risk>, new: Option<Risk>) {
+        // Raising or blocking a cell off the best path can't produce a better
+        // path, and lowering a cell can't make an unreachable exit reachable
+        let got_worse = match (old, new) {
+            (_, None) => true,
+            (None, Some(_)) => false,
+            (Some(o), Some(n)) => n >= o,
+        };
+        let keep = match &self.best {
+            Some(Some((path, _))) => got_worse && !path.contains(&p),
+            Some(None) => old.is_some(),
+            None => true,
+        };
+        if !keep {
+            self.best = None;
+        }
+    }
+}
+
+pub type Risk = u32;
+
+pub struct Puzzle {
+    cave: Cave,
+    search: Search,
+}
+
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        Ok(Puzzle { cave: Cave::from_reader(&mut input.lines()), search: Search::default() })
+    }
+
+    fn strategies() -> Strategies {
+        Strategies {
+            algos: &[
+                Strategy { name: "astar", about: "A* towards the exit" },
+                Strategy { name: "dijkstra", about: "Dijkstra, cell by cell" },
+                Strategy { name: "corridor", about: "Dijkstra between junctions, with corridors as single edges" },
+            ],
+            impls: &[],
+        }
+    }
+
+    /// `algo`: `astar`, `dijkstra` or `corridor`
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), AocError> {
+        if name != "algo" {
+            return Err(AocError::invalid(format!("no option {}", name)));
+        }
+        self.search = match value {
+            "astar" => Search::AStar,
+            "dijkstra" => Search::Dijkstra,
+            "corridor" => Search::Corridor,
+            _ => return Err(AocError::invalid(format!("bad value {:?} for option algo", value))),
+        };
+        Ok(())
+    }
+
+    fn part1(&self) -> String {
+        self.cave.best_path_by(self.search).map_or_else(|| "-".to_string(), |(_, c)| c.to_string())
+    }
+
+    fn part2(&self) -> String {
+        let cave = ExtendedCave::from_cave(&self.cave, 5);
+        cave.best_path_by(self.search).map_or_else(|| "-".to_string(), |(_, c)| c.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../input_test");
+
+    fn example() -> Cave {
+        Cave::from_reader(&mut EXAMPLE.lines())
+    }
+
+    #[test]
+    fn example_best_paths() {
+        let cave = example();
+        assert_eq!(cave.best_path().unwrap().1, 40);
+        assert_eq!(ExtendedCave::from_cave(&cave, 5).best_path().unwrap().1, 315);
+    }
+
+    #[test]
+    fn blocking_path_reroutes() {
+        let mut what_if = WhatIfCave::new(example());
+        let (path, risk) = what_if.best_path().cloned().unwrap();
+        assert_eq!(risk, 40);
+
+        what_if.block(path[5]);
+        let (new_path, new_risk) = what_if.best_path().cloned().unwrap();
+        assert!(!new_path.contains(&path[5]));
+        assert!(new_risk > 40);
+        assert_eq!(new_risk, what_if.cave().best_path().unwrap().1);
+    }
+
+    #[test]
+    fn edits_off_path_keep_cached_solution() {
+        let mut what_if = WhatIfCave::new(example());
+        let path = what_if.best_path().unwrap().0.clone();
+        let off_path = (0..10).flat_map(|y| (0..10).map(move |x| Pos::from([x, y])))
+            .find(|p| !path.contains(p))
+            .unwrap();
+
+        what_if.set_risk(off_path, Some(9));
+        assert!(what_if.best.is_some());
+
+        what_if.set_risk(off_path, Some(1));
+        let risk = what_if.best_path().unwrap().1;
+        assert_eq!(risk, what_if.cave().best_path().unwrap().1);
+    }
+
+    #[test]
+    fn walled_off_exit_has_no_path() {
+        let mut what_if = WhatIfCave::new(example());
+        what_if.block([8, 9].into());
+        what_if.block([9, 8].into());
+        assert!(what_if.best_path().is_none());
+
+        what_if.set_risk([9, 8].into(), Some(5));
+        assert!(what_if.best_path().is_some());
+    }
+
+    #[test]
+    fn noise_is_reproducible() {
+        let mut a = WhatIfCave::new(example());
+        let mut b = WhatIfCave::new(example());
+        a.add_noise(42, 2);
+        b.add_noise(42, 2);
+        assert_eq!(a.cave().risks.cells(), b.cave().risks.cells());
+        assert!(a.cave().risks.cells().iter().all(|r| (1..=9).contains(&r.unwrap())));
+        let risk = a.best_path().unwrap().1;
+        assert_eq!(risk, a.cave().best_path().unwrap().1);
+    }
+
+    /// The answers given in the puzzle text for its example
+    #[test]
+    fn example_answers() {
+        let puzzle = Puzzle::parse(include_str!("../input_test"));
+        assert_eq!(puzzle.part1(), "40");
+        assert_eq!(puzzle.part2(), "315");
+    }
+
+    #[test]
+    fn every_algo_finds_the_same_risk() {
+        let mut puzzle = Puzzle::parse(include_str!("../input_test"));
+        for algo in Puzzle::strategies().algos {
+            puzzle.set_option("algo", algo.name).unwrap();
+            assert_eq!((puzzle.part1(), puzzle.part2()), ("40".to_string(), "315".to_string()), "{}", algo.name);
+        }
+        assert!(puzzle.set_option("algo", "bfs").is_err());
+    }
+}
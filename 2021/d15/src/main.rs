@@ -0,0 +1,48 @@
+use std::io::BufRead;
+
+use d15::*;
+
+/// With `compress`, also searches the corridor graph and checks it agrees
+fn report(cave: &impl ChitonCave, compress: bool) {
+    println!("dimensions: {0}x{0}", cave.dim());
+
+    let best = cave.best_path();
+    match &best {
+        Some((_p, c)) => {
+            println!("shortest path: {}", c);
+            //println!("{:?}", p);
+        },
+        None => println!("NO PATH"),
+    }
+
+    if compress {
+        let graph = CorridorGraph::build(cave);
+        println!("corridor graph: {} nodes, {} edges for {} cells",
+            graph.node_count(), graph.edge_count(), cave.dim() * cave.dim());
+        let compressed = graph.best_path();
+        assert_eq!(compressed.as_ref().map(|b| b.1), best.as_ref().map(|b| b.1), "corridor search disagrees");
+    }
+}
+
+fn main() {
+    // --compress also runs the search on the cave with its corridors
+    // contracted, to compare
+    let mut compress = false;
+    let (input, args) = aoc_core::input_arg();
+    for arg in args {
+        match arg.as_str() {
+            "--compress" => compress = true,
+            _ => panic!("unknown argument: {}", arg),
+        }
+    }
+
+    let input = aoc_core::open_input(&input);
+    let mut lines = input.lines().map(|l| l.unwrap());
+    let cave = Cave::from_reader(&mut lines);
+
+    report(&cave, compress);
+
+    println!();
+
+    report(&ExtendedCave::from_cave(&cave, 5), compress);
+}
@@ -0,0 +1,395 @@
+//! Without the default `std` feature this is `no_std`, leaving the packet
+//! decoding and what reads it from hex, for embedding or fuzzing it.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::format;
+#[cfg(feature = "std")]
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+pub use aoc_bits::{BitsReader, IntoBitsReader};
+use aoc_core::AocError;
+#[cfg(feature = "std")]
+use aoc_core::Solver;
+
+mod packet;
+pub use packet::{
+    Arithmetic, EvalError, Header, InvalidOperator, LengthTypeId, Limits, OperatorId, Packet, PacketData, ParseError, ReadPackets,
+};
+
+pub fn bytes_from_hex(s: &str) -> Result<Vec<u8>, AocError> {
+    if !s.len().is_multiple_of(2) || !s.is_ascii() {
+        return Err(AocError::invalid(format!("expected pairs of hex digits, got {} characters", s.len())));
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| Ok(u8::from_str_radix(&s[i..i + 2], 16)?))
+        .collect()
+}
+
+/// The outermost packet of the transmission on the input's first line
+pub fn parse_packet(input: &str) -> Result<Packet, AocError> {
+    let line = input.lines().next().ok_or("empty transmission")?;
+    let data = bytes_from_hex(line.trim_end()).map_err(|e| e.at_line(1))?;
+    Packet::from_bits_limited(&mut data.read_bits(), &Limits::default()).map_err(|e| e.to_string().into())
+}
+
+#[cfg(feature = "std")]
+pub struct Puzzle {
+    packet: Packet,
+}
+
+#[cfg(feature = "std")]
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        Ok(Puzzle { packet: parse_packet(input)? })
+    }
+
+    fn part1(&self) -> String {
+        self.packet.version_sum().to_string()
+    }
+
+    /// In 128 bits, so a transmission that overflows 64 still gets its
+    /// answer. One that can't be evaluated gets why instead.
+    fn part2(&self) -> String {
+        self.packet.wide_value().map_or_else(|e| e.to_string(), |v| v.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_packet_fields() {
+        let data = bytes_from_hex("38006F45291200").unwrap();
+        let mut reader = data.read_bits();
+        assert!(matches!(reader.read_header(), Some(Header::Operator(1, OperatorId::LessThan))));
+        assert!(matches!(reader.read_length(), Some(LengthTypeId::Bits(27))));
+        assert!(matches!(reader.read_packet_data(), Some(PacketData::Literal(_, 10))));
+        assert_eq!(reader.position(), 33);
+        let mut next = reader.clone();
+        assert!(matches!(next.read_header(), Some(Header::Literal(2))));
+        assert_eq!(next.read_literal(), Some(20));
+
+        reader += 11;
+        assert!(reader.read_packet_data().is_none());
+    }
+
+    /// Bits of a transmission, for crafting packets the examples don't cover
+    #[derive(Default)]
+    struct Transmission(Vec<bool>);
+
+    impl Transmission {
+        fn push(&mut self, value: u64, bits: usize) -> &mut Self {
+            self.0.extend((0..bits).rev().map(|i| value >> i & 1 == 1));
+            self
+        }
+
+        fn literal(&mut self, v: u64) -> &mut Self {
+            self.push(0, 3).push(4, 3);
+            let groups = (64 - v.leading_zeros() as usize).div_ceil(4).max(1);
+            for g in (0..groups).rev() {
+                self.push((g > 0) as u64, 1).push(v >> (g * 4) & 0xf, 4);
+            }
+            self
+        }
+
+        fn operator(&mut self, id: u64, count: u64) -> &mut Self {
+            self.push(0, 3).push(id, 3).push(1, 1).push(count, 11)
+        }
+
+        fn parse(&self, limits: &Limits) -> Result<Packet, ParseError> {
+            let mut bytes: Vec<u8> = self.0.chunks(8)
+                .map(|c| c.iter().enumerate().fold(0, |b, (i, &bit)| b | (bit as u8) << (7 - i)))
+                .collect();
+            bytes.extend([0, 0]);
+            Packet::from_bits_limited(&mut bytes.read_bits(), limits)
+        }
+
+        fn packet(&self) -> Packet {
+            self.parse(&Limits::default()).unwrap()
+        }
+    }
+
+    #[test]
+    fn deep_nesting_is_refused() {
+        // a hundred thousand sums of one operand each, around a literal
+        let mut t = Transmission::default();
+        for _ in 0..100_000 {
+            t.operator(0, 1);
+        }
+        t.literal(7);
+        assert_eq!(t.parse(&Limits::default()).err(), Some(ParseError::TooDeep(256)));
+
+        let mut t = Transmission::default();
+        for _ in 0..256 {
+            t.operator(0, 1);
+        }
+        t.literal(7);
+        assert_eq!(t.packet().value(), 7);
+        let shallow = Limits { max_depth: 3, ..Limits::default() };
+        assert_eq!(t.parse(&shallow).err(), Some(ParseError::TooDeep(3)));
+    }
+
+    #[test]
+    fn wide_operators_are_refused() {
+        let narrow = Limits { max_subpackets: 100, ..Limits::default() };
+
+        let mut t = Transmission::default();
+        t.operator(0, 101);
+        for _ in 0..101 {
+            t.literal(1);
+        }
+        assert_eq!(t.parse(&narrow).err(), Some(ParseError::TooManySubpackets(100)));
+        assert_eq!(t.packet().value(), 101);
+
+        // the same literals counted in bits, 11 each
+        let mut t = Transmission::default();
+        t.push(0, 3).push(0, 3).push(0, 1).push(101 * 11, 15);
+        for _ in 0..101 {
+            t.literal(1);
+        }
+        assert_eq!(t.parse(&narrow).err(), Some(ParseError::TooManySubpackets(100)));
+        assert_eq!(t.packet().value(), 101);
+
+        let mut t = Transmission::default();
+        t.operator(0, 3).literal(1);
+        assert_eq!(t.parse(&narrow).err(), Some(ParseError::Truncated));
+    }
+
+    #[test]
+    fn checked_evaluation_reports_overflow() {
+        // ten nested products, each multiplying by 2^8 more: 2^80 in all
+        let mut t = Transmission::default();
+        for _ in 0..10 {
+            t.operator(1, 2).literal(256);
+        }
+        t.literal(1);
+        let packet = t.packet();
+
+        assert_eq!(packet.evaluate(Arithmetic::Checked), Err(EvalError::Overflow(OperatorId::Product)));
+        assert_eq!(packet.evaluate(Arithmetic::Saturating), Ok(u64::MAX));
+        assert_eq!(packet.evaluate(Arithmetic::Wrapping), Ok(0));
+        assert_eq!(packet.wide_value(), Ok(1 << 80));
+        assert_eq!(Puzzle { packet }.part2(), (1u128 << 80).to_string());
+
+        let mut t = Transmission::default();
+        t.operator(0, 2).literal(u64::MAX).literal(1);
+        assert!(t.packet().evaluate(Arithmetic::Checked).is_err());
+        assert_eq!(t.packet().wide_value(), Ok(1 << 64));
+    }
+
+    #[test]
+    fn comparisons_need_two_operands() {
+        let mut t = Transmission::default();
+        t.operator(5, 3).literal(1).literal(2).literal(3);
+        assert_eq!(t.packet().evaluate(Arithmetic::Checked), Err(EvalError::Operands(OperatorId::GreaterThan, 3)));
+
+        let mut t = Transmission::default();
+        t.operator(6, 2).literal(1).literal(2);
+        assert_eq!(t.packet().evaluate(Arithmetic::Checked), Ok(1));
+    }
+
+    #[test]
+    fn rejects_bad_transmissions() {
+        assert!(bytes_from_hex("D2F").is_err());
+        assert_eq!(parse_packet("D2FG28").err().unwrap().to_string(), "line 1: bad number: invalid digit found in string");
+        assert!(parse_packet("D2").is_err());
+        assert!(parse_packet("").is_err());
+    }
+
+    #[test]
+    fn parse_literal_sample() {
+        let input = bytes_from_hex("D2FE28").unwrap();
+        let packet = Packet::from_bits(&mut input.read_bits()).unwrap();
+
+        match *packet.data() {
+            PacketData::Literal(_, v) => assert_eq!(v, 0b_0111_1110_0101),
+            _ => panic!("failed to parse value"),
+        };
+    }
+
+    #[test]
+    fn parse_op_sample_1() {
+        let input = bytes_from_hex("38006F45291200").unwrap();
+        let packet = Packet::from_bits(&mut input.read_bits()).unwrap();
+
+        match *packet.data() {
+            PacketData::Operator(_, _, LengthTypeId::Bits(n)) => assert_eq!(n, 27),
+            _ => panic!("failed to parse value"),
+        };
+    }
+
+    #[test]
+    fn parse_op_sample_2() {
+        let input = bytes_from_hex("EE00D40C823060").unwrap();
+        let packet = Packet::from_bits(&mut input.read_bits()).unwrap();
+
+        match *packet.data() {
+            PacketData::Operator(_, _, LengthTypeId::Count(n)) => assert_eq!(n, 3),
+            _ => panic!("failed to parse value"),
+        };
+    }
+
+    #[test]
+    fn pass_test1() {
+        let input = bytes_from_hex("8A004A801A8002F478").unwrap();
+        let packet = Packet::from_bits(&mut input.read_bits()).unwrap();
+        let sum = packet.version_sum();
+
+        assert_eq!(sum, 16);
+    }
+
+    #[test]
+    fn pass_test2() {
+        let input = bytes_from_hex("620080001611562C8802118E34").unwrap();
+        let packet = Packet::from_bits(&mut input.read_bits()).unwrap();
+        let sum = packet.version_sum();
+
+        assert_eq!(sum, 12);
+    }
+
+    #[test]
+    fn pass_test3() {
+        let input = bytes_from_hex("C0015000016115A2E0802F182340").unwrap();
+        let packet = Packet::from_bits(&mut input.read_bits()).unwrap();
+        let sum = packet.version_sum();
+
+        assert_eq!(sum, 23);
+    }
+
+    #[test]
+    fn pass_test4() {
+        let input = bytes_from_hex("A0016C880162017C3686B18A3D4780").unwrap();
+        let packet = Packet::from_bits(&mut input.read_bits()).unwrap();
+        let sum = packet.version_sum();
+
+        assert_eq!(sum, 31);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let packet = parse_packet("9C0141080250320F1802104A08").unwrap();
+        let json = serde_json::to_string(&packet).unwrap();
+        let reloaded: Packet = serde_json::from_str(&json).unwrap();
+        assert_eq!(format!("{:?}", reloaded), format!("{:?}", packet));
+        assert_eq!(reloaded.value(), 1);
+    }
+
+    mod props {
+        use super::*;
+        use proptest::prelude::*;
+
+        /// Packet contents, independent of how they're encoded
+        #[derive(Clone, Debug, PartialEq)]
+        enum Spec {
+            Literal(u8, u64),
+            /// Version, type id, whether the length is a packet count, and
+            /// the sub-packets
+            Operator(u8, u8, bool, Vec<Spec>),
+        }
+
+        fn spec() -> impl Strategy<Value = Spec> {
+            let literal = (0..8u8, any::<u64>()).prop_map(|(v, n)| Spec::Literal(v, n));
+            literal.prop_recursive(4, 32, 4, |inner| {
+                (0..8u8, prop_oneof![Just(0u8), Just(1), Just(2), Just(3), Just(5), Just(6), Just(7)], any::<bool>(), prop::collection::vec(inner, 1..4))
+                    .prop_map(|(v, id, by_count, subs)| Spec::Operator(v, id, by_count, subs))
+            })
+        }
+
+        fn encode(spec: &Spec, t: &mut Transmission) {
+            match spec {
+                Spec::Literal(version, n) => {
+                    let mut lit = Transmission::default();
+                    lit.literal(*n);
+                    t.push(*version as u64, 3);
+                    t.0.extend(&lit.0[3..]);
+                },
+                Spec::Operator(version, id, by_count, subs) => {
+                    let mut body = Transmission::default();
+                    for s in subs {
+                        encode(s, &mut body);
+                    }
+                    t.push(*version as u64, 3).push(*id as u64, 3);
+                    if *by_count {
+                        t.push(1, 1).push(subs.len() as u64, 11);
+                    } else {
+                        t.push(0, 1).push(body.0.len() as u64, 15);
+                    }
+                    t.0.extend(body.0);
+                },
+            }
+        }
+
+        fn hex(t: &Transmission) -> String {
+            t.0.chunks(8)
+                .map(|c| c.iter().enumerate().fold(0u8, |b, (i, &bit)| b | (bit as u8) << (7 - i)))
+                .map(|b| format!("{:02X}", b))
+                .collect()
+        }
+
+        /// What was actually parsed, in the same terms as the spec
+        fn decoded(p: &Packet) -> Spec {
+            match *p.data() {
+                PacketData::Literal(h, n) => Spec::Literal(h.version(), n),
+                PacketData::Operator(h, _, length) => {
+                    let Header::Operator(_, id) = h else { panic!("operator with a literal header") };
+                    let id = (0..8).find(|&i| OperatorId::try_from(i).ok() == Some(id)).unwrap();
+                    Spec::Operator(h.version(), id, matches!(length, LengthTypeId::Count(_)), p.sub_packets().iter().map(decoded).collect())
+                },
+            }
+        }
+
+        fn version_sum(spec: &Spec) -> u32 {
+            match spec {
+                Spec::Literal(v, _) => *v as u32,
+                Spec::Operator(v, _, _, subs) => *v as u32 + subs.iter().map(version_sum).sum::<u32>(),
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn hex_round_trip(spec in spec()) {
+                let mut t = Transmission::default();
+                encode(&spec, &mut t);
+                let packet = parse_packet(&hex(&t)).unwrap();
+                prop_assert_eq!(decoded(&packet), spec.clone());
+                prop_assert_eq!(packet.version_sum(), version_sum(&spec));
+            }
+
+            #[test]
+            fn arithmetic_modes_agree_when_nothing_overflows(spec in spec()) {
+                let mut t = Transmission::default();
+                encode(&spec, &mut t);
+                let packet = parse_packet(&hex(&t)).unwrap();
+                match (packet.evaluate(Arithmetic::Checked), packet.wide_value()) {
+                    (Ok(v), wide) => {
+                        prop_assert_eq!(wide, Ok(v as u128));
+                        prop_assert_eq!(packet.evaluate(Arithmetic::Saturating), Ok(v));
+                        prop_assert_eq!(packet.evaluate(Arithmetic::Wrapping), Ok(v));
+                    },
+                    (Err(EvalError::Operands(..)), wide) => prop_assert!(wide.is_err()),
+                    (Err(EvalError::Overflow(_)), _) => {},
+                }
+            }
+        }
+    }
+
+    /// The answers given in the puzzle text for its examples, one
+    /// transmission per line
+    #[test]
+    fn example_answers() {
+        let version_sums = include_str!("../input_test").lines().map(|l| Puzzle::parse(l).part1());
+        assert!(version_sums.eq(["16", "12", "23", "31"]));
+        let values = include_str!("../input_test2").lines().map(|l| Puzzle::parse(l).part2());
+        assert!(values.eq(["3", "54", "7", "9", "1", "0", "0", "1"]));
+        // a comparison with only one operand parses, but has no value
+        assert_eq!(Puzzle::try_parse("16004438").unwrap().part2(), "GT can't take 1 operands");
+    }
+}
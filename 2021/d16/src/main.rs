@@ -0,0 +1,23 @@
+use std::io::Read;
+
+use aoc_core::AocError;
+
+use d16::*;
+
+fn main() {
+    let mut input = String::new();
+    aoc_core::or_exit(aoc_core::input().read_to_string(&mut input).map_err(AocError::from));
+
+    let root = aoc_core::or_exit(parse_packet(&input));
+    println!("{:?}", &root);
+
+    /*
+    for packet in data.read_bits() {
+        print!("{:?} ", packet);
+        sum += packet.version();
+    }
+    */
+    println!();
+    println!("sum: {}", &root.version_sum());
+    println!("value: {}", &root.value());
+}
@@ -0,0 +1,374 @@
+//! Reading packets off a transmission's bits and evaluating them, over
+//! `core` and `alloc` alone. Limits on depth and width are what keep a
+//! hostile transmission in check, so this can run where there's no `std` to
+//! fall back on.
+#![deny(clippy::std_instead_of_core, clippy::std_instead_of_alloc)]
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use aoc_bits::BitsReader;
+
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LengthTypeId {
+    Bits(usize),
+    Count(usize),
+}
+
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Header {
+    Literal(PacketVersion),
+    Operator(PacketVersion, OperatorId),
+}
+
+impl Header {
+    pub fn version(&self) -> PacketVersion {
+        match self {
+            Header::Literal(v) => *v,
+            Header::Operator(v, _) => *v,
+        }
+    }
+
+    pub fn is_literal(&self) -> bool {
+        matches!(self, Header::Literal(_))
+    }
+
+    pub fn is_operator(&self) -> bool {
+        !self.is_literal()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OperatorId {
+    Sum,
+    Product,
+    Min,
+    Max,
+    GreaterThan,
+    LessThan,
+    Equal,
+}
+
+/// A type id that's neither a literal nor one of the operators
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct InvalidOperator(pub u8);
+
+impl fmt::Display for InvalidOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid operator id {}", self.0)
+    }
+}
+
+impl core::error::Error for InvalidOperator {}
+
+impl TryFrom<u8> for OperatorId {
+    type Error = InvalidOperator;
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0 => OperatorId::Sum,
+            1 => OperatorId::Product,
+            2 => OperatorId::Min,
+            3 => OperatorId::Max,
+            5 => OperatorId::GreaterThan,
+            6 => OperatorId::LessThan,
+            7 => OperatorId::Equal,
+            _ => return Err(InvalidOperator(value)),
+        })
+    }
+}
+
+impl fmt::Debug for OperatorId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", match self {
+            OperatorId::Sum => "SUM",
+            OperatorId::Product => "PROD",
+            OperatorId::Min => "MIN",
+            OperatorId::Max => "MAX",
+            OperatorId::GreaterThan => "GT",
+            OperatorId::LessThan => "LT",
+            OperatorId::Equal => "EQ",
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PacketData {
+    Literal(Header, LiteralValue),
+    Operator(Header, OperatorId, LengthTypeId),
+}
+
+impl PacketData {
+    pub fn version(&self) -> PacketVersion {
+        match self {
+            PacketData::Literal(h, _) => h,
+            PacketData::Operator(h, _ , _) => h,
+        }.version()
+    }
+}
+
+impl fmt::Debug for PacketData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PacketData::Literal(h, v) => write!(f, "{}({})", v, h.version()),
+            PacketData::Operator(_, id, l) => {
+                let (prefix, n) = match l {
+                    LengthTypeId::Bits(b) => ("b", b),
+                    LengthTypeId::Count(c) => ("", c),
+                };
+                write!(f, "{:?}`{}{}({})", id, prefix, n, self.version())
+            },
+        }
+    }
+}
+
+/// How `Packet::evaluate` handles sums and products that don't fit
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Arithmetic {
+    Wrapping,
+    Checked,
+    Saturating,
+}
+
+/// Why a packet has no value
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EvalError {
+    /// A sum or product didn't fit, in checked arithmetic
+    Overflow(OperatorId),
+    /// An operator had a number of operands it can't work with
+    Operands(OperatorId, usize),
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EvalError::Overflow(id) => write!(f, "{:?} overflowed", id),
+            EvalError::Operands(id, n) => write!(f, "{:?} can't take {} operands", id, n),
+        }
+    }
+}
+
+/// How far the parser will follow a transmission, so a crafted one can't
+/// overflow the stack or build huge packet trees
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Limits {
+    /// Operators nested inside the outermost packet
+    pub max_depth: usize,
+    /// Sub-packets directly inside any one operator
+    pub max_subpackets: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits { max_depth: 256, max_subpackets: 4096 }
+    }
+}
+
+/// Why a transmission has no packet
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ParseError {
+    /// The bits ran out part way through a packet
+    Truncated,
+    /// Packets were nested deeper than the limit
+    TooDeep(usize),
+    /// An operator had more sub-packets than the limit
+    TooManySubpackets(usize),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Truncated => write!(f, "transmission ends mid packet"),
+            ParseError::TooDeep(max) => write!(f, "packets nested more than {} deep", max),
+            ParseError::TooManySubpackets(max) => write!(f, "operator with more than {} sub-packets", max),
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// Sum or product of two values, `None` when it overflows
+type Combine<T> = fn(T, T) -> Option<T>;
+
+type PacketVersion = u8;
+type BitsCount = usize;
+type LiteralValue = u64;
+
+/// The packet fields of a transmission, read off its bits
+pub trait ReadPackets {
+    fn read_header(&mut self) -> Option<Header>;
+
+    /// How the operator's sub-packets are counted
+    fn read_length(&mut self) -> Option<LengthTypeId>;
+
+    /// The literal's value, or `None` if the stream ends part way through it
+    fn read_literal(&mut self) -> Option<LiteralValue>;
+
+    /// A packet's header and what follows it up to any sub-packets, or
+    /// `None` if the stream ends first
+    fn read_packet_data(&mut self) -> Option<PacketData>;
+}
+
+impl ReadPackets for BitsReader<'_> {
+    fn read_header(&mut self) -> Option<Header> {
+        let version = self.read_u8(3)?;
+        Some(match self.read_u8(3)? {
+            4 => Header::Literal(version),
+            n => Header::Operator(version, n.try_into().unwrap()),
+        })
+    }
+
+    fn read_length(&mut self) -> Option<LengthTypeId> {
+        Some(if self.read_bit()? {
+            LengthTypeId::Count(self.read_u16(11)? as usize)
+        } else {
+            LengthTypeId::Bits(self.read_u16(15)? as usize)
+        })
+    }
+
+    fn read_literal(&mut self) -> Option<LiteralValue> {
+        let mut v = 0;
+        loop {
+            let group = self.read_u8(5)?;
+            log::trace!("lit: {}", group);
+            v = (v << 4) | (group & 0b_0000_1111) as LiteralValue;
+            if group & 0b_0001_0000 == 0 {
+                break;
+            }
+        }
+        Some(v)
+    }
+
+    fn read_packet_data(&mut self) -> Option<PacketData> {
+        let header = self.read_header()?;
+        match header {
+            Header::Literal(_) => Some(PacketData::Literal(header, self.read_literal()?)),
+            Header::Operator(_, id) => Some(PacketData::Operator(header, id, self.read_length()?)),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Packet(PacketData, Vec<Packet>);
+
+impl Packet {
+    pub fn data(&self) -> &PacketData { &self.0 }
+
+    /// Packets directly inside this one, none for a literal
+    pub fn sub_packets(&self) -> &[Packet] { &self.1 }
+
+    /// Value of the expression, wrapping around on overflow
+    pub fn value(&self) -> LiteralValue {
+        self.evaluate(Arithmetic::Wrapping).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Value of the expression, with sums and products done as `mode` says
+    pub fn evaluate(&self, mode: Arithmetic) -> Result<LiteralValue, EvalError> {
+        let (add, mul): (Combine<u64>, Combine<u64>) = match mode {
+            Arithmetic::Wrapping => (|a, b| Some(a.wrapping_add(b)), |a, b| Some(a.wrapping_mul(b))),
+            Arithmetic::Checked => (u64::checked_add, u64::checked_mul),
+            Arithmetic::Saturating => (|a, b| Some(a.saturating_add(b)), |a, b| Some(a.saturating_mul(b))),
+        };
+        self.fold_values(&|v| v, add, mul)
+    }
+
+    /// Value of the expression in 128 bits, which only the most adversarial
+    /// transmissions can overflow
+    pub fn wide_value(&self) -> Result<u128, EvalError> {
+        self.fold_values(&u128::from, u128::checked_add, u128::checked_mul)
+    }
+
+    fn fold_values<T>(&self, literal: &dyn Fn(LiteralValue) -> T, add: Combine<T>, mul: Combine<T>) -> Result<T, EvalError>
+    where T: Copy + Ord + From<u8>
+    {
+        let id = match self.0 {
+            PacketData::Literal(_, v) => return Ok(literal(v)),
+            PacketData::Operator(_, id, _) => id,
+        };
+        let values = self.1.iter()
+            .map(|p| p.fold_values(literal, add, mul))
+            .collect::<Result<Vec<T>, EvalError>>()?;
+        let (zero, one) = (T::from(0), T::from(1));
+        let pair = || match values[..] {
+            [a, b] => Ok((a, b)),
+            _ => Err(EvalError::Operands(id, values.len())),
+        };
+
+        match id {
+            OperatorId::Sum => values.iter().try_fold(zero, |a, &b| add(a, b).ok_or(EvalError::Overflow(id))),
+            OperatorId::Product => values.iter().try_fold(one, |a, &b| mul(a, b).ok_or(EvalError::Overflow(id))),
+            OperatorId::Min => values.iter().copied().min().ok_or(EvalError::Operands(id, 0)),
+            OperatorId::Max => values.iter().copied().max().ok_or(EvalError::Operands(id, 0)),
+            OperatorId::GreaterThan => pair().map(|(a, b)| if a > b { one } else { zero }),
+            OperatorId::LessThan => pair().map(|(a, b)| if a < b { one } else { zero }),
+            OperatorId::Equal => pair().map(|(a, b)| if a == b { one } else { zero }),
+        }
+    }
+
+    /// The packet at the reader, or `None` if it's cut short or goes past
+    /// the default `Limits`
+    pub fn from_bits(reader: &mut BitsReader) -> Option<Packet> {
+        Packet::from_bits_limited(reader, &Limits::default()).ok()
+    }
+
+    pub fn from_bits_limited(reader: &mut BitsReader, limits: &Limits) -> Result<Packet, ParseError> {
+        Packet::read(reader, limits, 0)
+    }
+
+    fn read(reader: &mut BitsReader, limits: &Limits, depth: usize) -> Result<Packet, ParseError> {
+        if depth > limits.max_depth {
+            return Err(ParseError::TooDeep(limits.max_depth));
+        }
+        let packet = reader.read_packet_data().ok_or(ParseError::Truncated)?;
+
+        log::trace!("{:?}", &packet);
+
+        let nodes = match packet {
+            PacketData::Operator(_, _, LengthTypeId::Count(len)) => {
+                if len > limits.max_subpackets {
+                    return Err(ParseError::TooManySubpackets(limits.max_subpackets));
+                }
+                (0..len).map(|_| Packet::read(reader, limits, depth + 1)).collect::<Result<_, _>>()?
+            },
+            PacketData::Operator(_, _, LengthTypeId::Bits(bits)) => {
+                Packet::take_until_bits(reader, bits, limits, depth + 1)?
+            },
+            _ => vec!(),
+        };
+        Ok(Packet(packet, nodes))
+    }
+
+    fn take_until_bits(reader: &mut BitsReader, len: BitsCount, limits: &Limits, depth: usize) -> Result<Vec<Packet>, ParseError> {
+        let end = reader.position() + len;
+        let mut packets = vec!();
+
+        while reader.position() < end {
+            if packets.len() == limits.max_subpackets {
+                return Err(ParseError::TooManySubpackets(limits.max_subpackets));
+            }
+            packets.push(Packet::read(reader, limits, depth)?);
+        }
+
+        Ok(packets)
+    }
+
+    pub fn version_sum(&self) -> u32 {
+        self.1
+            .iter()
+            .map(|n| n.version_sum())
+            .sum::<u32>() + self.0.version() as u32
+    }
+}
+
+impl fmt::Debug for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            PacketData::Literal(_, _) => write!(f, "{:?}", self.0),
+            PacketData::Operator(_, _, _) => write!(f, "{:?} {:?}", self.0, self.1),
+        }
+    }
+}
@@ -1,12 +1,12 @@
 use std::cmp;
 use std::cmp::{Ord};
 use std::fmt;
-use std::io;
-use std::io::{BufRead};
 use std::iter;
 use std::ops::{RangeInclusive, Add};
 use std::str::{FromStr};
 
+use aoc_core::{AocError, Solver};
+
 pub type Int = i32;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -53,10 +53,7 @@ impl Probe {
     }
 
     pub fn can_hit(&self, target: &Target) -> bool {
-        match self.fire_at(target).last().unwrap() {
-            Flight::Hit(_) => true,
-            _ => false,
-        }
+        matches!(self.fire_at(target).last().unwrap(), Flight::Hit(_))
     }
 }
 
@@ -73,8 +70,8 @@ impl fmt::Debug for Probe {
 }
 
 impl Pos {
-    fn x(&self) -> Int { self.0 }
-    fn y(&self) -> Int { self.1 }
+    pub fn x(&self) -> Int { self.0 }
+    pub fn y(&self) -> Int { self.1 }
 }
 
 impl From<(Int, Int)> for Pos {
@@ -195,7 +192,7 @@ impl<'a> TryFrom<&'a str> for Target {
     fn try_from(s: &'a str) -> Result<Target, Self::Error> {
         // target area: x=20..30, y=-10..-5
         let pos_x_start = "target area: x=".len();
-        let pos_x_end = s.find(|c| c == ',').unwrap();
+        let pos_x_end = s.find(',').unwrap();
 
         let x_str = &s[pos_x_start..pos_x_end];
         let y_str = &s[pos_x_end+4..];
@@ -211,60 +208,124 @@ fn parse_range<F>(s: &str) -> Result<RangeInclusive<F>, &'static str>
 where F: FromStr
 {
     // Assuming input string is always given in increasing order
-    let p1_end = s.find(|c| c == '.').ok_or("no .. separator found")?;
+    let p1_end = s.find('.').ok_or("no .. separator found")?;
     let start = s[0..p1_end].parse::<F>().map_err(|_| "invalid start")?;
     let end = s[p1_end+2..].parse::<F>().map_err(|_| "invalid end")?;
     Ok(start..=end)
 }
 
-fn p1(target: &Target) {
-    let v = Probe::find_highest_trajectory(&target).unwrap();
-    let probe = Probe::from(v);
-    println!("{:?}", &probe);
-
-    let mut max = 0;
-    for tick in probe.fire_at(target) {
-        match tick {
-            Flight::Flying(p) => {
-                max = cmp::max(max, p.position().y());
-                println!("{:?}", p)
-            },
-            Flight::Hit(p) => println!("HIT! {:?}", p.position()),
-            Flight::Missed(p) => println!("MISSED! {:?}", p.position()),
+/// An initial velocity that hits the target, and how its flight went
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Hit {
+    pub velocity: Velocity,
+    /// Highest point of the flight, which is the launch height for shots
+    /// fired downwards
+    pub apex: Int,
+    /// Steps until the probe is first inside the target
+    pub steps: usize,
+}
+
+impl Hit {
+    /// How the probe's flight goes, if it hits the target at all
+    pub fn of(probe: &Probe, target: &Target) -> Option<Hit> {
+        let mut apex = probe.position().y();
+        for (steps, tick) in probe.fire_at(target).enumerate() {
+            match tick {
+                Flight::Flying(p) => apex = cmp::max(apex, p.position().y()),
+                Flight::Hit(p) => {
+                    apex = cmp::max(apex, p.position().y());
+                    return Some(Hit { velocity: probe.velocity(), apex, steps: steps + 1 });
+                },
+                Flight::Missed(_) => return None,
+            }
         }
+        None
     }
-
-    println!("max height: {}", max);
 }
 
-fn p2(target: &Target) {
+/// Every initial velocity that ends up hitting the target, ordered by x
+/// then y
+pub fn firing_solutions(target: &Target) -> Vec<Hit> {
     let max_flight_v = Probe::find_highest_trajectory(target).unwrap();
     let min_x = max_flight_v.x();
     let max_x = *target.x.end();
     let max_y = max_flight_v.y();
     let min_y = *target.y.start();
 
-    let mut count = 0;
-    for vx in min_x..=max_x {
-        for vy in min_y..=max_y {
-            let v = Velocity::from((vx, vy));
-            let p = Probe::from(v);
-            if p.can_hit(target) {
-                println!("{:?} hits", &v);
-                count += 1;
-            }
-        }
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        // collecting keeps the x then y order, however columns were spread
+        // across threads
+        (min_x..=max_x).into_par_iter()
+            .flat_map_iter(|vx| (min_y..=max_y).map(move |vy| Velocity::from((vx, vy))))
+            .filter_map(|v| Hit::of(&Probe::from(v), target))
+            .collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    (min_x..=max_x)
+        .flat_map(|vx| (min_y..=max_y).map(move |vy| Velocity::from((vx, vy))))
+        .filter_map(|v| Hit::of(&Probe::from(v), target))
+        .collect()
+}
+
+/// Highest point reached on the way to the target
+pub fn max_height(probe: &Probe, target: &Target) -> Int {
+    probe.fire_at(target)
+        .filter_map(|tick| match tick {
+            Flight::Flying(p) => Some(p.position().y()),
+            _ => None,
+        })
+        .fold(0, cmp::max)
+}
+
+pub struct Puzzle {
+    target: Target,
+}
+
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        Ok(Puzzle { target: Target::try_from(input.trim_end()).unwrap() })
+    }
+
+    fn part1(&self) -> String {
+        let probe = Probe::from(Probe::find_highest_trajectory(&self.target).unwrap());
+        max_height(&probe, &self.target).to_string()
+    }
+
+    fn part2(&self) -> String {
+        firing_solutions(&self.target).len().to_string()
     }
-    println!("{} valid firing solutions", count);
 }
 
-fn main() {
-    let stdin = io::stdin();
-    let l = stdin.lock().lines().next().unwrap().unwrap();
-    let target = Target::try_from(l.as_str()).unwrap();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    println!("target: {:?}", &target);
+    /// The answers given in the puzzle text for its example
+    #[test]
+    fn example_answers() {
+        let puzzle = Puzzle::parse(include_str!("../input_test"));
+        assert_eq!(puzzle.part1(), "45");
+        assert_eq!(puzzle.part2(), "112");
+    }
 
-    //p1(&target);
-    p2(&target);
-}
\ No newline at end of file
+    #[test]
+    fn hits_know_their_flights() {
+        let target = Target::try_from("target area: x=20..30, y=-10..-5").unwrap();
+        let hits = firing_solutions(&target);
+        assert!(hits.windows(2).all(|w| (w[0].velocity.x(), w[0].velocity.y()) < (w[1].velocity.x(), w[1].velocity.y())));
+        let highest = hits.iter().max_by_key(|h| h.apex).unwrap();
+        assert_eq!(highest.apex, 45);
+        assert_eq!(Some(*highest), Hit::of(&Probe::from(highest.velocity), &target));
+
+        // fired straight into the target, taking one step and never rising
+        let direct = hits.iter().find(|h| h.velocity == Velocity::from((30, -10))).unwrap();
+        assert_eq!((direct.apex, direct.steps), (0, 1));
+        assert_eq!(hits.iter().filter(|h| h.steps == 1).count(), 11 * 6);
+
+        let slowest = hits.iter().min_by_key(|h| (h.velocity.x(), h.velocity.y())).unwrap();
+        assert_eq!(slowest.velocity, Velocity::from((6, 0)));
+        assert_eq!(Hit::of(&Probe::from(Velocity::from((17, -4))), &target), None);
+    }
+}
@@ -0,0 +1,51 @@
+use std::cmp;
+use std::io::BufRead;
+
+use d17::*;
+
+fn p1(target: &Target) {
+    let v = Probe::find_highest_trajectory(target).unwrap();
+    let probe = Probe::from(v);
+    println!("{:?}", &probe);
+
+    let mut max = 0;
+    for tick in probe.fire_at(target) {
+        match tick {
+            Flight::Flying(p) => {
+                max = cmp::max(max, p.position().y());
+                println!("{:?}", p)
+            },
+            Flight::Hit(p) => println!("HIT! {:?}", p.position()),
+            Flight::Missed(p) => println!("MISSED! {:?}", p.position()),
+        }
+    }
+
+    println!("max height: {}", max);
+}
+
+fn p2(target: &Target) {
+    let solutions = firing_solutions(target);
+    for hit in &solutions {
+        println!("{:?} hits after {} steps, apex {}", hit.velocity, hit.steps, hit.apex);
+    }
+    println!("{} valid firing solutions", solutions.len());
+}
+
+fn main() {
+    let (input, parts, args) = aoc_core::input_and_parts();
+    if let Some(arg) = args.first() {
+        panic!("unknown argument: {}", arg);
+    }
+    let input = aoc_core::open_input(&input);
+    let l = input.lines().next().unwrap().unwrap();
+    let target = Target::try_from(l.as_str()).unwrap();
+
+    println!("target: {:?}", &target);
+
+    if parts.has(1) {
+        p1(&target);
+    }
+    if parts.has(2) {
+        p2(&target);
+    }
+}
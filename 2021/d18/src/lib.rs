@@ -1,8 +1,21 @@
-use std::cmp;
-use std::io;
-use std::io::BufRead;
-use std::fmt;
-use itertools::Itertools;
+//! Without the default `std` feature this is `no_std`, leaving the
+//! snailfish numbers, their sums and magnitudes, for embedding or fuzzing.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use core::cmp;
+use core::fmt;
+use core::ops::Add;
+
+#[cfg(feature = "std")]
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use aoc_core::AocError;
+#[cfg(feature = "std")]
+use aoc_core::Solver;
 
 type Leaf = Option<u8>;
 
@@ -19,16 +32,13 @@ impl SnailfishNumber {
 
     fn magnitude_slice(s: &[Option<u8>]) -> Option<u32> {
         if s.len() == 1 {
-            match s[0] {
-                Some(n) => Some(n as u32),
-                None => None,
-            }
+            s[0].map(|n| n as u32)
         } else {
             let mid = s.len() / 2;
             let left = Self::magnitude_slice(&s[0..mid]);
             let right = Self::magnitude_slice(&s[mid..]);
             match (left, right) {
-                (Some(l), Some(r)) => Some(3 * l as u32 + 2 * r as u32),
+                (Some(l), Some(r)) => Some(3 * l + 2 * r),
                 (Some(l), None) => Some(l),
                 (None,  _) => None,
             }
@@ -78,30 +88,23 @@ impl SnailfishNumber {
             }
         };
 
-        match (left, right) {
-            (Some(l), Some(r)) => {
-                if left_idx.is_some() {
-                    let p = &mut self.l[left_idx.unwrap()];
-                    *p = Some(p.unwrap() + l);
-                }
-                if right_idx.is_some() {
-                    let i = right_idx.unwrap();
-                    let p = &mut self.l[i];
-                    *p = Some(p.unwrap() + r);
-                }
-                return true
-            },
-            _ => (),
+        if let (Some(l), Some(r)) = (left, right) {
+            if let Some(i) = left_idx {
+                let p = &mut self.l[i];
+                *p = Some(p.unwrap() + l);
+            }
+            if let Some(i) = right_idx {
+                let p = &mut self.l[i];
+                *p = Some(p.unwrap() + r);
+            }
+            return true
         }
         false
     }
 
     fn split(&mut self) -> bool {
         let left = self.l.iter()
-            .position(|n| match n {
-                Some(v) if *v > 9 => true,
-                _ => false,
-            });
+            .position(|n| matches!(n, Some(v) if *v > 9));
 
         let left = match left {
             Some(i) => i,
@@ -116,7 +119,7 @@ impl SnailfishNumber {
 
         let v = self.l[left].unwrap();
         self.l[left] = Some(v / 2);
-        self.l[right] = Some((v + 1) / 2);
+        self.l[right] = Some(v.div_ceil(2));
 
         true
     }
@@ -135,33 +138,42 @@ impl SnailfishNumber {
         write!(f, "]")
     }
 
-    fn read_tree(a: &mut [Leaf], s: &str, width: usize) -> usize {
-        let comma_pos = if s.starts_with("[[") {
-            1 + Self::read_tree(a, &s[1..], width / 2)
-        } else {
-            let n = s.find(',').unwrap();
-            a[0] = Some(s[1..n].parse::<u8>().unwrap());
-            a[1..width].fill(None);
-            n
-        };
+    /// Read the pair at the start of `s` into `a`, which holds `width`
+    /// leaves for each side, returning the length of the pair's text
+    fn read_tree(a: &mut [Leaf], s: &str, width: usize) -> Result<usize, &'static str> {
+        if width == 0 {
+            return Err("pairs nested more than four deep");
+        }
+        if !s.starts_with('[') {
+            return Err("expected '['");
+        }
+        let (left, right) = a.split_at_mut(width);
+        let comma_pos = 1 + Self::read_element(left, &s[1..], width, ',')?;
+        let end = Self::read_element(right, &s[comma_pos + 1..], width, ']')?;
 
-        let a = &mut a[width..];
-        let right_s = &s[comma_pos+1..];
-        //println!("!! {}", &right_s);
-        let end = if right_s.starts_with('[') {
-            Self::read_tree(a, right_s, width / 2)
+        Ok(comma_pos + 1 + end + 1)
+    }
+
+    /// Read a number or pair followed by `sep` from the start of `s`,
+    /// returning its length without the separator
+    fn read_element(a: &mut [Leaf], s: &str, width: usize, sep: char) -> Result<usize, &'static str> {
+        let len = if s.starts_with('[') {
+            Self::read_tree(a, s, width / 2)?
         } else {
-            let n = right_s.find(']').unwrap();
-            a[0] = Some(right_s[0..n].parse::<u8>().unwrap());
+            let n = s.find(sep).ok_or("unterminated pair")?;
+            a[0] = Some(s[0..n].parse::<u8>().map_err(|_| "expected a number")?);
             a[1..width].fill(None);
             n
         };
 
-        comma_pos + 1 + end + 1
+        if !s[len..].starts_with(sep) {
+            return Err(if sep == ',' { "expected ','" } else { "expected ']'" });
+        }
+        Ok(len)
     }
 }
 
-impl std::ops::Add<SnailfishNumber> for SnailfishNumber {
+impl Add<SnailfishNumber> for SnailfishNumber {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
@@ -187,7 +199,9 @@ impl TryFrom<&str> for SnailfishNumber {
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
         let mut l = [None; 32];
-        Self::read_tree(&mut l, s, 16);
+        if Self::read_tree(&mut l, s, 16)? != s.len() {
+            return Err("unexpected characters after the number");
+        }
         Ok(SnailfishNumber { l })
     }
 }
@@ -302,29 +316,67 @@ impl<'a> Iterator for SnailfishNumLeafs<'a> {
 */
 
 
-fn main() {
-    let stdin = io::stdin();
-    let nums: Vec<SnailfishNumber> = stdin.lock().lines()
-        .map(|l| SnailfishNumber::try_from(l.unwrap().as_str()).unwrap())
-        .collect();
-    let sum = nums.iter().copied().reduce(|a, n| {
-        let s = a + n;
-        println!("{} + {} = {}", &a, &n, &s);
-        s
-    }).unwrap();
-    println!("{}", &sum);
-    println!("magnitude {}", sum.magnitude());
-
-    let perms = nums.iter().permutations(2);
-    let max_magnitude = perms
-        .fold(0, |max, n| {
-            let a = n[0];
-            let b = n[1];
-            let m = (*a + *b).magnitude();
-            cmp::max(max, m)
-        });
-
-    println!("max sum {}", max_magnitude);
+/// Parse each line, reducing any number that isn't already
+pub fn parse_numbers(input: &str) -> Result<Vec<SnailfishNumber>, AocError> {
+    let parse = |(i, l): (usize, &str)| {
+        let mut n = SnailfishNumber::try_from(l.trim_end()).map_err(|e| AocError::from(e).at_line(i + 1))?;
+        n.reduce();
+        Ok(n)
+    };
+
+    #[cfg(feature = "parallel")]
+    let numbers: Vec<Result<SnailfishNumber, AocError>> = {
+        use rayon::prelude::*;
+        let lines: Vec<&str> = input.lines().collect();
+        lines.into_par_iter().enumerate().map(parse).collect()
+    };
+    #[cfg(not(feature = "parallel"))]
+    let numbers = input.lines().enumerate().map(parse);
+
+    // collecting in order reports the first bad line, however lines were
+    // spread across threads
+    numbers.into_iter().collect()
+}
+
+/// Largest magnitude from adding any two different numbers
+#[cfg(not(feature = "parallel"))]
+pub fn max_pair_magnitude(nums: &[SnailfishNumber]) -> u32 {
+    use itertools::Itertools;
+    nums.iter().permutations(2)
+        .fold(0, |max, n| cmp::max(max, (*n[0] + *n[1]).magnitude()))
+}
+
+/// Largest magnitude from adding any two different numbers, with each left
+/// hand number's sums on a thread of their own
+#[cfg(feature = "parallel")]
+pub fn max_pair_magnitude(nums: &[SnailfishNumber]) -> u32 {
+    use rayon::prelude::*;
+    nums.par_iter().enumerate()
+        .map(|(i, &a)| nums.iter().enumerate()
+            .filter(|&(j, _)| j != i)
+            .fold(0, |max, (_, &b)| cmp::max(max, (a + b).magnitude())))
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(feature = "std")]
+pub struct Puzzle {
+    numbers: Vec<SnailfishNumber>,
+}
+
+#[cfg(feature = "std")]
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        Ok(Puzzle { numbers: parse_numbers(input)? })
+    }
+
+    fn part1(&self) -> String {
+        self.numbers.iter().copied().reduce(|a, n| a + n).unwrap().magnitude().to_string()
+    }
+
+    fn part2(&self) -> String {
+        max_pair_magnitude(&self.numbers).to_string()
+    }
 }
 
 #[cfg(test)]
@@ -444,4 +496,68 @@ mod tests {
         let l = ["[1,1]", "[2,2]", "[3,3]", "[4,4]", "[5,5]", "[6,6]"];
         assert_eq!(adder(&l), SnailfishNumber::try_from("[[[[5,0],[7,4]],[5,5]],[6,6]]").unwrap());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn example_homework() {
+        let nums = parse_numbers(include_str!("../input_test")).unwrap();
+        assert_eq!(nums.iter().copied().reduce(|a, n| a + n).unwrap().magnitude(), 4140);
+        assert_eq!(max_pair_magnitude(&nums), 3993);
+
+        let err = parse_numbers("[1,2]\n[3,4\n[5]").unwrap_err();
+        assert!(err.to_string().contains("line 2"), "{}", err);
+    }
+
+    #[test]
+    fn try_from_rejects_malformed() {
+        for s in ["", "5", "[1,2", "[1,2]]", "[1;2]", "[,2]", "[1,[2,3]", "[[[[[[1,2],3],4],5],6],7]", "[300,1]", "[1,é]"] {
+            assert!(SnailfishNumber::try_from(s).is_err(), "{:?}", s);
+        }
+        assert!(SnailfishNumber::try_from("[[[[[1,2],3],4],5],6]").is_ok());
+    }
+
+    mod props {
+        use super::*;
+        use proptest::prelude::*;
+
+        /// Snailfish numbers as they appear in the input: pairs nested at
+        /// most four deep, regular numbers 0 to 9
+        fn snailfish(depth: u32) -> BoxedStrategy<String> {
+            let leaf = (0..=9u8).prop_map(|n| n.to_string());
+            let element = if depth == 1 { leaf.boxed() } else { prop_oneof![leaf, snailfish(depth - 1)].boxed() };
+            (element.clone(), element).prop_map(|(a, b)| format!("[{},{}]", a, b)).boxed()
+        }
+
+        proptest! {
+            #[test]
+            fn parse_format_round_trip(s in snailfish(4)) {
+                prop_assert_eq!(SnailfishNumber::try_from(s.as_str()).unwrap().to_string(), s);
+            }
+
+            #[test]
+            fn sums_come_out_reduced(a in snailfish(4), b in snailfish(4)) {
+                let a = SnailfishNumber::try_from(a.as_str()).unwrap();
+                let b = SnailfishNumber::try_from(b.as_str()).unwrap();
+                let mut sum = a + b;
+                prop_assert!(sum.l.iter().flatten().all(|&n| n <= 9));
+
+                let before = sum;
+                prop_assert!(!sum.explode());
+                prop_assert!(!sum.split());
+                sum.reduce();
+                prop_assert_eq!(sum, before);
+
+                let reparsed = SnailfishNumber::try_from(sum.to_string().as_str()).unwrap();
+                prop_assert_eq!(reparsed, sum);
+                prop_assert!(sum.magnitude() >= a.magnitude().min(b.magnitude()).min(1));
+            }
+        }
+    }
+
+    /// The answers given in the puzzle text for its example
+    #[test]
+    fn example_answers() {
+        let puzzle = Puzzle::parse(include_str!("../input_test"));
+        assert_eq!(puzzle.part1(), "4140");
+        assert_eq!(puzzle.part2(), "3993");
+    }
+}
@@ -0,0 +1,19 @@
+use std::io::BufRead;
+
+use d18::*;
+
+fn main() {
+    let input = aoc_core::input();
+    let nums: Vec<SnailfishNumber> = input.lines()
+        .map(|l| SnailfishNumber::try_from(l.unwrap().as_str()).unwrap())
+        .collect();
+    let sum = nums.iter().copied().reduce(|a, n| {
+        let s = a + n;
+        println!("{} + {} = {}", &a, &n, &s);
+        s
+    }).unwrap();
+    println!("{}", &sum);
+    println!("magnitude {}", sum.magnitude());
+
+    println!("max sum {}", max_pair_magnitude(&nums));
+}
@@ -0,0 +1,259 @@
+use std::cell::OnceCell;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write;
+
+mod ipos;
+mod sensitivity;
+pub use ipos::*;
+pub use sensitivity::{placements, reconstruct, sensitivity, Placement, Sensitivity};
+
+use aoc_core::progress::Progress;
+use aoc_core::{AocError, LineBlocks, Solver};
+
+/// Beacons two overlapping scanners are promised to have in common
+pub const MIN_OVERLAP: usize = 12;
+
+/// Beacons found so far, each with the scanner that first reported it
+pub type Beacons = HashMap<Pos, usize>;
+
+/// Where a scan fits onto the beacons found so far
+pub struct Merge {
+    pub rotation: Rotation,
+    pub offset: Pos,
+    /// How many of the scan's beacons were already known, by the scanner
+    /// that first reported them
+    pub matched: BTreeMap<usize, usize>,
+}
+
+/// Rotation and offset that line up at least `MIN_OVERLAP` of `other`'s
+/// beacons with ones already found, if there are any
+pub fn find_overlap(beacons: &Beacons, other: &[Pos]) -> Option<(Rotation, Pos)> {
+    for rotation in Rotation::all() {
+        let new_data: Vec<Pos> = other.iter().map(|&p| rotation.apply(p)).collect();
+        for &pin in beacons.keys() {
+            for &other_pin in new_data.iter() {
+                let offset = pin - other_pin;
+                let mut matched = 1;
+
+                for pos in new_data.iter().map(|&p| p + offset) {
+                    if beacons.contains_key(&pos) {
+                        matched += 1;
+                    }
+                    if matched >= MIN_OVERLAP {
+                        return Some((rotation, offset));
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Adds `scanner`'s scan to `beacons`, placed with `rotation` and `offset`
+pub fn merge(beacons: &mut Beacons, scanner: usize, other: &[Pos], rotation: Rotation, offset: Pos) -> Merge {
+    let mut matched = BTreeMap::new();
+    for p in other.iter().map(|&p| rotation.apply(p) + offset) {
+        match beacons.get(&p) {
+            Some(&owner) => *matched.entry(owner).or_insert(0) += 1,
+            None => { beacons.insert(p, scanner); },
+        }
+    }
+    Merge { rotation, offset, matched }
+}
+
+/// Merges `scanner`'s scan into `beacons` if at least `MIN_OVERLAP` of its beacons can
+/// be lined up with ones already found
+pub fn merge_if_overlap(beacons: &mut Beacons, scanner: usize, other: &[Pos]) -> Option<Merge>
+{
+    let (rotation, offset) = find_overlap(beacons, other)?;
+    Some(merge(beacons, scanner, other, rotation, offset))
+}
+
+pub fn find_max_manhattan(positions: impl IntoIterator<Item = Pos>) -> Int {
+    let beacons: Vec<Pos> = positions.into_iter().collect();
+    let mut max = 0;
+    for i in 0..beacons.len() {
+        let sub = &beacons[i..];
+        let a = sub[0];
+        max = std::cmp::max(
+            max,
+            sub[1..].iter()
+                .map(|&p| a.manhattan(p))
+                .max()
+                .unwrap_or(0));
+    }
+    max
+}
+
+/// Each scanner's beacons, from blocks of positions under a
+/// `--- scanner N ---` header
+pub fn read_input(lines: &mut impl Iterator<Item = String>) -> Vec<Vec<Pos>> {
+    lines.blocks()
+        .map(|block| block[1..].iter().map(|s| s.parse::<Pos>().unwrap()).collect())
+        .collect()
+}
+
+/// Scanner that has been placed relative to scanner 0
+pub struct Located {
+    pub scanner: usize,
+    pub merge: Merge,
+}
+
+/// Repeatedly merges scans that overlap the beacons found so far, returning
+/// the full set of beacons and where each scanner was found, in the order they
+/// were located
+#[cfg(not(feature = "parallel"))]
+pub fn locate_scanners(data: &[Vec<Pos>]) -> (HashSet<Pos>, Vec<Located>) {
+    let _span = tracing::info_span!("locate scanners").entered();
+    let mut scanners = Vec::with_capacity(data.len());
+    let mut beacons: Beacons = data[0].iter().map(|&p| (p, 0)).collect();
+    let mut to_match: Vec<(usize, &Vec<Pos>)> = data.iter().enumerate().skip(1).collect();
+    let mut progress = Progress::bar("scanners located", data.len() as u64);
+    progress.set(1);
+
+    while let Some(&(scanner, sensor_data)) = to_match.last() {
+        let merged = tracing::info_span!("match scanner", scanner)
+            .in_scope(|| merge_if_overlap(&mut beacons, scanner, sensor_data));
+        match merged {
+            Some(merge) => {
+                scanners.push(Located { scanner, merge });
+                to_match.pop();
+                progress.inc(1);
+            },
+            None => {
+                to_match.rotate_left(1);
+                assert_ne!(to_match.len(), 1);
+            }
+        };
+    }
+
+    (beacons.into_keys().collect(), scanners)
+}
+
+/// Repeatedly merges scans that overlap the beacons found so far, returning
+/// the full set of beacons and where each scanner was found, in the order they
+/// were located. Each round looks for overlaps with every scan left on all
+/// cores, then merges the ones found in scanner order.
+#[cfg(feature = "parallel")]
+pub fn locate_scanners(data: &[Vec<Pos>]) -> (HashSet<Pos>, Vec<Located>) {
+    use rayon::prelude::*;
+
+    let span = tracing::info_span!("locate scanners");
+    let _entered = span.enter();
+    let mut scanners = Vec::with_capacity(data.len());
+    let mut beacons: Beacons = data[0].iter().map(|&p| (p, 0)).collect();
+    let mut to_match: Vec<(usize, &Vec<Pos>)> = data.iter().enumerate().skip(1).collect();
+    let mut progress = Progress::bar("scanners located", data.len() as u64);
+    progress.set(1);
+
+    while !to_match.is_empty() {
+        let overlaps: Vec<Option<(Rotation, Pos)>> = to_match.par_iter()
+            .map(|&(scanner, sensor_data)| {
+                // under the outer span whichever thread this runs on
+                tracing::info_span!(parent: &span, "match scanner", scanner)
+                    .in_scope(|| find_overlap(&beacons, sensor_data))
+            })
+            .collect();
+        assert!(overlaps.iter().any(Option::is_some), "scanners {:?} overlap none located", to_match.iter().map(|(s, _)| s).collect::<Vec<_>>());
+
+        let mut left = Vec::with_capacity(to_match.len());
+        for ((scanner, sensor_data), overlap) in to_match.into_iter().zip(overlaps) {
+            match overlap {
+                Some((rotation, offset)) => {
+                    let merge = merge(&mut beacons, scanner, sensor_data, rotation, offset);
+                    scanners.push(Located { scanner, merge });
+                    progress.inc(1);
+                },
+                None => left.push((scanner, sensor_data)),
+            }
+        }
+        to_match = left;
+    }
+
+    (beacons.into_keys().collect(), scanners)
+}
+
+/// Graphviz digraph of which scanners' beacons each scan was matched
+/// against, edges labelled with the number of shared beacons and scanners
+/// with the order they were located in
+pub fn overlap_graph(located: &[Located]) -> String {
+    let mut dot = String::from("digraph scanners {\n    0 [label=\"0\\n#0\"];\n");
+    for (order, l) in located.iter().enumerate() {
+        writeln!(dot, "    {} [label=\"{}\\n#{}\"];", l.scanner, l.scanner, order + 1).unwrap();
+        for (from, shared) in &l.merge.matched {
+            writeln!(dot, "    {} -> {} [label=\"{}\"];", from, l.scanner, shared).unwrap();
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// Scanner reports, with the (expensive) alignment shared between both parts
+pub struct Puzzle {
+    scans: Vec<Vec<Pos>>,
+    located: OnceCell<(HashSet<Pos>, Vec<Located>)>,
+}
+
+impl Puzzle {
+    fn located(&self) -> &(HashSet<Pos>, Vec<Located>) {
+        self.located.get_or_init(|| locate_scanners(&self.scans))
+    }
+}
+
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        let scans = read_input(&mut input.lines().map(String::from));
+        Ok(Puzzle { scans, located: OnceCell::new() })
+    }
+
+    fn part1(&self) -> String {
+        self.located().0.len().to_string()
+    }
+
+    fn part2(&self) -> String {
+        let positions = std::iter::once(ORIGIN).chain(self.located().1.iter().map(|l| l.merge.offset));
+        find_max_manhattan(positions).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlaps_form_a_tree() {
+        let scans = read_input(&mut include_str!("../input_test.txt").lines().map(String::from));
+        let (beacons, located) = locate_scanners(&scans);
+        assert_eq!(beacons.len(), 79);
+
+        // every scanner is matched against at least one located before it
+        let mut seen = HashSet::from([0]);
+        for l in &located {
+            assert!(l.merge.matched.keys().any(|s| seen.contains(s)));
+            assert!(l.merge.matched.values().sum::<usize>() >= 11);
+            seen.insert(l.scanner);
+        }
+        assert_eq!(seen.len(), scans.len());
+
+        let dot = overlap_graph(&located);
+        assert!(dot.starts_with("digraph scanners {\n"));
+        assert_eq!(dot.matches(" -> ").count(), located.iter().map(|l| l.merge.matched.len()).sum::<usize>());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let rotations: Vec<Rotation> = Rotation::all().collect();
+        let json = serde_json::to_string(&(rotations.clone(), Pos::new(1, -2, 3))).unwrap();
+        assert!(serde_json::from_str::<(Vec<Rotation>, Pos)>(&json).unwrap() == (rotations, Pos::new(1, -2, 3)));
+    }
+
+    /// The answers given in the puzzle text for its example
+    #[test]
+    fn example_answers() {
+        let puzzle = Puzzle::parse(include_str!("../input_test.txt"));
+        assert_eq!(puzzle.part1(), "79");
+        assert_eq!(puzzle.part2(), "3621");
+    }
+}
@@ -0,0 +1,52 @@
+use std::io::BufRead;
+
+use d19::*;
+
+fn main() {
+    /*
+    for r in Rotation::all() {
+        let v = r.apply(Pos::from([1, 2, 3]));
+        println!("{}", v);
+    }
+    */
+    // --dot prints the scanner overlap graph for graphviz instead;
+    // --progress shows scanners being located; --sensitivity places the
+    // scanners again with each minimum overlap from 12 down to 3
+    let mut dot = false;
+    let mut sensitivity = false;
+    let (input, args) = aoc_core::input_arg();
+    for arg in args {
+        match arg.as_str() {
+            "--dot" => dot = true,
+            "--sensitivity" => sensitivity = true,
+            "--progress" => aoc_core::progress::enable(true),
+            _ => panic!("unknown argument: {}", arg),
+        }
+    }
+
+    let mut lines = aoc_core::open_input(&input).lines().map(|l| l.unwrap());
+    let data = read_input(&mut lines);
+
+    if sensitivity {
+        for run in d19::sensitivity(&data, 3) {
+            println!("{}", run);
+        }
+        return;
+    }
+
+    let (beacons, scanners) = locate_scanners(&data);
+    if dot {
+        print!("{}", overlap_graph(&scanners));
+        return;
+    }
+
+    for Located { scanner, merge } in &scanners {
+        let matched: Vec<String> = merge.matched.iter().map(|(s, n)| format!("{}x{}", s, n)).collect();
+        println!("scanner {} matched with {:?} + {} against {}", scanner, merge.rotation, merge.offset, matched.join(" "));
+    }
+
+    println!("{} total beacons", beacons.len());
+
+    let scanner_positions: Vec<Pos> = scanners.iter().map(|l| l.merge.offset).collect();
+    println!("max manhattan: {}", find_max_manhattan(scanner_positions));
+}
@@ -0,0 +1,153 @@
+//! How much the reconstruction leans on the puzzle's promise that
+//! overlapping scanners share at least 12 beacons. Placing every scanner
+//! again with a lower minimum shows where a scan starts to fit more than one
+//! way, and how far the beacon count drifts once wrong fits get merged.
+
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::{Beacons, Pos, Rotation, MIN_OVERLAP};
+
+/// One way a scan lines up with the beacons found so far
+#[derive(Clone, Copy)]
+pub struct Placement {
+    pub rotation: Rotation,
+    pub offset: Pos,
+    /// How many of the scan's beacons land on known ones
+    pub overlap: usize,
+}
+
+/// Every distinct way `scan` lines up with at least `min_overlap` of
+/// `beacons`, biggest overlap first. Fits that put the scan's beacons in the
+/// same places count once, however they were rotated.
+pub fn placements(beacons: &Beacons, scan: &[Pos], min_overlap: usize) -> Vec<Placement> {
+    let mut found = vec!();
+    let mut seen: HashSet<Vec<Pos>> = HashSet::new();
+    for rotation in Rotation::all() {
+        let rotated: Vec<Pos> = scan.iter().map(|&p| rotation.apply(p)).collect();
+        // each pair of a known beacon and a scanned one votes for the offset
+        // that would put them on top of each other
+        let mut votes: HashMap<Pos, usize> = HashMap::new();
+        for &pin in beacons.keys() {
+            for &p in &rotated {
+                *votes.entry(pin - p).or_insert(0) += 1;
+            }
+        }
+        for (offset, overlap) in votes {
+            if overlap < min_overlap {
+                continue;
+            }
+            let mut points: Vec<Pos> = rotated.iter().map(|&p| p + offset).collect();
+            points.sort();
+            if seen.insert(points) {
+                found.push(Placement { rotation, offset, overlap });
+            }
+        }
+    }
+    found.sort_by_key(|p: &Placement| (Reverse(p.overlap), p.offset));
+    found
+}
+
+/// How the reconstruction went with one minimum overlap
+#[derive(Debug, PartialEq, Eq)]
+pub struct Sensitivity {
+    pub min_overlap: usize,
+    pub beacons: usize,
+    /// Scanners that couldn't be placed at all
+    pub unplaced: usize,
+    /// Scanners that fit more than one way when they were placed, with how
+    /// many ways
+    pub ambiguous: Vec<(usize, usize)>,
+}
+
+/// Places every scanner it can, needing `min_overlap` shared beacons and
+/// taking the biggest overlap when a scan fits more than one way
+pub fn reconstruct(data: &[Vec<Pos>], min_overlap: usize) -> Sensitivity {
+    let mut beacons: Beacons = data[0].iter().map(|&p| (p, 0)).collect();
+    let mut to_match: Vec<usize> = (1..data.len()).collect();
+    let mut ambiguous = vec!();
+    // scanners tried in a row without placing one
+    let mut misses = 0;
+
+    while misses < to_match.len() {
+        let scanner = to_match[0];
+        let fits = placements(&beacons, &data[scanner], min_overlap);
+        let Some(best) = fits.first() else {
+            to_match.rotate_left(1);
+            misses += 1;
+            continue;
+        };
+        if fits.len() > 1 {
+            ambiguous.push((scanner, fits.len()));
+        }
+        for &p in &data[scanner] {
+            beacons.entry(best.rotation.apply(p) + best.offset).or_insert(scanner);
+        }
+        to_match.remove(0);
+        misses = 0;
+    }
+
+    Sensitivity { min_overlap, beacons: beacons.len(), unplaced: to_match.len(), ambiguous }
+}
+
+/// `reconstruct` with each minimum overlap from 12 down to `lowest`
+pub fn sensitivity(data: &[Vec<Pos>], lowest: usize) -> Vec<Sensitivity> {
+    (lowest..=MIN_OVERLAP).rev().map(|n| reconstruct(data, n)).collect()
+}
+
+impl fmt::Display for Sensitivity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "min overlap {:>2}: {} beacons", self.min_overlap, self.beacons)?;
+        if self.unplaced > 0 {
+            write!(f, ", {} scanners unplaced", self.unplaced)?;
+        }
+        if self.ambiguous.is_empty() {
+            write!(f, ", unambiguous")
+        } else {
+            let scanners: Vec<String> = self.ambiguous.iter().map(|(s, n)| format!("{} ({} ways)", s, n)).collect();
+            write!(f, ", ambiguous: scanner {}", scanners.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_input;
+
+    #[test]
+    fn example_is_unambiguous_down_to_3() {
+        let scans = read_input(&mut include_str!("../input_test.txt").lines().map(String::from));
+        let runs = sensitivity(&scans, 3);
+        assert_eq!(runs.len(), 10);
+        for (run, n) in runs.iter().zip((3..=12).rev()) {
+            assert_eq!(*run, Sensitivity { min_overlap: n, beacons: 79, unplaced: 0, ambiguous: vec!() });
+        }
+        assert_eq!(runs[0].to_string(), "min overlap 12: 79 beacons, unambiguous");
+
+        // below 3 scans start fitting more than one way, though the biggest
+        // overlap is still the right one
+        let pairs = reconstruct(&scans, 2);
+        assert_eq!(pairs.ambiguous, [(1, 2)]);
+        assert_eq!(pairs.to_string(), "min overlap  2: 79 beacons, ambiguous: scanner 1 (2 ways)");
+        let single = reconstruct(&scans, 1);
+        assert_eq!(single.ambiguous.len(), scans.len() - 1);
+        assert_eq!(single.beacons, 79);
+    }
+
+    #[test]
+    fn lower_minimum_places_a_thin_overlap() {
+        // scanner 1 sees only 3 of scanner 0's 12 beacons, plus 9 of its own
+        let beacon = |i: i32| Pos::new(i * i * 37 % 1000 - 500, i * 71 % 997 - 400, i * i * i % 911 - 300);
+        let offset = Pos::new(700, -300, 150);
+        let first: Vec<Pos> = (1..=12).map(beacon).collect();
+        let second: Vec<Pos> = (1..=3).chain(13..=21).map(|i| beacon(i) - offset).collect();
+        let scans = [first, second];
+
+        let strict = reconstruct(&scans, MIN_OVERLAP);
+        assert_eq!(strict, Sensitivity { min_overlap: 12, beacons: 12, unplaced: 1, ambiguous: vec!() });
+        let loose = reconstruct(&scans, 3);
+        assert_eq!(loose, Sensitivity { min_overlap: 3, beacons: 21, unplaced: 0, ambiguous: vec!() });
+    }
+}
@@ -0,0 +1,252 @@
+use std::fmt;
+use std::io::{BufRead, BufReader, Read};
+use std::str::FromStr;
+
+use aoc_core::{AocError, Solver};
+
+#[derive(Clone,Copy)]
+pub enum Direction {
+    Forward,
+    Up,
+    Down,
+}
+
+#[derive(Clone,Copy)]
+pub struct Movement {
+    pub direction: Direction,
+    pub distance: i32,
+}
+
+#[derive(Default,Clone,Copy,PartialEq,Eq,Debug)]
+pub struct Position {
+    pub depth: i32,
+    pub horizontal: i32,
+    pub aim: i32,
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", match self {
+            Direction::Forward => "FORWARD",
+            Direction::Up => "UP",
+            Direction::Down => "DOWN",
+        })
+    }
+}
+
+/// `forward 5`, `up 3` or `down 8`
+impl FromStr for Movement {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Movement, AocError> {
+        let mut iter = s.split_ascii_whitespace();
+        let direction = match iter.next() {
+            Some("forward") => Direction::Forward,
+            Some("up") => Direction::Up,
+            Some("down") => Direction::Down,
+            Some(d) => return Err(AocError::invalid(format!("bad direction {:?}", d))),
+            None => return Err("missing direction".into()),
+        };
+        let distance = iter.next().ok_or("missing distance")?.parse::<i32>()?;
+
+        Ok(Movement { direction, distance })
+    }
+}
+
+impl fmt::Display for Movement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.direction, self.distance)
+    }
+}
+
+impl Position {
+    pub fn new(depth: i32, horizontal: i32, aim: i32) -> Self {
+        Self { depth, horizontal, aim}
+    }
+
+    pub fn move_by(&self, m: Movement) -> Position {
+        match m.direction {
+            Direction::Forward => Position::new(self.depth + self.aim * m.distance, self.horizontal + m.distance, self.aim),
+            Direction::Up => Position::new(self.depth, self.horizontal, self.aim - m.distance),
+            Direction::Down => Position::new(self.depth, self.horizontal, self.aim + m.distance),
+        }
+    }
+}
+
+/// Orders read a line at a time, with errors tagged by line number
+pub fn get_orders<R: Read>(rdr: R) -> impl Iterator<Item = Result<Movement, AocError>> {
+    let reader = BufReader::with_capacity(16, rdr);
+    reader
+        .lines()
+        .enumerate()
+        .map(|(i, l)| l.map_err(AocError::from)
+            .and_then(|l| l.parse::<Movement>())
+            .map_err(|e| e.at_line(i + 1)))
+}
+
+/// Final position after following every order.
+pub fn navigate(orders: impl Iterator<Item = Movement>) -> Position {
+    orders.fold(Position::default(), |p, m| p.move_by(m))
+}
+
+/// How a movement changes the submarine's position
+pub trait Navigator {
+    fn step(&self, p: Position, m: Movement) -> Position;
+}
+
+/// First reading of the manual: up and down change the depth directly
+pub struct Direct;
+
+/// Up and down change the aim, forward dives along it
+pub struct Aimed;
+
+impl Navigator for Direct {
+    fn step(&self, p: Position, m: Movement) -> Position {
+        match m.direction {
+            Direction::Forward => Position::new(p.depth, p.horizontal + m.distance, p.aim),
+            Direction::Up => Position::new(p.depth - m.distance, p.horizontal, p.aim),
+            Direction::Down => Position::new(p.depth + m.distance, p.horizontal, p.aim),
+        }
+    }
+}
+
+impl Navigator for Aimed {
+    fn step(&self, p: Position, m: Movement) -> Position {
+        p.move_by(m)
+    }
+}
+
+/// A sequence of movements, either parsed or built up with
+/// `Course::new().forward(5).down(3)`
+#[derive(Clone, Default)]
+pub struct Course(Vec<Movement>);
+
+impl Course {
+    pub fn new() -> Course {
+        Course::default()
+    }
+
+    pub fn then(mut self, direction: Direction, distance: i32) -> Course {
+        self.0.push(Movement { direction, distance });
+        self
+    }
+
+    pub fn forward(self, distance: i32) -> Course {
+        self.then(Direction::Forward, distance)
+    }
+
+    pub fn up(self, distance: i32) -> Course {
+        self.then(Direction::Up, distance)
+    }
+
+    pub fn down(self, distance: i32) -> Course {
+        self.then(Direction::Down, distance)
+    }
+
+    pub fn movements(&self) -> &[Movement] {
+        &self.0
+    }
+
+    /// Where following the whole course ends up
+    pub fn evaluate(&self, nav: &impl Navigator) -> Position {
+        self.0.iter().fold(Position::default(), |p, &m| nav.step(p, m))
+    }
+}
+
+/// Where the submarine is under both readings of the manual, so one pass
+/// over the orders answers both parts
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Fixes {
+    pub direct: Position,
+    pub aimed: Position,
+}
+
+impl Fixes {
+    pub fn step(self, m: Movement) -> Fixes {
+        Fixes { direct: Direct.step(self.direct, m), aimed: Aimed.step(self.aimed, m) }
+    }
+
+    /// Depth times horizontal position, for `Direct` then `Aimed`
+    pub fn products(&self) -> (i32, i32) {
+        (self.direct.depth * self.direct.horizontal, self.aimed.depth * self.aimed.horizontal)
+    }
+}
+
+impl FromIterator<Movement> for Course {
+    fn from_iter<I: IntoIterator<Item = Movement>>(iter: I) -> Course {
+        Course(iter.into_iter().collect())
+    }
+}
+
+/// Both parts' answers from the orders in `reader`, each one followed as
+/// it's read and let go
+pub fn stream<R: Read>(reader: R) -> Result<[Option<String>; 2], AocError> {
+    let (direct, aimed) = follow(reader)?.products();
+    Ok([Some(direct.to_string()), Some(aimed.to_string())])
+}
+
+fn follow<R: Read>(reader: R) -> Result<Fixes, AocError> {
+    get_orders(reader).try_fold(Fixes::default(), |f, m| m.map(|m| f.step(m)))
+}
+
+pub struct Puzzle {
+    fixes: Fixes,
+}
+
+impl Solver for Puzzle {
+    /// Follows the orders as they're parsed, without keeping them
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        Ok(Puzzle { fixes: follow(input.as_bytes())? })
+    }
+
+    fn part1(&self) -> String {
+        self.fixes.products().0.to_string()
+    }
+
+    fn part2(&self) -> String {
+        self.fixes.products().1.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn built_course_matches_parsed() {
+        let course = Course::new().forward(5).down(5).forward(8).up(3).down(8).forward(2);
+        let parsed: Course = get_orders("forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2\n".as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let direct = course.evaluate(&Direct);
+        assert_eq!((direct.horizontal, direct.depth), (15, 10));
+        let aimed = course.evaluate(&Aimed);
+        assert_eq!((aimed.horizontal, aimed.depth), (15, 60));
+
+        let reparsed = parsed.evaluate(&Aimed);
+        assert_eq!((reparsed.horizontal, reparsed.depth), (15, 60));
+
+        let fixes = course.movements().iter().fold(Fixes::default(), |f, &m| f.step(m));
+        assert_eq!(fixes, Fixes { direct, aimed });
+        assert_eq!(fixes.products(), (150, 900));
+    }
+
+    #[test]
+    fn bad_order_reports_line() {
+        let err = Puzzle::try_parse("forward 5\nsideways 2\n").err().unwrap();
+        assert_eq!(err.to_string(), "line 2: bad direction \"sideways\"");
+        assert!(Puzzle::try_parse("up x").is_err());
+    }
+
+    /// The answers given in the puzzle text for its example
+    #[test]
+    fn example_answers() {
+        let puzzle = Puzzle::parse(include_str!("../test_input"));
+        assert_eq!(puzzle.part1(), "150");
+        assert_eq!(puzzle.part2(), "900");
+
+        let answers = stream(include_str!("../test_input").as_bytes()).unwrap();
+        assert_eq!(answers, [Some("150".to_string()), Some("900".to_string())]);
+    }
+}
@@ -0,0 +1,17 @@
+use d2::*;
+
+fn main() {
+    // both readings of the manual, followed in one pass as the orders are
+    // read
+    let fixes = get_orders(aoc_core::input())
+        .map(aoc_core::or_exit)
+        .fold(Fixes::default(), |f, m| {
+            let next = f.step(m);
+            println!("{} ({}, {}) ({}, {})", m, next.direct.depth, next.direct.horizontal, next.aimed.depth, next.aimed.horizontal);
+            next
+        });
+
+    let (direct, aimed) = fixes.products();
+    println!("direct: {}", direct);
+    println!("aimed: {}", aimed);
+}
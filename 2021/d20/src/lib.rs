@@ -0,0 +1,547 @@
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+
+use std::cmp;
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::{Index, Range};
+
+use aoc_core::{option_value, AocError, Solver};
+use aoc_geometry::Point2;
+use aoc_grid::Grid;
+
+type Int = i32;
+
+pub type Pos = Point2<Int>;
+
+/// One bit of an enhancer index
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum Pixel {
+    Dark = 0,
+    Light = 1,
+}
+
+impl fmt::Display for Pixel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Pixel::Light => write!(f, "#"),
+            Pixel::Dark => write!(f, "."),
+        }
+    }
+}
+
+pub struct Enhancer([Pixel; 512]);
+
+impl Enhancer {
+    pub fn try_from_str<S: AsRef<str>>(s: S) -> Result<Self, &'static str> {
+        let s = s.as_ref();
+        assert_eq!(s.len(), 512);
+
+        let mut a = [Pixel::Dark; 512];
+        for (i,c) in s.char_indices() {
+            a[i] = match c {
+                '#' => Pixel::Light,
+                '.' => Pixel::Dark,
+                _ => return Err("Invalid pixel character"),
+            }
+        }
+
+        Ok(Enhancer(a))
+    }
+}
+
+/// Dense row-major image, double-buffered so that enhancing reuses the
+/// same allocations round after round
+#[derive(Clone)]
+pub struct Image {
+    pixels: Grid<Pixel>,
+    back: Vec<Pixel>,
+    dim: Dimensions,
+    inf: Pixel,
+}
+
+impl Image {
+    pub fn new() -> Self {
+        Image { pixels: Grid::default(), back: Vec::new(), dim: Dimensions::new(), inf: Pixel::Dark }
+    }
+
+    pub fn dimensions(&self) -> Dimensions {
+        self.dim.clone()
+    }
+
+    fn dimensions_of(points: &HashSet<Pos>) -> Dimensions {
+        let mut min_x = i32::MAX;
+        let mut max_x = i32::MIN;
+        let mut min_y = i32::MAX;
+        let mut max_y = i32::MIN;
+
+        for p in points {
+            let x = p.x;
+            let y = p.y;
+            min_x = cmp::min(min_x, x);
+            max_x = cmp::max(max_x, x);
+            min_y = cmp::min(min_y, y);
+            max_y = cmp::max(max_y, y);
+        }
+
+        Dimensions { x: min_x..max_x+1, y: min_y..max_y+1 }
+    }
+
+    /// Reserve buffer space so the next `rounds` enhancements don't allocate
+    pub fn reserve_rounds(&mut self, rounds: usize) {
+        let width = self.dim.width() + 2 * rounds;
+        let height = self.dim.height() + 2 * rounds;
+        let len = width * height;
+        let mut pixels = std::mem::take(&mut self.pixels).into_vec();
+        pixels.reserve(len.saturating_sub(pixels.len()));
+        self.pixels = Grid::from_vec(self.dim.width(), pixels);
+        self.back.reserve(len.saturating_sub(self.back.len()));
+    }
+
+    pub fn count_lit(&self) -> usize {
+        self.pixels.cells().iter().filter(|&&p| p == Pixel::Light).count()
+    }
+
+    pub fn infinity(&self) -> Pixel {
+        self.inf
+    }
+
+    pub fn enhance(&mut self, enhancer: &Enhancer) {
+        let dimensions = self.dimensions();
+        let dim_x = dimensions.x();
+        let dim_y = dimensions.y();
+        let next_dim = Dimensions { x: dim_x.start-1..dim_x.end+1, y: dim_y.start-1..dim_y.end+1 };
+
+        let mut back = std::mem::take(&mut self.back);
+        back.clear();
+        for y in next_dim.y() {
+            self.row_enhancer_indices(y, |i| back.push(enhancer.0[i]));
+        }
+
+        let front = std::mem::replace(&mut self.pixels, Grid::from_vec(next_dim.width(), back));
+        self.back = front.into_vec();
+
+        self.inf = enhancer.0[
+            match self.inf {
+                Pixel::Light => 0b111111111,
+                Pixel::Dark => 0b000000000,
+            }];
+
+        self.dim = next_dim;
+    }
+
+    /// Image over `dim` with pixels from `pixel`, keeping the background
+    fn with_pixels(&self, dim: Dimensions, pixel: impl Fn(Pos) -> Pixel) -> Image {
+        let mut cells = Vec::with_capacity(dim.width() * dim.height());
+        for y in dim.y() {
+            for x in dim.x() {
+                cells.push(pixel(Pos::new(x, y)));
+            }
+        }
+        Image { pixels: Grid::from_vec(dim.width(), cells), back: Vec::new(), dim, inf: self.inf }
+    }
+
+    /// Just the pixels within `bounds`, with the background everywhere else.
+    /// Bounds reaching past the image take the background there too.
+    pub fn crop(&self, bounds: &Dimensions) -> Image {
+        self.with_pixels(bounds.clone(), |p| self[p])
+    }
+
+    /// The image with a border `margin` pixels wide of `pixel` around it,
+    /// leaving the background past the border as it was
+    pub fn pad(&self, margin: usize, pixel: Pixel) -> Image {
+        let m = margin as Int;
+        let dim = Dimensions { x: self.dim.x.start - m..self.dim.x.end + m, y: self.dim.y.start - m..self.dim.y.end + m };
+        self.with_pixels(dim, |p| if self.dim.contains(p) { self[p] } else { pixel })
+    }
+
+    /// The same image moved by `offset`
+    pub fn translate(&self, offset: Pos) -> Image {
+        let dim = Dimensions {
+            x: self.dim.x.start + offset.x..self.dim.x.end + offset.x,
+            y: self.dim.y.start + offset.y..self.dim.y.end + offset.y,
+        };
+        Image { pixels: self.pixels.clone(), back: Vec::new(), dim, inf: self.inf }
+    }
+
+    pub fn enhanced_pixel(&self, p: Pos, enhancer: &Enhancer) -> Pixel {
+        enhancer.0[self.enhancer_index(p)]
+    }
+
+    pub fn enhancer_index(&self, p: Pos) -> usize {
+        let mut idx = 0;
+        for y in p.y-1..=p.y+1 {
+            for x in p.x-1..=p.x+1 {
+                idx = (idx << 1) | (match self[Pos::from([x, y])] {
+                    Pixel::Light => 1,
+                    Pixel::Dark => 0,
+                });
+            }
+        }
+        //println!("{},{} -> {:09b}", p.x, p.y, idx);
+        idx
+    }
+
+    /// `enhancer_index` of every pixel along row `y`, from the column left
+    /// of the image to the one right of it
+    pub fn row_enhancer_indices(&self, y: Int, mut index: impl FnMut(usize)) {
+        let xs = self.dim.x();
+        if xs.len() < 2 {
+            return (xs.start - 1..xs.end + 1).for_each(|x| index(self.enhancer_index(Pos::new(x, y))));
+        }
+        // the two columns at each side see past the edge of the image
+        index(self.enhancer_index(Pos::new(xs.start - 1, y)));
+        index(self.enhancer_index(Pos::new(xs.start, y)));
+        let rows = [y - 1, y, y + 1].map(|y| self.row_bits(y));
+        interior_indices(rows, xs.len(), &mut index);
+        index(self.enhancer_index(Pos::new(xs.end - 1, y)));
+        index(self.enhancer_index(Pos::new(xs.end, y)));
+    }
+
+    /// Pixels of row `y` as bits, or the background if it's outside the image
+    fn row_bits(&self, y: Int) -> RowBits<'_> {
+        if !self.dim.y.contains(&y) {
+            return RowBits::Background(self.inf as u8);
+        }
+        let start = (y - self.dim.y.start) as usize * self.dim.width();
+        let row = &self.pixels.cells()[start..start + self.dim.width()];
+        // Pixel is repr(u8), so its slice can be read as the bytes 0 and 1
+        RowBits::Pixels(unsafe { std::slice::from_raw_parts(row.as_ptr() as *const u8, row.len()) })
+    }
+}
+
+enum RowBits<'a> {
+    Pixels(&'a [u8]),
+    Background(u8),
+}
+
+impl RowBits<'_> {
+    /// The three bits around column `i` as a number
+    fn around(&self, i: usize) -> usize {
+        match *self {
+            RowBits::Pixels(px) => (px[i - 1] as usize) << 2 | (px[i] as usize) << 1 | px[i + 1] as usize,
+            RowBits::Background(b) => b as usize * 0b111,
+        }
+    }
+}
+
+/// Enhancer indices for columns `1..width - 1`, whose neighbours are all
+/// inside the image across the row
+#[cfg(not(feature = "simd"))]
+fn interior_indices(rows: [RowBits; 3], width: usize, index: &mut impl FnMut(usize)) {
+    for i in 1..width - 1 {
+        index(rows[0].around(i) << 6 | rows[1].around(i) << 3 | rows[2].around(i));
+    }
+}
+
+/// `interior_indices` 16 columns at a time
+#[cfg(feature = "simd")]
+fn interior_indices(rows: [RowBits; 3], width: usize, index: &mut impl FnMut(usize)) {
+    use std::simd::prelude::*;
+
+    const LANES: usize = 16;
+    let around = |row: &RowBits, i: usize| -> Simd<u16, LANES> {
+        match *row {
+            RowBits::Pixels(px) => {
+                let at = |j: usize| Simd::<u8, LANES>::from_slice(&px[j..j + LANES]).cast::<u16>();
+                at(i - 1) << 2 | at(i) << 1 | at(i + 1)
+            },
+            RowBits::Background(b) => Simd::splat(b as u16 * 0b111),
+        }
+    };
+    let mut i = 1;
+    while i + LANES < width {
+        let lanes = around(&rows[0], i) << 6 | around(&rows[1], i) << 3 | around(&rows[2], i);
+        lanes.to_array().iter().for_each(|&n| index(n as usize));
+        i += LANES;
+    }
+    for i in i..width - 1 {
+        index(rows[0].around(i) << 6 | rows[1].around(i) << 3 | rows[2].around(i));
+    }
+}
+
+impl Default for Image {
+    fn default() -> Self { Self::new() }
+}
+
+impl From<HashSet<Pos>> for Image {
+    fn from(points: HashSet<Pos>) -> Self {
+        let dim = Image::dimensions_of(&points);
+        let mut pixels = Grid::filled(dim.width(), dim.height(), Pixel::Dark);
+        for p in &points {
+            pixels[dim.grid_pos(*p)] = Pixel::Light;
+        }
+        Image { pixels, back: Vec::new(), dim, inf: Pixel::Dark }
+    }
+}
+
+impl Index<Pos> for Image {
+    type Output = Pixel;
+    fn index(&self, p: Pos) -> &Self::Output {
+        if self.dim.contains(p) {
+            &self.pixels[self.dim.grid_pos(p)]
+        } else {
+            &self.inf
+        }
+    }
+}
+
+impl fmt::Display for Image {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let dimensions = self.dimensions();
+        let dim_y = dimensions.y();
+        let dim_x = dimensions.x();
+
+        for y in dim_y.start-1..dim_y.end+1 {
+            for x in dim_x.start-1..dim_x.end+1 {
+                write!(f, "{}", self[Pos::from([x, y])])?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Dimensions {
+    x: Range<i32>,
+    y: Range<i32>,
+}
+
+impl Dimensions {
+    pub fn new() -> Dimensions {
+        Dimensions { x: 0..0, y: 0..0 }
+    }
+
+    /// The columns `x` by the rows `y`
+    pub fn from_ranges(x: Range<i32>, y: Range<i32>) -> Dimensions {
+        Dimensions { x, y }
+    }
+
+    pub fn x(&self) -> Range<i32> {
+        self.x.clone()
+    }
+
+    pub fn y(&self) -> Range<i32> {
+        self.y.clone()
+    }
+
+    pub fn width(&self) -> usize {
+        self.x.len()
+    }
+
+    pub fn height(&self) -> usize {
+        self.y.len()
+    }
+
+    pub fn contains(&self, p: Pos) -> bool {
+        self.x.contains(&p.x) && self.y.contains(&p.y)
+    }
+
+    /// Where `p` falls in a grid covering these dimensions
+    fn grid_pos(&self, p: Pos) -> aoc_grid::Pos {
+        (p - Pos::new(self.x.start, self.y.start)).try_convert().unwrap()
+    }
+}
+
+impl Default for Dimensions {
+    fn default() -> Self { Self::new() }
+}
+
+impl fmt::Debug for Dimensions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "x={:?} y={:?}", self.x(), self.y())
+    }
+}
+
+pub fn read_input(lines: &mut impl Iterator<Item = String>) -> (Enhancer, Image) {
+    let l = lines.next().unwrap();
+    let enhancer = Enhancer::try_from_str(l).unwrap();
+    lines.next();
+
+    let mut image_set = HashSet::with_capacity(300);
+    for (y, s) in lines.enumerate() {
+        for (x, c) in s.as_str().char_indices() {
+            if c == '#' {
+                image_set.insert(Pos::from([x as i32, y as i32]));
+            }
+        }
+    }
+    (enhancer, Image::from(image_set))
+}
+
+pub struct Puzzle {
+    enhancer: Enhancer,
+    image: Image,
+    /// Rounds of enhancement for each part
+    rounds: [usize; 2],
+}
+
+impl Puzzle {
+    /// Number of lit pixels after enhancing the input image `rounds` times
+    fn lit_after(&self, rounds: usize) -> usize {
+        let mut image = self.image.clone();
+        image.reserve_rounds(rounds);
+        for _ in 0..rounds {
+            image.enhance(&self.enhancer);
+        }
+        image.count_lit()
+    }
+}
+
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        let (enhancer, image) = read_input(&mut input.lines().map(String::from));
+        Ok(Puzzle { enhancer, image, rounds: [2, 50] })
+    }
+
+    /// `part1_rounds` and `part2_rounds`
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), AocError> {
+        match name {
+            "part1_rounds" => self.rounds[0] = option_value(name, value)?,
+            "part2_rounds" => self.rounds[1] = option_value(name, value)?,
+            _ => return Err(AocError::invalid(format!("no option {}", name))),
+        }
+        Ok(())
+    }
+
+    fn part1(&self) -> String {
+        self.lit_after(self.rounds[0]).to_string()
+    }
+
+    fn part2(&self) -> String {
+        self.lit_after(self.rounds[1]).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    struct CountingAlloc;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|c| c.set(c.get() + 1));
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) }
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: CountingAlloc = CountingAlloc;
+
+    fn allocations() -> usize {
+        ALLOCATIONS.with(|c| c.get())
+    }
+
+    const EXAMPLE: &str = include_str!("../input_test.txt");
+
+    fn example() -> (Enhancer, Image) {
+        read_input(&mut EXAMPLE.lines().map(String::from))
+    }
+
+    #[test]
+    fn enhancing_draws_as_golden() {
+        let (enhancer, mut image) = example();
+        let mut drawn = image.to_string();
+        for _ in 0..2 {
+            image.enhance(&enhancer);
+            drawn += &format!("\n{}", image);
+        }
+        aoc_core::golden::check(env!("CARGO_MANIFEST_DIR"), "enhanced", &drawn);
+    }
+
+    #[test]
+    fn enhance_example() {
+        let (enhancer, mut image) = example();
+        image.enhance(&enhancer);
+        image.enhance(&enhancer);
+        assert_eq!(image.count_lit(), 35);
+
+        for _ in 2..50 {
+            image.enhance(&enhancer);
+        }
+        assert_eq!(image.count_lit(), 3351);
+    }
+
+    #[test]
+    fn row_indices_match_each_pixel() {
+        let (enhancer, mut image) = example();
+        for _ in 0..3 {
+            image = image.pad(20, Pixel::Dark);
+            let xs = image.dimensions().x();
+            for y in image.dimensions().y().start - 1..image.dimensions().y().end + 1 {
+                let mut row = vec!();
+                image.row_enhancer_indices(y, |i| row.push(i));
+                let each: Vec<usize> = (xs.start - 1..xs.end + 1).map(|x| image.enhancer_index(Pos::new(x, y))).collect();
+                assert_eq!(row, each);
+            }
+            image.enhance(&enhancer);
+        }
+    }
+
+    #[test]
+    fn enhance_reuses_buffers() {
+        let (enhancer, mut image) = example();
+        image.reserve_rounds(10);
+
+        let before = allocations();
+        for _ in 0..10 {
+            image.enhance(&enhancer);
+        }
+        assert_eq!(allocations(), before);
+    }
+
+    #[test]
+    fn crop_pad_and_translate() {
+        let (enhancer, image) = example();
+        let top_left = image.crop(&Dimensions::from_ranges(0..2, 0..2));
+        assert_eq!(top_left.dimensions(), Dimensions::from_ranges(0..2, 0..2));
+        assert_eq!(top_left.to_string(), "....\n.#..\n.#..\n....\n");
+        // cropping past the edge fills in the background
+        assert_eq!(image.crop(&Dimensions::from_ranges(3..7, 4..5)).count_lit(), 2);
+
+        let padded = image.pad(2, Pixel::Light);
+        assert_eq!(padded.dimensions(), Dimensions::from_ranges(-2..7, -2..7));
+        assert_eq!(padded.count_lit(), image.count_lit() + 9 * 9 - 5 * 5);
+        assert_eq!(padded.crop(&image.dimensions()).to_string(), image.to_string());
+        assert_eq!(padded.infinity(), Pixel::Dark);
+
+        let moved = image.translate(Pos::new(10, -3));
+        assert_eq!(moved.dimensions(), Dimensions::from_ranges(10..15, -3..2));
+        assert_eq!(moved[Pos::new(10, -3)], image[Pos::new(0, 0)]);
+        assert_eq!(moved.to_string(), image.to_string());
+
+        // a dark border makes no difference to enhancing
+        let (mut a, mut b) = (image.clone(), image.pad(3, Pixel::Dark));
+        a.enhance(&enhancer);
+        b.enhance(&enhancer);
+        assert_eq!(a.count_lit(), b.count_lit());
+    }
+
+    /// The answers given in the puzzle text for its example
+    #[test]
+    fn example_answers() {
+        let puzzle = Puzzle::parse(include_str!("../input_test.txt"));
+        assert_eq!(puzzle.part1(), "35");
+        assert_eq!(puzzle.part2(), "3351");
+    }
+
+    #[test]
+    fn rounds_are_an_option() {
+        let mut puzzle = Puzzle::parse(EXAMPLE);
+        puzzle.set_option("part2_rounds", "2").unwrap();
+        assert_eq!(puzzle.part2(), "35");
+        assert!(puzzle.set_option("part2_rounds", "-1").is_err());
+    }
+}
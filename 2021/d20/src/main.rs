@@ -0,0 +1,61 @@
+use std::io::BufRead;
+use std::path::PathBuf;
+
+use aoc_render::Raster;
+use d20::*;
+
+/// Every pixel inside the image's dimensions, lit or not
+fn raster(image: &Image) -> Raster {
+    let dim = image.dimensions();
+    let (x0, y0) = (dim.x().start, dim.y().start);
+    Raster::from_fn(dim.width(), dim.height(), |x, y| {
+        (image[Pos::new(x0 + x as i32, y0 + y as i32)] == Pixel::Light) as u8
+    })
+}
+
+fn main() {
+    // --render <path> draws the image after every round, to a PNG of the
+    // result or a GIF of the lot
+    let mut render: Option<PathBuf> = None;
+    let (path, args) = aoc_core::input_arg();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--render" => render = Some(args.next().expect("--render needs a path").into()),
+            _ => panic!("unknown argument: {}", arg),
+        }
+    }
+
+    let input = aoc_core::open_input(&path);
+    let lines = &mut input.lines().map(|l| l.unwrap());
+    let (enhancer, mut image) = read_input(lines);
+    //println!("{:?}", &enhancer.0);
+
+    println!("dim: {:?}  inf: {}", image.dimensions(), image.infinity());
+    println!("{}", image);
+
+    /*
+    image.enhance(&enhancer);
+    println!("dim: {:?}  inf: {}", image.dimensions(), image.infinity());
+    println!("{}", image);
+
+    image.enhance(&enhancer);
+    println!("dim: {:?}  inf: {}", image.dimensions(), image.infinity());
+    println!("{}", image);
+    */
+    image.reserve_rounds(50);
+    let mut frames = vec!(raster(&image));
+    for _ in 0..50 {
+        image.enhance(&enhancer);
+        if render.is_some() {
+            frames.push(raster(&image));
+        }
+    }
+    println!("dim: {:?}  inf: {}", image.dimensions(), image.infinity());
+    println!("{}", image);
+    println!("lit: {}", image.count_lit());
+
+    if let Some(path) = render {
+        aoc_render::write(&path, &frames, 2).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+    }
+}
@@ -0,0 +1,482 @@
+//! The deterministic and Dirac dice games, as plain state machines over
+//! `core` and `alloc`. Universe counts are kept in `BTreeMap`s keyed by
+//! player state, which is all the quantum game needs and works without
+//! `std`.
+#![deny(clippy::std_instead_of_core, clippy::std_instead_of_alloc)]
+// universe counts are cloned for the sake of `bigint`, where they aren't `Copy`
+#![cfg_attr(not(feature = "bigint"), allow(clippy::clone_on_copy))]
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::fmt;
+use core::ops::{Add, AddAssign};
+
+/// Board square, stored from 0 but serialized from 1 as the puzzle numbers
+/// them
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(from = "u32", into = "u32"))]
+pub struct Position(u32);
+
+pub type Roll = u32;
+pub type Score = u32;
+
+const BOARD_SIZE: u32 = 10;
+
+/// Score that wins the quantum (Dirac dice) game
+const DIRAC_TARGET: Score = 21;
+
+impl Position {
+    pub fn pos(&self) -> u32 { self.0 + 1 }
+}
+
+impl From<u32> for Position {
+    fn from(start: u32) -> Position {
+        Position(start - 1)
+    }
+}
+
+impl From<Position> for u32 {
+    fn from(p: Position) -> u32 {
+        p.pos()
+    }
+}
+
+impl Add<Roll> for Position {
+    type Output = Self;
+    fn add(self, roll: Roll) -> Self::Output { Position((self.0 + roll ) % BOARD_SIZE) }
+}
+
+impl AddAssign<Roll> for Position {
+    fn add_assign(&mut self, roll: Roll)  { *self = *self + roll }
+}
+
+impl Add<Position> for Score {
+    type Output = Self;
+    fn add(self, pos: Position) -> Self::Output { self + pos.pos()}
+}
+
+impl AddAssign<Position> for Score {
+    fn add_assign(&mut self, pos: Position) { *self = *self + pos }
+}
+
+/// Rolls 1, 2, 3 and so on up to its number of sides, then back to 1
+pub struct DeterministicDice {
+    n: DiceRoll,
+    max: DiceRoll,
+    count: u32,
+}
+
+#[deprecated(note = "renamed to DeterministicDice")]
+pub type DetermenisticDice = DeterministicDice;
+
+pub type DiceRoll = u32;
+
+pub trait Dice {
+    fn roll(&mut self) -> DiceRoll;
+    fn count(&self) -> u32;
+}
+
+impl DeterministicDice {
+    /// The puzzle's 100-sided die
+    pub fn new() -> Self {
+        Self::with_sides(100)
+    }
+
+    pub fn with_sides(sides: DiceRoll) -> Self {
+        assert!(sides > 0, "a die needs at least one side");
+        DeterministicDice { n: 0, max: sides, count: 0 }
+    }
+}
+
+impl Default for DeterministicDice {
+    fn default() -> Self { Self::new() }
+}
+
+impl Dice for DeterministicDice {
+    fn roll(&mut self) -> DiceRoll {
+        let n = self.n;
+        self.n = (self.n + 1) % self.max;
+        self.count += 1;
+        n + 1
+    }
+
+    fn count(&self) -> u32 { self.count }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Player {
+    position: Position,
+    score: Score,
+}
+
+impl Player {
+    pub fn position(&self) -> Position { self.position }
+    pub fn score(&self) -> Score { self.score }
+
+    pub fn start_at(position: Position) -> Player {
+        Player { position, score: 0 }
+    }
+
+    pub fn take_turn(&mut self, dice: &mut impl Dice) -> [DiceRoll; 3] {
+        let mut rolls = [Default::default(); 3];
+        rolls.fill_with(|| dice.roll());
+        self.take_turn_det(&rolls);
+        rolls
+    }
+
+    pub fn take_turn_det(&mut self, rolls: &[DiceRoll; 3]) {
+        self.position += rolls.iter().sum();
+        self.score += self.position();
+    }
+}
+
+impl fmt::Debug for Player {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}({})", self.position().pos(), self.score)
+    }
+}
+
+#[cfg(not(feature = "bigint"))]
+pub type Universes = usize;
+/// Without a limit, for games to targets far past 21
+#[cfg(feature = "bigint")]
+pub type Universes = num_bigint::BigUint;
+
+pub type DiracDiceStateCounter = BTreeMap<Player, Universes>;
+
+pub struct DiracDiceTurn {
+    turn: usize,
+    player1: DiracDiceStateCounter,
+    player2: DiracDiceStateCounter,
+    player1_wins: Universes,
+    player2_wins: Universes,
+    /// Score that wins
+    target: Score,
+}
+
+impl DiracDiceTurn {
+    pub fn turn(&self) -> usize { self.turn }
+
+    /// Universes won so far by each player
+    pub fn wins(&self) -> (Universes, Universes) { (self.player1_wins.clone(), self.player2_wins.clone()) }
+
+    /// Universes still in play for each player, by player state
+    pub fn states(&self) -> (&DiracDiceStateCounter, &DiracDiceStateCounter) { (&self.player1, &self.player2) }
+
+    pub fn from_starts(player1: Position, player2: Position) -> DiracDiceTurn {
+        Self::with_target(player1, player2, DIRAC_TARGET)
+    }
+
+    /// The game played to `target` instead of the puzzle's 21
+    pub fn with_target(player1: Position, player2: Position, target: Score) -> DiracDiceTurn {
+        DiracDiceTurn {
+            turn: 0,
+            player1: DiracDiceStateCounter::from([(Player::start_at(player1), Universes::from(1u8))]),
+            player2: DiracDiceStateCounter::from([(Player::start_at(player2), Universes::from(1u8))]),
+            player1_wins: Universes::default(),
+            player2_wins: Universes::default(),
+            target,
+        }
+    }
+
+    pub fn player_turn(now: &DiracDiceStateCounter, next: &mut DiracDiceStateCounter, other_player_states: Universes, target: Score) -> Universes {
+        let mut wins = Universes::default();
+        for (p, count) in now.iter() {
+            for roll in &Self::ROLLS {
+                let player = &mut p.clone();
+                player.take_turn_det(roll);
+
+                if player.score() >= target {
+                    wins += count;
+                } else {
+                    *next.entry(*player).or_default() += count;
+                }
+            }
+        }
+        wins * other_player_states
+    }
+
+    /// Every total of three Dirac dice, with the number of universes it
+    /// happens in
+    const ROLL_TOTALS: [(Roll, u8); 7] = [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)];
+
+    const ROLLS: [[Roll; 3]; 27] = [
+        [3,3,1],[3,3,2],[3,3,3],
+        [3,2,1],[3,2,2],[3,2,3],
+        [3,1,1],[3,1,2],[3,1,3],
+        [2,3,1],[2,3,2],[2,3,3],
+        [2,2,1],[2,2,2],[2,2,3],
+        [2,1,1],[2,1,2],[2,1,3],
+        [1,1,1],[1,1,2],[1,1,3],
+        [1,2,1],[1,2,2],[1,2,3],
+        [1,3,1],[1,3,2],[1,3,3],
+    ];
+}
+
+impl Iterator for DiracDiceTurn {
+    type Item = Self;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.player1.is_empty() && self.player2.is_empty() {
+            return None
+        }
+
+        let mut player1 = BTreeMap::new();
+        let mut player2 = BTreeMap::new();
+        let mut player1_wins = self.player1_wins.clone();
+        let mut player2_wins = self.player2_wins.clone();
+        let target = self.target;
+
+        // Player 1 takes turn
+        player1_wins += Self::player_turn(&self.player1, &mut player1, self.player2.values().sum(), target);
+
+        // Player 2 turn
+        player2_wins += Self::player_turn(&self.player2, &mut player2, player1.values().sum(), target);
+
+        Some(Self { turn: self.turn + 1, player1, player2, player1_wins, player2_wins, target })
+    }
+}
+
+/// Memo table for `quantum_wins`, keyed by (player to move, other player)
+pub type DiracDiceMemo = BTreeMap<(Player, Player), (Universes, Universes)>;
+
+/// Universes won by the player about to move and by the other player, from
+/// this pair of states. The memoized alternative to stepping `DiracDiceTurn`.
+pub fn quantum_wins(current: Player, other: Player, memo: &mut DiracDiceMemo) -> (Universes, Universes) {
+    if let Some(wins) = memo.get(&(current, other)) {
+        return wins.clone();
+    }
+
+    let mut wins = (Universes::default(), Universes::default());
+    for (total, universes) in DiracDiceTurn::ROLL_TOTALS {
+        let universes = Universes::from(universes);
+        let mut player = current;
+        player.take_turn_det(&[total, 0, 0]);
+        if player.score() >= DIRAC_TARGET {
+            wins.0 += universes;
+        } else {
+            let (other_wins, our_wins) = quantum_wins(other, player, memo);
+            wins.0 += our_wins * universes.clone();
+            wins.1 += other_wins * universes;
+        }
+    }
+    memo.insert((current, other), wins.clone());
+    wins
+}
+
+/// Size of the quantum game's state space, measured with both solvers, for
+/// choosing how to store it
+#[derive(Debug, PartialEq, Eq)]
+pub struct StateSpace {
+    /// Distinct undecided (position, score) states each player reaches
+    pub reachable: [usize; 2],
+    /// Most states either player had in play after any one turn
+    pub peak: [usize; 2],
+    /// Turns until every universe was decided
+    pub turns: usize,
+    /// Entries in the memoized solver's table
+    pub memo_entries: usize,
+}
+
+impl StateSpace {
+    /// (position, score) states a player can be in with the game undecided,
+    /// which is what a dense array per player would need room for
+    pub const PLAYER_STATES: usize = (BOARD_SIZE * DIRAC_TARGET) as usize;
+
+    pub fn measure(player1: Position, player2: Position) -> StateSpace {
+        let mut seen = [BTreeSet::new(), BTreeSet::new()];
+        let mut peak = [0, 0];
+        let mut turn = DiracDiceTurn::from_starts(player1, player2);
+        loop {
+            let (states1, states2) = turn.states();
+            for (i, states) in [states1, states2].into_iter().enumerate() {
+                seen[i].extend(states.keys().copied());
+                peak[i] = peak[i].max(states.len());
+            }
+            match turn.next() {
+                Some(next) => turn = next,
+                None => break,
+            }
+        }
+
+        let mut memo = DiracDiceMemo::new();
+        quantum_wins(Player::start_at(player1), Player::start_at(player2), &mut memo);
+
+        StateSpace {
+            reachable: [seen[0].len(), seen[1].len()],
+            peak,
+            turns: turn.turn(),
+            memo_entries: memo.len(),
+        }
+    }
+}
+
+impl fmt::Display for StateSpace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for i in 0..2 {
+            writeln!(f, "player {}: {} of {} states reachable, at most {} in play at once",
+                i + 1, self.reachable[i], Self::PLAYER_STATES, self.peak[i])?;
+        }
+        writeln!(f, "iterator: {} turns", self.turns)?;
+        let pairs = Self::PLAYER_STATES * Self::PLAYER_STATES;
+        writeln!(f, "memo: {} entries of {} possible pairs ({:.1}%)",
+            self.memo_entries, pairs, 100.0 * self.memo_entries as f64 / pairs as f64)
+    }
+}
+
+/// Snapshot of a single turn of the deterministic game
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TurnState {
+    /// Index of the player who took the turn: 0 for player 1, as for
+    /// `DeterministicGame::players` and `winner`
+    pub player: usize,
+    pub rolls: [DiceRoll; 3],
+    pub position: Position,
+    pub score: Score,
+}
+
+/// Deterministic dice game, iterated one turn at a time until a player wins
+pub struct DeterministicGame<D: Dice> {
+    players: [Player; 2],
+    dice: D,
+    next_player: usize,
+    winning_score: Score,
+    finished: bool,
+}
+
+impl<D: Dice> DeterministicGame<D> {
+    pub fn new(pos1: Position, pos2: Position, dice: D, winning_score: Score) -> Self {
+        DeterministicGame {
+            players: [Player::start_at(pos1), Player::start_at(pos2)],
+            dice,
+            next_player: 0,
+            winning_score,
+            finished: false,
+        }
+    }
+
+    pub fn players(&self) -> &[Player; 2] { &self.players }
+    pub fn dice(&self) -> &D { &self.dice }
+
+    /// Index of the winning player into `players`, 0 for player 1, once the
+    /// game is over
+    pub fn winner(&self) -> Option<usize> {
+        self.players.iter().position(|p| p.score() >= self.winning_score)
+    }
+
+    pub fn loser_score(&self) -> Option<u32> {
+        self.winner().map(|w| self.players[1 - w].score() * self.dice.count())
+    }
+}
+
+impl<D: Dice> Iterator for DeterministicGame<D> {
+    type Item = TurnState;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None
+        }
+
+        let player_ix = self.next_player;
+        let player = &mut self.players[player_ix];
+        let rolls = player.take_turn(&mut self.dice);
+        let state = TurnState {
+            player: player_ix,
+            rolls,
+            position: player.position(),
+            score: player.score(),
+        };
+
+        self.finished = player.score() >= self.winning_score;
+        self.next_player = 1 - player_ix;
+        Some(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dice_wrap_after_the_last_side() {
+        let mut d100 = DeterministicDice::new();
+        let rolls: Vec<DiceRoll> = (0..203).map(|_| d100.roll()).collect();
+        assert_eq!(rolls[..3], [1, 2, 3]);
+        assert_eq!(rolls[98..103], [99, 100, 1, 2, 3]);
+        assert_eq!(rolls[198..], [99, 100, 1, 2, 3]);
+        assert_eq!(d100.count(), 203);
+
+        let mut d3 = DeterministicDice::with_sides(3);
+        let rolls: Vec<DiceRoll> = (0..7).map(|_| d3.roll()).collect();
+        assert_eq!(rolls, [1, 2, 3, 1, 2, 3, 1]);
+        let mut d1 = DeterministicDice::with_sides(1);
+        assert!((0..5).all(|_| d1.roll() == 1));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn misspelled_name_still_works() {
+        let mut dice = DetermenisticDice::default();
+        assert_eq!(dice.roll(), 1);
+    }
+
+    #[test]
+    fn deterministic_game_trace() {
+        let mut game = DeterministicGame::new(Position::from(4), Position::from(8), DeterministicDice::new(), 1000);
+        let trace: Vec<(usize, u32, Score)> = game.by_ref()
+            .take(8)
+            .map(|t| (t.player, t.position.pos(), t.score))
+            .collect();
+
+        assert_eq!(trace, [
+            (0, 10, 10), (1, 3, 3),
+            (0, 4, 14), (1, 6, 9),
+            (0, 6, 20), (1, 7, 16),
+            (0, 6, 26), (1, 6, 22),
+        ]);
+    }
+
+    #[test]
+    fn deterministic_game_result() {
+        let mut game = DeterministicGame::new(Position::from(4), Position::from(8), DeterministicDice::new(), 1000);
+        let last = game.by_ref().last().unwrap();
+
+        assert_eq!(last, TurnState { player: 0, rolls: [91, 92, 93], position: Position::from(10), score: 1000 });
+        assert_eq!(game.winner(), Some(0));
+        assert_eq!(game.loser_score(), Some(739785));
+    }
+
+    #[test]
+    fn memoized_matches_iterator() {
+        let (pos1, pos2) = (Position::from(4), Position::from(8));
+        let mut memo = DiracDiceMemo::new();
+        let wins = quantum_wins(Player::start_at(pos1), Player::start_at(pos2), &mut memo);
+        assert_eq!([wins.0.to_string(), wins.1.to_string()], ["444356092776315", "341960390180808"]);
+
+        let mut turn = DiracDiceTurn::from_starts(pos1, pos2);
+        while let Some(next) = turn.next() {
+            turn = next;
+        }
+        assert_eq!(turn.wins(), wins);
+    }
+
+    #[test]
+    fn state_space_fits_dense_tables() {
+        let space = StateSpace::measure(Position::from(4), Position::from(8));
+        for i in 0..2 {
+            assert!(space.reachable[i] <= StateSpace::PLAYER_STATES);
+            assert!(space.peak[i] <= space.reachable[i]);
+        }
+        assert!(space.memo_entries <= StateSpace::PLAYER_STATES.pow(2));
+        assert!(space.turns > 0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let player = Player::start_at(Position::from(4));
+        let json = serde_json::to_string(&player).unwrap();
+        assert_eq!(json, r#"{"position":4,"score":0}"#);
+        assert!(serde_json::from_str::<Player>(&json).unwrap() == player);
+    }
+}
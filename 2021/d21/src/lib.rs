@@ -0,0 +1,87 @@
+extern crate alloc;
+
+use aoc_core::{option_value, AocError, Solver};
+
+mod dice;
+#[allow(deprecated)]
+pub use dice::DetermenisticDice;
+pub use dice::{
+    quantum_wins, Dice, DiceRoll, DeterministicDice, DeterministicGame, DiracDiceMemo, DiracDiceStateCounter, DiracDiceTurn,
+    Player, Position, Roll, Score, StateSpace, TurnState, Universes,
+};
+
+/// Starting positions from lines like `Player 1 starting position: 4`
+pub fn parse_starts(input: &str) -> (Position, Position) {
+    let mut starts = input.lines()
+        .map(|l| Position::from(l.rsplit(' ').next().unwrap().trim_end().parse::<u32>().unwrap()));
+    (starts.next().unwrap(), starts.next().unwrap())
+}
+
+pub struct Puzzle {
+    starts: (Position, Position),
+    /// Score that wins the Dirac dice game of part 2
+    dirac_target: Score,
+}
+
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        Ok(Puzzle { starts: parse_starts(input), dirac_target: 21 })
+    }
+
+    fn part1(&self) -> String {
+        let (pos1, pos2) = self.starts;
+        let mut game = DeterministicGame::new(pos1, pos2, DeterministicDice::new(), 1000);
+        game.by_ref().for_each(drop);
+        game.loser_score().unwrap().to_string()
+    }
+
+    fn part2(&self) -> String {
+        let (pos1, pos2) = self.starts;
+        let mut turn = DiracDiceTurn::with_target(pos1, pos2, self.dirac_target);
+        while let Some(next) = turn.next() {
+            turn = next;
+        }
+        let (wins1, wins2) = turn.wins();
+        wins1.max(wins2).to_string()
+    }
+
+    /// `dirac_target`: score that wins part 2's game, 21 in the puzzle.
+    /// Past 27 the universes overflow 64 bits, without the `bigint` feature.
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), AocError> {
+        match name {
+            "dirac_target" => self.dirac_target = option_value(name, value)?,
+            _ => return Err(AocError::invalid(format!("no option {}", name))),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The answers given in the puzzle text for its example
+    #[test]
+    fn example_answers() {
+        let puzzle = Puzzle::parse(include_str!("../input_test"));
+        assert_eq!(puzzle.part1(), "739785");
+        assert_eq!(puzzle.part2(), "444356092776315");
+    }
+
+    #[test]
+    fn target_is_an_option() {
+        let mut puzzle = Puzzle::parse(include_str!("../input_test"));
+        puzzle.set_option("dirac_target", "10").unwrap();
+        assert_eq!(puzzle.part2(), "18973591");
+        assert!(puzzle.set_option("dirac_target", "ten").is_err());
+        assert!(puzzle.set_option("target", "10").is_err());
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn counts_far_past_u64() {
+        let mut puzzle = Puzzle::parse(include_str!("../input_test"));
+        puzzle.set_option("dirac_target", "100").unwrap();
+        assert_eq!(puzzle.part2(), "55038535590428753856514661082323914715870927758485665656548544838675");
+    }
+}
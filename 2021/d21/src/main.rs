@@ -0,0 +1,64 @@
+use std::io::Read;
+
+use d21::*;
+
+fn p1(pos1: Position, pos2: Position, dice: impl Dice) {
+    let mut game = DeterministicGame::new(pos1, pos2, dice, 1000);
+
+    for turn in game.by_ref() {
+        log::debug!("player{} :: {}({}) after {:?}", turn.player + 1, turn.position.pos(), turn.score, &turn.rolls);
+    }
+
+    let winner = game.winner().unwrap();
+    let loser = &game.players()[1 - winner];
+    println!("player {} wins!", winner + 1);
+    println!("loser score: {}*{} = {}", loser.score(), game.dice().count(), game.loser_score().unwrap());
+}
+
+fn p2(pos1: Position, pos2: Position) {
+    let mut turn = DiracDiceTurn::from_starts(pos1, pos2);
+    for _ in 0..11 {
+        turn = match turn.next() {
+            Some(turn) => turn,
+            None => break
+        };
+        
+        log::debug!("Turn {}: ", turn.turn());
+        let (player1_wins, player2_wins) = turn.wins();
+        let (player1, player2) = turn.states();
+        log::debug!("wins: {} vs {}", player1_wins, player2_wins);
+        log::trace!("player1 states: {:?}", player1);
+        log::trace!("player2 states: {:?}", player2);
+    }
+    let (player1_wins, player2_wins) = turn.wins();
+    println!("wins after turn {}: {} vs {}", turn.turn(), player1_wins, player2_wins);
+}
+
+fn main() {
+    // --analyze reports the size of the quantum game's state space instead,
+    // --part 1|2|both picks which parts to run
+    let mut analyze = false;
+    let (input, parts, args) = aoc_core::input_and_parts();
+    for arg in args {
+        match arg.as_str() {
+            "--analyze" => analyze = true,
+            _ => panic!("unknown argument: {}", arg),
+        }
+    }
+
+    let mut text = String::new();
+    aoc_core::open_input(&input).read_to_string(&mut text).unwrap();
+    let (pos1, pos2) = parse_starts(&text);
+
+    if analyze {
+        print!("{}", StateSpace::measure(pos1, pos2));
+        return;
+    }
+
+    if parts.has(1) {
+        p1(pos1, pos2, DeterministicDice::new());
+    }
+    if parts.has(2) {
+        p2(pos1, pos2);
+    }
+}
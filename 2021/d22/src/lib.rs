@@ -0,0 +1,655 @@
+use std::cmp::min;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::ops::RangeInclusive;
+
+use aoc_core::{AocError, Solver, Strategies, Strategy};
+pub use aoc_intervals::Coord;
+
+pub type ReactorIx = i64;
+pub type ReactorRange<T = ReactorIx> = RangeInclusive<T>;
+pub type Cuboid<T = ReactorIx> = aoc_intervals::Cuboid<T>;
+pub type Overlap<T = ReactorIx> = aoc_intervals::Overlap<T, 3>;
+
+/// Initialization procedure region used by part 1
+pub const BOOT_REGION: Cuboid = Cuboid::new([
+    RangeInclusive::new(-50, 50),
+    RangeInclusive::new(-50, 50),
+    RangeInclusive::new(-50, 50),
+]);
+
+/// Instructions to set every cube in a cuboid
+pub trait Switch<T> {
+    fn into_on(self) -> Instruction<T>;
+    fn into_off(self) -> Instruction<T>;
+}
+
+impl<T: Coord> Switch<T> for Cuboid<T> {
+    fn into_off(self) -> Instruction<T> {
+        Instruction { state: CubeState::Off, cuboid: self }
+    }
+
+    fn into_on(self) -> Instruction<T> {
+        Instruction { state: CubeState::On, cuboid: self }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CubeState {
+    On,
+    Off,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Instruction<T = ReactorIx> {
+    state: CubeState,
+    cuboid: Cuboid<T>,
+}
+
+impl Instruction {
+    pub fn is_boot(&self) -> bool {
+        self.is_within(&BOOT_REGION)
+    }
+}
+
+impl<T: Coord> Instruction<T> {
+    pub fn cuboid(&self) -> &Cuboid<T> { &self.cuboid }
+
+    pub fn is_within(&self, region: &Cuboid<T>) -> bool {
+        region.contains(self.cuboid())
+    }
+
+    /// Same instruction restricted to `region`, or `None` if it doesn't touch it
+    pub fn clip(&self, region: &Cuboid<T>) -> Option<Instruction<T>> {
+        self.cuboid.intersection(region).map(|cuboid| Instruction { state: self.state, cuboid })
+    }
+
+    pub fn is_on(&self) -> bool {
+        self.state == CubeState::On
+    }
+
+    pub fn is_off(&self) -> bool {
+        self.state == CubeState::Off
+    }
+}
+
+pub trait Reactor<T> {
+    fn concat_instruction(&self, inst: &Instruction<T>) -> Self;
+}
+impl<T: Coord> Reactor<T> for Vec<Cuboid<T>> {
+    fn concat_instruction(&self, inst: &Instruction<T>) -> Self {
+        let mut l: Vec<Cuboid<T>> = Vec::with_capacity(self.len() + min(self.len() / 2, 10));
+        let mut add_this = self.is_empty() && inst.is_on();
+        let mut is_duplicate = false;
+        let new_cuboid = inst.cuboid();
+        for existing in self {
+            match new_cuboid.overlaps(existing) {
+                Overlap::Enclosing => { add_this = inst.is_on(); },
+                Overlap::Same | Overlap::Enclosed if inst.is_off() => {
+                    // Remove from existing
+                    l.extend(existing.sub_into_parts(new_cuboid));
+                },
+                Overlap::Same | Overlap::Enclosed => {
+                    log::debug!("Same/Enclosed: inverse={} new:{} vs existing:{}",
+                        match existing.overlaps(new_cuboid) {
+                            Overlap::Same => "Same",
+                            Overlap::Enclosing => "Enclosing",
+                            Overlap::Enclosed => "Enclosed",
+                            Overlap::Intersection(_) => "Intersection",
+                            Overlap::None => "None",
+                        },
+                        new_cuboid, existing);
+                    is_duplicate = true;
+                    l.push(existing.clone());
+                },
+                Overlap::Intersection(overlap) => {
+                    let parts = existing.sub_into_parts(&overlap);
+                    //l.extend(existing.sub_into_parts(&overlap));
+
+                    if inst.is_on() {
+                        for e in &parts {
+                            match e.overlaps(new_cuboid) {
+                                Overlap::None => (),
+                                _ => { log::debug!("INTERSECTION {} overlaps {}", new_cuboid, e) },
+                            };
+                        }
+                    }
+
+                    l.extend(parts);
+                    add_this = inst.is_on();
+                }
+                Overlap::None => {
+                    if !is_duplicate && inst.is_on() {
+                        for e in &l {
+                            match e.overlaps(new_cuboid) {
+                                Overlap::None => (),
+                                _ => panic!("overlapping cuboid found before: {} overlaps {}", new_cuboid, e),
+                            };
+                        }
+                    }
+
+                    l.push(existing.clone());
+                    add_this = !is_duplicate && inst.is_on();
+
+                    if !is_duplicate && inst.is_on() {
+                        for e in &l {
+                            match e.overlaps(new_cuboid) {
+                                Overlap::None => (),
+                                _ => panic!("overlapping cuboid found after: {} overlaps {}", new_cuboid, e),
+                            };
+                        }
+                    }
+                },
+            };
+        }
+        if add_this && !is_duplicate {
+            l.push(new_cuboid.clone());
+        }
+        l
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Instruction<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}",
+            match self.state { CubeState::On => "on", CubeState::Off => "off"},
+            self.cuboid)
+    }
+}
+
+impl<T: Coord> TryFrom<&str> for Instruction<T> {
+    type Error = AocError;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let (state, cuboid) = s.split_once(' ').ok_or("failed to find space")?;
+        let state = match state {
+            "on" => CubeState::On,
+            "off" => CubeState::Off,
+            _ => return Err(AocError::invalid(format!("invalid state {:?}", state))),
+        };
+        let cuboid = Cuboid::try_from(cuboid)?;
+        Ok(Instruction { state, cuboid })
+    }
+}
+
+
+/// Instructions parsed a line at a time, with errors tagged by line number
+pub struct Instructions<I, T = ReactorIx> where I: Iterator<Item = String> {
+    lines: I,
+    line: usize,
+    coord: PhantomData<T>,
+}
+
+impl<I, T> From<I> for Instructions<I, T>
+where I: Iterator<Item = String>
+{
+    fn from(lines: I) -> Self {
+        Self { lines, line: 0, coord: PhantomData }
+    }
+}
+
+impl<I, T: Coord> Iterator for Instructions<I, T>
+where I: Iterator<Item = String>
+{
+    type Item = Result<Instruction<T>, AocError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let s = self.lines.next()?;
+        self.line += 1;
+        Some(Instruction::try_from(s.as_str()).map_err(|e| e.at_line(self.line)))
+    }
+}
+
+fn _count_on(countable: &[Instruction]) -> Result<u64, AocError> {
+    let mut sum = 0;
+    for i in countable {
+        let v = i.cuboid().volume()?;
+        if i.is_on() {
+            sum += v;
+        } else {
+            sum -= v;
+        }
+    }
+    Ok(sum)
+}
+
+/// How many instructions `normalize` removed, and why
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Normalization {
+    /// Entirely outside the region
+    pub outside: usize,
+    /// Inside a later instruction, which overwrites every cube they set
+    pub shadowed: usize,
+    /// Folded into the instruction before, with the same state
+    pub merged: usize,
+}
+
+impl Normalization {
+    pub fn eliminated(&self) -> usize {
+        self.outside + self.shadowed + self.merged
+    }
+}
+
+impl fmt::Display for Normalization {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} instructions eliminated ({} outside region, {} shadowed, {} merged)",
+            self.eliminated(), self.outside, self.shadowed, self.merged)
+    }
+}
+
+/// Instructions that leave the same cubes on (within `region`, if given) but
+/// are cheaper to fold: clipped to the region, without the ones a later
+/// instruction completely covers, and with neighbouring instructions of the
+/// same state joined when together they make a cuboid
+pub fn normalize<T: Coord>(instructions: &[Instruction<T>], region: Option<&Cuboid<T>>) -> (Vec<Instruction<T>>, Normalization) {
+    let mut report = Normalization::default();
+
+    let clipped: Vec<Instruction<T>> = match region {
+        Some(region) => instructions.iter().filter_map(|i| i.clip(region)).collect(),
+        None => instructions.to_vec(),
+    };
+    report.outside = instructions.len() - clipped.len();
+
+    let visible: Vec<Instruction<T>> = clipped.iter()
+        .enumerate()
+        .filter(|(n, i)| !clipped[n + 1..].iter().any(|later| later.cuboid.contains(&i.cuboid)))
+        .map(|(_, i)| i.clone())
+        .collect();
+    report.shadowed = clipped.len() - visible.len();
+
+    let mut merged: Vec<Instruction<T>> = Vec::with_capacity(visible.len());
+    for inst in visible {
+        match merged.last_mut() {
+            Some(prev) if prev.state == inst.state => match prev.cuboid.merge(&inst.cuboid) {
+                Some(cuboid) => {
+                    prev.cuboid = cuboid;
+                    report.merged += 1;
+                },
+                None => merged.push(inst),
+            },
+            _ => merged.push(inst),
+        }
+    }
+
+    (merged, report)
+}
+
+/// Number of cubes left on after running all instructions, or an error if
+/// that doesn't fit in a u64
+pub fn solve<T: Coord>(instructions: &[Instruction<T>]) -> Result<u64, AocError> {
+    let on_cuboids = instructions.iter().fold(vec!(), |accum, inst| accum.concat_instruction(inst));
+    on_cuboids.iter().try_fold(0u64, |sum, c| {
+        sum.checked_add(c.volume()?).ok_or_else(|| AocError::invalid("number of cubes on overflows u64"))
+    })
+}
+
+/// `solve` by inclusion and exclusion instead of splitting cuboids apart:
+/// each instruction cancels what's already counted where it overlaps, by
+/// adding the overlap with the opposite sign, and then an `on` adds its own
+/// cuboid. Overlaps that come out the same are counted together.
+pub fn solve_signed<T: Coord + Hash>(instructions: &[Instruction<T>]) -> Result<u64, AocError> {
+    let mut signed: HashMap<Cuboid<T>, i64> = HashMap::new();
+    for inst in instructions {
+        let mut update: HashMap<Cuboid<T>, i64> = HashMap::new();
+        for (cuboid, &sign) in &signed {
+            if let Some(overlap) = cuboid.intersection(inst.cuboid()) {
+                *update.entry(overlap).or_default() -= sign;
+            }
+        }
+        if inst.is_on() {
+            *update.entry(inst.cuboid().clone()).or_default() += 1;
+        }
+        for (cuboid, sign) in update {
+            *signed.entry(cuboid).or_default() += sign;
+        }
+        signed.retain(|_, sign| *sign != 0);
+    }
+
+    let total = signed.iter().try_fold(0i128, |sum, (c, &sign)| Ok::<_, AocError>(sum + c.volume()? as i128 * sign as i128))?;
+    u64::try_from(total).map_err(|_| AocError::invalid("number of cubes on overflows u64"))
+}
+
+/// Number of cubes left on inside `region` after running all instructions
+pub fn count_on_within<T: Coord>(instructions: &[Instruction<T>], region: &Cuboid<T>) -> Result<u64, AocError> {
+    solve(&normalize(instructions, Some(region)).0)
+}
+
+fn answer(count: Result<u64, AocError>) -> String {
+    count.map_or_else(|e| format!("error: {}", e), |n| n.to_string())
+}
+
+pub struct Puzzle {
+    instructions: Vec<Instruction>,
+    /// Count with `solve_signed` rather than `solve`
+    signed: bool,
+}
+
+impl Puzzle {
+    fn count_on(&self, region: Option<&Cuboid>) -> Result<u64, AocError> {
+        let (instructions, _) = normalize(&self.instructions, region);
+        match self.signed {
+            true => solve_signed(&instructions),
+            false => solve(&instructions),
+        }
+    }
+}
+
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        let instructions = Instructions::from(input.lines().map(String::from)).collect::<Result<_, _>>()?;
+        Ok(Puzzle { instructions, signed: false })
+    }
+
+    fn strategies() -> Strategies {
+        Strategies {
+            algos: &[
+                Strategy { name: "splitting", about: "keep the cubes on as disjoint cuboids, splitting them where instructions cut" },
+                Strategy { name: "signed-volume", about: "add and take away overlapping volumes" },
+            ],
+            impls: &[],
+        }
+    }
+
+    /// `algo`: `splitting` or `signed-volume`
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), AocError> {
+        if name != "algo" {
+            return Err(AocError::invalid(format!("no option {}", name)));
+        }
+        self.signed = match value {
+            "splitting" => false,
+            "signed-volume" => true,
+            _ => return Err(AocError::invalid(format!("bad value {:?} for option algo", value))),
+        };
+        Ok(())
+    }
+
+    fn part1(&self) -> String {
+        answer(self.count_on(Some(&BOOT_REGION)))
+    }
+
+    fn part2(&self) -> String {
+        answer(self.count_on(None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tiny_case() {
+        let input = vec![
+            Cuboid::from([10..=12, 10..=12, 10..=12]).into_on(),
+            Cuboid::from([11..=13, 11..=13, 11..=13]).into_on(),
+            Cuboid::from([9..=11, 9..=11, 9..=11]).into_off(),
+            Cuboid::from([10..=10, 10..=10, 10..=10]).into_on(),
+        ];
+
+        assert_eq!(solve(&input).unwrap(), 39);
+    }
+
+    #[test]
+    fn parse_errors_name_the_line() {
+        let input = "on x=10..12,y=10..12,z=10..12\nflip x=1..2,y=1..2,z=1..2\n";
+        let err = Puzzle::try_parse(input).err().unwrap();
+        assert_eq!(err.to_string(), "line 2: invalid state \"flip\"");
+        assert!(Instruction::<i64>::try_from("on x=1..2,y=1..b,z=1..2").is_err());
+        assert!(Instruction::<i64>::try_from("on x=1..2").is_err());
+        assert!(Instruction::<i32>::try_from("on x=0..3000000000,y=1..2,z=1..2").is_err());
+    }
+
+    #[test]
+    fn p1_example_case() {
+        let input = vec![
+            Cuboid::from([ -20..=26, -36..=17, -47..=7]).into_on(),
+            Cuboid::from([-20..=33, -21..=23, -26..=28]).into_on(),
+            Cuboid::from([-22..=28, -29..=23, -38..=16]).into_on(),
+            Cuboid::from([-46..=7, -6..=46, -50..=-1]).into_on(),
+            Cuboid::from([-49..=1, -3..=46, -24..=28]).into_on(),
+            Cuboid::from([2..=47, -22..=22, -23..=27]).into_on(),
+            Cuboid::from([-27..=23, -28..=26, -21..=29]).into_on(),
+            Cuboid::from([-39..=5, -6..=47, -3..=44]).into_on(),
+            Cuboid::from([-30..=21, -8..=43, -13..=34]).into_on(),
+            Cuboid::from([-22..=26, -27..=20, -29..=19]).into_on(),
+            Cuboid::from([-48..=-32, 26..=41, -47..=-37]).into_off(),
+            Cuboid::from([-12..=35, 6..=50, -50..=-2]).into_on(),
+            Cuboid::from([-48..=-32, -32..=-16, -15..=-5]).into_off(),
+            Cuboid::from([-18..=26, -33..=15, -7..=46]).into_on(),
+            Cuboid::from([-40..=-22, -38..=-28, 23..=41]).into_off(),
+            Cuboid::from([-16..=35, -41..=10, -47..=6]).into_on(),
+            Cuboid::from([-32..=-23, 11..=30, -14..=3]).into_off(),
+            Cuboid::from([-49..=-5, -3..=45, -29..=18]).into_on(),
+            Cuboid::from([18..=30, -20..=-8, -3..=13]).into_off(),
+            Cuboid::from([-41..=9, -7..=43, -33..=15]).into_on(),
+        ];
+
+        assert_eq!(solve(&input).unwrap(), 590784);
+    }
+
+    #[test]
+    fn boot_region_clips_instructions() {
+        let input = vec![
+            Cuboid::from([-54..=-40, 0..=0, 0..=0]).into_on(),
+            Cuboid::from([45..=60, 0..=2, 0..=0]).into_on(),
+            Cuboid::from([-45..=-45, 0..=0, 0..=0]).into_off(),
+            Cuboid::from([100..=200, 0..=0, 0..=0]).into_on(),
+        ];
+
+        assert!(!input[0].is_boot());
+        assert_eq!(count_on_within(&input, &BOOT_REGION).unwrap(), 11 - 1 + 6 * 3);
+        assert_eq!(count_on_within(&input, &Cuboid::from([150..=500, -1..=1, -1..=1])).unwrap(), 51);
+        assert_eq!(count_on_within(&input, &Cuboid::from([-100..=-60, 0..=0, 0..=0])).unwrap(), 0);
+    }
+
+    #[test]
+    fn normalization_keeps_the_count() {
+        let input = vec![
+            Cuboid::from([0..=4, 0..=4, 0..=4]).into_on(),
+            Cuboid::from([1..=2, 1..=2, 1..=2]).into_off(),
+            Cuboid::from([0..=9, 0..=9, 0..=9]).into_on(),
+            Cuboid::from([20..=29, 0..=9, 0..=9]).into_on(),
+            Cuboid::from([30..=31, 0..=9, 0..=9]).into_on(),
+            Cuboid::from([28..=35, 0..=9, 0..=9]).into_on(),
+            Cuboid::from([5..=5, 5..=5, 5..=5]).into_off(),
+            Cuboid::from([500..=501, 0..=0, 0..=0]).into_on(),
+        ];
+
+        let (normalized, report) = normalize(&input, None);
+        assert_eq!(report, Normalization { outside: 0, shadowed: 3, merged: 1 });
+        assert_eq!(normalized.len(), input.len() - report.eliminated());
+        assert_eq!(solve(&normalized).unwrap(), solve(&input).unwrap());
+        assert!(normalized[1].cuboid() == &Cuboid::from([20..=35, 0..=9, 0..=9]));
+
+        let region = Cuboid::from([0..=100, 0..=100, 0..=100]);
+        let (clipped, report) = normalize(&input, Some(&region));
+        assert_eq!(report.outside, 1);
+        assert_eq!(solve(&clipped).unwrap(), solve(&input).unwrap() - 2);
+    }
+
+    #[test]
+    fn p2_example_case() {
+        let input = vec![
+            Cuboid::from([-5..=47, -31..=22, -19..=33]).into_on(),
+            Cuboid::from([-44..=5, -27..=21, -14..=35]).into_on(),
+            Cuboid::from([-49..=-1, -11..=42, -10..=38]).into_on(),
+            Cuboid::from([-20..=34, -40..=6, -44..=1]).into_on(),
+            Cuboid::from([26..=39, 40..=50, -2..=11]).into_off(),
+            Cuboid::from([-41..=5, -41..=6, -36..=8]).into_on(),
+            Cuboid::from([-43..=-33, -45..=-28, 7..=25]).into_off(),
+            Cuboid::from([-33..=15, -32..=19, -34..=11]).into_on(),
+            Cuboid::from([35..=47, -46..=-34, -11..=5]).into_off(),
+            Cuboid::from([-14..=36, -6..=44, -16..=29]).into_on(),
+            Cuboid::from([-57795..=-6158, 29564..=72030, 20435..=90618]).into_on(),
+            Cuboid::from([36731..=105352, -21140..=28532, 16094..=90401]).into_on(),
+            Cuboid::from([30999..=107136, -53464..=15513, 8553..=71215]).into_on(),
+            Cuboid::from([13528..=83982, -99403..=-27377, -24141..=23996]).into_on(),
+            Cuboid::from([-72682..=-12347, 18159..=111354, 7391..=80950]).into_on(),
+            Cuboid::from([-1060..=80757, -65301..=-20884, -103788..=-16709]).into_on(),
+            Cuboid::from([-83015..=-9461, -72160..=-8347, -81239..=-26856]).into_on(),
+            Cuboid::from([-52752..=22273, -49450..=9096, 54442..=119054]).into_on(),
+            Cuboid::from([-29982..=40483, -108474..=-28371, -24328..=38471]).into_on(),
+            Cuboid::from([-4958..=62750, 40422..=118853, -7672..=65583]).into_on(),
+            Cuboid::from([55694..=108686, -43367..=46958, -26781..=48729]).into_on(),
+            Cuboid::from([-98497..=-18186, -63569..=3412, 1232..=88485]).into_on(),
+            Cuboid::from([-726..=56291, -62629..=13224, 18033..=85226]).into_on(),
+            Cuboid::from([-110886..=-34664, -81338..=-8658, 8914..=63723]).into_on(),
+            Cuboid::from([-55829..=24974, -16897..=54165, -121762..=-28058]).into_on(),
+            Cuboid::from([-65152..=-11147, 22489..=91432, -58782..=1780]).into_on(),
+            Cuboid::from([-120100..=-32970, -46592..=27473, -11695..=61039]).into_on(),
+            Cuboid::from([-18631..=37533, -124565..=-50804, -35667..=28308]).into_on(),
+            Cuboid::from([-57817..=18248, 49321..=117703, 5745..=55881]).into_on(),
+            Cuboid::from([14781..=98692, -1341..=70827, 15753..=70151]).into_on(),
+            Cuboid::from([-34419..=55919, -19626..=40991, 39015..=114138]).into_on(),
+            Cuboid::from([-60785..=11593, -56135..=2999, -95368..=-26915]).into_on(),
+            Cuboid::from([-32178..=58085, 17647..=101866, -91405..=-8878]).into_on(),
+            Cuboid::from([-53655..=12091, 50097..=105568, -75335..=-4862]).into_on(),
+            Cuboid::from([-111166..=-40997, -71714..=2688, 5609..=50954]).into_on(),
+            Cuboid::from([-16602..=70118, -98693..=-44401, 5197..=76897]).into_on(),
+            Cuboid::from([16383..=101554, 4615..=83635, -44907..=18747]).into_on(),
+            Cuboid::from([-95822..=-15171, -19987..=48940, 10804..=104439]).into_off(),
+            Cuboid::from([-89813..=-14614, 16069..=88491, -3297..=45228]).into_on(),
+            Cuboid::from([41075..=99376, -20427..=49978, -52012..=13762]).into_on(),
+            Cuboid::from([-21330..=50085, -17944..=62733, -112280..=-30197]).into_on(),
+            Cuboid::from([-16478..=35915, 36008..=118594, -7885..=47086]).into_on(),
+            Cuboid::from([-98156..=-27851, -49952..=43171, -99005..=-8456]).into_off(),
+            Cuboid::from([2032..=69770, -71013..=4824, 7471..=94418]).into_off(),
+            Cuboid::from([43670..=120875, -42068..=12382, -24787..=38892]).into_on(),
+            Cuboid::from([37514..=111226, -45862..=25743, -16714..=54663]).into_off(),
+            Cuboid::from([25699..=97951, -30668..=59918, -15349..=69697]).into_off(),
+            Cuboid::from([-44271..=17935, -9516..=60759, 49131..=112598]).into_off(),
+            Cuboid::from([-61695..=-5813, 40978..=94975, 8655..=80240]).into_on(),
+            Cuboid::from([-101086..=-9439, -7088..=67543, 33935..=83858]).into_off(),
+            Cuboid::from([18020..=114017, -48931..=32606, 21474..=89843]).into_off(),
+            Cuboid::from([-77139..=10506, -89994..=-18797, -80..=59318]).into_off(),
+            Cuboid::from([8476..=79288, -75520..=11602, -96624..=-24783]).into_off(),
+            Cuboid::from([-47488..=-1262, 24338..=100707, 16292..=72967]).into_on(),
+            Cuboid::from([-84341..=13987, 2429..=92914, -90671..=-1318]).into_off(),
+            Cuboid::from([-37810..=49457, -71013..=-7894, -105357..=-13188]).into_off(),
+            Cuboid::from([-27365..=46395, 31009..=98017, 15428..=76570]).into_off(),
+            Cuboid::from([-70369..=-16548, 22648..=78696, -1892..=86821]).into_off(),
+            Cuboid::from([-53470..=21291, -120233..=-33476, -44150..=38147]).into_on(),
+            Cuboid::from([-93533..=-4276, -16170..=68771, -104985..=-24507]).into_off(),
+        ];
+        assert_eq!(solve(&input).unwrap(), 2758514936282235);
+    }
+
+    #[test]
+    fn extreme_coordinates() {
+        let line = format!("on x={}..{},y=0..0,z=0..0", i64::MIN, i64::MAX);
+        let whole_axis = Instruction::<i64>::try_from(line.as_str()).unwrap();
+        assert_eq!(whole_axis.to_string(), line);
+        assert_eq!(whole_axis.cuboid().volume().unwrap_err().to_string(),
+            format!("volume of {} overflows u64", &line[3..]));
+
+        // each half of the axis fits, but not both together
+        let halves = vec![
+            Cuboid::from([i64::MIN..=-1, 0..=0, 0..=0]).into_on(),
+            Cuboid::from([0..=i64::MAX, 0..=0, 0..=0]).into_on(),
+        ];
+        assert_eq!(halves[0].cuboid().volume().unwrap(), 1 << 63);
+        assert!(solve(&halves).is_err());
+        assert_eq!(normalize(&halves, None).1.merged, 1);
+
+        let corners = vec![
+            Cuboid::from([i64::MAX - 2..=i64::MAX, i64::MIN..=i64::MIN + 2, 0..=2]).into_on(),
+            Cuboid::from([i64::MAX..=i64::MAX, i64::MIN..=i64::MIN, 1..=1]).into_off(),
+            Cuboid::from([i64::MAX - 1..=i64::MAX, i64::MIN..=i64::MIN + 1, 0..=0]).into_off(),
+        ];
+        assert_eq!(solve(&corners).unwrap(), 27 - 1 - 4);
+
+        let narrow = vec![
+            Cuboid::from([i32::MIN..=i32::MAX, 0..=9, i32::MAX..=i32::MAX]).into_on(),
+            Cuboid::from([i32::MIN..=i32::MIN, 0..=9, i32::MAX..=i32::MAX]).into_off(),
+        ];
+        assert_eq!(solve(&narrow).unwrap(), 10 * (1 << 32) - 10);
+        let cube = Cuboid::from([i32::MIN..=i32::MAX, i32::MIN..=i32::MAX, i32::MIN..=i32::MAX]);
+        assert!(cube.volume().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let input = vec![
+            Cuboid::from([10..=12, 10..=12, 10..=12]).into_on(),
+            Cuboid::from([9..=11, 9..=11, 9..=11]).into_off(),
+        ];
+        let json = serde_json::to_string(&input).unwrap();
+        assert_eq!(serde_json::from_str::<Vec<Instruction>>(&json).unwrap(), input);
+    }
+
+    mod props {
+        use super::*;
+        use proptest::prelude::*;
+        use proptest::strategy::Strategy;
+
+        /// Small cuboids, so the result can be checked cube by cube
+        fn cuboid() -> impl Strategy<Value = Cuboid> {
+            let range = (-6i64..6, 0i64..6).prop_map(|(start, len)| start..=start + len);
+            [range.clone(), range.clone(), range].prop_map(Cuboid::from)
+        }
+
+        fn instruction() -> impl Strategy<Value = Instruction> {
+            (cuboid(), any::<bool>()).prop_map(|(c, on)| if on { c.into_on() } else { c.into_off() })
+        }
+
+        /// Cubes left on, by turning each one on and off
+        fn brute_force(instructions: &[Instruction]) -> u64 {
+            let mut on = std::collections::HashSet::new();
+            for inst in instructions {
+                let c = inst.cuboid();
+                for x in c.x() {
+                    for y in c.y() {
+                        for z in c.z() {
+                            if inst.is_on() {
+                                on.insert((x, y, z));
+                            } else {
+                                on.remove(&(x, y, z));
+                            }
+                        }
+                    }
+                }
+            }
+            on.len() as u64
+        }
+
+        proptest! {
+            #[test]
+            fn parse_format_round_trip(inst in instruction()) {
+                let line = inst.to_string();
+                prop_assert!(Instruction::try_from(line.as_str()).unwrap() == inst, "{}", line);
+            }
+
+            #[test]
+            fn solve_counts_every_cube(instructions in prop::collection::vec(instruction(), 0..8)) {
+                let count = solve(&instructions).unwrap();
+                prop_assert_eq!(count, brute_force(&instructions));
+
+                let on_volume: u64 = instructions.iter().filter(|i| i.is_on()).map(|i| i.cuboid().volume().unwrap()).sum();
+                prop_assert!(count <= on_volume);
+                prop_assert_eq!(solve(&normalize(&instructions, None).0).unwrap(), count);
+                prop_assert_eq!(solve_signed(&instructions).unwrap(), count);
+            }
+        }
+    }
+
+    /// The answers given in the puzzle text for its example
+    #[test]
+    fn example_answers() {
+        let puzzle = Puzzle::parse(include_str!("../input_test"));
+        assert_eq!(puzzle.part1(), "590784");
+        let puzzle = Puzzle::parse(include_str!("../input_test2"));
+        assert_eq!(puzzle.part1(), "474140");
+        assert_eq!(puzzle.part2(), "2758514936282235");
+    }
+
+    #[test]
+    fn signed_volumes_count_the_same() {
+        let mut puzzle = Puzzle::parse(include_str!("../input_test2"));
+        puzzle.set_option("algo", "signed-volume").unwrap();
+        assert_eq!(puzzle.part1(), "474140");
+        assert_eq!(puzzle.part2(), "2758514936282235");
+        assert!(puzzle.set_option("algo", "octree").is_err());
+    }
+}
@@ -0,0 +1,72 @@
+use std::io::BufRead;
+
+use d22::*;
+
+fn _p1(instructions: &[Instruction], region: &Cuboid) {
+    let size = |r: ReactorRange| (r.end() - r.start() + 1) as usize;
+    let (sx, sy, sz) = (size(region.x()), size(region.y()), size(region.z()));
+    let mut reactor = vec![false; sx * sy * sz];
+    println!("start...");
+    for i in instructions.iter().filter_map(|ist| ist.clip(region)) {
+        for x in i.cuboid().x() {
+            for y in i.cuboid().y() {
+                for z in i.cuboid().z() {
+                    let x = (x - region.x().start()) as usize;
+                    let y = (y - region.y().start()) as usize;
+                    let z = (z - region.z().start()) as usize;
+                    reactor[(x * sy + y) * sz + z] = i.is_on();
+                }
+            }
+        }
+    }
+
+    let on_count = reactor.iter().filter(|&&s| s).count();
+
+    println!("ON: {}", on_count);
+}
+
+fn _p1v2(instructions: &[Instruction], region: &Cuboid) {
+    let sum = aoc_core::or_exit(count_on_within(instructions, region));
+    println!("result: {}", sum);
+}
+
+fn _p2(instructions: &[Instruction]) {
+    let solution = aoc_core::or_exit(solve(instructions));
+    println!("result: {}", solution);
+}
+
+fn main() {
+    // --region x=A..B,y=C..D,z=E..F limits the count to that window,
+    // --boot is shorthand for the part 1 initialization region
+    let mut region: Option<Cuboid> = None;
+    let (input, args) = aoc_core::input_arg();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--boot" => region = Some(BOOT_REGION),
+            "--region" => {
+                let spec = args.next().expect("--region needs a value");
+                region = Some(aoc_core::or_exit(Cuboid::try_from(spec.as_str())));
+            },
+            _ => panic!("unknown argument: {}", arg),
+        }
+    }
+
+    let input = aoc_core::open_input(&input);
+    let lines = input.lines().map(|l| l.unwrap());
+
+    let instructions: Vec<Instruction> = aoc_core::or_exit(Instructions::from(lines).collect());
+
+    for inst in &instructions {
+        println!("{}", inst);
+    }
+
+    let (_, report) = normalize(&instructions, region.as_ref());
+    println!("normalized: {}", report);
+
+    match region {
+        //Some(region) => _p1(instructions.as_slice(), &region),
+        Some(region) => _p1v2(&instructions, &region),
+        None => _p2(&instructions),
+    }
+}
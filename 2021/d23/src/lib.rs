@@ -0,0 +1,820 @@
+use std::cmp::{Ord, Ordering};
+use std::hash::Hash;
+use std::fmt;
+use std::sync::OnceLock;
+
+use aoc_core::color::{paint, Color};
+use aoc_core::progress::Progress;
+use aoc_core::{AocError, Solver, Strategies, Strategy};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Amphipod {
+    Amber,
+    Bronze,
+    Copper,
+    Desert,
+}
+
+const ALL_AMPHIPOD_TYPES: &[Amphipod] = &[Amphipod::Amber, Amphipod::Bronze, Amphipod::Copper, Amphipod::Desert];
+
+pub type Room = Amphipod;
+pub type RoomPos = u8;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Position {
+    Hallway(u8),
+    Room(Room, RoomPos),
+}
+impl Position {
+    pub fn hallway_pos(&self) -> u8 {
+        match self {
+            Position::Hallway(n) => *n,
+            Position::Room(Room::Amber, _) => 2,
+            Position::Room(Room::Bronze, _) => 4,
+            Position::Room(Room::Copper, _) => 6,
+            Position::Room(Room::Desert, _) => 8,
+        }
+    }
+
+    pub fn into_hallway(&self) -> Position {
+        match self {
+            Position::Hallway(_) => *self,
+            Position::Room(Room::Amber, _) => Position::Hallway(2),
+            Position::Room(Room::Bronze, _) => Position::Hallway(4),
+            Position::Room(Room::Copper, _) => Position::Hallway(6),
+            Position::Room(Room::Desert, _) => Position::Hallway(8),
+        }
+    }
+
+    /// Index of this cell in an `Occupancy` mask
+    pub fn cell(&self) -> usize {
+        match self {
+            Position::Hallway(n) => *n as usize,
+            Position::Room(rm, d) => HALLWAY_CELLS + *rm as usize * MAX_ROOM_SIZE + *d as usize,
+        }
+    }
+
+    pub fn bit(&self) -> Occupancy {
+        1 << self.cell()
+    }
+}
+
+const HALLWAY_CELLS: usize = 11;
+const MAX_ROOM_SIZE: usize = 4;
+const CELLS: usize = HALLWAY_CELLS + 4 * MAX_ROOM_SIZE;
+
+/// One bit per cell of the burrow, set where an amphipod is: the hallway in
+/// the low 11 bits, then each room from the hallway down
+pub type Occupancy = u32;
+impl Ord for Position {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.eq(other) {
+            Ordering::Equal
+        } else {
+            match (self, other) {
+                (Position::Room(_,_), Position::Hallway(_)) => Ordering::Less,
+                (Position::Hallway(me), Position::Hallway(other)) => me.cmp(other),
+                (Position::Room(ar, ad), Position::Room(br, bd)) if ar == br => bd.cmp(ad),
+                (Position::Room(ar, _), Position::Room(br, _)) => ar.cmp(br),
+                _ => Ordering::Greater,
+            }
+        }
+    }
+}
+impl PartialOrd for Position {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The type's letter, in a colour of its own when colour is on
+impl fmt::Display for Amphipod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (letter, color) = match self {
+            Amphipod::Amber => ('A', Color::Yellow),
+            Amphipod::Bronze => ('B', Color::Red),
+            Amphipod::Copper => ('C', Color::Green),
+            Amphipod::Desert => ('D', Color::Cyan),
+        };
+        write!(f, "{}", paint(letter, color))
+    }
+}
+impl fmt::Debug for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Position::Room(a, d) => write!(f, "{}{}", a, d),
+            Position::Hallway(n) => write!(f, "={}=", n),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct State([Position; 8]);
+
+pub trait RoomSize {
+    fn room_size() -> usize;
+}
+
+pub trait SliceBackedBurrow {
+    fn stride() -> usize;
+    fn positions_slice(&self, a: Amphipod) -> &[Position];
+    fn positions_mut(&mut self, a: Amphipod) -> &mut [Position];
+}
+
+/// Burrows that keep an `Occupancy` mask up to date alongside their
+/// positions, so blocking checks don't have to search the positions
+pub trait PackedOccupancy {
+    fn occupancy(&self) -> Occupancy;
+    fn occupancy_mut(&mut self) -> &mut Occupancy;
+}
+
+/// Burrows that keep their `min_energy` lower bound up to date as
+/// amphipods move, rather than summing it afresh at every node
+pub trait CachedMinEnergy {
+    fn cached_min_energy(&self) -> Energy;
+    fn cached_min_energy_mut(&mut self) -> &mut Energy;
+}
+
+/// Least energy for `a` at `p` to reach its own room, ignoring everyone else
+fn goal_distance(a: Amphipod, p: Position) -> Energy {
+    match p {
+        Position::Room(rm, _) if rm == a => 0,
+        p => Path::from([p, Position::Room(a, 0)]).cost(a),
+    }
+}
+
+/// Sum of every amphipod's goal distance, for positions grouped by type
+/// `stride` at a time
+fn min_energy_of(positions: &[Position], stride: usize) -> Energy {
+    positions.chunks(stride)
+        .zip(ALL_AMPHIPOD_TYPES)
+        .flat_map(|(ps, &a)| ps.iter().map(move |&p| goal_distance(a, p)))
+        .sum()
+}
+
+pub trait BurrowState: RoomSize {
+    fn room_size() -> usize;
+
+    fn is_goal(&self) -> bool;
+
+    fn get(&self, pos: &Position) -> Option<Amphipod>;
+
+    fn apply_movement<B: BurrowState + Copy>(&mut self, t: &StateTransition<B>);
+
+    fn occupancy(&self) -> Occupancy;
+
+    fn occupied(&self, pos: &Position) -> bool {
+        self.occupancy() & pos.bit() != 0
+    }
+
+    fn min_energy(&self) -> Energy;
+
+    fn is_blocked(&self, a: Amphipod, path: &Path) -> bool {
+        match path.end() {
+            Position::Room(rm, _) if !self.can_enter_room(a, rm) => false,
+            _ => self.occupancy() & path.blocking_mask() != 0,
+        }
+    }
+
+    fn can_enter_room(&self, ap: Amphipod, room: Room) -> bool {
+        if room == ap {
+            (0..<Self as BurrowState>::room_size())
+                .all(|d| match self.get(&Position::Room(room, d as u8)) {
+                    Some(a) if a == ap => true,
+                    None => true,
+                    _ => false
+                })
+        } else {
+            false
+        }
+    }
+}
+
+impl<B> BurrowState for B
+where B: SliceBackedBurrow + RoomSize + PackedOccupancy + CachedMinEnergy + AsRef<[Position]> + Copy
+{
+    fn room_size() -> usize {
+        Self::stride()
+    }
+
+    fn occupancy(&self) -> Occupancy {
+        PackedOccupancy::occupancy(self)
+    }
+
+    fn get(&self, pos: &Position) -> Option<Amphipod> {
+        self.as_ref().iter().position(|p| *p == *pos).map(|n| match n / Self::room_size() {
+            0 => Amphipod::Amber,
+            1 => Amphipod::Bronze,
+            2 => Amphipod::Copper,
+            3 => Amphipod::Desert,
+            _ => panic!("Out of bound when searching through positions"),
+        })
+    }
+
+    fn is_goal(&self) -> bool {
+        ALL_AMPHIPOD_TYPES
+            .iter()
+            .all(|&a| self.positions_slice(a)
+                .iter()
+                .all(|p| match p { Position::Room(rm, _) => *rm == a, _ => false}))
+    }
+
+    fn min_energy(&self) -> Energy {
+        self.cached_min_energy()
+    }
+
+    fn apply_movement<S>(&mut self, t: &StateTransition<S>) where S: BurrowState + Copy {
+        let path = t.path;
+        let pos = self.positions_mut(t.a);
+        match pos.iter_mut().find(|p| **p == path.start()) {
+            Some(e) => *e = path.end(),
+            None => panic!("Not a valid movement"),
+        };
+        pos.sort();
+        *self.occupancy_mut() ^= path.start().bit() | path.end().bit();
+        let bound = self.cached_min_energy_mut();
+        *bound = *bound - goal_distance(t.a, path.start()) + goal_distance(t.a, path.end());
+    }
+}
+
+fn occupancy_of(positions: &[Position]) -> Occupancy {
+    positions.iter().fold(0, |mask, p| mask | p.bit())
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Burrow2(pub [Position; 8], Occupancy, Energy);
+impl Default for Burrow2 {
+    fn default() -> Self { Burrow2::new([Position::Hallway(0); 8]) }
+}
+impl Burrow2 {
+    pub fn new(positions: [Position; 8]) -> Self {
+        Burrow2(positions, occupancy_of(&positions), min_energy_of(&positions, 2))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Burrow4(pub [Position; 16], Occupancy, Energy);
+impl Default for Burrow4 {
+    fn default() -> Self { Burrow4::new([Position::Hallway(0); 16]) }
+}
+impl Burrow4 {
+    pub fn new(positions: [Position; 16]) -> Self {
+        Burrow4(positions, occupancy_of(&positions), min_energy_of(&positions, 4))
+    }
+}
+
+impl RoomSize for Burrow2 {
+    fn room_size() -> usize { 2 }
+}
+impl AsRef<[Position]> for Burrow2 {
+    fn as_ref(&self) -> &[Position] { &self.0 }
+}
+impl AsMut<[Position]> for Burrow2 {
+    fn as_mut(&mut self) -> &mut [Position] { &mut self.0 }
+}
+
+impl PackedOccupancy for Burrow2 {
+    fn occupancy(&self) -> Occupancy { self.1 }
+    fn occupancy_mut(&mut self) -> &mut Occupancy { &mut self.1 }
+}
+
+impl CachedMinEnergy for Burrow2 {
+    fn cached_min_energy(&self) -> Energy { self.2 }
+    fn cached_min_energy_mut(&mut self) -> &mut Energy { &mut self.2 }
+}
+
+impl RoomSize for Burrow4 {
+    fn room_size() -> usize { 4 }
+}
+impl AsRef<[Position]> for Burrow4 {
+    fn as_ref(&self) -> &[Position] { &self.0 }
+}
+impl AsMut<[Position]> for Burrow4 {
+    fn as_mut(&mut self) -> &mut [Position] { &mut self.0 }
+}
+
+impl PackedOccupancy for Burrow4 {
+    fn occupancy(&self) -> Occupancy { self.1 }
+    fn occupancy_mut(&mut self) -> &mut Occupancy { &mut self.1 }
+}
+
+impl CachedMinEnergy for Burrow4 {
+    fn cached_min_energy(&self) -> Energy { self.2 }
+    fn cached_min_energy_mut(&mut self) -> &mut Energy { &mut self.2 }
+}
+
+impl<B> SliceBackedBurrow for B
+where B: AsRef<[Position]> + AsMut<[Position]> + RoomSize
+{
+    fn stride() -> usize {
+        B::room_size()
+    }
+
+    fn positions_slice(&self, a: Amphipod) -> &[Position] {
+        let stride = Self::stride();
+        let i = a as usize;
+        &self.as_ref()[i*stride..(i+1)*stride]
+    }
+
+    fn positions_mut(&mut self, a: Amphipod) -> &mut [Position] {
+        let stride = Self::stride();
+        let i = a as usize;
+        &mut self.as_mut()[i*stride..(i+1)*stride]
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Path([Position; 2]);
+
+impl Path {
+    pub fn start(&self) -> Position { self.0[0] }
+    pub fn end(&self) -> Position { self.0[1] }
+
+    pub fn walk(&self) -> PathWalk {
+        PathWalk { here: Some(self.start()), end: self.end() }
+    }
+
+    pub fn steps(&self) -> usize {
+        self.walk().count() - 1
+    }
+
+    /// Cells that must be empty for this move: everything walked through
+    /// after leaving the start, looked up from a table built on first use
+    pub fn blocking_mask(&self) -> Occupancy {
+        static MASKS: OnceLock<Vec<[Occupancy; CELLS]>> = OnceLock::new();
+        let masks = MASKS.get_or_init(|| {
+            let cells: Vec<Position> = (0..HALLWAY_CELLS as u8).map(Position::Hallway)
+                .chain(ALL_AMPHIPOD_TYPES.iter()
+                    .flat_map(|&rm| (0..MAX_ROOM_SIZE as u8).map(move |d| Position::Room(rm, d))))
+                .collect();
+            cells.iter().map(|&start| {
+                let mut row = [0; CELLS];
+                for &end in &cells {
+                    row[end.cell()] = Path([start, end]).walk().skip(1).fold(0, |m, p| m | p.bit());
+                }
+                row
+            }).collect()
+        });
+        masks[self.start().cell()][self.end().cell()]
+    }
+
+    pub fn cost(&self, a: Amphipod) -> Energy {
+        (self.steps() as Energy) * match a {
+            Amphipod::Amber => 1,
+            Amphipod::Bronze => 10,
+            Amphipod::Copper => 100,
+            Amphipod::Desert => 1000,
+        }
+    }
+}
+
+impl From<[Position; 2]> for Path {
+    fn from(a: [Position; 2]) -> Path { Path(a) }
+}
+impl AsRef<[Position; 2]> for Path {
+    fn as_ref(&self) -> &[Position; 2] { &self.0 }
+}
+
+pub struct PathWalk {
+    here: Option<Position>,
+    end: Position,
+}
+impl Iterator for PathWalk {
+    type Item = Position;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.here {
+            Some(p) if p == self.end => {
+                self.here = None;
+                Some(p)
+            },
+            Some(p) => {
+                self.here = match (p, self.end) {
+                    (Position::Hallway(n), Position::Room(a, _)) => {
+                        let rm = Position::Room(a, 0);
+                        if let Position::Hallway(t) = rm.into_hallway() {
+                            if n == t {
+                                Some(rm)
+                            } else {
+                                Some(Position::Hallway(if n < t { n + 1 } else { n - 1 }))
+                            }
+                        } else {
+                            panic!();
+                        }
+                    },
+                    (Position::Hallway(a), Position::Hallway(b))
+                        => Some(Position::Hallway(if a < b { a + 1 } else { a - 1 })),
+                    (Position::Room(aa, ad), Position::Room(ba, bd)) if aa == ba
+                        => Some(Position::Room(aa, if ad > bd { ad - 1 } else { ad + 1 })),
+                    (Position::Room(a, d), _) if d == 0 => Some(Position::Room(a, d).into_hallway()),
+                    (Position::Room(a, d), _) => Some(Position::Room(a, d - 1)),
+                };
+                Some(p)
+            }
+            None => None,
+        }
+    }
+}
+
+/// Every move an amphipod can make from `state`: into its own room when
+/// that's clear, otherwise out of a room into the hallway
+fn transitions<B>(state: B) -> Vec<StateTransition<B>>
+where B: BurrowState + SliceBackedBurrow + Copy {
+    // Generate all possible state transitions
+    let mut transitions = Vec::with_capacity(8);
+
+    for &a in ALL_AMPHIPOD_TYPES {
+        // Can amphipods go home?
+        let room_is_clear = state.can_enter_room(a, a);
+
+        // TODO: This requires SliceBackedBurrow
+        for p in state.positions_slice(a) {
+            match p {
+                // Already home
+                Position::Room(rm, _) if room_is_clear && *rm == a => (),
+                p if room_is_clear => {
+                    // Find deepest room spot and go there
+                    let target = (0..<B as BurrowState>::room_size()).rev()
+                        .find_map(|d| {
+                            let target_pos = Position::Room(a, d as u8);
+                            if state.occupied(&target_pos) {
+                                None
+                            } else {
+                                Some(target_pos)
+                            }
+                        }).unwrap();
+
+                    let path = [*p, target].into();
+                    if !state.is_blocked(a, &path) {
+                        transitions.push(StateTransition { start: state, a, path });
+                    }
+                },
+                Position::Room(..) => {
+                    // Go to all the hallway spots
+                    for h in [0, 1, 3, 5, 7, 9, 10] {
+                        let path = [*p, Position::Hallway(h)].into();
+                        if !state.is_blocked(a, &path) {
+                            transitions.push(StateTransition { start: state, a, path });
+                        }
+                    }
+                },
+                _ => (),
+            }
+        }
+    }
+    transitions
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StateTransition<B> where B: BurrowState + Copy {
+    start: B,
+    a: Amphipod,
+    path: Path,
+}
+
+pub type Energy = u32;
+
+impl<B> StateTransition<B> where B: BurrowState + Copy {
+    pub fn cost(&self) -> Energy {
+        self.path.cost(self.a)
+    }
+
+    /// The burrow after the move
+    pub fn target(&self) -> B {
+        let mut target = self.start;
+        target.apply_movement(self);
+        target
+    }
+}
+
+
+// #############
+// #...........#
+// ###B#C#B#D###
+//   #A#D#C#A#
+//   #########
+pub const SAMPLE_INPUT: [Position; 8] = [
+    Position::Room(Room::Amber, 1),
+    Position::Room(Room::Desert, 1),
+    Position::Room(Room::Amber, 0),
+    Position::Room(Room::Copper, 0),
+    Position::Room(Room::Bronze, 0),
+    Position::Room(Room::Copper, 1),
+    Position::Room(Room::Bronze, 1),
+    Position::Room(Room::Desert, 0),
+];
+
+impl From<&[Position]> for Burrow2 {
+    fn from(p: &[Position]) -> Burrow2 {
+        let mut d = [Position::Hallway(0); 8];
+        d.clone_from_slice(p);
+        Burrow2::new(d)
+    }
+}
+impl fmt::Debug for Burrow2 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for n in 0..=10 {
+            match self.get(&Position::Hallway(n)) {
+                Some(a) => write!(f, "{}", a)?,
+                None => write!(f, ".")?,
+            }
+        }
+        writeln!(f)?;
+        for d in 0..2 {
+            write!(f, "  ")?;
+            for a in [Amphipod::Amber, Amphipod::Bronze, Amphipod::Copper, Amphipod::Desert] {
+                match self.get(&Position::Room(a, d)) {
+                    Some(a) => write!(f, "{} ", a)?,
+                    None => write!(f, ". ")?,
+                }
+            }
+            writeln!(f)?
+        }
+        write!(f, "")
+    }
+}
+
+impl From<&[Position]> for Burrow4 {
+    fn from(p: &[Position]) -> Burrow4 {
+        let mut d = [Position::Hallway(0); 16];
+        for i in 0..4 {
+            d[i*4..i*4+2].clone_from_slice(&p[i*2..i*2+2]);
+            for n in 0..2 {
+                let idx = i*4+n;
+                if let Position::Room(rm, r) = d[idx] {
+                    if r == 1 {
+                        d[idx] = Position::Room(rm, 3);
+                    }
+                }
+            }
+        }
+
+        // INSERT:
+        //   #D#C#B#A#
+        //   #D#B#A#C#
+        d[2] = Position::Room(Room::Copper, 2);
+        d[3] = Position::Room(Room::Desert, 1);
+        d[6] = Position::Room(Room::Bronze, 2);
+        d[7] = Position::Room(Room::Copper, 1);
+        d[10] = Position::Room(Room::Bronze, 1);
+        d[11] = Position::Room(Room::Desert, 2);
+        d[14] = Position::Room(Room::Amber, 1);
+        d[15] = Position::Room(Room::Amber, 2);
+        Burrow4::new(d)
+    }
+}
+impl fmt::Debug for Burrow4 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for n in 0..=10 {
+            match self.get(&Position::Hallway(n)) {
+                Some(a) => write!(f, "{}", a)?,
+                None => write!(f, ".")?,
+            }
+        }
+        writeln!(f)?;
+        for d in 0..4 {
+            write!(f, "  ")?;
+            for a in [Amphipod::Amber, Amphipod::Bronze, Amphipod::Copper, Amphipod::Desert] {
+                match self.get(&Position::Room(a, d)) {
+                    Some(a) => write!(f, "{} ", a)?,
+                    None => write!(f, ". ")?,
+                }
+            }
+            writeln!(f)?
+        }
+        write!(f, "")
+    }
+}
+
+
+/// How to look for the cheapest way to sort the burrow
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Search {
+    #[default]
+    AStar,
+    /// IDA*, which makes a pass over the states for every energy bound it
+    /// tries: far slower here, as the bounds go up in small steps
+    Ida,
+}
+
+pub fn find_shortest<B>(start: &B) -> Option<(Energy, Vec<B>)>
+where B: BurrowState + SliceBackedBurrow + Copy + Eq + Hash {
+    find_shortest_by(start, Search::AStar)
+}
+
+pub fn find_shortest_by<B>(start: &B, search: Search) -> Option<(Energy, Vec<B>)>
+where B: BurrowState + SliceBackedBurrow + Copy + Eq + Hash {
+    let mut progress = Progress::counter("states expanded");
+    let successors = |&s: &B| {
+        progress.inc(1);
+        transitions(s).into_iter().map(|t| (t.target(), t.cost()))
+    };
+    let (path, energy) = match search {
+        Search::AStar => aoc_search::astar(start, successors, |s| s.min_energy(), |s| s.is_goal())?,
+        Search::Ida => aoc_search::ida_star(start, successors, |s| s.min_energy(), |s| s.is_goal())?,
+    };
+    Some((energy, path))
+}
+
+/// Reads the starting positions from the burrow diagram, grouped by
+/// amphipod type as `Burrow2::from` expects
+pub fn parse_burrow(input: &str) -> [Position; 8] {
+    let mut positions = [Position::Hallway(0); 8];
+    let mut found = [0; 4];
+    for (depth, line) in input.lines().skip(2).take(2).enumerate() {
+        let line = line.as_bytes();
+        for (room, &col) in ALL_AMPHIPOD_TYPES.iter().zip(&[3, 5, 7, 9]) {
+            let a = match line[col] {
+                b'A' => Amphipod::Amber,
+                b'B' => Amphipod::Bronze,
+                b'C' => Amphipod::Copper,
+                b'D' => Amphipod::Desert,
+                c => panic!("unexpected {:?} in room", c as char),
+            };
+            let i = a as usize;
+            positions[i * 2 + found[i]] = Position::Room(*room, depth as u8);
+            found[i] += 1;
+        }
+    }
+    for pair in positions.chunks_mut(2) {
+        pair.sort();
+    }
+    positions
+}
+
+pub struct Puzzle {
+    start: [Position; 8],
+    search: Search,
+}
+
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        Ok(Puzzle { start: parse_burrow(input), search: Search::default() })
+    }
+
+    fn strategies() -> Strategies {
+        Strategies {
+            algos: &[
+                Strategy { name: "astar", about: "A*, with the energy to move each amphipod home as the estimate" },
+                Strategy { name: "ida", about: "IDA*, deepening the energy bound pass by pass (minutes for part 2)" },
+            ],
+            impls: &[],
+        }
+    }
+
+    /// `algo`: `astar` or `ida`
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), AocError> {
+        if name != "algo" {
+            return Err(AocError::invalid(format!("no option {}", name)));
+        }
+        self.search = match value {
+            "astar" => Search::AStar,
+            "ida" => Search::Ida,
+            _ => return Err(AocError::invalid(format!("bad value {:?} for option algo", value))),
+        };
+        Ok(())
+    }
+
+    fn part1(&self) -> String {
+        let burrow = Burrow2::from(self.start.as_ref());
+        find_shortest_by(&burrow, self.search).map_or_else(|| "-".to_string(), |(cost, _)| cost.to_string())
+    }
+
+    fn part2(&self) -> String {
+        let burrow = Burrow4::from(self.start.as_ref());
+        find_shortest_by(&burrow, self.search).map_or_else(|| "-".to_string(), |(cost, _)| cost.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+    #[test]
+    fn wins() {
+        // #############
+        // #.....D.D.A.#
+        // ###.#B#C#.###
+        //   #A#B#C#.#
+        //   #########
+        let ALMOST_WIN = State([
+            Position::Room(Amphipod::Amber, Room::Inner),
+            Position::Hallway(9),
+            Position::Room(Amphipod::Bronze, Room::Inner),
+            Position::Room(Amphipod::Bronze, Room::Outer),
+            Position::Room(Amphipod::Copper, Room::Inner),
+            Position::Room(Amphipod::Copper, Room::Outer),
+            Position::Hallway(5),
+            Position::Hallway(7),
+        ]);
+        let energy = find_shortest(&ALMOST_WIN).unwrap().0;
+        assert_eq!(7008, energy);
+    }
+
+    #[test]
+    fn rooms_sort_before_hallway() {
+        let h = Position::Hallway(5);
+        let r = Position::Room(Amphipod::Amber, Room::Outer);
+        println!("{}", match r.cmp(&h) {
+            Ordering::Less => "<",
+            Ordering::Greater => ">",
+            Ordering::Equal => "=",
+        });
+        assert!(r < h);
+    }
+    */
+
+    #[test]
+    fn parses_sample_diagram() {
+        let diagram = "#############\n#...........#\n###B#C#B#D###\n  #A#D#C#A#\n  #########\n";
+        let mut sample = SAMPLE_INPUT;
+        for pair in sample.chunks_mut(2) {
+            pair.sort();
+        }
+        assert_eq!(parse_burrow(diagram), sample);
+    }
+
+    #[test]
+    fn burrows_draw_as_golden() {
+        let mut burrow = Burrow2::from(SAMPLE_INPUT.as_ref());
+        let mut drawn = format!("{:?}", burrow);
+        let path = Path::from([Position::Room(Room::Copper, 0), Position::Hallway(3)]);
+        burrow.apply_movement(&StateTransition { start: burrow, a: Amphipod::Bronze, path });
+        drawn += &format!("\n{:?}\n{:?}", burrow, Burrow4::from(SAMPLE_INPUT.as_ref()));
+        aoc_core::golden::check(env!("CARGO_MANIFEST_DIR"), "burrows", &drawn);
+    }
+
+    #[test]
+    fn blocking_masks_match_walked_paths() {
+        let burrow = Burrow4::from(SAMPLE_INPUT.as_ref());
+        for start in burrow.0 {
+            for h in [0, 1, 3, 5, 7, 9, 10] {
+                let path = Path::from([start, Position::Hallway(h)]);
+                let walked = path.walk().skip(1).any(|p| burrow.get(&p).is_some());
+                let a = burrow.get(&start).unwrap();
+                assert_eq!(burrow.is_blocked(a, &path), walked, "{:?}", path.0);
+            }
+        }
+    }
+
+    #[test]
+    fn occupancy_follows_moves() {
+        let mut burrow = Burrow2::from(SAMPLE_INPUT.as_ref());
+        let path = Path::from([Position::Room(Room::Copper, 0), Position::Hallway(3)]);
+        let t = StateTransition { start: burrow, a: Amphipod::Bronze, path };
+        burrow.apply_movement(&t);
+        assert!(burrow.occupied(&Position::Hallway(3)));
+        assert!(!burrow.occupied(&Position::Room(Room::Copper, 0)));
+        assert_eq!(burrow.1, occupancy_of(&burrow.0));
+    }
+
+    /// Checks the cached bound against a fresh sum on the first `limit`
+    /// states a breadth-first walk from `start` reaches
+    fn check_cached_bound<B>(start: B, stride: usize, limit: usize)
+    where B: BurrowState + SliceBackedBurrow + AsRef<[Position]> + Copy + Eq + Hash {
+        let mut seen = std::collections::HashSet::from([start]);
+        let mut queue = std::collections::VecDeque::from([start]);
+        while let Some(s) = queue.pop_front() {
+            assert_eq!(s.min_energy(), min_energy_of(s.as_ref(), stride));
+            for t in transitions(s) {
+                let next = t.target();
+                if seen.len() < limit && seen.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cached_bound_matches_recomputed() {
+        check_cached_bound(Burrow2::from(SAMPLE_INPUT.as_ref()), 2, 5000);
+        check_cached_bound(Burrow4::from(SAMPLE_INPUT.as_ref()), 4, 5000);
+        let solved = ALL_AMPHIPOD_TYPES.iter()
+            .flat_map(|&a| [Position::Room(a, 0), Position::Room(a, 1)]);
+        let solved: Vec<Position> = solved.collect();
+        assert_eq!(Burrow2::new(solved.try_into().unwrap()).min_energy(), 0);
+    }
+
+    #[test]
+    fn room_to_room_has_correct_steps() {
+        let p = Path::from([Position::Room(Room::Amber, 1), Position::Room(Room::Bronze, 0)]);
+        assert_eq!(p.walk().take(20).count(), 6);
+    }
+
+    #[test]
+    fn ida_finds_the_same_energy() {
+        let mut puzzle = Puzzle::parse(include_str!("../input_test"));
+        puzzle.set_option("algo", "ida").unwrap();
+        assert_eq!(puzzle.part1(), "12521");
+        assert!(puzzle.set_option("algo", "bfs").is_err());
+    }
+
+    /// The answers given in the puzzle text for its example
+    #[test]
+    fn example_answers() {
+        let puzzle = Puzzle::parse(include_str!("../input_test"));
+        assert_eq!(puzzle.part1(), "12521");
+        assert_eq!(puzzle.part2(), "44169");
+    }
+}
@@ -0,0 +1,40 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::io::Read;
+
+use d23::*;
+
+/// Every state on the cheapest way to sort `burrow`, then the energy it took
+fn solve<B>(burrow: B) where B: BurrowState + SliceBackedBurrow + Copy + Eq + Hash + Debug {
+    match find_shortest(&burrow) {
+        Some((cost, states)) => {
+            for s in states {
+                println!("{:?}", s);
+            }
+            println!("{} energy", cost);
+        },
+        None => println!("NO SOLUTION"),
+    };
+}
+
+fn main() {
+    // --progress shows how many states each search has expanded; --color
+    // draws the amphipods by type
+    let (input, args) = aoc_core::input_arg();
+    for arg in args {
+        match arg.as_str() {
+            "--progress" => aoc_core::progress::enable(true),
+            "--color" => aoc_core::color::enable(true),
+            _ => panic!("unknown argument: {}", arg),
+        }
+    }
+
+    let mut text = String::new();
+    aoc_core::open_input(&input).read_to_string(&mut text).unwrap();
+    let start = parse_burrow(&text);
+
+    println!("part 1");
+    solve(Burrow2::from(start.as_ref()));
+    println!("part 2");
+    solve(Burrow4::from(start.as_ref()));
+}
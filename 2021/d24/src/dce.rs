@@ -0,0 +1,117 @@
+//! Dead code elimination for MONAD programs. Only z's final value matters,
+//! so working back from the end, a register is live while some later
+//! instruction still reads it on the way to z. Writes to a register that
+//! isn't live can go, as can instructions that leave their register as it
+//! was. Inputs always stay, since dropping one would shift the digits read
+//! after it.
+
+use aoc_core::rng::Rng;
+
+use crate::{Alu, Instruction, Op1, Op2, Operand, RegisterId, Word, REGISTERS};
+
+/// Set of registers, one bit each
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Live(u8);
+
+impl Live {
+    fn bit(r: RegisterId) -> u8 {
+        1 << REGISTERS.iter().position(|&q| q == r).unwrap()
+    }
+
+    fn contains(self, r: RegisterId) -> bool {
+        self.0 & Live::bit(r) != 0
+    }
+
+    fn with(self, r: RegisterId) -> Live {
+        Live(self.0 | Live::bit(r))
+    }
+
+    fn without(self, r: RegisterId) -> Live {
+        Live(self.0 & !Live::bit(r))
+    }
+}
+
+/// Whether the instruction leaves its register unchanged
+fn is_identity(inst: &Instruction) -> bool {
+    matches!(inst,
+        Instruction::Op2(Op2::Add, _, Operand::Literal(0))
+        | Instruction::Op2(Op2::Mul | Op2::Div, _, Operand::Literal(1)))
+}
+
+/// Indices of the instructions that can't change z's final value
+pub fn dead_instructions(program: &[Instruction]) -> Vec<usize> {
+    let mut live = Live(0).with(RegisterId::Z);
+    let mut dead = vec!();
+    for (i, inst) in program.iter().enumerate().rev() {
+        match *inst {
+            Instruction::Op1(Op1::Input, r) => live = live.without(r),
+            Instruction::Op2(op, r, operand) => {
+                if !live.contains(r) || is_identity(inst) {
+                    dead.push(i);
+                    continue;
+                }
+                // multiplying by zero overwrites the register without reading it
+                if let (Op2::Mul, Operand::Literal(0)) = (op, operand) {
+                    live = live.without(r);
+                }
+                if let Operand::Register(q) = operand {
+                    live = live.with(q);
+                }
+            },
+        }
+    }
+    dead.reverse();
+    dead
+}
+
+/// The program without its dead instructions
+pub fn eliminate_dead_code(program: &[Instruction]) -> Vec<Instruction> {
+    let dead = dead_instructions(program);
+    program.iter().enumerate()
+        .filter(|(i, _)| dead.binary_search(i).is_err())
+        .map(|(_, &inst)| inst)
+        .collect()
+}
+
+/// Run both programs on `samples` random model numbers, returning the first
+/// digits they leave a different z for
+pub fn cross_validate(a: &[Instruction], b: &[Instruction], samples: usize, rng: &mut Rng) -> Result<(), Vec<Word>> {
+    let inputs = a.iter().filter(|i| matches!(i, Instruction::Op1(..))).count();
+    for _ in 0..samples {
+        let digits: Vec<Word> = (0..inputs).map(|_| rng.range(1..10)).collect();
+        let (.., za, _) = Alu::new().execute(a.iter(), &digits);
+        let (.., zb, _) = Alu::new().execute(b.iter(), &digits);
+        if za != zb {
+            return Err(digits);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_program;
+
+    fn parse(text: &str) -> Vec<Instruction> {
+        parse_program(text).unwrap()
+    }
+
+    #[test]
+    fn overwritten_writes_are_dead() {
+        let program = parse("inp w\nadd x w\nmul x 0\nadd x 2\nadd y 3\nmul z 1\nadd z x\nadd y w\ninp x\nadd z x\n");
+        assert_eq!(dead_instructions(&program), [1, 4, 5, 7]);
+        assert!(eliminate_dead_code(&program) == parse("inp w\nmul x 0\nadd x 2\nadd z x\ninp x\nadd z x\n"));
+    }
+
+    #[test]
+    fn reduced_monad_agrees() {
+        let program = parse(include_str!("../input.txt"));
+        let reduced = eliminate_dead_code(&program);
+        assert!(reduced.len() < program.len());
+        assert_eq!(cross_validate(&program, &reduced, 200, &mut Rng::seed_from(24)), Ok(()));
+
+        let text: String = reduced.iter().map(|i| format!("{}\n", i)).collect();
+        assert!(parse(&text) == reduced);
+    }
+}
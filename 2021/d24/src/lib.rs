@@ -0,0 +1,348 @@
+use std::cell::OnceCell;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+pub use aoc_alu::*;
+mod dce;
+pub use dce::{cross_validate, dead_instructions, eliminate_dead_code};
+
+use aoc_core::progress::Progress;
+use aoc_core::{AocError, Solver};
+
+pub struct DescendingModelNumbers([Word; 14]);
+impl DescendingModelNumbers {
+    pub fn new() -> Self {
+        Self([9; 14])
+    }
+}
+impl Default for DescendingModelNumbers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl From<[Word; 14]> for DescendingModelNumbers {
+    fn from(a: [Word; 14]) -> Self {
+        Self(a)
+    }
+}
+impl Iterator for DescendingModelNumbers {
+    type Item = [Word; 14];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0[0] == 0 {
+            return None
+        }
+        let r = self.0;
+
+        self.0[13] -= 1;
+        let a = &mut self.0;
+        for i in (1..=13).rev() {
+            if a[i] == 0 {
+                a[i] = 9;
+                a[i-1] -= 1;
+            }
+        }
+
+        Some(r)
+    }
+}
+
+/// Digit, z before the digit's block and z after it
+pub type DigitMatch = (Word, Word, Word);
+
+pub fn find_valid(digits: &mut Vec<i64>, sieve: &[Vec<DigitMatch>]) -> Option<u64> {
+    if sieve.is_empty() {
+        let mut n = 0;
+        for d in digits {
+            n = n * 10 + *d as u64;
+        }
+        return Some(n);
+    }
+
+    let candidates = &sieve[0];
+
+    for (w, ..) in candidates {
+        digits.push(*w);
+        if let Some(n) = find_valid(digits, &sieve[1..]) {
+            return Some(n);
+        }
+        digits.pop();
+    }
+    None
+}
+
+/// z values tried going into each digit's block
+const Z_SEARCH: RangeInclusive<Word> = -20000..=20000;
+
+/// For each digit, every (digit, z in, z out) whose z out is a z in that the
+/// next digit can take on to z=0 at the end. Built one digit at a time from
+/// the last, which takes minutes, so it can be saved part way and resumed.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ZMatches {
+    tables: Vec<Vec<DigitMatch>>,
+    /// Digits from here to the end have their tables built
+    done_from: usize,
+    /// Which program the tables were built for
+    fingerprint: u64,
+}
+
+fn fingerprint(instructions: &[Instruction]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    instructions.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl ZMatches {
+    pub fn new(instructions: &[Instruction]) -> Self {
+        let mut tables = vec![vec!(); 15];
+        tables[14].push((9, 0, 0));
+        ZMatches { tables, done_from: 14, fingerprint: fingerprint(instructions) }
+    }
+
+    pub fn digits_done(&self) -> usize {
+        14 - self.done_from
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.done_from == 0
+    }
+
+    /// Build the table for the next digit back, returning which digit it was
+    pub fn step(&mut self, instructions: &[Instruction]) -> Option<usize> {
+        if self.is_complete() {
+            return None;
+        }
+        let digit = self.done_from - 1;
+        let inst = &instructions[digit * 18..(digit + 1) * 18];
+        let z_wanted: HashSet<Word> = self.tables[digit + 1].iter().map(|p| p.1).collect();
+
+        let _span = tracing::info_span!("digit table", digit = digit + 1).entered();
+        let zs = &mut self.tables[digit];
+        zs.clear();
+        let mut progress = Progress::bar(&format!("digit {} z values", digit + 1), Z_SEARCH.count() as u64);
+        for z_init in Z_SEARCH {
+            progress.inc(1);
+            for d in (1..=9).rev() {
+                let mut alu = Alu::initialized(0,0, z_init, 0);
+                let (.., z, _) = alu.execute(inst.iter(), [d].iter());
+
+                if z_wanted.contains(&z) {
+                    zs.push((d, z_init, z));
+                }
+            }
+        }
+        self.done_from = digit;
+        Some(digit)
+    }
+
+    /// Build every remaining table
+    pub fn complete(&mut self, instructions: &[Instruction]) {
+        while self.step(instructions).is_some() {}
+    }
+
+    /// Write the tables built so far, replacing `path` only once the new
+    /// checkpoint is fully written
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut text = format!("d24 z-matches {:016x} {}\n", self.fingerprint, self.done_from);
+        for digit in self.done_from..14 {
+            for (w, z_in, z_out) in &self.tables[digit] {
+                text += &format!("{} {} {} {}\n", digit, w, z_in, z_out);
+            }
+        }
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, text)?;
+        fs::rename(tmp, path)
+    }
+
+    /// Tables saved by `save`, provided they were built for `instructions`
+    pub fn load(path: &Path, instructions: &[Instruction]) -> Result<Self, AocError> {
+        let text = fs::read_to_string(path)?;
+        let mut lines = text.lines();
+        let header: Vec<&str> = lines.next().unwrap_or_default().split(' ').collect();
+        let (fp, done_from) = match header[..] {
+            ["d24", "z-matches", fp, done_from] => (u64::from_str_radix(fp, 16)?, done_from.parse::<usize>()?),
+            _ => return Err(AocError::invalid(format!("{} is not a d24 checkpoint", path.display()))),
+        };
+        let mut matches = ZMatches::new(instructions);
+        if fp != matches.fingerprint {
+            return Err(AocError::invalid(format!("{} was saved for a different program", path.display())));
+        }
+        if done_from > 14 {
+            return Err(AocError::invalid(format!("checkpoint can't start at digit {}", done_from)));
+        }
+
+        for (i, line) in lines.enumerate() {
+            let fields = line.split(' ')
+                .map(|f| f.parse::<Word>())
+                .collect::<Result<Vec<Word>, _>>()
+                .map_err(|e| AocError::from(e).at_line(i + 2))?;
+            match fields[..] {
+                [digit, w, z_in, z_out] if (done_from..14).contains(&(digit as usize)) => {
+                    matches.tables[digit as usize].push((w, z_in, z_out));
+                },
+                _ => return Err(AocError::invalid("bad checkpoint entry").at_line(i + 2)),
+            }
+        }
+        matches.done_from = done_from;
+        Ok(matches)
+    }
+
+    /// Chain the tables forward from z=0, keeping only matches reachable
+    /// from the first digit, ordered so the largest (or smallest) model
+    /// number is found first
+    pub fn sieve(&self, largest: bool) -> Vec<Vec<DigitMatch>> {
+        assert!(self.is_complete(), "sieving before every digit's table is built");
+        let z_matches = &self.tables;
+
+        let mut sieve = vec![vec!(); 14];
+        let min_or_max = |(w1, ..): &DigitMatch, (w2, ..): &DigitMatch| -> Ordering {
+            if largest { w2.cmp(w1) } else { w1.cmp(w2) }
+        };
+
+        // Seed solution for first digit
+        for (w, z_init, z) in &z_matches[0] {
+            if *z_init == 0 {
+                sieve[0].push((*w, *z_init, *z));
+            }
+        }
+        sieve[0].sort_by(min_or_max);
+
+        for digit in 1..14 {
+            let allowed_zs: Vec<Word> = sieve[digit-1].iter().map(|(_, _, z)| *z).collect();
+            for (w, z_init, z) in &z_matches[digit] {
+                if allowed_zs.contains(z_init) {
+                    sieve[digit].push((*w, *z_init, *z));
+                }
+            }
+            sieve[digit].sort_by(min_or_max);
+        }
+
+        sieve
+    }
+}
+
+/// For each digit, every (digit, z in, z out) that can still lead to z=0 at
+/// the end, with the digits ordered so the largest (or smallest) model number
+/// is found first
+pub fn build_sieve(instructions: &[Instruction], largest: bool) -> Vec<Vec<DigitMatch>> {
+    let mut matches = ZMatches::new(instructions);
+    matches.complete(instructions);
+    matches.sieve(largest)
+}
+
+pub fn find_model_number(instructions: &[Instruction], largest: bool) -> Option<u64> {
+    let sieve = build_sieve(instructions, largest);
+    find_valid(&mut Vec::with_capacity(14), &sieve)
+}
+
+/// Where `Puzzle` keeps its z matches in a cache directory
+pub const CACHE_FILE: &str = "d24-z-matches.txt";
+
+/// The program, with the z matches (the slow part) shared between both
+/// parts and kept in the cache directory if there is one
+pub struct Puzzle {
+    instructions: Vec<Instruction>,
+    cache: Option<PathBuf>,
+    matches: OnceCell<ZMatches>,
+}
+
+impl Puzzle {
+    fn matches(&self) -> &ZMatches {
+        self.matches.get_or_init(|| {
+            let path = self.cache.as_ref().map(|dir| dir.join(CACHE_FILE));
+            let mut matches = path.as_ref()
+                .and_then(|p| ZMatches::load(p, &self.instructions).ok())
+                .unwrap_or_else(|| ZMatches::new(&self.instructions));
+            if matches.is_complete() {
+                return matches;
+            }
+            matches.complete(&self.instructions);
+            if let Some(path) = path {
+                if let Err(e) = matches.save(&path) {
+                    log::warn!("caching z matches in {}: {}", path.display(), e);
+                }
+            }
+            matches
+        })
+    }
+
+    fn model_number(&self, largest: bool) -> String {
+        find_valid(&mut Vec::with_capacity(14), &self.matches().sieve(largest))
+            .map_or_else(|| "-".to_string(), |n| n.to_string())
+    }
+}
+
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        Ok(Puzzle { instructions: parse_program(input)?, cache: None, matches: OnceCell::new() })
+    }
+
+    fn use_cache(&mut self, dir: &Path) {
+        self.cache = Some(dir.to_path_buf());
+    }
+
+    fn part1(&self) -> String {
+        self.model_number(true)
+    }
+
+    fn part2(&self) -> String {
+        self.model_number(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn program() -> Vec<Instruction> {
+        parse_program(include_str!("../input.txt")).unwrap()
+    }
+
+    #[test]
+    fn checkpoint_round_trip() {
+        let instructions = program();
+        let mut matches = ZMatches::new(&instructions);
+        assert_eq!(matches.step(&instructions), Some(13));
+        assert_eq!(matches.digits_done(), 1);
+
+        let path = std::env::temp_dir().join(format!("d24-checkpoint-{}.txt", std::process::id()));
+        matches.save(&path).unwrap();
+        let loaded = ZMatches::load(&path, &instructions);
+
+        let mut other = instructions.clone();
+        other[0] = Instruction::Op1(Op1::Input, RegisterId::X);
+        let mismatched = ZMatches::load(&path, &other);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.unwrap(), matches);
+        assert!(mismatched.is_err());
+    }
+
+    #[test]
+    fn parts_use_cached_matches() {
+        let instructions = program();
+        let dir = std::env::temp_dir().join(format!("d24-cache-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        // a made-up set of tables, which nothing but the cache could have
+        // come up with
+        let mut text = format!("d24 z-matches {:016x} 0\n", fingerprint(&instructions));
+        for digit in 0..14 {
+            text += &format!("{} 4 0 0\n{} 7 0 0\n", digit, digit);
+        }
+        fs::write(dir.join(CACHE_FILE), text).unwrap();
+
+        let mut puzzle = Puzzle::parse(include_str!("../input.txt"));
+        puzzle.use_cache(&dir);
+        let (part1, part2) = (puzzle.part1(), puzzle.part2());
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(part1, "77777777777777");
+        assert_eq!(part2, "44444444444444");
+    }
+}
@@ -0,0 +1,76 @@
+use std::path::PathBuf;
+
+use aoc_core::rng::Rng;
+
+use d24::*;
+
+/// Print the reduced program, and how it compares with the original
+fn eliminate(instructions: &[Instruction]) {
+    const SAMPLES: usize = 10_000;
+    let reduced = eliminate_dead_code(instructions);
+    for inst in &reduced {
+        println!("{}", inst);
+    }
+    eprintln!("removed {} of {} instructions", instructions.len() - reduced.len(), instructions.len());
+    match cross_validate(instructions, &reduced, SAMPLES, &mut Rng::seed_from(0x24)) {
+        Ok(()) => eprintln!("same z on {} random model numbers", SAMPLES),
+        Err(digits) => panic!("z differs for {:?}", digits),
+    }
+}
+
+fn main() {
+    // --checkpoint <path> saves the z-match tables after every digit,
+    // --resume picks up from what was saved there; --dce prints the program
+    // without the instructions that can't affect z, checked against the
+    // original on random model numbers; --progress shows each digit's sweep
+    let mut checkpoint: Option<PathBuf> = None;
+    let mut resume = false;
+    let mut dce = false;
+    let (input, args) = aoc_core::input_arg();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--checkpoint" => checkpoint = Some(args.next().expect("--checkpoint needs a path").into()),
+            "--resume" => resume = true,
+            "--dce" => dce = true,
+            "--progress" => aoc_core::progress::enable(true),
+            _ => panic!("unknown argument: {}", arg),
+        }
+    }
+    if resume && checkpoint.is_none() {
+        panic!("--resume needs --checkpoint <path>");
+    }
+
+    let instructions = aoc_core::or_exit(read_program(aoc_core::open_input(&input)));
+
+    if dce {
+        return eliminate(&instructions);
+    }
+
+    let mut matches = match &checkpoint {
+        Some(path) if resume && path.exists() => aoc_core::or_exit(ZMatches::load(path, &instructions)),
+        _ => ZMatches::new(&instructions),
+    };
+    eprintln!("{}/14 digits done", matches.digits_done());
+    while let Some(digit) = matches.step(&instructions) {
+        if let Some(path) = &checkpoint {
+            matches.save(path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+        }
+        eprintln!("{}/14 digits done (digit {})", matches.digits_done(), digit + 1);
+    }
+
+    //let sieve = matches.sieve(false); // min
+    let sieve = matches.sieve(true); // max
+    for digit in &sieve {
+        println!("{:?}", digit);
+    }
+
+    let mut solution = Vec::with_capacity(14);
+    if let Some(n) = find_valid(&mut solution, &sieve) {
+        println!("{}", n);
+    }
+
+    let mut alu = Alu::new();
+    alu.execute(instructions.iter(), solution.iter());
+    println!("{:?}", &alu);
+}
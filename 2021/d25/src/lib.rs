@@ -0,0 +1,220 @@
+use std::fmt;
+use std::ops::Deref;
+
+use aoc_core::{AocError, Solver};
+use aoc_grid::{Grid, Pos};
+
+pub type ParseError = &'static str;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Cucumber {
+    Easterly,
+    Southerly,
+}
+
+#[derive(Clone)]
+pub struct Map {
+    locations: Grid<Option<Cucumber>>,
+}
+
+impl Map {
+    pub fn try_from_lines<I, S>(lines: I) -> Result<Map, ParseError>
+    where
+        I: Iterator<Item = S>,
+        S: Deref<Target = str>,
+    {
+        let mut width = 0;
+        let mut locations = Vec::with_capacity(100);
+
+        for l in lines {
+            width = l.len();
+            locations.extend(l.as_bytes().iter().map(|&ch| Cucumber::try_from(ch).ok()));
+        }
+        Ok(Map { locations: Grid::from_vec(width, locations) })
+    }
+
+    pub fn width(&self) -> usize { self.locations.width() }
+    pub fn height(&self) -> usize { self.locations.height() }
+
+    /// The cucumber at `p`, if there is one
+    pub fn get(&self, p: Pos) -> Option<Cucumber> {
+        self.locations.get(p).copied().flatten()
+    }
+
+    /// This map and the ones after each step, numbered from 0. Once the
+    /// herds stop the same map repeats forever.
+    pub fn steps(&self) -> Steps {
+        Steps { map: self.clone(), step: 0, stopped: false }
+    }
+
+    /// The map as in the puzzle text, a row per line
+    pub fn to_ascii(&self) -> String {
+        format!("{:?}", self)
+    }
+
+    /// `{"width":W,"height":H,"east":[[x,y],...],"south":[[x,y],...]}`,
+    /// with each herd's cucumbers in reading order
+    pub fn to_json(&self) -> String {
+        let herd = |h| {
+            let cells: Vec<String> = self.locations.iter()
+                .filter(|(_, &c)| c == Some(h))
+                .map(|(p, _)| format!("[{},{}]", p.x, p.y))
+                .collect();
+            cells.join(",")
+        };
+        format!("{{\"width\":{},\"height\":{},\"east\":[{}],\"south\":[{}]}}",
+            self.width(), self.height(), herd(Cucumber::Easterly), herd(Cucumber::Southerly))
+    }
+
+    pub fn step(&mut self) -> usize {
+        self.step_herd(Cucumber::Easterly) + self.step_herd(Cucumber::Southerly)
+    }
+
+    /// Where a cucumber of `herd` at `pos` would move to, wrapping around
+    /// the edges
+    fn ahead(&self, pos: Pos, herd: Cucumber) -> Pos {
+        match herd {
+            Cucumber::Easterly => Pos::new((pos.x + 1) % self.width(), pos.y),
+            Cucumber::Southerly => Pos::new(pos.x, (pos.y + 1) % self.height()),
+        }
+    }
+
+    pub fn step_herd(&mut self, herd: Cucumber) -> usize {
+        let locations = &self.locations;
+        let mut movements = Vec::with_capacity(locations.cells().len() / 4);
+
+        for (pos, _) in locations.iter().filter(|(_, &c)| c == Some(herd)) {
+            let next = self.ahead(pos, herd);
+            if locations[next].is_none() {
+                movements.push((pos, next));
+            }
+        }
+
+        let locations = &mut self.locations;
+        for &(from, to) in &movements {
+            locations[from] = None;
+            locations[to] = Some(herd);
+        }
+
+        movements.len()
+    }
+}
+
+impl TryFrom<u8> for Cucumber {
+    type Error = ParseError;
+    fn try_from(c: u8) -> Result<Cucumber, ParseError> {
+        match c {
+            b'>' => Ok(Cucumber::Easterly),
+            b'v' => Ok(Cucumber::Southerly),
+            _ => Err("Not a cucumber"),
+        }
+    }
+}
+impl Cucumber {
+    pub fn symbol(&self) -> char {
+        match self {
+            Cucumber::Easterly => '>',
+            Cucumber::Southerly => 'v',
+        }
+    }
+}
+impl fmt::Display for Cucumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+impl fmt::Debug for Map {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let shown = self.locations.map(|c| c.map_or('.', |c| c.symbol()));
+        write!(f, "{}", shown)
+    }
+}
+
+/// Iterator over a map's states, from `Map::steps`
+pub struct Steps {
+    map: Map,
+    step: usize,
+    stopped: bool,
+}
+
+impl Iterator for Steps {
+    type Item = (usize, Map);
+    fn next(&mut self) -> Option<(usize, Map)> {
+        let state = (self.step, self.map.clone());
+        if !self.stopped {
+            self.stopped = self.map.step() == 0;
+        }
+        self.step += 1;
+        Some(state)
+    }
+}
+
+pub fn steps_to_stop(map: &Map) -> usize {
+    let map = &mut map.clone();
+    let mut steps = 0;
+    loop {
+        steps += 1;
+        if map.step() == 0 {
+            return steps;
+        }
+    }
+}
+
+pub struct Puzzle {
+    map: Map,
+}
+
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        Ok(Puzzle { map: Map::try_from_lines(input.lines()).unwrap() })
+    }
+
+    fn part1(&self) -> String {
+        steps_to_stop(&self.map).to_string()
+    }
+
+    /// Day 25 only has the one puzzle
+    fn part2(&self) -> String {
+        "n/a".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The answers given in the puzzle text for its example
+    #[test]
+    fn example_answers() {
+        let puzzle = Puzzle::parse(include_str!("../input_sample"));
+        assert_eq!(puzzle.part1(), "58");
+        assert_eq!(puzzle.part2(), "n/a");
+    }
+
+    #[test]
+    fn steps_match_the_example() {
+        let input = include_str!("../input_sample");
+        let map = Map::try_from_lines(input.lines()).unwrap();
+        let mut steps = map.steps();
+        let start: String = input.lines().map(|l| format!("{}\n", l)).collect();
+        assert_eq!(steps.next().unwrap().1.to_ascii(), start);
+        let (n, first) = steps.next().unwrap();
+        assert_eq!(n, 1);
+        assert_eq!(first.to_ascii(), concat!(
+            "....>.>v.>\n", "v.v>.>v.v.\n", ">v>>..>v..\n", ">>v>v>.>.v\n", ".>v.v...v.\n",
+            "v>>.>vvv..\n", "..v...>>..\n", "vv...>>vv.\n", ">.v.v..v.v\n"));
+
+        let mut states = map.steps().skip(57).map(|(_, m)| m.to_ascii());
+        let stopped = states.next().unwrap();
+        assert_eq!(stopped, concat!(
+            "..>>v>vv..\n", "..v.>>vv..\n", "..>>v>>vv.\n", "..>>>>>vv.\n", "v......>vv\n",
+            "v>v....>>v\n", "vvv.....>>\n", ">vv......>\n", ".>v.vv.v..\n"));
+        assert!(states.take(3).all(|m| m == stopped));
+    }
+
+    #[test]
+    fn json_lists_each_herd() {
+        let map = Map::try_from_lines(["..>v", "v>.."].into_iter()).unwrap();
+        assert_eq!(map.to_json(), r#"{"width":4,"height":2,"east":[[2,0],[1,1]],"south":[[3,0],[0,1]]}"#);
+    }
+}
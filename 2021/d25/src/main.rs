@@ -0,0 +1,91 @@
+use std::fs;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+use aoc_core::frame::{compose, Frame};
+use aoc_core::InputSource;
+
+use d25::*;
+
+fn read_input(source: &InputSource) -> Map {
+    let input = aoc_core::open_input(source);
+    let lines = input.lines().map(|l| l.unwrap());
+    
+    Map::try_from_lines(lines).unwrap()
+}
+
+/// Write the map at each of `wanted` steps to `step-N.txt` (or `.json`)
+/// under `dir`
+fn dump_steps(map: &Map, wanted: &[usize], json: bool, dir: &Path) {
+    let last = wanted.iter().copied().max().unwrap_or(0);
+    for (n, state) in map.steps().take(last + 1).filter(|(n, _)| wanted.contains(n)) {
+        let (name, text) = if json {
+            (format!("step-{}.json", n), state.to_json() + "\n")
+        } else {
+            (format!("step-{}.txt", n), state.to_ascii())
+        };
+        let path = dir.join(name);
+        fs::write(&path, text).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+        println!("wrote {}", path.display());
+    }
+}
+
+fn main() {
+    // --explain shows the starting map next to where the herds end up;
+    // --dump-steps 0,10,57 writes the map at those steps to files, as ASCII
+    // or with --dump-format json as cucumber coordinates, into --dump-dir
+    // (default the current directory)
+    let mut explain_mode = false;
+    let mut dump = None;
+    let mut json = false;
+    let mut dir = PathBuf::from(".");
+    let (input, args) = aoc_core::input_arg();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--explain" => explain_mode = true,
+            "--dump-steps" => {
+                let steps = args.next().expect("--dump-steps needs a list of steps");
+                let steps: Vec<usize> = steps.split(',').map(|n| n.parse().expect("bad step number")).collect();
+                dump = Some(steps);
+            },
+            "--dump-format" => json = match args.next().as_deref() {
+                Some("json") => true,
+                Some("ascii") => false,
+                f => panic!("unknown dump format: {:?}", f),
+            },
+            "--dump-dir" => dir = args.next().expect("--dump-dir needs a directory").into(),
+            _ => panic!("unknown argument: {}", arg),
+        }
+    }
+    let map = read_input(&input);
+
+    if let Some(wanted) = dump {
+        return dump_steps(&map, &wanted, json, &dir);
+    }
+
+    println!("{}x{}", &map.width(), &map.height());
+    if !explain_mode {
+        println!("{:?}", &map);
+    }
+
+    let steps = steps_to_stop(&map);
+    println!("{} steps to stop", steps);
+
+    if explain_mode {
+        let mut stopped = map.clone();
+        while stopped.step() != 0 {}
+        print!("{}", compose(&[
+            Frame::new("start", &format!("{:?}", &map)),
+            Frame::new(&format!("after {} steps", steps), &format!("{:?}", &stopped)),
+        ]));
+    }
+
+    /*
+    for step in 1..=5 {
+        map.step();
+        println!("After step {}", step);
+        println!("{:?}", &map);
+    }
+    */
+}
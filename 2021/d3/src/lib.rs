@@ -0,0 +1,267 @@
+use std::io::BufRead;
+use std::str::FromStr;
+
+use aoc_core::{AocError, Solver};
+
+/// Widest number a report holds, in bits
+pub const MAX_WIDTH: usize = 32;
+
+/// One of the report's numbers, and how many bits it was written with
+struct Reading {
+    value: u32,
+    width: usize,
+}
+
+impl FromStr for Reading {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() > MAX_WIDTH {
+            return Err(AocError::invalid(format!("{} bit numbers, at most {} fit", s.len(), MAX_WIDTH)));
+        }
+        let value = s.chars().try_fold(0u32, |a, c| match c {
+            '0' => Ok(a << 1),
+            '1' => Ok((a << 1) | 1),
+            _ => Err(AocError::invalid(format!("bad bit {:?}", c))),
+        })?;
+        Ok(Reading { value, width: s.len() })
+    }
+}
+
+/// The report's numbers and how many bits each has, going by the first
+/// line
+pub fn read_report(input: &[u8]) -> Result<DiagnosticReport, AocError> {
+    let mut width = None;
+    let mut values = vec!();
+    for (i, reading) in aoc_input::stream_lines::<Reading, _>(input).enumerate() {
+        let reading = reading?;
+        if reading.width == 0 {
+            continue;
+        }
+        let width = *width.get_or_insert(reading.width);
+        if reading.width != width {
+            return Err(AocError::invalid(format!("expected {} bits, got {}", width, reading.width)).at_line(i + 1));
+        }
+        values.push(reading.value);
+    }
+    Ok(DiagnosticReport::from_values(values, width.unwrap_or(0)))
+}
+
+/// `true` for a one
+pub type Bit = bool;
+
+/// The diagnostic report, one binary number per line. The numbers are kept
+/// sorted, so the ones sharing their leading bits with any given number sit
+/// next to each other and narrowing them down by another bit only splits a
+/// range in two.
+pub struct DiagnosticReport {
+    values: Vec<u32>,
+    width: usize,
+}
+
+impl DiagnosticReport {
+    pub fn from_values(mut values: Vec<u32>, width: usize) -> DiagnosticReport {
+        values.sort_unstable();
+        DiagnosticReport { values, width }
+    }
+
+    /// Narrows the report down to a single number, one bit position at a
+    /// time: `criterion` is given the count of ones and zeros at that
+    /// position among the numbers still in the running, and picks the bit
+    /// to keep. A pick that would leave nothing is ignored, so the rating
+    /// is always one of the report's numbers.
+    pub fn rating(&self, criterion: impl Fn(usize, usize) -> Bit) -> u32 {
+        let (mut from, mut to) = (0, self.values.len());
+        for bit in (0..self.width).rev() {
+            if to - from <= 1 {
+                break;
+            }
+            // everything in range agrees above `bit`, so the zeros there
+            // all come before the ones
+            let split = from + self.values[from..to].partition_point(|v| v & 1 << bit == 0);
+            let (ones, zeros) = (to - split, split - from);
+            match criterion(ones, zeros) {
+                true if ones > 0 => from = split,
+                false if zeros > 0 => to = split,
+                _ => {},
+            }
+        }
+
+        self.values[from]
+    }
+
+    /// How many of the numbers have a one in each column, most significant
+    /// first
+    pub fn ones(&self) -> Vec<u64> {
+        (0..self.width).rev()
+            .map(|bit| self.values.iter().filter(|&&v| v & 1 << bit != 0).count() as u64)
+            .collect()
+    }
+
+    /// Gamma and epsilon rates: each column's most common bit, and its least
+    /// common, with ties going to 0 for gamma
+    pub fn gamma_epsilon(&self) -> (u64, u64) {
+        let count = self.values.len() as u64;
+        let gamma = self.ones().iter().fold(0u64, |g, &ones| g << 1 | (ones * 2 > count) as u64);
+        (gamma, gamma ^ ((1 << self.width) - 1))
+    }
+
+    /// Most common bit wins, ties go to 1
+    pub fn oxygen_rating(&self) -> u32 {
+        self.rating(|ones, zeros| ones >= zeros)
+    }
+
+    /// Least common bit wins, ties go to 0
+    pub fn co2_rating(&self) -> u32 {
+        self.rating(|ones, zeros| zeros > ones)
+    }
+}
+
+/// Part 1's answer from one pass over `reader`, counting the ones in each
+/// column as the numbers go by. Part 2 narrows the numbers down bit by bit,
+/// which needs all of them at once, so it gets no answer here.
+pub fn stream<R: BufRead>(reader: R) -> Result<[Option<String>; 2], AocError> {
+    let mut ones = [0u64; MAX_WIDTH];
+    let (mut count, mut width) = (0u64, None);
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| AocError::from(e).at_line(i + 1))?;
+        let bits = line.trim_end().as_bytes();
+        if bits.is_empty() {
+            continue;
+        }
+        let width = *width.get_or_insert(bits.len());
+        if bits.len() != width || width > MAX_WIDTH {
+            return Err(AocError::invalid(format!("expected {} bits, got {}", width.min(MAX_WIDTH), bits.len())).at_line(i + 1));
+        }
+        for (c, &b) in bits.iter().enumerate() {
+            match b {
+                b'0' => {},
+                b'1' => ones[c] += 1,
+                _ => return Err(AocError::invalid(format!("bad bit {:?}", b as char)).at_line(i + 1)),
+            }
+        }
+        count += 1;
+    }
+
+    let width = width.unwrap_or(0);
+    let gamma = (0..width).filter(|&c| ones[c] * 2 > count).fold(0u64, |g, c| g | 1 << (width - 1 - c));
+    let epsilon = gamma ^ ((1 << width) - 1);
+    Ok([Some((gamma * epsilon).to_string()), None])
+}
+
+pub struct Puzzle {
+    report: DiagnosticReport,
+}
+
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        Ok(Puzzle { report: read_report(input.as_bytes())? })
+    }
+
+    fn part1(&self) -> String {
+        let (gamma, epsilon) = self.report.gamma_epsilon();
+        (gamma * epsilon).to_string()
+    }
+
+    fn part2(&self) -> String {
+        (self.report.oxygen_rating() * self.report.co2_rating()).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const SAMPLE: &str = "00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010\n";
+
+    #[test]
+    fn sample_ratings() {
+        let report = read_report(SAMPLE.as_bytes()).unwrap();
+        assert_eq!(report.oxygen_rating(), 23);
+        assert_eq!(report.co2_rating(), 10);
+    }
+
+    #[test]
+    fn streams_part_1() {
+        assert_eq!(stream(SAMPLE.as_bytes()).unwrap(), [Some("198".to_string()), None]);
+        let err = stream("101\n10\n".as_bytes()).err().unwrap();
+        assert_eq!(err.to_string(), "line 2: expected 3 bits, got 2");
+        assert!(stream("121\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn lopsided_columns_count_past_a_byte() {
+        // every number leads with a one, far more than an i8 could count
+        let input: String = (0..200u32).map(|i| format!("1{:011b}\n", i * 7 % 2048)).collect();
+        let puzzle = Puzzle::parse(&input);
+        assert_eq!(puzzle.report.ones()[0], 200);
+        assert_eq!(Some(puzzle.part1()), stream(input.as_bytes()).unwrap()[0]);
+    }
+
+    #[test]
+    fn wide_numbers() {
+        let input = format!("{}\n{}\n", "1".repeat(20), "0".repeat(20));
+        assert_eq!(Puzzle::parse(&input).part1(), "0");
+        let err = Puzzle::try_parse(&"1".repeat(33)).err().unwrap();
+        assert_eq!(err.to_string(), "line 1: 33 bit numbers, at most 32 fit");
+    }
+
+    #[test]
+    fn malformed_lines() {
+        let err = Puzzle::try_parse("0011\n01x1\n").err().unwrap();
+        assert_eq!(err.to_string(), "line 2: bad bit 'x'");
+        let err = Puzzle::try_parse("0011\n\n011\n").err().unwrap();
+        assert_eq!(err.to_string(), "line 3: expected 4 bits, got 3");
+    }
+
+    /// The rating the slow way, filtering the numbers down a bit at a time
+    fn filtered(values: &[u32], width: usize, picks: impl Fn(usize, usize, usize) -> bool) -> u32 {
+        let mut left = values.to_vec();
+        for (i, bit) in (0..width).rev().enumerate() {
+            if left.len() == 1 {
+                break;
+            }
+            let ones = left.iter().filter(|&&v| v & 1 << bit != 0).count();
+            let pick = picks(i, ones, left.len() - ones);
+            if left.iter().any(|&v| (v & 1 << bit != 0) == pick) {
+                left.retain(|&v| (v & 1 << bit != 0) == pick);
+            }
+        }
+        left[0]
+    }
+
+    fn values() -> impl Strategy<Value = (Vec<u32>, usize)> {
+        (1..=12usize).prop_flat_map(|bits| (prop::collection::vec(0..1u32 << bits, 1..64), Just(bits)))
+    }
+
+    proptest! {
+        #[test]
+        fn ratings_match_filtering((values, bits) in values()) {
+            let report = DiagnosticReport::from_values(values.clone(), bits);
+            prop_assert_eq!(report.oxygen_rating(), filtered(&values, bits, |_, ones, zeros| ones >= zeros));
+            prop_assert_eq!(report.co2_rating(), filtered(&values, bits, |_, ones, zeros| zeros > ones));
+        }
+
+        #[test]
+        fn any_criterion_comes_from_the_report((values, bits) in values(), picks in prop::collection::vec(any::<bool>(), 12)) {
+            let report = DiagnosticReport::from_values(values.clone(), bits);
+            let calls = std::cell::Cell::new(0);
+            let rating = report.rating(|_, _| {
+                let i = calls.get();
+                calls.set(i + 1);
+                picks[i]
+            });
+            prop_assert!(values.contains(&rating));
+            prop_assert_eq!(rating, filtered(&values, bits, |i, _, _| picks[i]));
+        }
+    }
+
+    /// The answers given in the puzzle text for its example
+    #[test]
+    fn example_answers() {
+        let puzzle = Puzzle::parse(include_str!("../test_input"));
+        assert_eq!(puzzle.part1(), "198");
+        assert_eq!(puzzle.part2(), "230");
+    }
+}
@@ -0,0 +1,43 @@
+use std::io::Read;
+
+use d3::*;
+
+fn part1(report: &DiagnosticReport) {
+    println!("{:?}", report.ones());
+
+    let (gamma, epsilon) = report.gamma_epsilon();
+    println!("gamma: {}, eps: {}", gamma, epsilon);
+
+    let consumption = gamma * epsilon;
+    println!("{}", consumption);
+
+}
+
+fn part2(report: &DiagnosticReport) {
+
+    let oxygen = report.oxygen_rating();
+    println!("{:?}", oxygen);
+
+    let co2 = report.co2_rating();
+    println!("{:?}", co2);
+
+    println!("{}", oxygen * co2)
+}
+
+fn main() {
+    let (input, parts, args) = aoc_core::input_and_parts();
+    if let Some(arg) = args.first() {
+        panic!("unknown argument: {}", arg);
+    }
+    let mut text = Vec::new();
+    aoc_core::open_input(&input).read_to_end(&mut text).unwrap();
+
+    let report = read_report(&text).unwrap();
+
+    if parts.has(1) {
+        part1(&report);
+    }
+    if parts.has(2) {
+        part2(&report);
+    }
+}
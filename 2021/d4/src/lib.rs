@@ -0,0 +1,263 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{BufReader, Read};
+
+use aoc_core::{AocError, Solver};
+use aoc_input::Block;
+
+mod tournament;
+pub use tournament::{shuffled_orders, BoardStats, MarkIndex, Standings, Tournament};
+
+pub type BingoCell = u8;
+
+#[derive(Copy,Clone)]
+pub struct BingoBoard {
+    values: [BingoCell; 25],
+}
+
+pub struct BingoBoardState {
+    pub board: BingoBoard,
+    pub stamps: [bool; 25],
+    pub bingo: Option<BingoCell>,
+}
+
+impl BingoBoard {
+    /// A board from a block of five rows of five numbers
+    pub fn from_block(block: &Block) -> Result<BingoBoard, AocError> {
+        if block.lines.len() != 5 {
+            return Err(AocError::invalid("a board has 5 rows").at_line(block.line));
+        }
+        let rows = block.parse_each(|row| {
+            let row: Vec<BingoCell> = row.split_whitespace().map(str::parse).collect::<Result<_, _>>()?;
+            <[BingoCell; 5]>::try_from(row).map_err(|_| AocError::invalid("a row has 5 numbers"))
+        })?;
+
+        Ok(BingoBoard { values: rows.concat().try_into().unwrap() })
+    }
+}
+
+impl fmt::Display for BingoBoard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for r in 0..5 {
+            writeln!(f, "{:?}", &self.values[r*5..(r+1)*5])?
+        }
+        Ok(())
+    }
+}
+
+impl BingoBoardState {
+    pub fn from_board(board: BingoBoard) -> BingoBoardState {
+        BingoBoardState { board, stamps: [false; 25], bingo: None }
+    }
+
+    pub fn try_mark_value(&mut self, value: BingoCell) -> Option<(usize, usize, bool)> {
+        if self.bingo.is_some() {
+            return Some((0, 0, true));
+        }
+        for r in 0..5 {
+            for c in 0..5 {
+                let i = r * 5 + c;
+                if self.board.values[i] == value {
+                    self.stamps[i] = true;
+
+                    let bingo = self.stamps[r*5..r*5+5].iter().all(|b| *b)
+                        || [self.stamps[c], self.stamps[c + 5], self.stamps[c + 10], self.stamps[c + 15], self.stamps[c + 20]].iter().all(|b| *b);
+
+                    if bingo {
+                        self.bingo = Some(value);
+                    }
+                    return Some((r, c, bingo));
+                }
+            }
+        }
+        None
+    }
+
+    pub fn score(&self) -> u32 {
+        let sum_uncalled: u32 = self.board.values.iter()
+            .zip(self.stamps)
+            .filter(|(_, stamped)| !*stamped)
+            .map(|(v, _)| *v as u32)
+            .sum();
+        sum_uncalled * (self.bingo.unwrap() as u32)
+    }
+}
+
+impl fmt::Display for BingoBoardState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for r in 0..5 {
+            for c in 0..5 {
+                let i = r * 5 + c;
+                let v = self.board.values[i];
+                if self.stamps[i] {
+                    write!(f, "|{:2}| ", v)?
+                }
+                else {
+                    write!(f, " {:2}  ", v)?
+                }
+            }
+            writeln!(f)?
+        }
+        Ok(())
+    }
+}
+
+/// The calls on the first line, then the boards, with blank lines between
+pub fn read_input<R: Read>(rdr: R) -> Result<(Vec<BingoCell>, Vec<BingoBoard>), AocError> {
+    let blocks = aoc_input::split_blank_blocks(BufReader::new(rdr))?;
+    let Some((calls, boards)) = blocks.split_first() else {
+        return Err(AocError::invalid("no calls"));
+    };
+
+    let calls = calls.parse_each(aoc_input::parse_csv_line)?.concat();
+    let boards = boards.iter().map(BingoBoard::from_block).collect::<Result<_, _>>()?;
+
+    Ok((calls, boards))
+}
+
+pub fn new_game(base_boards: &[BingoBoard]) -> Vec<BingoBoardState> {
+    base_boards
+        .iter()
+        .map(|b| BingoBoardState::from_board(*b))
+        .collect()
+}
+
+/// Something that happened while playing a game
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum GameEvent {
+    NumberCalled(BingoCell),
+    /// `cell` is the (row, column) that was stamped
+    BoardMarked { board: usize, cell: (usize, usize) },
+    BoardWon { board: usize, score: u32 },
+}
+
+/// Play-by-play of a game: each call is followed by the marks it made and
+/// any boards it won. Boards stop taking part once they've won.
+pub struct Game<'a> {
+    calls: std::slice::Iter<'a, BingoCell>,
+    boards: Vec<BingoBoardState>,
+    pending: VecDeque<GameEvent>,
+}
+
+impl<'a> Game<'a> {
+    pub fn new(calls: &'a [BingoCell], base_boards: &[BingoBoard]) -> Game<'a> {
+        Game { calls: calls.iter(), boards: new_game(base_boards), pending: VecDeque::new() }
+    }
+
+    /// Boards as they stand after the events yielded so far
+    pub fn boards(&self) -> &[BingoBoardState] {
+        &self.boards
+    }
+
+    fn call(&mut self, call: BingoCell) {
+        self.pending.push_back(GameEvent::NumberCalled(call));
+        for (b, board) in self.boards.iter_mut().enumerate().filter(|(_, b)| b.bingo.is_none()) {
+            if let Some((r, c, bingo)) = board.try_mark_value(call) {
+                self.pending.push_back(GameEvent::BoardMarked { board: b, cell: (r, c) });
+                if bingo {
+                    self.pending.push_back(GameEvent::BoardWon { board: b, score: board.score() });
+                }
+            }
+        }
+    }
+}
+
+impl Iterator for Game<'_> {
+    type Item = GameEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pending.is_empty() {
+            let &call = self.calls.next()?;
+            self.call(call);
+        }
+        self.pending.pop_front()
+    }
+}
+
+/// Score of every board in the order they win.
+pub fn winning_scores(calls: &[BingoCell], base_boards: &[BingoBoard]) -> Vec<u32> {
+    Game::new(calls, base_boards)
+        .filter_map(|e| match e {
+            GameEvent::BoardWon { score, .. } => Some(score),
+            _ => None,
+        })
+        .collect()
+}
+
+pub struct Puzzle {
+    calls: Vec<BingoCell>,
+    boards: Vec<BingoBoard>,
+}
+
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        let (calls, boards) = read_input(input.as_bytes())?;
+        Ok(Puzzle { calls, boards })
+    }
+
+    fn part1(&self) -> String {
+        winning_scores(&self.calls, &self.boards).first().unwrap().to_string()
+    }
+
+    fn part2(&self) -> String {
+        winning_scores(&self.calls, &self.boards).last().unwrap().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_stream() {
+        let (calls, boards) = read_input(include_str!("../test_input").as_bytes()).unwrap();
+        let events: Vec<GameEvent> = Game::new(&calls, &boards).collect();
+
+        assert_eq!(events[..4], [
+            GameEvent::NumberCalled(7),
+            GameEvent::BoardMarked { board: 0, cell: (2, 4) },
+            GameEvent::BoardMarked { board: 1, cell: (2, 2) },
+            GameEvent::BoardMarked { board: 2, cell: (4, 4) },
+        ]);
+
+        let wins: Vec<GameEvent> = events.into_iter()
+            .filter(|e| matches!(e, GameEvent::BoardWon { .. }))
+            .collect();
+        assert_eq!(wins, [
+            GameEvent::BoardWon { board: 2, score: 4512 },
+            GameEvent::BoardWon { board: 0, score: 2192 },
+            GameEvent::BoardWon { board: 1, score: 1924 },
+        ]);
+    }
+
+    #[test]
+    fn boards_draw_as_golden() {
+        let (calls, boards) = read_input(include_str!("../test_input").as_bytes()).unwrap();
+        let mut states = new_game(&boards);
+        // the third board wins on the twelfth call
+        for &call in &calls[..12] {
+            for state in &mut states {
+                state.try_mark_value(call);
+            }
+        }
+        let drawn: Vec<String> = states.iter().map(|s| s.to_string()).collect();
+        aoc_core::golden::check(env!("CARGO_MANIFEST_DIR"), "first_win", &drawn.join("\n"));
+    }
+
+    #[test]
+    fn bad_input_names_its_line() {
+        let input = include_str!("../test_input");
+        let err = |input: &str| Puzzle::try_parse(input).err().unwrap().to_string();
+        assert_eq!(err(&input.replacen("7,4,9", "7,x,9", 1)), "line 1: value 2: bad number: invalid digit found in string");
+        assert_eq!(err(&input.replacen("\n 8  2 23  4 24", "\n 8  2 23  4", 1)), "line 4: a row has 5 numbers");
+        assert_eq!(err("1,2\n\n1 2 3 4 5\n"), "line 3: a board has 5 rows");
+    }
+
+    /// The answers given in the puzzle text for its example
+    #[test]
+    fn example_answers() {
+        let puzzle = Puzzle::parse(include_str!("../test_input"));
+        assert_eq!(puzzle.part1(), "4512");
+        assert_eq!(puzzle.part2(), "1924");
+    }
+}
@@ -0,0 +1,105 @@
+use aoc_core::frame::{compose, Frame};
+use aoc_core::rng::Rng;
+
+use d4::*;
+
+fn p1(calls: Vec<BingoCell>, base_boards: Vec<BingoBoard>) {
+    let mut boards = new_game(&base_boards);
+
+    let mut winner = None;
+    for call in calls {
+
+        for (b, board) in boards.iter_mut().enumerate() {
+            if let Some((_, _, true)) = board.try_mark_value(call) {
+                winner = Some((b, call));
+            }
+        }
+
+        if let Some((b, call)) = winner {
+            println!("BINGO on board {}: {}\n", b, call);
+            break;
+        }
+    }
+
+    for board in &boards {
+        println!("{}", board);
+    }
+
+    let (winning_board, _winning_call) = winner.unwrap();
+
+    let score = &boards[winning_board].score();
+    println!("{}", score);
+}
+
+fn p2(calls: Vec<BingoCell>, base_boards: Vec<BingoBoard>) {
+    let mut game = Game::new(&calls, &base_boards);
+
+    let mut first = None;
+    let mut last = None;
+    while let Some(event) = game.next() {
+        if let GameEvent::BoardWon { board, score } = event {
+            let last_call = game.boards()[board].bingo.unwrap();
+            println!("BINGO on board {}: {}", board, last_call);
+            println!("score: {}", score);
+
+            let frame = Frame::new(&format!("board {}: {}", board, score), &game.boards()[board].to_string());
+            if first.is_none() {
+                first = Some(frame);
+            } else {
+                last = Some(frame);
+            }
+        }
+    }
+
+    let frames: Vec<Frame> = first.into_iter().chain(last).collect();
+    print!("{}", compose(&frames));
+}
+
+/// Each board's record over `games` shuffles of the calls
+fn tournament(calls: &[BingoCell], boards: &[BingoBoard], games: usize, seed: u64) {
+    let mut rng = Rng::seed_from(seed);
+    let standings = Tournament::new(boards).run(shuffled_orders(calls, games, &mut rng));
+    println!("board | first  | last  | won   | mean calls to win");
+    for (b, stats) in standings.boards.iter().enumerate() {
+        let mean = stats.mean_calls_to_win().map_or("-".to_string(), |m| format!("{:.1}", m));
+        println!("{:5} | {:5.1}% | {:5} | {:5} | {}", b, standings.win_rate(b) * 100.0, stats.lasts, stats.wins, mean);
+    }
+}
+
+fn main() {
+    // --tournament <games> plays the boards against that many shuffles of
+    // the calls, from --seed <n>, and reports how each board did
+    let (input, parts, args) = aoc_core::input_and_parts();
+    let mut games = None;
+    let mut seed = 4;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--tournament" => games = args.next().and_then(|n| n.parse::<usize>().ok()),
+            "--seed" => seed = args.next().and_then(|n| n.parse::<u64>().ok()).expect("--seed needs a number"),
+            _ => panic!("unknown argument: {}", arg),
+        }
+    }
+    let (calls, base_boards) = aoc_core::or_exit(read_input(aoc_core::open_input(&input)));
+
+    if let Some(games) = games {
+        tournament(&calls, &base_boards, games, seed);
+        return;
+    }
+
+    println!("{:?}", calls);
+    println!();
+
+    /*
+    for board in &base_boards {
+        println!("{}", board);
+    }
+    */
+
+    if parts.has(1) {
+        p1(calls.clone(), base_boards.clone());
+    }
+    if parts.has(2) {
+        p2(calls, base_boards);
+    }
+}
@@ -0,0 +1,186 @@
+//! The same boards played against many call orders, to see which boards
+//! tend to win first and which hold out longest. Every game starts from
+//! the same index of where each number sits, so a call marks only the
+//! cells that hold it, and each board's marks are a bitboard checked
+//! against the ten winning lines at once.
+
+use aoc_core::rng::Rng;
+
+use crate::{BingoBoard, BingoCell};
+
+/// Bits of the cells in each row and column, cell `r * 5 + c` being bit
+/// `r * 5 + c`
+const LINES: [u32; 10] = {
+    let mut lines = [0; 10];
+    let mut i = 0;
+    while i < 5 {
+        lines[i] = 0b11111 << (i * 5);
+        lines[5 + i] = 0b00001_00001_00001_00001_00001 << i;
+        i += 1;
+    }
+    lines
+};
+
+fn has_line(marks: u32) -> bool {
+    // a line with no unmarked cells
+    LINES.iter().any(|&line| line & !marks == 0)
+}
+
+/// Every (board, cell) each number appears in
+pub struct MarkIndex {
+    cells: Vec<Vec<(usize, u8)>>,
+}
+
+impl MarkIndex {
+    pub fn new(boards: &[BingoBoard]) -> MarkIndex {
+        let mut cells = vec![vec!(); BingoCell::MAX as usize + 1];
+        for (b, board) in boards.iter().enumerate() {
+            for (i, &value) in board.values.iter().enumerate() {
+                cells[value as usize].push((b, i as u8));
+            }
+        }
+        MarkIndex { cells }
+    }
+
+    pub fn cells(&self, value: BingoCell) -> &[(usize, u8)] {
+        &self.cells[value as usize]
+    }
+}
+
+/// How one board did over every game of a tournament
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct BoardStats {
+    /// Games it was the first to win
+    pub firsts: usize,
+    /// Games it was the last to win
+    pub lasts: usize,
+    /// Games it won at all before the calls ran out
+    pub wins: usize,
+    /// Calls it took to win, summed over the games it won
+    pub calls_to_win: usize,
+}
+
+impl BoardStats {
+    pub fn mean_calls_to_win(&self) -> Option<f64> {
+        (self.wins > 0).then(|| self.calls_to_win as f64 / self.wins as f64)
+    }
+}
+
+/// Each board's record over `games` games
+#[derive(Clone, PartialEq, Debug)]
+pub struct Standings {
+    pub games: usize,
+    pub boards: Vec<BoardStats>,
+}
+
+impl Standings {
+    /// Share of the games `board` won first
+    pub fn win_rate(&self, board: usize) -> f64 {
+        self.boards[board].firsts as f64 / self.games as f64
+    }
+}
+
+pub struct Tournament<'a> {
+    boards: &'a [BingoBoard],
+    index: MarkIndex,
+}
+
+impl<'a> Tournament<'a> {
+    pub fn new(boards: &'a [BingoBoard]) -> Tournament<'a> {
+        Tournament { boards, index: MarkIndex::new(boards) }
+    }
+
+    /// Boards in the order they win with `calls`, with how many calls each
+    /// took. Boards winning on the same call go in board order, the way
+    /// `winning_scores` has them.
+    pub fn play(&self, calls: &[BingoCell]) -> Vec<(usize, usize)> {
+        let mut marks = vec![0u32; self.boards.len()];
+        let mut won = vec![false; self.boards.len()];
+        let mut winners = Vec::with_capacity(self.boards.len());
+        for (turn, &call) in calls.iter().enumerate() {
+            let first = winners.len();
+            for &(b, cell) in self.index.cells(call) {
+                if won[b] {
+                    continue;
+                }
+                marks[b] |= 1 << cell;
+                if has_line(marks[b]) {
+                    won[b] = true;
+                    winners.push((b, turn + 1));
+                }
+            }
+            winners[first..].sort_unstable();
+            if winners.len() == self.boards.len() {
+                break;
+            }
+        }
+        winners
+    }
+
+    /// Plays every call order in `orders` and tallies up how each board did
+    pub fn run<C: AsRef<[BingoCell]>>(&self, orders: impl IntoIterator<Item = C>) -> Standings {
+        let mut standings = Standings { games: 0, boards: vec![BoardStats::default(); self.boards.len()] };
+        for calls in orders {
+            let winners = self.play(calls.as_ref());
+            standings.games += 1;
+            if let (Some(&(first, _)), Some(&(last, _))) = (winners.first(), winners.last()) {
+                standings.boards[first].firsts += 1;
+                standings.boards[last].lasts += 1;
+            }
+            for (b, calls) in winners {
+                standings.boards[b].wins += 1;
+                standings.boards[b].calls_to_win += calls;
+            }
+        }
+        standings
+    }
+}
+
+/// `games` shuffles of `calls`
+pub fn shuffled_orders<'c>(calls: &'c [BingoCell], games: usize, rng: &'c mut Rng) -> impl Iterator<Item = Vec<BingoCell>> + 'c {
+    (0..games).map(move |_| {
+        let mut order = calls.to_vec();
+        rng.shuffle(&mut order);
+        order
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{read_input, Game, GameEvent};
+
+    #[test]
+    fn lines_cover_rows_and_columns() {
+        assert_eq!(LINES[0], 0b11111);
+        assert_eq!(LINES[9], 1 << 4 | 1 << 9 | 1 << 14 | 1 << 19 | 1 << 24);
+        assert!(!has_line(0b11110 | 1 << 5 | 1 << 10 | 1 << 15));
+        assert!(has_line(1 << 1 | 1 << 6 | 1 << 11 | 1 << 16 | 1 << 21));
+    }
+
+    #[test]
+    fn plays_like_the_game() {
+        let (calls, boards) = read_input(include_str!("../test_input").as_bytes()).unwrap();
+        let tournament = Tournament::new(&boards);
+        let mut rng = Rng::seed_from(4);
+        let orders: Vec<Vec<BingoCell>> = std::iter::once(calls.clone()).chain(shuffled_orders(&calls, 20, &mut rng)).collect();
+        for order in &orders {
+            let won: Vec<usize> = Game::new(order, &boards)
+                .filter_map(|e| match e {
+                    GameEvent::BoardWon { board, .. } => Some(board),
+                    _ => None,
+                })
+                .collect();
+            let played: Vec<usize> = tournament.play(order).into_iter().map(|(b, _)| b).collect();
+            assert_eq!(played, won);
+        }
+        assert_eq!(tournament.play(&calls), [(2, 12), (0, 14), (1, 15)]);
+
+        let standings = tournament.run(&orders);
+        assert_eq!(standings.games, 21);
+        assert_eq!(standings.boards.iter().map(|s| s.firsts).sum::<usize>(), 21);
+        assert_eq!(standings.boards.iter().map(|s| s.lasts).sum::<usize>(), 21);
+        assert!(standings.boards.iter().all(|s| s.wins == 21));
+        assert!((0..3).map(|b| standings.win_rate(b)).sum::<f64>() > 0.999);
+    }
+}
@@ -0,0 +1,409 @@
+use std::cmp;
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+
+use aoc_core::{AocError, Solver};
+
+mod sweep;
+pub use sweep::count_overlaps_sweep;
+
+pub type Height = i8;
+
+#[derive(Clone, Copy)]
+pub struct Vent {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Clone, Copy)]
+pub struct VentInput {
+    pub a: Vent,
+    pub b: Vent,
+}
+
+pub struct SeaFloor {
+    floor: Vec<Vec<Height>>,
+    x_dim: usize,
+    y_dim: usize,
+    overlaps: usize,
+}
+
+impl Vent {
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Vent, AocError> {
+        let (x, y) = s.split_once(',').ok_or("expected x,y")?;
+        Ok(Vent {
+            x: x.parse::<i32>()?,
+            y: y.parse::<i32>()?,
+        })
+    }
+}
+
+impl VentInput {
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<VentInput, AocError> {
+        let (a, b) = s.split_once(" -> ").ok_or("expected x1,y1 -> x2,y2")?;
+        let a = Vent::from_str(a)?;
+        let b = Vent::from_str(b)?;
+
+        Ok(VentInput { a, b })
+    }
+
+    pub fn is_axis_aligned(&self) -> bool {
+        self.a.x == self.b.x || self.a.y == self.b.y
+    }
+
+    pub fn iter(&self) -> VentIter {
+        let dx = match self.b.x - self.a.x {
+            0 => 0,
+            d if d < 0 => -1,
+            _ => 1,
+        };
+        let dy = match self.b.y - self.a.y {
+            0 => 0,
+            d if d < 0 => -1,
+            _ => 1,
+        };
+
+        VentIter {
+            x: self.a.x as isize, y: self.a.y as isize,
+            dx, dy,
+            len: (cmp::max((self.b.x - self.a.x).abs(), (self.b.y - self.a.y).abs()) + 1) as isize,
+            i: 0,
+        }
+    }
+
+    pub fn apply_to_map(&self, map: &mut [Vec<Height>]) {
+        for (x, y) in self.iter() {
+            map[y][x] += 1;
+        }
+    }
+}
+
+pub struct VentIter {
+    x: isize,
+    y: isize,
+    dx: isize,
+    dy: isize,
+    len: isize,
+    i: isize,
+}
+
+impl Iterator for VentIter {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.i >= self.len {
+            return None;
+        }
+        let r = Some((self.x as usize, self.y as usize));
+        self.i += 1;
+        self.x += self.dx;
+        self.y += self.dy;
+        r
+    }
+}
+
+impl SeaFloor {
+    pub fn from_lines(lines: &[VentInput], (x_dim, y_dim): (usize, usize)) -> SeaFloor {
+        let mut floor = SeaFloor { floor: vec!(vec!(0i8; x_dim); y_dim), x_dim, y_dim, overlaps: 0 };
+
+        for l in lines {
+            floor.add_line(l);
+        }
+
+        floor
+    }
+
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.x_dim, self.y_dim)
+    }
+
+    /// Number of vent lines covering (x, y)
+    pub fn height_at(&self, x: usize, y: usize) -> Height {
+        self.floor.get(y).and_then(|row| row.get(x)).copied().unwrap_or(0)
+    }
+
+    /// Add a single vent line, growing the map if it reaches past the
+    /// current dimensions
+    pub fn add_line(&mut self, line: &VentInput) {
+        let x_dim = cmp::max(line.a.x, line.b.x) as usize + 1;
+        let y_dim = cmp::max(line.a.y, line.b.y) as usize + 1;
+        self.grow(x_dim, y_dim);
+
+        for (x, y) in line.iter() {
+            let h = &mut self.floor[y][x];
+            *h = h.saturating_add(1);
+            if *h == 2 {
+                self.overlaps += 1;
+            }
+        }
+    }
+
+    /// Remove a vent line previously added. Returns false, leaving the map
+    /// untouched, if the line isn't fully present or crosses a cell with
+    /// too many lines to have counted them all.
+    pub fn remove_line(&mut self, line: &VentInput) -> bool {
+        if line.iter().any(|(x, y)| matches!(self.height_at(x, y), 0 | Height::MAX)) {
+            return false;
+        }
+
+        for (x, y) in line.iter() {
+            let h = &mut self.floor[y][x];
+            if *h == 2 {
+                self.overlaps -= 1;
+            }
+            *h -= 1;
+        }
+        true
+    }
+
+    fn grow(&mut self, x_dim: usize, y_dim: usize) {
+        if x_dim > self.x_dim {
+            self.x_dim = x_dim;
+            for row in self.floor.iter_mut() {
+                row.resize(x_dim, 0);
+            }
+        }
+        if y_dim > self.y_dim {
+            self.y_dim = y_dim;
+            self.floor.resize(y_dim, vec!(0i8; self.x_dim));
+        }
+    }
+
+    pub fn count_overlaps(&self) -> usize {
+        self.overlaps
+    }
+}
+
+impl fmt::Display for SeaFloor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in &self.floor {
+            for col in row {
+                match col {
+                    0 => write!(f, ".")?,
+                    h => write!(f, "{}", h)?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Cells that two or more vents cover, counting only the cells vents
+/// touch rather than the whole floor
+pub fn count_overlaps_hashed(vents: &[VentInput]) -> usize {
+    let mut heights = HashMap::new();
+    let mut overlaps = 0;
+    for v in vents {
+        add_hashed(&mut heights, &mut overlaps, v);
+    }
+    overlaps
+}
+
+fn add_hashed(heights: &mut HashMap<(usize, usize), Height>, overlaps: &mut usize, line: &VentInput) {
+    for (x, y) in line.iter() {
+        let h = heights.entry((x, y)).or_insert(0);
+        *h = h.saturating_add(1);
+        if *h == 2 {
+            *overlaps += 1;
+        }
+    }
+}
+
+/// Largest floor `Puzzle` counts on a dense map, in cells. Anything bigger
+/// is counted with the sweep instead.
+pub const DENSE_LIMIT: usize = 1 << 26;
+
+/// Each vent line in `reader` as it's read
+pub fn vent_lines(reader: impl io::BufRead) -> impl Iterator<Item = Result<VentInput, AocError>> {
+    reader.lines()
+        .enumerate()
+        .map(|(i, l)| VentInput::from_str(l?.trim_end()).map_err(|e| e.at_line(i + 1)))
+}
+
+pub fn read_input(reader: impl io::BufRead) -> Result<(Vec<VentInput>, usize, usize), AocError> {
+    let mut lines = vec!();
+    let mut x_dim: usize = 0;
+    let mut y_dim: usize = 0;
+
+    for input in vent_lines(reader) {
+        let input = input?;
+        lines.push(input);
+
+        x_dim = cmp::max(x_dim, (cmp::max(input.a.x, input.b.x) + 1) as usize);
+        y_dim = cmp::max(y_dim, (cmp::max(input.a.y, input.b.y) + 1) as usize);
+    }
+
+    Ok((lines, x_dim, y_dim))
+}
+
+/// A floor for `stream`: dense while it fits in `DENSE_LIMIT` cells, then
+/// only the cells lines touch, with the overlaps counted so far
+enum StreamFloor {
+    Dense(SeaFloor),
+    Sparse(HashMap<(usize, usize), Height>, usize),
+}
+
+impl StreamFloor {
+    fn add_line(&mut self, line: &VentInput) {
+        if let StreamFloor::Dense(floor) = self {
+            let x_dim = cmp::max(floor.x_dim, cmp::max(line.a.x, line.b.x) as usize + 1);
+            let y_dim = cmp::max(floor.y_dim, cmp::max(line.a.y, line.b.y) as usize + 1);
+            if x_dim.saturating_mul(y_dim) <= DENSE_LIMIT {
+                return floor.add_line(line);
+            }
+            let heights = floor.floor.iter().enumerate()
+                .flat_map(|(y, row)| row.iter().enumerate().filter(|(_, &h)| h > 0).map(move |(x, &h)| ((x, y), h)))
+                .collect();
+            *self = StreamFloor::Sparse(heights, floor.overlaps);
+        }
+        if let StreamFloor::Sparse(heights, overlaps) = self {
+            add_hashed(heights, overlaps, line);
+        }
+    }
+
+    fn count_overlaps(&self) -> usize {
+        match self {
+            StreamFloor::Dense(floor) => floor.count_overlaps(),
+            StreamFloor::Sparse(_, overlaps) => *overlaps,
+        }
+    }
+}
+
+/// Both parts' answers from one pass over `reader`, drawing each line onto
+/// a floor for each part and letting it go. The floors grow to fit the
+/// lines, so memory goes with how far the lines reach rather than how many
+/// there are, until they'd pass `DENSE_LIMIT` and keep only the cells the
+/// lines cover.
+pub fn stream(reader: impl io::BufRead) -> Result<[Option<String>; 2], AocError> {
+    let mut aligned = StreamFloor::Dense(SeaFloor::from_lines(&[], (0, 0)));
+    let mut all = StreamFloor::Dense(SeaFloor::from_lines(&[], (0, 0)));
+    for line in vent_lines(reader) {
+        let line = line?;
+        if line.is_axis_aligned() {
+            aligned.add_line(&line);
+        }
+        all.add_line(&line);
+    }
+    Ok([aligned, all].map(|floor| Some(floor.count_overlaps().to_string())))
+}
+
+pub struct Puzzle {
+    lines: Vec<VentInput>,
+    dim: (usize, usize),
+}
+
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        let (lines, x_dim, y_dim) = read_input(input.as_bytes())?;
+        Ok(Puzzle { lines, dim: (x_dim, y_dim) })
+    }
+
+    fn part1(&self) -> String {
+        let lines: Vec<VentInput> = self.lines.iter().copied().filter(|l| l.is_axis_aligned()).collect();
+        self.count_overlaps(&lines).to_string()
+    }
+
+    fn part2(&self) -> String {
+        self.count_overlaps(&self.lines).to_string()
+    }
+}
+
+impl Puzzle {
+    fn count_overlaps(&self, lines: &[VentInput]) -> usize {
+        if self.dim.0.saturating_mul(self.dim.1) > DENSE_LIMIT {
+            count_overlaps_sweep(lines)
+        } else {
+            SeaFloor::from_lines(lines, self.dim).count_overlaps()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_vent_line() {
+        let s = "0,9 -> 5,9";
+        let line = VentInput::from_str(s).unwrap();
+
+        assert_eq!(line.a.x, 0);
+        assert_eq!(line.a.y, 9);
+        assert_eq!(line.b.x, 5);
+        assert_eq!(line.b.y, 9);
+
+        assert!(VentInput::from_str("0,9 5,9").is_err());
+        let err = read_input("0,9 -> 5,9\n8,0 -> 0,x\n".as_bytes()).err().unwrap();
+        assert!(err.to_string().starts_with("line 2: bad number"));
+    }
+
+    #[test]
+    fn incremental_updates_match_rebuild() {
+        let (lines, x_dim, y_dim) = read_input(include_str!("../test_input").as_bytes()).unwrap();
+        let full = SeaFloor::from_lines(&lines, (x_dim, y_dim));
+        assert_eq!(full.count_overlaps(), 12);
+
+        let mut map = SeaFloor::from_lines(&[], (0, 0));
+        for l in &lines {
+            map.add_line(l);
+        }
+        assert_eq!(map.dimensions(), (x_dim, y_dim));
+        assert_eq!(map.count_overlaps(), 12);
+
+        for (i, l) in lines.iter().enumerate() {
+            assert!(map.remove_line(l));
+            let mut rest = lines.clone();
+            rest.remove(i);
+            let rebuilt = SeaFloor::from_lines(&rest, (x_dim, y_dim));
+            assert_eq!(map.count_overlaps(), rebuilt.count_overlaps());
+            map.add_line(l);
+        }
+
+        let stray = VentInput::from_str("0,0 -> 0,3").unwrap();
+        assert!(!map.remove_line(&stray));
+        assert_eq!(map.count_overlaps(), 12);
+
+        // a cell too deep to count stays an overlap for good
+        let mut map = SeaFloor::from_lines(&vec![stray; 200], (0, 0));
+        assert_eq!(map.height_at(0, 2), Height::MAX);
+        assert!(!map.remove_line(&stray));
+        assert_eq!(map.count_overlaps(), 4);
+    }
+
+    /// The puzzle's two diagrams of the example
+    #[test]
+    fn floors_draw_as_golden() {
+        let (lines, x_dim, y_dim) = read_input(include_str!("../test_input").as_bytes()).unwrap();
+        let aligned: Vec<VentInput> = lines.iter().copied().filter(|l| l.is_axis_aligned()).collect();
+        let dir = env!("CARGO_MANIFEST_DIR");
+        aoc_core::golden::check(dir, "aligned", &SeaFloor::from_lines(&aligned, (x_dim, y_dim)).to_string());
+        aoc_core::golden::check(dir, "all", &SeaFloor::from_lines(&lines, (x_dim, y_dim)).to_string());
+    }
+
+    #[test]
+    fn huge_floors_are_swept() {
+        let puzzle = Puzzle::parse("0,0 -> 3000000,3000000\n3000000,0 -> 0,3000000\n1500000,0 -> 1500000,2000000\n");
+        assert_eq!(puzzle.part1(), "0");
+        assert_eq!(puzzle.part2(), "1");
+
+        // the first line fits a dense floor, the second takes it past the limit
+        let input = "0,0 -> 9999,0\n9999,9999 -> 9999,0\n0,0 -> 9999,9999\n";
+        let answers = stream(input.as_bytes()).unwrap();
+        assert_eq!(answers, [Some("1".to_string()), Some("3".to_string())]);
+    }
+
+    /// The answers given in the puzzle text for its example
+    #[test]
+    fn example_answers() {
+        let puzzle = Puzzle::parse(include_str!("../test_input"));
+        assert_eq!(puzzle.part1(), "5");
+        assert_eq!(puzzle.part2(), "12");
+
+        let answers = stream(include_str!("../test_input").as_bytes()).unwrap();
+        assert_eq!(answers, [Some("5".to_string()), Some("12".to_string())]);
+    }
+}
@@ -0,0 +1,39 @@
+use std::path::PathBuf;
+
+use aoc_render::Raster;
+use d5::*;
+
+fn main() {
+    // --render <path> draws the vents, brighter where more lines overlap
+    let mut render: Option<PathBuf> = None;
+    let (path, args) = aoc_core::input_arg();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--render" => render = Some(args.next().expect("--render needs a path").into()),
+            _ => panic!("unknown argument: {}", arg),
+        }
+    }
+
+    let (lines, x_dim, y_dim) = aoc_core::or_exit(read_input(aoc_core::open_input(&path)));
+
+    if x_dim.saturating_mul(y_dim) > DENSE_LIMIT {
+        println!("{}x{}, too big to draw", x_dim, y_dim);
+        println!("overlaps: {}", count_overlaps_sweep(&lines));
+        if render.is_some() {
+            eprintln!("not rendering a floor that big");
+        }
+        return;
+    }
+
+    let map = SeaFloor::from_lines(&lines, (x_dim, y_dim));
+    println!("{}x{}", x_dim, y_dim);
+    println!("{}", map);
+
+    println!("overlaps: {}", map.count_overlaps());
+
+    if let Some(path) = render {
+        let raster = Raster::from_fn(x_dim, y_dim, |x, y| map.height_at(x, y) as u8);
+        aoc_render::write(&path, &[raster], 1).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+    }
+}
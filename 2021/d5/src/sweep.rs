@@ -0,0 +1,241 @@
+//! Counting overlaps without a map, for vents spread over millions of
+//! cells. Every vent runs along one of four directions, so a cell is
+//! covered twice either by vents along the same line overlapping, or by
+//! vents along different directions crossing there. The first come from a
+//! sweep along each line, the second from a sweep over x of which vents
+//! are alive to cross.
+
+use std::cmp;
+use std::collections::HashMap;
+
+use crate::VentInput;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum Direction {
+    /// y stays put
+    Horizontal,
+    /// x stays put
+    Vertical,
+    /// y - x stays put
+    Rising,
+    /// x + y stays put
+    Falling,
+}
+
+use Direction::*;
+
+const DIRECTIONS: [Direction; 4] = [Horizontal, Vertical, Rising, Falling];
+
+impl Direction {
+    /// Which line of this direction a cell is on, and how far along it
+    fn place(self, (x, y): (i64, i64)) -> (i64, i64) {
+        match self {
+            Horizontal => (y, x),
+            Vertical => (x, y),
+            Rising => (y - x, x),
+            Falling => (x + y, x),
+        }
+    }
+
+    fn cell(self, line: i64, t: i64) -> (i64, i64) {
+        match self {
+            Horizontal => (t, line),
+            Vertical => (line, t),
+            Rising => (t, t + line),
+            Falling => (t, line - t),
+        }
+    }
+}
+
+/// A vent as a run along one line
+#[derive(Clone, Copy, Debug)]
+struct Run {
+    direction: Direction,
+    line: i64,
+    from: i64,
+    to: i64,
+}
+
+impl Run {
+    fn of(vent: &VentInput) -> Run {
+        let (a, b) = ((vent.a.x as i64, vent.a.y as i64), (vent.b.x as i64, vent.b.y as i64));
+        let direction = match (b.0 - a.0, b.1 - a.1) {
+            (_, 0) => Horizontal,
+            (0, _) => Vertical,
+            (dx, dy) if dx == dy => Rising,
+            (dx, dy) if dx == -dy => Falling,
+            _ => panic!("vent {},{} -> {},{} isn't straight or diagonal", a.0, a.1, b.0, b.1),
+        };
+        let (line, ta) = direction.place(a);
+        let (_, tb) = direction.place(b);
+        Run { direction, line, from: cmp::min(ta, tb), to: cmp::max(ta, tb) }
+    }
+
+    fn xs(&self) -> (i64, i64) {
+        let (x1, _) = self.direction.cell(self.line, self.from);
+        let (x2, _) = self.direction.cell(self.line, self.to);
+        (cmp::min(x1, x2), cmp::max(x1, x2))
+    }
+
+    fn contains(&self, cell: (i64, i64)) -> bool {
+        let (line, t) = self.direction.place(cell);
+        line == self.line && (self.from..=self.to).contains(&t)
+    }
+
+    /// The cell two runs along different directions share, if any
+    fn crossing(&self, other: &Run) -> Option<(i64, i64)> {
+        let cell = match (self.direction, other.direction) {
+            (Horizontal, Vertical) => (other.line, self.line),
+            (Horizontal, Rising) => (self.line - other.line, self.line),
+            (Horizontal, Falling) => (other.line - self.line, self.line),
+            (Vertical, Rising) => (self.line, self.line + other.line),
+            (Vertical, Falling) => (self.line, other.line - self.line),
+            (Rising, Falling) => {
+                // the lines cross between cells unless x + y and y - x
+                // are both odd or both even
+                if (other.line - self.line) % 2 != 0 {
+                    return None;
+                }
+                ((other.line - self.line) / 2, (other.line + self.line) / 2)
+            },
+            (a, b) if a == b => return None,
+            _ => return other.crossing(self),
+        };
+        (self.contains(cell) && other.contains(cell)).then_some(cell)
+    }
+}
+
+/// Stretches of each line that two or more runs along it cover, sorted
+/// and apart from each other
+fn overlaps_along_lines(runs: &[Run]) -> HashMap<(Direction, i64), Vec<(i64, i64)>> {
+    let mut events: HashMap<(Direction, i64), Vec<(i64, i32)>> = HashMap::new();
+    for run in runs {
+        let events = events.entry((run.direction, run.line)).or_default();
+        events.push((run.from, 1));
+        events.push((run.to + 1, -1));
+    }
+
+    events.into_iter()
+        .map(|(line, mut events)| {
+            events.sort_unstable();
+            let mut overlaps = vec!();
+            let mut depth = 0;
+            let mut start = None;
+            for (t, change) in events {
+                depth += change;
+                match start {
+                    None if depth >= 2 => start = Some(t),
+                    Some(s) if depth < 2 => {
+                        if t > s {
+                            overlaps.push((s, t - 1));
+                        }
+                        start = None;
+                    },
+                    _ => {},
+                }
+            }
+            (line, overlaps)
+        })
+        .filter(|(_, overlaps)| !overlaps.is_empty())
+        .collect()
+}
+
+/// Every cell where runs along different directions cross, once each
+fn crossings(runs: &[Run]) -> Vec<(i64, i64)> {
+    let mut by_x: Vec<(i64, i64, &Run)> = runs.iter().map(|r| {
+        let (x1, x2) = r.xs();
+        (x1, x2, r)
+    }).collect();
+    by_x.sort_unstable_by_key(|&(x1, ..)| x1);
+
+    let mut cells = vec!();
+    let mut alive: Vec<(i64, &Run)> = vec!();
+    for (x1, x2, run) in by_x {
+        alive.retain(|&(end, _)| end >= x1);
+        cells.extend(alive.iter().filter_map(|(_, other)| run.crossing(other)));
+        alive.push((x2, run));
+    }
+    cells.sort_unstable();
+    cells.dedup();
+    cells
+}
+
+/// Cells that two or more vents cover, worked out from the vents alone so
+/// the size of the floor doesn't matter. Vents must be horizontal, vertical
+/// or at 45 degrees.
+pub fn count_overlaps_sweep(vents: &[VentInput]) -> usize {
+    let runs: Vec<Run> = vents.iter().map(Run::of).collect();
+    let overlaps = overlaps_along_lines(&runs);
+    let along_lines: i64 = overlaps.values().flatten().map(|(from, to)| to - from + 1).sum();
+
+    // a crossing on no overlap still needs counting, and one on overlaps
+    // along several lines has been counted that many times
+    let in_overlaps = |cell| DIRECTIONS.iter()
+        .filter(|&&d| {
+            let (line, t) = d.place(cell);
+            overlaps.get(&(d, line)).is_some_and(|o| {
+                let i = o.partition_point(|&(_, to)| to < t);
+                o.get(i).is_some_and(|&(from, _)| from <= t)
+            })
+        })
+        .count() as i64;
+    let correction: i64 = crossings(&runs).into_iter()
+        .map(|cell| match in_overlaps(cell) {
+            0 => 1,
+            n => 1 - n,
+        })
+        .sum();
+
+    (along_lines + correction) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{read_input, SeaFloor};
+    use aoc_core::rng::Rng;
+
+    fn vents(text: &str) -> Vec<VentInput> {
+        read_input(text.as_bytes()).unwrap().0
+    }
+
+    #[test]
+    fn example_overlaps() {
+        let all = vents(include_str!("../test_input"));
+        assert_eq!(count_overlaps_sweep(&all), 12);
+        let straight: Vec<VentInput> = all.iter().copied().filter(VentInput::is_axis_aligned).collect();
+        assert_eq!(count_overlaps_sweep(&straight), 5);
+    }
+
+    #[test]
+    fn diagonals_can_pass_between_cells() {
+        assert_eq!(count_overlaps_sweep(&vents("0,0 -> 1,1\n0,1 -> 1,0\n")), 0);
+        assert_eq!(count_overlaps_sweep(&vents("0,0 -> 2,2\n0,2 -> 2,0\n")), 1);
+        // every direction and an overlap all through 2,2, which counts
+        // once, and a vent crossing two others away from the overlap
+        let vents = vents("0,2 -> 4,2\n1,2 -> 3,2\n2,0 -> 2,4\n0,0 -> 4,4\n0,4 -> 4,0\n0,0 -> 0,3\n");
+        assert_eq!(count_overlaps_sweep(&vents), 5);
+    }
+
+    #[test]
+    fn matches_the_dense_map() {
+        let mut rng = Rng::seed_from(5);
+        for _ in 0..50 {
+            let mut text = String::new();
+            for _ in 0..rng.range(1..30) {
+                let (x, y) = (rng.range(0..40), rng.range(0..40));
+                let len = rng.range(0..15);
+                let (dx, dy) = *rng.choose(&[(1, 0), (0, 1), (1, 1), (1, -1), (-1, 0)]).unwrap();
+                let (x2, y2) = (x + dx * len, y + dy * len);
+                if x2 < 0 || y2 < 0 {
+                    continue;
+                }
+                text += &format!("{},{} -> {},{}\n", x, y, x2, y2);
+            }
+            let vents = vents(&text);
+            let dense = SeaFloor::from_lines(&vents, (0, 0)).count_overlaps();
+            assert_eq!(count_overlaps_sweep(&vents), dense, "{}", text);
+            assert_eq!(crate::count_overlaps_hashed(&vents), dense);
+        }
+    }
+}
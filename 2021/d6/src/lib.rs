@@ -0,0 +1,100 @@
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+
+extern crate alloc;
+
+use aoc_core::{option_value, AocError, Solver, Strategies, Strategy};
+
+mod lineage;
+mod population;
+pub use lineage::Lineage;
+pub use population::{Age, Checkpoint, Count, Population};
+
+/// Only a SIMD build has another way to count, and can still count without.
+/// Big integers don't fit SIMD lanes, so a `bigint` build has only the one.
+#[cfg(all(feature = "simd", not(feature = "bigint")))]
+const IMPLS: &[Strategy] = &[
+    Strategy { name: "simd", about: "the first 16 ages a day at once" },
+    Strategy { name: "scalar", about: "an age at a time" },
+];
+#[cfg(any(not(feature = "simd"), feature = "bigint"))]
+const IMPLS: &[Strategy] = &[];
+
+pub struct Puzzle {
+    population: Population,
+    /// Days simulated for each part
+    days: [u32; 2],
+    /// Count an age at a time, even if built with SIMD
+    scalar: bool,
+}
+
+impl Puzzle {
+    fn total_after(&self, days: u32) -> Count {
+        match self.scalar {
+            true => self.population.clone().total_after_scalar(days),
+            false => self.population.clone().total_after(days),
+        }
+    }
+}
+
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        let ages: Vec<Age> = aoc_input::parse_csv_line(input)?;
+        Ok(Puzzle { population: Population::from_ages(ages.into_iter()), days: [80, 256], scalar: false })
+    }
+
+    fn strategies() -> Strategies {
+        Strategies { algos: &[], impls: IMPLS }
+    }
+
+    /// `part1_days` and `part2_days`, and `impl` if built with SIMD
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), AocError> {
+        match name {
+            "part1_days" => self.days[0] = option_value(name, value)?,
+            "part2_days" => self.days[1] = option_value(name, value)?,
+            "impl" if IMPLS.iter().any(|s| s.name == value) => self.scalar = value == "scalar",
+            _ => return Err(AocError::invalid(format!("no option {}", name))),
+        }
+        Ok(())
+    }
+
+    fn part1(&self) -> String {
+        self.total_after(self.days[0]).to_string()
+    }
+
+    fn part2(&self) -> String {
+        self.total_after(self.days[1]).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_are_an_option() {
+        let mut puzzle = Puzzle::parse("3,4,3,1,2");
+        puzzle.set_option("part1_days", "18").unwrap();
+        assert_eq!(puzzle.part1(), "26");
+        assert_eq!(puzzle.part2(), "26984457539");
+        assert_eq!(puzzle.set_option("part1_days", "x").unwrap_err().to_string(), "bad value \"x\" for option part1_days");
+        assert!(puzzle.set_option("rounds", "3").is_err());
+    }
+
+    #[test]
+    fn impls_count_the_same() {
+        let mut puzzle = Puzzle::parse("3,4,3,1,2");
+        for s in Puzzle::strategies().impls {
+            puzzle.set_option("impl", s.name).unwrap();
+            assert_eq!(puzzle.part2(), "26984457539", "{}", s.name);
+        }
+        assert_eq!(puzzle.set_option("impl", "simd").is_ok(), cfg!(all(feature = "simd", not(feature = "bigint"))));
+    }
+
+    /// The answers given in the puzzle text for its example
+    #[test]
+    fn example_answers() {
+        let puzzle = Puzzle::parse(include_str!("../test_input"));
+        assert_eq!(puzzle.part1(), "5934");
+        assert_eq!(puzzle.part2(), "26984457539");
+    }
+}
@@ -0,0 +1,108 @@
+//! Which of the starting fish each fish descends from. Fish that start the
+//! same age have families that grow the same way, so the population is kept
+//! as one cohort per starting age, each evolving a distribution of its own,
+//! with every starting fish pointing at its cohort.
+#![deny(clippy::std_instead_of_core, clippy::std_instead_of_alloc)]
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{Age, Count, Population};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Lineage {
+    /// Descendants (and the founder itself) of a single fish of each
+    /// starting age, in the order the ages first appear
+    cohorts: Vec<(Age, Population)>,
+    /// Index into `cohorts` for each starting fish, in input order
+    founders: Vec<usize>,
+}
+
+impl Lineage {
+    pub fn from_ages(ages: impl Iterator<Item = Age>) -> Lineage {
+        let mut cohorts: Vec<(Age, Population)> = vec!();
+        let founders = ages
+            .map(|age| match cohorts.iter().position(|&(a, _)| a == age) {
+                Some(c) => c,
+                None => {
+                    cohorts.push((age, Population::from_ages(core::iter::once(age))));
+                    cohorts.len() - 1
+                },
+            })
+            .collect();
+        Lineage { cohorts, founders }
+    }
+
+    pub fn tick_day(&mut self) {
+        for (_, cohort) in &mut self.cohorts {
+            cohort.tick_day();
+        }
+    }
+
+    /// Number of starting fish
+    pub fn founders(&self) -> usize {
+        self.founders.len()
+    }
+
+    pub fn starting_age(&self, fish: usize) -> Age {
+        self.cohorts[self.founders[fish]].0
+    }
+
+    /// Fish descended from the `fish`th starting fish, counting itself
+    pub fn family_size(&self, fish: usize) -> Count {
+        self.cohorts[self.founders[fish]].1.total()
+    }
+
+    /// Starting fish with the most descendants and the size of its family,
+    /// the first in the input if several are tied
+    pub fn largest_family(&self) -> Option<(usize, Count)> {
+        (0..self.founders()).rev()
+            .map(|fish| (fish, self.family_size(fish)))
+            .max_by(|(_, a), (_, b)| a.cmp(b))
+    }
+
+    /// Every family counted together, the way `Population` would have it
+    pub fn population(&self) -> Population {
+        self.founders.iter().map(|&c| &self.cohorts[c].1).sum()
+    }
+
+    pub fn total(&self) -> Count {
+        self.founders.iter().map(|&c| self.cohorts[c].1.total()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn families_add_up_to_the_population() {
+        let ages = [3, 4, 3, 1, 2];
+        let mut lineage = Lineage::from_ages(ages.iter().copied());
+        let mut population = Population::from_ages(ages.iter().copied());
+        assert_eq!(lineage.cohorts.len(), 4);
+
+        for day in 1..=80 {
+            lineage.tick_day();
+            population.tick_day();
+            if day == 18 {
+                let sizes: Vec<Count> = (0..lineage.founders()).map(|f| lineage.family_size(f)).collect();
+                assert_eq!(sizes, [5u32, 4, 5, 7, 5].map(Count::from));
+            }
+        }
+        assert_eq!(lineage.population(), population);
+        assert_eq!(lineage.total(), Count::from(5934u32));
+        assert_eq!(lineage.largest_family(), Some((3, Count::from(1401u32))));
+        assert_eq!(lineage.starting_age(3), 1);
+    }
+
+    #[test]
+    fn ties_go_to_the_first_fish() {
+        let mut lineage = Lineage::from_ages([2, 1, 1].iter().copied());
+        lineage.tick_day();
+        assert_eq!(lineage.largest_family(), Some((0, Count::from(1u32))));
+        lineage.tick_day();
+        assert_eq!(lineage.largest_family(), Some((1, Count::from(2u32))));
+        assert_eq!(Lineage::from_ages(core::iter::empty()).largest_family(), None);
+    }
+}
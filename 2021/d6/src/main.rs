@@ -0,0 +1,85 @@
+use std::fs;
+use std::io;
+
+use aoc_core::InputSource;
+use d6::*;
+
+fn save_checkpoint(state: &Checkpoint, path: &str) -> io::Result<()> {
+    fs::write(path, state.to_string())
+}
+
+fn load_checkpoint(path: &str) -> io::Result<Checkpoint> {
+    fs::read_to_string(path)?
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn run_milestones(state: &mut Checkpoint, checkpoint_path: Option<&str>) {
+    let start_day = state.day;
+    for milestone in [18, 80, 256].into_iter().filter(|&m| m >= start_day) {
+        while state.day < milestone {
+            state.population.tick_day();
+            state.day += 1;
+        }
+        println!("day {}: {}", milestone, state.population.total());
+
+        if let Some(path) = checkpoint_path {
+            save_checkpoint(state, path).unwrap();
+        }
+    }
+}
+
+/// Reports each milestone with the starting fish whose family is largest
+fn run_lineage_milestones(mut lineage: Lineage) {
+    let mut day = 0;
+    for milestone in [18, 80, 256] {
+        while day < milestone {
+            lineage.tick_day();
+            day += 1;
+        }
+        match lineage.largest_family() {
+            Some((fish, size)) => println!("day {}: {}, largest family from fish {} (starting age {}): {}",
+                milestone, lineage.total(), fish, lineage.starting_age(fish), size),
+            None => println!("day {}: 0", milestone),
+        }
+    }
+}
+
+fn read_ages(input: &InputSource) -> Vec<Age> {
+    aoc_core::or_exit(aoc_input::read_csv_line(aoc_core::open_input(input)))
+}
+
+fn main() {
+    // --resume <file> continues from a saved checkpoint instead of reading
+    // the input, --checkpoint <file> saves the state at every reported day,
+    // --lineage also reports which starting fish has the largest family
+    let (input, args) = aoc_core::input_arg();
+    let mut resume = None;
+    let mut checkpoint = None;
+    let mut lineage = false;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--resume" => resume = args.next(),
+            "--checkpoint" => checkpoint = args.next(),
+            "--lineage" => lineage = true,
+            _ => panic!("unknown argument: {}", arg),
+        }
+    }
+
+    if lineage {
+        if resume.is_some() || checkpoint.is_some() {
+            eprintln!("--lineage starts from the input, and checkpoints don't keep families apart");
+            std::process::exit(2);
+        }
+        run_lineage_milestones(Lineage::from_ages(read_ages(&input).into_iter()));
+        return;
+    }
+
+    let mut state = match resume {
+        Some(path) => load_checkpoint(&path).unwrap(),
+        None => Checkpoint { day: 0, population: Population::from_ages(read_ages(&input).into_iter()) },
+    };
+
+    run_milestones(&mut state, checkpoint.as_deref());
+}
@@ -0,0 +1,247 @@
+//! The population math on its own, needing nothing from `std` beyond
+//! `alloc`, so it can be built into a `no_std` target. Reading the input and
+//! saving checkpoints is left to the binary.
+#![deny(clippy::std_instead_of_core, clippy::std_instead_of_alloc)]
+// counts are cloned for the sake of `bigint`, where they aren't `Copy`
+#![cfg_attr(not(feature = "bigint"), allow(clippy::clone_on_copy))]
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::Sum;
+use core::str::FromStr;
+
+pub type Age = u32;
+#[cfg(not(feature = "bigint"))]
+pub type Count = u64;
+/// Without a limit, for simulating many thousands of days
+#[cfg(feature = "bigint")]
+pub type Count = num_bigint::BigUint;
+
+/// Counts for every age, all zero
+fn no_fish() -> [Count; 300] {
+    core::array::from_fn(|_| Count::default())
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Population {
+    dist: [Count; 300],
+}
+
+impl Population {
+    pub fn from_ages(ages: impl Iterator<Item = Age>) -> Population {
+        let mut dist = no_fish();
+        for age in ages {
+            dist[age as usize] += Count::from(1u8);
+        }
+
+        Population { dist }
+    }
+
+    pub fn tick_day(&mut self) {
+        #[cfg(all(feature = "simd", not(feature = "bigint")))]
+        self.tick_simd();
+        #[cfg(any(not(feature = "simd"), feature = "bigint"))]
+        self.tick_scalar();
+    }
+
+    /// `tick_day` an age at a time
+    pub fn tick_scalar(&mut self) {
+        let spawning_pop = self.dist[0].clone();
+        for i in 0..9 {
+            self.dist[i] = self.dist[i+1].clone();
+        }
+        self.dist[6] += &spawning_pop;
+        self.dist[8] += spawning_pop;
+
+        self.dist[self.dist.len()-1] = Count::default();
+    }
+
+    /// `tick_day` on the first 16 ages at once
+    #[cfg(all(feature = "simd", not(feature = "bigint")))]
+    pub fn tick_simd(&mut self) {
+        use core::simd::prelude::*;
+
+        let spawning_pop = self.dist[0];
+        let head = Simd::<Count, 16>::from_slice(&self.dist[..16]);
+        // ages from 9 up only ever come from the input, and stay put
+        let moving = mask64x16::from_array(core::array::from_fn(|age| age < 9));
+        let mut spawned = [0; 16];
+        spawned[6] = spawning_pop;
+        spawned[8] = spawning_pop;
+        let next = moving.select(head.rotate_elements_left::<1>(), head) + Simd::from_array(spawned);
+        next.copy_to_slice(&mut self.dist[..16]);
+
+        self.dist[self.dist.len()-1] = 0;
+    }
+
+    pub fn total(&self) -> Count {
+        self.dist.iter().sum()
+    }
+
+    /// Parses the puzzle input: a single line of comma-separated ages.
+    pub fn parse_ages(s: &str) -> Population {
+        Population::from_ages(s.trim_end()
+            .split(',')
+            .map(|s| s.parse::<Age>().unwrap()))
+    }
+
+    /// Total population after `days` more days.
+    pub fn total_after(mut self, days: u32) -> Count {
+        for _ in 0..days {
+            self.tick_day();
+        }
+        self.total()
+    }
+
+    /// `total_after`, an age at a time even when built with SIMD
+    pub fn total_after_scalar(mut self, days: u32) -> Count {
+        for _ in 0..days {
+            self.tick_scalar();
+        }
+        self.total()
+    }
+}
+
+/// Populations counted together, age by age
+impl<'a> Sum<&'a Population> for Population {
+    fn sum<I: Iterator<Item = &'a Population>>(iter: I) -> Population {
+        let mut dist = no_fish();
+        for pop in iter {
+            for (total, count) in dist.iter_mut().zip(&pop.dist) {
+                *total += count;
+            }
+        }
+        Population { dist }
+    }
+}
+
+/// Population counts written as comma-separated counts per age, starting at
+/// age 0 and omitting trailing zeros
+impl fmt::Display for Population {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let len = self.dist.iter().rposition(|c| *c != Count::default()).map_or(0, |i| i + 1);
+        let counts: Vec<String> = self.dist[..len].iter().map(|c| c.to_string()).collect();
+        write!(f, "{}", counts.join(","))
+    }
+}
+
+impl FromStr for Population {
+    type Err = &'static str;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut dist = no_fish();
+        if s.is_empty() {
+            return Ok(Population { dist });
+        }
+        for (i, c) in s.split(',').enumerate() {
+            *dist.get_mut(i).ok_or("too many age groups")? = c.parse().or(Err("invalid count"))?;
+        }
+        Ok(Population { dist })
+    }
+}
+
+/// Simulation state that can be saved and resumed later
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Checkpoint {
+    pub day: u32,
+    pub population: Population,
+}
+
+impl fmt::Display for Checkpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "day {}", self.day)?;
+        writeln!(f, "population {}", self.population)
+    }
+}
+
+impl FromStr for Checkpoint {
+    type Err = &'static str;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        let day = lines.next()
+            .and_then(|l| l.strip_prefix("day "))
+            .ok_or("missing day")?
+            .parse().or(Err("invalid day"))?;
+        let population = lines.next()
+            .and_then(|l| l.strip_prefix("population"))
+            .ok_or("missing population")?
+            .trim()
+            .parse()?;
+        Ok(Checkpoint { day, population })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts<const N: usize>(counts: [u32; N]) -> [Count; N] {
+        counts.map(Count::from)
+    }
+
+    #[test]
+    fn can_parse_input() {
+        let ages = [3 as Age, 4, 3, 1, 2];
+        let pop = Population::from_ages(ages.iter().copied());
+
+        assert_eq!(counts([0, 1, 1, 2, 1, 0]), pop.dist[..6]);
+    }
+
+    #[test]
+    fn get_population_total() {
+        let pop = Population::from_ages([1, 1, 2, 1, 3].iter().copied());
+        let total = pop.total();
+
+        assert_eq!(Count::from(5u32), total);
+    }
+
+    #[test]
+    fn tick_ages_population() {
+        let mut pop = Population::from_ages([1, 2, 2, 3, 4, 4, 4].iter().copied());
+        pop.tick_day();
+
+        assert_eq!(counts([1, 2, 1, 3, 0]), pop.dist[..5]);
+    }
+
+    #[test]
+    fn population_text_round_trip() {
+        let pop = Population::from_ages([3, 4, 3, 1, 2].iter().copied());
+
+        assert_eq!(pop.to_string(), "0,1,1,2,1");
+        assert_eq!("0,1,1,2,1".parse::<Population>(), Ok(pop));
+        assert_eq!("".parse::<Population>().map(|p| p.total()), Ok(Count::default()));
+        assert!("1,x".parse::<Population>().is_err());
+    }
+
+    #[test]
+    fn resumed_checkpoint_matches_uninterrupted_run() {
+        let mut uninterrupted = Population::from_ages([3, 4, 3, 1, 2].iter().copied());
+        let mut state = Checkpoint { day: 0, population: uninterrupted.clone() };
+        for _ in 0..18 {
+            uninterrupted.tick_day();
+            state.population.tick_day();
+            state.day += 1;
+        }
+
+        let mut resumed: Checkpoint = state.to_string().parse().unwrap();
+        assert_eq!(resumed, state);
+        assert_eq!(resumed.population.total(), Count::from(26u32));
+
+        for _ in 18..80 {
+            uninterrupted.tick_day();
+            resumed.population.tick_day();
+        }
+        assert_eq!(resumed.population, uninterrupted);
+        assert_eq!(resumed.population.total(), Count::from(5934u32));
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn counts_far_past_u64() {
+        let pop = Population::from_ages([3, 4, 3, 1, 2].iter().copied());
+        let total = pop.total_after(10_000).to_string();
+        // growing by about 9% a day
+        assert_eq!(total.len(), 380);
+        assert!(total.starts_with("122952209137"));
+    }
+}
@@ -0,0 +1,173 @@
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+
+use aoc_core::{AocError, Solver, Strategies, Strategy};
+
+pub type Position = i32;
+pub type Fuel = i32;
+pub type PosInput = [Position];
+pub type FuelCalculator = fn(&PosInput, Position) -> Fuel;
+
+pub fn get_fuel_cost(positions: &PosInput, target_pos: Position) -> Fuel {
+    #[cfg(feature = "simd")]
+    return simd::total_cost(positions, target_pos, |d| d);
+    #[cfg(not(feature = "simd"))]
+    positions
+        .iter()
+        .map(|p| (target_pos - p).abs() as Fuel)
+        .sum()
+}
+
+pub fn get_true_fuel_cost(positions: &PosInput, target_pos: Position) -> Fuel {
+    #[cfg(feature = "simd")]
+    return simd::total_cost(positions, target_pos, |d| (d + simd::Lanes::splat(1)) * d / simd::Lanes::splat(2));
+    #[cfg(not(feature = "simd"))]
+    positions
+        .iter()
+        .map(|p| {
+            let d = (target_pos - p).abs();
+            (d + 1) * d / 2 as Fuel
+        })
+        .sum()
+}
+
+#[cfg(feature = "simd")]
+mod simd {
+    use std::simd::prelude::*;
+
+    use super::{Fuel, PosInput, Position};
+
+    pub type Lanes = Simd<Fuel, 8>;
+
+    /// Sum of `cost` of the distance from each position to the target,
+    /// eight positions at a time
+    pub fn total_cost(positions: &PosInput, target_pos: Position, cost: impl Fn(Lanes) -> Lanes) -> Fuel {
+        let target = Lanes::splat(target_pos);
+        let (chunks, rest) = positions.as_chunks::<8>();
+        let mut total = Lanes::splat(0);
+        for chunk in chunks {
+            total += cost((target - Lanes::from_array(*chunk)).abs());
+        }
+        // the last few positions, with the spare lanes at a distance of 0
+        let mut last = [target_pos; 8];
+        last[..rest.len()].copy_from_slice(rest);
+        total += cost((target - Lanes::from_array(last)).abs());
+        total.reduce_sum()
+    }
+}
+
+/// Cheapest position to line up on and its fuel cost, the leftmost if
+/// there's a tie
+pub fn get_optimal_pos(positions: &PosInput, get_fuel: FuelCalculator) -> (Position, Fuel) {
+    let min_pos = *positions.iter().min().unwrap();
+    let max_pos = *positions.iter().max().unwrap();
+
+    let cost = |p| (p, get_fuel(positions, p));
+    // ties go to the lower position, whichever thread found them
+    let cheapest = |&(p, fuel): &(Position, Fuel)| (fuel, p);
+
+    #[cfg(feature = "parallel")]
+    let optimal = {
+        use rayon::prelude::*;
+        (min_pos..=max_pos).into_par_iter().map(cost).min_by_key(cheapest)
+    };
+    #[cfg(not(feature = "parallel"))]
+    let optimal = (min_pos..=max_pos).map(cost).min_by_key(cheapest);
+
+    optimal.unwrap()
+}
+
+/// Fuel to line up at a median position, where the distances add up to
+/// the least
+pub fn median_fuel(positions: &PosInput) -> Fuel {
+    let mut sorted = positions.to_vec();
+    sorted.sort_unstable();
+    get_fuel_cost(positions, sorted[sorted.len() / 2])
+}
+
+/// Fuel to line up at the cheapest position when each step costs one more
+/// than the last. That position is within half a step of the mean, so only
+/// the positions either side of it need trying.
+pub fn mean_fuel(positions: &PosInput) -> Fuel {
+    let mean = positions.iter().sum::<Position>().div_euclid(positions.len() as Position);
+    (mean - 1..=mean + 1).map(|p| get_true_fuel_cost(positions, p)).min().unwrap()
+}
+
+pub fn parse_positions(line: &str) -> Result<Vec<Position>, AocError> {
+    aoc_input::parse_csv_line(line)
+}
+
+pub struct Puzzle {
+    positions: Vec<Position>,
+    /// Work the answers out with `median_fuel` and `mean_fuel` rather
+    /// than trying every position
+    analytic: bool,
+}
+
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        Ok(Puzzle { positions: parse_positions(input)?, analytic: false })
+    }
+
+    fn strategies() -> Strategies {
+        Strategies {
+            algos: &[
+                Strategy { name: "scan", about: "try every position between the outermost crabs" },
+                Strategy { name: "analytic", about: "the median for part 1, and either side of the mean for part 2" },
+            ],
+            impls: &[],
+        }
+    }
+
+    /// `algo`: `scan` or `analytic`
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), AocError> {
+        if name != "algo" {
+            return Err(AocError::invalid(format!("no option {}", name)));
+        }
+        self.analytic = match value {
+            "scan" => false,
+            "analytic" => true,
+            _ => return Err(AocError::invalid(format!("bad value {:?} for option algo", value))),
+        };
+        Ok(())
+    }
+
+    fn part1(&self) -> String {
+        match self.analytic {
+            true => median_fuel(&self.positions),
+            false => get_optimal_pos(&self.positions, get_fuel_cost).1,
+        }.to_string()
+    }
+
+    fn part2(&self) -> String {
+        match self.analytic {
+            true => mean_fuel(&self.positions),
+            false => get_optimal_pos(&self.positions, get_true_fuel_cost).1,
+        }.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The answers given in the puzzle text for its example
+    #[test]
+    fn example_answers() {
+        let puzzle = Puzzle::parse(include_str!("../test_input"));
+        assert_eq!(puzzle.part1(), "37");
+        assert_eq!(puzzle.part2(), "168");
+    }
+
+    #[test]
+    fn optimal_positions() {
+        let positions = parse_positions(include_str!("../test_input")).unwrap();
+        assert_eq!(get_optimal_pos(&positions, get_fuel_cost), (2, 37));
+        assert_eq!(get_optimal_pos(&positions, get_true_fuel_cost), (5, 168));
+        // both ends cost the same, and the left one wins
+        assert_eq!(get_optimal_pos(&[0, 4], get_fuel_cost), (0, 4));
+
+        assert_eq!(median_fuel(&positions), 37);
+        assert_eq!(mean_fuel(&positions), 168);
+        assert_eq!((median_fuel(&[7]), mean_fuel(&[7])), (0, 0));
+    }
+}
@@ -0,0 +1,29 @@
+use d7::*;
+
+fn part1(positions: &PosInput) {
+    let (min_pos, min_fuel) = get_optimal_pos(positions, get_fuel_cost);
+
+    println!("{} for {}", min_pos, min_fuel);
+}
+
+fn part2(positions: &PosInput) {
+    let (min_pos, min_fuel) = get_optimal_pos(positions, get_true_fuel_cost);
+
+    println!("{} for {}", min_pos, min_fuel);
+}
+
+fn main() {
+    let (input, parts, args) = aoc_core::input_and_parts();
+    if let Some(arg) = args.first() {
+        panic!("unknown argument: {}", arg);
+    }
+    let input = aoc_core::open_input(&input);
+    let positions: Vec<Position> = aoc_core::or_exit(aoc_input::read_csv_line(input));
+
+    if parts.has(1) {
+        part1(&positions);
+    }
+    if parts.has(2) {
+        part2(&positions);
+    }
+}
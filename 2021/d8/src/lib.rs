@@ -0,0 +1,287 @@
+use std::fmt;
+use std::ops;
+
+use aoc_core::{AocError, Solver};
+
+mod wiring;
+pub use wiring::{candidate_wirings, entry_decoder, possible_outputs, solve_wiring, Wiring, WiringError};
+
+pub enum Segment {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+pub struct SevenSegDisplay(u8);
+
+impl SevenSegDisplay {
+    pub fn empty() -> SevenSegDisplay {
+        SevenSegDisplay(0)
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<SevenSegDisplay, &'static str> {
+        let mut segments = 0u8;
+        for c in s.chars() {
+            segments |= match c {
+                'a' => 0b01000000,
+                'b' => 0b00100000,
+                'c' => 0b00010000,
+                'd' => 0b00001000,
+                'e' => 0b00000100,
+                'f' => 0b00000010,
+                'g' => 0b00000001,
+                _ => return Err("bad segment specifier"),
+            };
+        }
+
+        Ok(SevenSegDisplay(segments))
+    }
+
+    pub const fn raw(&self) -> u8 {
+        self.0
+    }
+
+    pub const fn count_segments(&self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn has_segment(&self, s: Segment) -> bool {
+        let mask = match s {
+            Segment::A => 0b01000000,
+            Segment::B => 0b00100000,
+            Segment::C => 0b00010000,
+            Segment::D => 0b00001000,
+            Segment::E => 0b00000100,
+            Segment::F => 0b00000010,
+            Segment::G => 0b00000001,
+        };
+        self.0 & mask == mask
+    }
+
+    pub fn to_value(&self) -> Option<u8> {
+        Some(match self.0.count_ones() {
+            2 => 1,
+            4 => 4,
+            3 => 7,
+            7 => 8,
+            _ => return None,
+        })
+    }
+
+    pub fn to_decoded_value(&self, decoder: &Decoder) -> Option<u8> {
+        decoder.decode(self)
+    }
+}
+
+impl ops::BitAnd for SevenSegDisplay {
+    type Output = Self;
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl SevenSegDisplay {
+    pub const ART_HEIGHT: usize = 7;
+
+    /// Lines of the ASCII art for this display, top to bottom
+    pub fn art_rows(&self) -> [String; Self::ART_HEIGHT] {
+        let horizontal = |s| format!(" {} ", if self.has_segment(s) { "XX" } else { ".." });
+        let vertical = |l, r| format!("{}  {}",
+            if self.has_segment(l) { "X" } else { "." },
+            if self.has_segment(r) { "X" } else { "." });
+
+        [
+            horizontal(Segment::A),
+            vertical(Segment::B, Segment::C),
+            vertical(Segment::B, Segment::C),
+            horizontal(Segment::D),
+            vertical(Segment::E, Segment::F),
+            vertical(Segment::E, Segment::F),
+            horizontal(Segment::G),
+        ]
+    }
+}
+
+impl fmt::Display for SevenSegDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.art_rows() {
+            writeln!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+/// Several displays rendered side by side as one multi-digit readout
+pub struct Readout<'a>(pub &'a [SevenSegDisplay]);
+
+impl fmt::Display for Readout<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits: Vec<_> = self.0.iter().map(|d| d.art_rows()).collect();
+        for row in 0..SevenSegDisplay::ART_HEIGHT {
+            let line: Vec<&str> = digits.iter().map(|d| d[row].as_str()).collect();
+            writeln!(f, "{}", line.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Decoder([SevenSegDisplay; 10]);
+
+impl Decoder {
+    pub fn from_samples(samples: &[SevenSegDisplay]) -> Decoder {
+        let mut map = [SevenSegDisplay::empty(); 10];
+
+        for &s in samples {
+            if let Some(v) = s.to_value() {
+                map[v as usize] = s;
+            }
+        }
+
+        for &s in samples.iter().filter(|&d| d.to_value().is_none()) {
+            let num = match (s, s.count_segments()) {
+                (m, c) if c == 5 && (m & map[4]).count_segments() == 2 => 2,
+                (m, c) if c == 5 && (m & map[7]).count_segments() == 3 => 3,
+                (m, c) if c == 5 && (m & map[7]).count_segments() == 2 => 5,
+                (m, c) if c == 6 && (m & map[7]).count_segments() == 2 => 6,
+                (m, c) if c == 6 && (m & map[4]).count_segments() == 3 => 0, // will also match 6 case, so  order matters
+                (m, c) if c == 6 && (m & map[4]).count_segments() == 4 => 9,
+                _ => panic!(),
+            };
+            map[num] = s;
+        }
+
+        Decoder(map)
+    }
+
+    pub fn decode(&self, d: &SevenSegDisplay) -> Option<u8> {
+        self.0.iter().position(|v| v == d).map(|i| i as u8)
+    }
+
+    /// Display that lights up for digit `n`
+    pub fn encode(&self, n: u8) -> SevenSegDisplay {
+        self.0[n as usize]
+    }
+}
+
+/// Canonical wiring, where segment `a` lights up segment A and so on
+pub fn canonical_digit(n: u8) -> SevenSegDisplay {
+    const DIGITS: [&str; 10] = ["abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg"];
+    SevenSegDisplay::from_str(DIGITS[n as usize]).unwrap()
+}
+
+/// Number of displays that show a digit with a unique segment count
+pub fn count_unique(actual: &[SevenSegDisplay]) -> usize {
+    actual.iter()
+        .filter(|d| d.to_value().is_some())
+        .count()
+}
+
+pub fn parse_line(l: &str) -> (Vec<SevenSegDisplay>, Vec<SevenSegDisplay>) {
+    let mut parts = l.split('|');
+    let samples = parts.next().unwrap().trim_end().split_ascii_whitespace()
+        .map(|s| SevenSegDisplay::from_str(s).unwrap())
+        .collect();
+
+    let actual = parts.next().unwrap().trim().split_ascii_whitespace()
+        .map(|s| SevenSegDisplay::from_str(s).unwrap())
+        .collect();
+
+    (samples, actual)
+}
+
+/// Number shown by the output displays, skipping any that fail to decode
+pub fn decode_output(actual: &[SevenSegDisplay], decoder: &Decoder) -> u32 {
+    actual.iter()
+        .filter_map(|d| d.to_decoded_value(decoder))
+        .fold(0, |num, v| num * 10 + v as u32)
+}
+
+pub struct Puzzle {
+    entries: Vec<(Vec<SevenSegDisplay>, Vec<SevenSegDisplay>)>,
+    /// Each entry's decoder, checked against every wiring its samples
+    /// allow, so an entry they leave ambiguous is an error, not a guess
+    decoders: Vec<Decoder>,
+}
+
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        let entries: Vec<_> = input.lines().map(parse_line).collect();
+        let decoders = entries.iter()
+            .enumerate()
+            .map(|(i, (samples, actual))| entry_decoder(samples, actual).map_err(|e| AocError::invalid(e.to_string()).at_line(i + 1)))
+            .collect::<Result<_, _>>()?;
+        Ok(Puzzle { entries, decoders })
+    }
+
+    fn part1(&self) -> String {
+        self.entries.iter()
+            .map(|(_, actual)| count_unique(actual))
+            .sum::<usize>()
+            .to_string()
+    }
+
+    fn part2(&self) -> String {
+        self.entries.iter()
+            .zip(&self.decoders)
+            .map(|((_, actual), decoder)| decode_output(actual, decoder))
+            .sum::<u32>()
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readout_renders_side_by_side() {
+        let digits = [canonical_digit(1), canonical_digit(7)];
+        let expected = [
+            " ..   XX ",
+            ".  X .  X",
+            ".  X .  X",
+            " ..   .. ",
+            ".  X .  X",
+            ".  X .  X",
+            " ..   .. ",
+        ].map(|l| format!("{}\n", l)).concat();
+        assert_eq!(Readout(&digits).to_string(), expected);
+    }
+
+    #[test]
+    fn single_display_matches_readout() {
+        let d = canonical_digit(8);
+        assert_eq!(d.to_string(), Readout(&[d]).to_string());
+    }
+
+    #[test]
+    fn decoder_round_trip() {
+        let (samples, actual) = parse_line(include_str!("../test_input_single"));
+        let decoder = Decoder::from_samples(&samples);
+        let values: Vec<u8> = actual.iter().map(|d| d.to_decoded_value(&decoder).unwrap()).collect();
+
+        assert_eq!(values, [5, 3, 5, 3]);
+        assert!(actual.iter().all(|&d| decoder.encode(d.to_decoded_value(&decoder).unwrap()) == d));
+    }
+
+    /// The answers given in the puzzle text for its example
+    #[test]
+    fn example_answers() {
+        let puzzle = Puzzle::parse(include_str!("../test_input"));
+        assert_eq!(puzzle.part1(), "26");
+        assert_eq!(puzzle.part2(), "61229");
+    }
+
+    #[test]
+    fn ambiguous_entries_are_errors() {
+        let input = "be cfbegad | fdgacbe cefdb\nbe cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb\n";
+        let err = Puzzle::try_parse(input).err().unwrap();
+        assert!(err.to_string().starts_with("line 1: ambiguous, "));
+    }
+}
@@ -0,0 +1,93 @@
+use std::io::BufRead;
+
+use aoc_core::frame::{compose, Frame};
+
+use d8::*;
+
+/// Show the scrambled output next to what it decodes to
+fn explain(actual: &[SevenSegDisplay], decoder: &Decoder) {
+    let decoded: Vec<SevenSegDisplay> = actual.iter()
+        .map(|d| d.to_decoded_value(decoder).map_or(SevenSegDisplay::empty(), canonical_digit))
+        .collect();
+    print!("{}", compose(&[
+        Frame::new("scrambled", &Readout(actual).to_string()),
+        Frame::new("decoded", &Readout(&decoded).to_string()),
+    ]));
+}
+
+/// The decoder for an entry's only wiring, or `None` after reporting why
+/// its samples don't pin one down
+fn check(line: usize, samples: &[SevenSegDisplay], actual: &[SevenSegDisplay]) -> Option<Decoder> {
+    match solve_wiring(samples) {
+        Ok(wiring) => return Some(wiring.decoder()),
+        Err(WiringError::Ambiguous(candidates)) => {
+            let outputs: Vec<String> = possible_outputs(actual, &candidates).iter()
+                .map(|o| o.map_or("?".to_string(), |n| n.to_string()))
+                .collect();
+            let wirings: Vec<String> = candidates.iter().map(Wiring::to_string).collect();
+            eprintln!("line {}: {} wirings fit ({}), output reads {}", line, candidates.len(), wirings.join(" "), outputs.join(" or "));
+        },
+        Err(e) => eprintln!("line {}: {}", line, e),
+    }
+    None
+}
+
+fn main() {
+    // --explain draws each output before and after decoding; --check tries
+    // every wiring on each entry instead of deducing it, reporting and
+    // skipping the entries its samples leave ambiguous
+    let explain_mode = std::env::args().any(|a| a == "--explain");
+    let check_mode = std::env::args().any(|a| a == "--check");
+    let input = aoc_core::input();
+    let mut p1_total: usize = 0;
+    let mut sum = 0u32;
+    for (i, l) in input.lines().enumerate() {
+        let (samples, actual) = parse_line(&l.unwrap());
+        let decoder = if check_mode {
+            let Some(decoder) = check(i + 1, &samples, &actual) else {
+                continue;
+            };
+            decoder
+        } else {
+            Decoder::from_samples(&samples)
+        };
+        for d in &samples {
+            match d.to_decoded_value(&decoder) {
+                Some(d) => print!("{} ", d),
+                None => print!("? "),
+            }
+        }
+        print!("| ");
+        for d in &actual {
+            match d.to_value() {
+                Some(d) => print!("{} ", d),
+                None => print!("? "),
+            }
+        }
+        let c = count_unique(&actual);
+        print!("({})", c);
+        p1_total += c;
+
+        // Part 2
+        let mut num = 0u32;
+        print!(" || ");
+        for d in &actual {
+            match d.to_decoded_value(&decoder) {
+                Some(v) => {
+                    print!("{} ", v);
+                    num = num * 10 + v as u32;
+                },
+                None => print!("? "),
+            }
+        }
+        print!("| {}", num);
+        sum += num;
+
+        println!();
+        if explain_mode {
+            explain(&actual, &decoder);
+        }
+    }
+    println!("p1: {}", p1_total);
+    println!("p2: {}", sum);
+}
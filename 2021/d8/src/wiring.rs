@@ -0,0 +1,191 @@
+//! Solving an entry's wiring by trying every way the seven wires could be
+//! connected to the seven segments, rather than by the deductions `Decoder`
+//! makes. That finds every wiring the samples allow, so an entry with
+//! missing or damaged samples is reported as ambiguous instead of decoded
+//! with one of its wirings picked at random.
+
+use std::fmt;
+
+use itertools::Itertools;
+
+use crate::{canonical_digit, Decoder, SevenSegDisplay};
+
+/// Bit of wire or segment `i`, `a` being 0
+const fn bit(i: usize) -> u8 {
+    0b01000000 >> i
+}
+
+/// The segment each wire drives, `a` to `g`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Wiring([usize; 7]);
+
+impl Wiring {
+    /// The segments lit by the wires in `d`
+    pub fn apply(&self, d: SevenSegDisplay) -> SevenSegDisplay {
+        let mut segments = 0;
+        for (wire, &segment) in self.0.iter().enumerate() {
+            if d.raw() & bit(wire) != 0 {
+                segments |= bit(segment);
+            }
+        }
+        SevenSegDisplay(segments)
+    }
+
+    /// Digit `d` shows through this wiring
+    pub fn value(&self, d: SevenSegDisplay) -> Option<u8> {
+        let lit = self.apply(d);
+        (0..10).find(|&n| canonical_digit(n) == lit)
+    }
+
+    pub fn decoder(&self) -> Decoder {
+        let mut map = [SevenSegDisplay::empty(); 10];
+        for (n, scrambled) in map.iter_mut().enumerate() {
+            let lit = canonical_digit(n as u8);
+            let wires = (0..7).filter(|&w| lit.raw() & bit(self.0[w]) != 0).fold(0, |m, w| m | bit(w));
+            *scrambled = SevenSegDisplay(wires);
+        }
+        Decoder(map)
+    }
+}
+
+/// The segment each wire drives, like `cfgabde`
+impl fmt::Display for Wiring {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.iter().try_for_each(|&s| write!(f, "{}", (b'a' + s as u8) as char))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum WiringError {
+    /// No wiring makes every sample a digit
+    NoWiring,
+    /// More than one wiring fits the samples
+    Ambiguous(Vec<Wiring>),
+}
+
+impl fmt::Display for WiringError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WiringError::NoWiring => write!(f, "no wiring fits the samples"),
+            WiringError::Ambiguous(candidates) => write!(f, "ambiguous, {} wirings fit the samples", candidates.len()),
+        }
+    }
+}
+
+impl std::error::Error for WiringError {}
+
+/// Every wiring under which each sample shows a digit
+pub fn candidate_wirings(samples: &[SevenSegDisplay]) -> Vec<Wiring> {
+    // the digits as a set of displays, bit `d.raw()` for display `d`, to
+    // check thousands of wirings against without looking each digit up
+    let digits = (0..10).fold(0u128, |set, n| set | 1 << canonical_digit(n).raw());
+    // the fewest lit segments first, as they rule out the most wirings
+    let samples: Vec<_> = samples.iter().copied().sorted_by_key(|s| s.raw().count_ones()).collect();
+    (0..7).permutations(7)
+        .map(|p| Wiring(p.try_into().unwrap()))
+        .filter(|w| samples.iter().all(|&s| digits & 1 << w.apply(s).raw() != 0))
+        .collect()
+}
+
+/// How many of `displays` light each number of segments
+fn sizes<'a>(displays: impl Iterator<Item = &'a SevenSegDisplay>) -> [u8; 8] {
+    displays.fold([0; 8], |mut n, d| {
+        n[d.raw().count_ones() as usize] += 1;
+        n
+    })
+}
+
+/// The wiring for samples that are the ten digits, without trying every
+/// other. Each segment is in digits of different sizes from every other
+/// segment, which tells which wire drives it. No second wiring can fit as
+/// well, as that would take a reordering of the segments that turns the
+/// digits into each other, and there is none.
+fn ten_digit_wiring(samples: &[SevenSegDisplay]) -> Option<Wiring> {
+    let digits: Vec<_> = (0..10).map(canonical_digit).collect();
+    let segments: Vec<_> = (0..7).map(|s| sizes(digits.iter().filter(|d| d.raw() & bit(s) != 0))).collect();
+    let mut wiring = [0; 7];
+    for (wire, segment) in wiring.iter_mut().enumerate() {
+        let lit = sizes(samples.iter().filter(|d| d.raw() & bit(wire) != 0));
+        *segment = segments.iter().position(|&s| s == lit)?;
+    }
+    let wiring = Wiring(wiring);
+    let shown: Option<Vec<u8>> = samples.iter().map(|&s| wiring.value(s)).collect();
+    (shown?.into_iter().sorted().dedup().count() == 10).then_some(wiring)
+}
+
+/// The only wiring that fits the samples
+pub fn solve_wiring(samples: &[SevenSegDisplay]) -> Result<Wiring, WiringError> {
+    if let Some(wiring) = ten_digit_wiring(samples) {
+        return Ok(wiring);
+    }
+    let mut candidates = candidate_wirings(samples);
+    match candidates.len() {
+        0 => Err(WiringError::NoWiring),
+        1 => Ok(candidates.pop().unwrap()),
+        _ => Err(WiringError::Ambiguous(candidates)),
+    }
+}
+
+/// The number the output displays show under each wiring, deduplicated, so
+/// an ambiguous entry can still be read if every candidate agrees on it
+pub fn possible_outputs(actual: &[SevenSegDisplay], candidates: &[Wiring]) -> Vec<Option<u32>> {
+    candidates.iter()
+        .map(|w| actual.iter().try_fold(0, |num, &d| Some(num * 10 + w.value(d)? as u32)))
+        .sorted()
+        .dedup()
+        .collect()
+}
+
+/// The decoder for the only wiring that fits the samples, or for any of
+/// them when every one reads `actual` the same
+pub fn entry_decoder(samples: &[SevenSegDisplay], actual: &[SevenSegDisplay]) -> Result<Decoder, WiringError> {
+    match solve_wiring(samples) {
+        Err(WiringError::Ambiguous(candidates)) if possible_outputs(actual, &candidates).len() == 1 => {
+            Ok(candidates[0].decoder())
+        },
+        wiring => wiring.map(|w| w.decoder()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode_output, parse_line};
+
+    #[test]
+    fn full_samples_have_one_wiring() {
+        let (samples, actual) = parse_line(include_str!("../test_input_single"));
+        let wiring = solve_wiring(&samples).unwrap();
+        assert_eq!(wiring.to_string(), "cfgabde");
+        assert_eq!(candidate_wirings(&samples), [wiring]);
+        assert_eq!(decode_output(&actual, &wiring.decoder()), 5353);
+        assert_eq!(decode_output(&actual, &Decoder::from_samples(&samples)), 5353);
+    }
+
+    #[test]
+    fn missing_samples_are_ambiguous() {
+        let (samples, actual) = parse_line(include_str!("../test_input_single"));
+        let without = |drop: &[usize]| -> Vec<SevenSegDisplay> {
+            samples.iter().enumerate().filter(|(i, _)| !drop.contains(i)).map(|(_, &d)| d).collect()
+        };
+        // without 9, 6 and 4, two wirings fit and read the output differently
+        let Err(WiringError::Ambiguous(candidates)) = solve_wiring(&without(&[5, 6, 7])) else {
+            panic!("expected an ambiguous wiring");
+        };
+        assert_eq!(candidates.len(), 2);
+        assert!(candidates.contains(&solve_wiring(&samples).unwrap()));
+        assert_eq!(possible_outputs(&actual, &candidates), [Some(2323), Some(5353)]);
+
+        // without 2, 7 and 1 two still fit, but agree on the output
+        let Err(WiringError::Ambiguous(candidates)) = solve_wiring(&without(&[2, 4, 9])) else {
+            panic!("expected an ambiguous wiring");
+        };
+        assert_eq!(possible_outputs(&actual, &candidates), [Some(5353)]);
+        assert_eq!(decode_output(&actual, &entry_decoder(&without(&[2, 4, 9]), &actual).unwrap()), 5353);
+        assert!(matches!(entry_decoder(&without(&[5, 6, 7]), &actual), Err(WiringError::Ambiguous(_))));
+
+        // two different displays can't both be a 1
+        let ones = ["ab", "cd"].map(|s| SevenSegDisplay::from_str(s).unwrap());
+        assert_eq!(solve_wiring(&ones), Err(WiringError::NoWiring));
+    }
+}
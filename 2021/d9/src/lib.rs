@@ -0,0 +1,334 @@
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use std::iter::Iterator;
+use std::collections::{BTreeMap, HashMap};
+
+use aoc_core::{AocError, Solver, UnionFind};
+use aoc_grid::Grid;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct Height(u8);
+
+impl Height {
+    pub fn from_char(c: char) -> Option<Height> {
+        Some(Height(c.to_digit(10)? as u8))
+    }
+
+    pub fn risk_level(&self) -> u32 {
+        self.0 as u32 + 1
+    }
+}
+impl std::ops::Add for Height {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Height(self.0 + other.0)
+    }
+}
+
+impl fmt::Display for Height {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Ok(write!(f, "{}", self.0)?)
+    }
+}
+
+pub use aoc_grid::Pos;
+
+pub type HeightInfo<'a> = (Pos, &'a Height);
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    InvalidHeight { line: usize, col: usize, byte: u8 },
+    RaggedRow { line: usize, expected: usize, found: usize },
+    Empty,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::InvalidHeight { line, col, byte } =>
+                write!(f, "line {}, column {}: invalid height {:?}", line, col, *byte as char),
+            ParseError::RaggedRow { line, expected, found } =>
+                write!(f, "line {}: expected {} heights, found {}", line, expected, found),
+            ParseError::Empty => write!(f, "empty height map"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub type Result<T> = std::result::Result<T, ParseError>;
+
+#[derive(PartialEq, Eq)]
+pub struct HeightMap {
+    map: Grid<Height>,
+}
+
+impl HeightMap {
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(lines: impl Iterator<Item = String>) -> HeightMap {
+        let mut map = Vec::with_capacity(256);
+        let mut width = 0;
+        for l in lines {
+            let s = l.trim_end();
+            map.extend(s.chars().map(|c| Height::from_char(c).unwrap()));
+            width = s.len();
+        }
+        HeightMap { map: Grid::from_vec(width, map) }
+    }
+
+    /// Parse rows of ASCII digits straight out of a byte buffer. Accepts
+    /// `\n` or `\r\n` line endings and an optional trailing newline.
+    pub fn from_bytes(bytes: &[u8]) -> Result<HeightMap> {
+        let bytes = bytes.strip_suffix(b"\n").unwrap_or(bytes);
+        let width = bytes.iter().position(|&b| b == b'\n').unwrap_or(bytes.len());
+        let width = if bytes[..width].ends_with(b"\r") { width - 1 } else { width };
+        if width == 0 {
+            return Err(ParseError::Empty);
+        }
+
+        let mut map = Vec::with_capacity(bytes.len());
+        for (i, row) in bytes.split(|&b| b == b'\n').enumerate() {
+            let row = row.strip_suffix(b"\r").unwrap_or(row);
+            if row.len() != width {
+                return Err(ParseError::RaggedRow { line: i + 1, expected: width, found: row.len() });
+            }
+            if let Some(col) = row.iter().position(|b| !b.is_ascii_digit()) {
+                return Err(ParseError::InvalidHeight { line: i + 1, col: col + 1, byte: row[col] });
+            }
+            map.extend(row.iter().map(|b| Height(b - b'0')));
+        }
+
+        Ok(HeightMap { map: Grid::from_vec(width, map) })
+    }
+
+    /// Load a height map from a file, memory-mapping it when the `mmap`
+    /// feature is enabled
+    pub fn open(path: impl AsRef<Path>) -> io::Result<HeightMap> {
+        let file = File::open(path)?;
+
+        #[cfg(feature = "mmap")]
+        let bytes = unsafe { memmap2::Mmap::map(&file)? };
+        #[cfg(not(feature = "mmap"))]
+        let bytes = {
+            use std::io::Read;
+            let mut file = file;
+            let mut buf = Vec::new();
+            file.read_to_end(&mut buf)?;
+            buf
+        };
+
+        Self::from_bytes(&bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn width(&self) -> usize { self.map.width() }
+    pub fn height(&self) -> usize { self.map.height() }
+
+    pub fn iter_with_pos(&self) -> impl Iterator<Item = HeightInfo<'_>> {
+        self.map.iter()
+    }
+
+    pub fn adjacents(&self, p: Pos) -> impl Iterator<Item = HeightInfo<'_>> {
+        self.map.neighbors4(p).map(|p| (p, &self[p]))
+    }
+
+    /// Positions lower than all of their neighbours
+    pub fn low_points(&self) -> Vec<(Pos, Height)> {
+        self.iter_with_pos()
+            .filter(|&(p, h)| self.adjacents(p).all(|(_, ah)| ah > h))
+            .map(|(p, &h)| (p, h))
+            .collect()
+    }
+
+    /// Path from `from` to `to` whose cells, not counting `from`, have the
+    /// least total height, along with that total
+    pub fn lowest_path(&self, from: Pos, to: Pos) -> Option<(Vec<Pos>, u32)> {
+        // every step costs at least the lowest height on the map
+        let floor = self.map.cells().iter().min().map_or(0, |h| h.0 as u32);
+        aoc_search::astar(
+            &from,
+            |&p| self.adjacents(p).map(|(q, h)| (q, h.0 as u32)),
+            |&p| floor * p.manhattan(to) as u32,
+            |&p| p == to)
+    }
+}
+
+impl std::ops::Index<Pos> for HeightMap {
+    type Output = Height;
+
+    fn index(&self, p: Pos) -> &Self::Output {
+        &self.map[p]
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Basin(pub Pos, pub usize);
+
+pub type BasinMap = Grid<Basin>;
+
+/// Labels every cell with the low point its basin drains to, along with the
+/// size of each basin. Cells below height 9 are joined to their neighbours
+/// below 9, and each basin's low point is its lowest cell, the first in
+/// reading order if several are as low.
+pub fn find_basins(map: &HeightMap) -> (BasinMap, HashMap<Pos, usize>) {
+    let width = map.width();
+    let index = |p: Pos| p.y * width + p.x;
+    let in_basin = |h: &Height| *h < Height(9);
+
+    let mut sets = UnionFind::new(width * map.height());
+    for (p, _) in map.iter_with_pos().filter(|(_, h)| in_basin(h)) {
+        for (q, _) in map.adjacents(p).filter(|(_, h)| in_basin(h)) {
+            sets.union(index(p), index(q));
+        }
+    }
+
+    let mut low_points = HashMap::<usize, Pos>::with_capacity(sets.set_count());
+    for (p, h) in map.iter_with_pos().filter(|(_, h)| in_basin(h)) {
+        let low = low_points.entry(sets.find(index(p))).or_insert(p);
+        if *h < map[*low] {
+            *low = p;
+        }
+    }
+
+    let basins = map.iter_with_pos()
+        .map(|(p, h)| match in_basin(h) {
+            true => Basin(low_points[&sets.find(index(p))], 1),
+            false => Basin(p, 0),
+        })
+        .collect();
+    let basin_sizes = low_points.iter()
+        .map(|(&root, &low)| (low, sets.set_size(root)))
+        .collect();
+
+    (Grid::from_vec(width, basins), basin_sizes)
+}
+
+/// Outline of a basin
+#[derive(Debug, PartialEq, Eq)]
+pub struct BasinBoundary {
+    pub low_point: Pos,
+    /// Cells of the basin next to a height 9 or the edge of the map, in
+    /// reading order
+    pub cells: Vec<Pos>,
+    /// Sides of the basin's cells that don't face another of its cells
+    pub perimeter: usize,
+}
+
+/// The outline of every basin labelled by `find_basins`, ordered by low point
+pub fn basin_boundaries(basins: &BasinMap) -> Vec<BasinBoundary> {
+    let mut boundaries = BTreeMap::new();
+    for (p, &Basin(low_point, _)) in basins.iter().filter(|(_, b)| b.1 != 0) {
+        let inside = basins.neighbors4(p)
+            .filter(|&q| basins[q].1 != 0 && basins[q].0 == low_point)
+            .count();
+        let boundary = boundaries.entry(low_point)
+            .or_insert_with(|| BasinBoundary { low_point, cells: vec!(), perimeter: 0 });
+        if inside < 4 {
+            boundary.cells.push(p);
+            boundary.perimeter += 4 - inside;
+        }
+    }
+    boundaries.into_values().collect()
+}
+
+/// Product of the three largest basin sizes
+pub fn largest_basins_score(basin_sizes: &HashMap<Pos, usize>) -> usize {
+    let mut sizes_ordered: Vec<usize> = basin_sizes.values().copied().collect();
+    sizes_ordered.sort_by(|a, b| b.cmp(a));
+    sizes_ordered[0..3].iter().product()
+}
+
+pub struct Puzzle {
+    map: HeightMap,
+}
+
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> std::result::Result<Self, AocError> {
+        let map = HeightMap::from_bytes(input.as_bytes()).map_err(|e| AocError::invalid(e.to_string()))?;
+        Ok(Puzzle { map })
+    }
+
+    fn part1(&self) -> String {
+        self.map.low_points().iter().map(|(_, h)| h.risk_level()).sum::<u32>().to_string()
+    }
+
+    fn part2(&self) -> String {
+        largest_basins_score(&find_basins(&self.map).1).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test_input");
+
+    #[test]
+    fn from_bytes_matches_from_str() {
+        let by_lines = HeightMap::from_str(EXAMPLE.lines().map(String::from));
+        let by_bytes = HeightMap::from_bytes(EXAMPLE.as_bytes()).unwrap();
+
+        assert_eq!((by_bytes.width(), by_bytes.height()), (10, 5));
+        assert!(by_lines.map == by_bytes.map);
+    }
+
+    #[test]
+    fn from_bytes_accepts_crlf() {
+        let map = HeightMap::from_bytes(b"219\r\n398\r\n").unwrap();
+        assert_eq!((map.width(), map.height()), (3, 2));
+        assert!(map[Pos::new(2, 1)] == Height(8));
+    }
+
+    #[test]
+    fn from_bytes_reports_bad_input() {
+        assert_eq!(HeightMap::from_bytes(b"219\n3x8").err(),
+            Some(ParseError::InvalidHeight { line: 2, col: 2, byte: b'x' }));
+        assert_eq!(HeightMap::from_bytes(b"219\n39\n").err(),
+            Some(ParseError::RaggedRow { line: 2, expected: 3, found: 2 }));
+        assert_eq!(HeightMap::from_bytes(b"\n").err(), Some(ParseError::Empty));
+    }
+
+    #[test]
+    fn lowest_path_follows_the_valleys() {
+        let map = HeightMap::from_bytes(EXAMPLE.as_bytes()).unwrap();
+        let (path, total) = map.lowest_path(Pos::new(0, 0), Pos::new(9, 4)).unwrap();
+        assert_eq!(total, 58);
+        assert_eq!(total, path[1..].iter().map(|&p| map[p].0 as u32).sum::<u32>());
+        assert!(path.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+
+        assert_eq!(map.lowest_path(Pos::new(9, 0), Pos::new(9, 0)), Some((vec!(Pos::new(9, 0)), 0)));
+    }
+
+    #[test]
+    fn basin_outlines() {
+        let map = HeightMap::from_bytes(EXAMPLE.as_bytes()).unwrap();
+        let (basins, sizes) = find_basins(&map);
+        let boundaries = basin_boundaries(&basins);
+        assert_eq!(boundaries.len(), 4);
+
+        // the top left basin: 21 / 3 along the top edge
+        let top_left = &boundaries[0];
+        assert_eq!(top_left.low_point, Pos::new(1, 0));
+        assert_eq!(top_left.cells, [Pos::new(0, 0), Pos::new(1, 0), Pos::new(0, 1)]);
+        assert_eq!(top_left.perimeter, 8);
+
+        for b in &boundaries {
+            assert!(b.cells.len() <= sizes[&b.low_point]);
+            assert!(b.cells.iter().all(|&p| basins[p].0 == b.low_point && map[p] != Height(9)));
+        }
+        let middle = boundaries.iter().find(|b| b.low_point == Pos::new(2, 2)).unwrap();
+        assert_eq!(sizes[&middle.low_point], 14);
+        // 2,2 3,2 4,2 and 1,3 are surrounded by the basin
+        assert_eq!(middle.cells.len(), 10);
+        assert!(!middle.cells.contains(&Pos::new(2, 2)));
+    }
+
+    /// The answers given in the puzzle text for its example
+    #[test]
+    fn example_answers() {
+        let puzzle = Puzzle::parse(include_str!("../test_input"));
+        assert_eq!(puzzle.part1(), "15");
+        assert_eq!(puzzle.part2(), "1134");
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::time::Instant;
+
+use aoc_core::color::{paint, Color};
+use aoc_core::rng::Rng;
+use aoc_core::InputSource;
+
+use d9::*;
+
+fn part1(map: &HeightMap) {
+    for r in 0..map.height() {
+        for c in 0..map.width() {
+            print!("{}", map[Pos::new(c, r)]);
+        }
+        println!();
+    }
+
+    let lows = map.low_points();
+    println!("lows:");
+    for (p, h) in &lows {
+        println!("{}:{}", p, h);
+    }
+
+    let risk: u32 = lows.iter().map(|(_, h)| h.risk_level()).sum();
+    println!("risk: {}", risk);
+}
+
+fn part2(map: &HeightMap) {
+    let (basin_map, basin_sizes) = find_basins(map);
+
+    // with --color each basin is drawn in its own colour, numbered by where
+    // its low point is in reading order
+    let mut lows: Vec<Pos> = basin_sizes.keys().copied().collect();
+    lows.sort_by_key(|p| (p.y, p.x));
+    let colors: HashMap<Pos, Color> = lows.into_iter().enumerate().map(|(i, p)| (p, Color::nth(i))).collect();
+    for r in 0..map.height() {
+        for c in 0..map.width() {
+            let Basin(low, mark) = basin_map[Pos::new(c, r)];
+            match colors.get(&low) {
+                Some(&color) if mark > 0 => print!("{}", paint(mark, color)),
+                _ => print!("{}", mark),
+            }
+        }
+        println!();
+    }
+
+    println!("{:?}", &basin_sizes);
+
+    println!("{}", largest_basins_score(&basin_sizes));
+}
+
+/// Draw each basin's outline cells over the map and list its perimeter
+fn boundaries(map: &HeightMap) {
+    let (basin_map, _) = find_basins(map);
+    let boundaries = basin_boundaries(&basin_map);
+    let outline: HashSet<Pos> = boundaries.iter().flat_map(|b| b.cells.iter().copied()).collect();
+    for r in 0..map.height() {
+        for c in 0..map.width() {
+            let p = Pos::new(c, r);
+            // cells outside every basin are the height 9s
+            print!("{}", if basin_map[p].1 == 0 { '#' } else if outline.contains(&p) { 'o' } else { '.' });
+        }
+        println!();
+    }
+    for b in &boundaries {
+        println!("{}: {} cells on the outline, perimeter {}", b.low_point, b.cells.len(), b.perimeter);
+    }
+}
+
+/// Time line-based and byte-based parsing of a generated `dim`x`dim` grid
+fn bench_parse(dim: usize) {
+    let mut bytes = Vec::with_capacity((dim + 1) * dim);
+    let mut rng = Rng::seed_from(0x9e3779b9);
+    for _ in 0..dim {
+        for _ in 0..dim {
+            bytes.push(b'0' + rng.below(10) as u8);
+        }
+        bytes.push(b'\n');
+    }
+
+    let t = Instant::now();
+    let text = String::from_utf8(bytes.clone()).unwrap();
+    let by_lines = HeightMap::from_str(text.lines().map(String::from));
+    println!("from_str:   {:?}", t.elapsed());
+
+    let t = Instant::now();
+    let by_bytes = HeightMap::from_bytes(&bytes).unwrap();
+    println!("from_bytes: {:?}", t.elapsed());
+
+    assert!(by_lines == by_bytes);
+}
+
+/// Print the least-height path from `from` to `to` over the map
+fn lowest_path(map: &HeightMap, from: Pos, to: Pos) {
+    let Some((path, total)) = map.lowest_path(from, to) else {
+        return println!("{} is out of reach of {}", to, from);
+    };
+    for r in 0..map.height() {
+        for c in 0..map.width() {
+            let p = Pos::new(c, r);
+            print!("{}", if path.contains(&p) { map[p].to_string() } else { ".".into() });
+        }
+        println!();
+    }
+    println!("{} steps, total height {}", path.len() - 1, total);
+}
+
+fn main() {
+    // the map can be named with --input or as the only argument; `--path x,y
+    // x,y` finds the least-height path between two positions instead of the
+    // basins, and --boundaries draws the outline of each basin. --part
+    // 1|2|both picks which parts to run, and --color draws the basins in
+    // colour.
+    let (mut input, parts, args) = aoc_core::input_and_parts();
+    let mut args = args.into_iter();
+    let mut ends = None;
+    let mut outlines = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--bench" => {
+                let dim = args.next().map_or(4000, |n| n.parse().unwrap());
+                return bench_parse(dim);
+            },
+            "--path" => {
+                let mut pos = || -> Pos { args.next().expect("--path needs two x,y positions").parse().unwrap() };
+                ends = Some((pos(), pos()));
+            },
+            "--boundaries" => outlines = true,
+            "--color" => aoc_core::color::enable(true),
+            _ if input == InputSource::Stdin => input = InputSource::File(arg.into()),
+            _ => panic!("unknown argument: {}", arg),
+        }
+    }
+    let map = match input.path() {
+        Some(path) => HeightMap::open(path).unwrap(),
+        None => {
+            let mut bytes = Vec::new();
+            aoc_core::open_input(&input).read_to_end(&mut bytes).unwrap();
+            HeightMap::from_bytes(&bytes).unwrap()
+        },
+    };
+
+    println!("map dim: {}x{}", map.width(), map.height());
+
+    if let Some((from, to)) = ends {
+        return lowest_path(&map, from, to);
+    }
+    if outlines {
+        return boundaries(&map);
+    }
+
+    if parts.has(1) {
+        part1(&map);
+    }
+    if parts.has(2) {
+        part2(&map);
+    }
+}
@@ -0,0 +1,367 @@
+//! The four-register ALU the d24 MONAD programs run on: its instruction
+//! set, loading programs from their text and running them. Without the
+//! default `std` feature it's `no_std`, with programs parsed from text
+//! already in memory.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{AddAssign, DivAssign, Index, IndexMut, RemAssign, MulAssign};
+#[cfg(feature = "std")]
+use std::io::BufRead;
+
+use aoc_core::AocError;
+
+pub type Word = i64;
+
+pub const REGISTERS: [RegisterId; 4] = [RegisterId::X, RegisterId::Y, RegisterId::Z, RegisterId::W];
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Instruction {
+    Op1(Op1, RegisterId),
+    Op2(Op2, RegisterId, Operand),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Op1 {
+    Input
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Op2 {
+    Add,
+    Mul,
+    Div,
+    Mod,
+    Eql,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RegisterId {
+    X,
+    Y,
+    Z,
+    W,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operand {
+    Register(RegisterId),
+    Literal(Word),
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Register(Word);
+impl Register {
+    pub fn value(&self) -> Word { self.0 }
+
+    pub fn set(&mut self, v: Word) {
+        self.0 = v
+    }
+}
+impl From<Word> for Register {
+    fn from(r: Word) -> Self { Register(r) }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Alu {
+    x: Register,
+    y: Register,
+    z: Register,
+    w: Register,
+}
+
+impl Default for Alu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AddAssign<Word> for Register {
+    fn add_assign(&mut self, lhs: Word) { self.0 = self.0 + lhs }
+}
+impl DivAssign<Word> for Register {
+    fn div_assign(&mut self, lhs: Word) { self.0 = self.0 / lhs }
+}
+impl MulAssign<Word> for Register {
+    fn mul_assign(&mut self, lhs: Word) { self.0 = self.0 * lhs }
+}
+impl RemAssign<Word> for Register {
+    fn rem_assign(&mut self, lhs: Word) { self.0 = self.0 % lhs }
+}
+
+impl Alu {
+    pub fn new() -> Alu {
+        Alu { x: 0.into(), y: 0.into(), z: 0.into(), w: 0.into() }
+    }
+
+    pub fn initialized(x: Word, y: Word, z: Word, w: Word) -> Alu {
+        Alu { x: x.into(), y: y.into(), z: z.into(), w: w.into() }
+    }
+
+    pub fn execute<'a, 'b>(
+        &mut self,
+        instructions: impl Iterator<Item = &'a Instruction>,
+        inputs: impl IntoIterator<Item = &'b Word>
+    ) -> (Word, Word, Word, Word) {
+        let mut inputs = inputs.into_iter();
+        for i in instructions {
+            match i {
+                Instruction::Op1(Op1::Input, r) => self[*r].set(*inputs.next().unwrap()),
+                Instruction::Op2(op, r, l) => self.execute_op2(*op, *r, *l),
+            }
+        }
+        self.registers()
+    }
+
+    /// Runs `program` like `execute`, but stops with an error at the first
+    /// instruction the puzzle says crashes the ALU: an input with none left,
+    /// a division by zero or a modulo of a negative or by a non-positive
+    /// number. The error gives the instruction's line in the program.
+    pub fn run(&mut self, program: &[Instruction], inputs: impl IntoIterator<Item = Word>) -> Result<(), AocError> {
+        let mut inputs = inputs.into_iter();
+        for (i, inst) in program.iter().enumerate() {
+            let crash = |why: &str| AocError::invalid(format!("{}: {}", inst, why)).at_line(i + 1);
+            match *inst {
+                Instruction::Op1(Op1::Input, r) => self[r].set(inputs.next().ok_or_else(|| crash("no input left"))?),
+                Instruction::Op2(op, r, operand) => {
+                    let v = self.operand(operand);
+                    match op {
+                        Op2::Div if v == 0 => return Err(crash("division by zero")),
+                        Op2::Mod if self[r].value() < 0 || v <= 0 => return Err(crash("modulo out of range")),
+                        _ => self.execute_op2(op, r, operand),
+                    }
+                },
+            }
+        }
+        Ok(())
+    }
+
+    /// x, y, z and w
+    pub fn registers(&self) -> (Word, Word, Word, Word) {
+        (self.x.value(), self.y.value(), self.z.value(), self.w.value())
+    }
+
+    fn operand(&self, operand: Operand) -> Word {
+        match operand {
+            Operand::Literal(n) => n,
+            Operand::Register(r) => self[r].value(),
+        }
+    }
+
+    pub fn execute_op2(&mut self, op: Op2, reg: RegisterId, operand: Operand) {
+        let v = self.operand(operand);
+        let r0 = &mut self[reg];
+        match op {
+            Op2::Add => *r0 += v,
+            Op2::Div => *r0 /= v,
+            Op2::Mod => *r0 %= v,
+            Op2::Mul => *r0 *= v,
+            Op2::Eql => r0.set(if r0.value() == v { 1 } else { 0 }),
+        }
+    }
+}
+impl Index<RegisterId> for Alu {
+    type Output = Register;
+
+    fn index(&self, r: RegisterId) -> &Self::Output {
+        match r {
+            RegisterId::X => &self.x,
+            RegisterId::Y => &self.y,
+            RegisterId::Z => &self.z,
+            RegisterId::W => &self.w,
+        }
+    }
+}
+impl IndexMut<RegisterId> for Alu {
+    fn index_mut(&mut self, r: RegisterId) -> &mut Self::Output {
+        match r {
+            RegisterId::X => &mut self.x,
+            RegisterId::Y => &mut self.y,
+            RegisterId::Z => &mut self.z,
+            RegisterId::W => &mut self.w,
+        }
+    }
+}
+
+impl fmt::Debug for Alu {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{ x={} y={} z={} w={} }}", self.x.value(), self.y.value(), self.z.value(), self.w.value())
+    }
+}
+
+impl fmt::Display for RegisterId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            RegisterId::X => "x",
+            RegisterId::Y => "y",
+            RegisterId::Z => "z",
+            RegisterId::W => "w",
+        })
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operand::Register(r) => write!(f, "{}", r),
+            Operand::Literal(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+/// One line of a MONAD program, as `Instruction::try_from` reads it
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Instruction::Op1(Op1::Input, r) => write!(f, "inp {}", r),
+            Instruction::Op2(op, r, operand) => {
+                let name = match op {
+                    Op2::Add => "add",
+                    Op2::Mul => "mul",
+                    Op2::Div => "div",
+                    Op2::Mod => "mod",
+                    Op2::Eql => "eql",
+                };
+                write!(f, "{} {} {}", name, r, operand)
+            },
+        }
+    }
+}
+
+impl TryFrom<u8> for RegisterId {
+    type Error = AocError;
+    fn try_from(c: u8) -> Result<Self, Self::Error> {
+        match c {
+            b'x' => Ok(RegisterId::X),
+            b'y' => Ok(RegisterId::Y),
+            b'z' => Ok(RegisterId::Z),
+            b'w' => Ok(RegisterId::W),
+            _ => Err(AocError::invalid(format!("unknown register {:?}", c as char))),
+        }
+    }
+}
+
+impl Operand {
+    fn try_from(s: &str) -> Result<Self, AocError> {
+        if s.len() == 1 {
+            if let Ok(r) = RegisterId::try_from(s.as_bytes()[0]) {
+                return Ok(Operand::Register(r));
+            }
+        }
+
+        Ok(Operand::Literal(s.parse::<Word>()?))
+    }
+}
+
+impl TryFrom<&str> for Instruction
+{
+    type Error = AocError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let mut words = s.split(' ');
+        let instr = words.next().unwrap_or_default();
+        let reg = match words.next() {
+            Some(r) if r.len() == 1 => r.as_bytes()[0].try_into()?,
+            _ => return Err(AocError::invalid(format!("expected a register in {:?}", s))),
+        };
+
+        let op = words.next().map(Operand::try_from).transpose()?;
+        if words.next().is_some() {
+            return Err(AocError::invalid(format!("too many operands in {:?}", s)));
+        }
+        Ok(match (instr, op) {
+            ("inp", None) => Instruction::Op1(Op1::Input, reg),
+            ("add", Some(op)) => Instruction::Op2(Op2::Add, reg, op),
+            ("mul", Some(op)) => Instruction::Op2(Op2::Mul, reg, op),
+            ("div", Some(op)) => Instruction::Op2(Op2::Div, reg, op),
+            ("mod", Some(op)) => Instruction::Op2(Op2::Mod, reg, op),
+            ("eql", Some(op)) => Instruction::Op2(Op2::Eql, reg, op),
+            ("inp" | "add" | "mul" | "div" | "mod" | "eql", _) => {
+                return Err(AocError::invalid(format!("wrong number of operands in {:?}", s)))
+            },
+            _ => return Err(AocError::invalid(format!("unknown instruction {:?}", instr))),
+        })
+    }
+}
+impl TryFrom<&String> for Instruction
+{
+    type Error = AocError;
+    fn try_from(s: &String) -> Result<Self, Self::Error> {
+        Self::try_from(s.as_str())
+    }
+}
+
+/// A program, one instruction per line
+#[cfg(feature = "std")]
+pub fn read_program(reader: impl BufRead) -> Result<Vec<Instruction>, AocError> {
+    let mut instructions = Vec::with_capacity(100);
+
+    for (i, l) in reader.lines().enumerate() {
+        let s = l?;
+        instructions.push(Instruction::try_from(&s).map_err(|e| e.at_line(i + 1))?);
+    }
+
+    Ok(instructions)
+}
+
+pub fn parse_program(text: &str) -> Result<Vec<Instruction>, AocError> {
+    aoc_core::parse_lines(text, |l| Instruction::try_from(l))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negate() {
+        let instr = [
+            Instruction::Op1(Op1::Input, RegisterId::X),
+            Instruction::Op2(Op2::Mul, RegisterId::X, Operand::Literal(-1))
+        ];
+
+        let mut alu = Alu::new();
+        let (x, ..) = alu.execute(instr.iter(), [5].iter());
+
+        assert_eq!(-5, x);
+    }
+
+    #[test]
+    fn rejects_malformed_instructions() {
+        assert!(matches!(Instruction::try_from("add x -3"), Ok(Instruction::Op2(Op2::Add, RegisterId::X, Operand::Literal(-3)))));
+        assert_eq!(Instruction::try_from("sub x 1").err().unwrap().to_string(), "unknown instruction \"sub\"");
+        assert!(Instruction::try_from("inp q").is_err());
+        assert!(Instruction::try_from("inp").is_err());
+        assert!(Instruction::try_from("inp w 2").is_err());
+        assert!(Instruction::try_from("mul x").is_err());
+        assert!(Instruction::try_from("mul x y z").is_err());
+    }
+
+    #[test]
+    fn binary_digits() {
+        // the puzzle's example: w, x, y and z get the lowest four bits
+        let program = parse_program("inp w\nadd z w\nmod z 2\ndiv w 2\nadd y w\nmod y 2\ndiv w 2\nadd x w\nmod x 2\ndiv w 2\nmod w 2\n").unwrap();
+        let mut alu = Alu::new();
+        alu.run(&program, [13]).unwrap();
+        assert_eq!(alu.registers(), (1, 0, 1, 1));
+
+        let text: String = program.iter().map(|i| format!("{}\n", i)).collect();
+        assert!(parse_program(&text).unwrap() == program);
+        assert_eq!(parse_program("inp w\nadd w\n").err().unwrap().to_string(), "line 2: wrong number of operands in \"add w\"");
+    }
+
+    #[test]
+    fn crashes_stop_the_run() {
+        let program = parse_program("inp x\ninp y\ndiv x y\nmod x y\n").unwrap();
+        assert_eq!(Alu::new().run(&program, [6]).unwrap_err().to_string(), "line 2: inp y: no input left");
+        assert_eq!(Alu::new().run(&program, [6, 0]).unwrap_err().to_string(), "line 3: div x y: division by zero");
+        assert_eq!(Alu::new().run(&program, [-6, 1]).unwrap_err().to_string(), "line 4: mod x y: modulo out of range");
+        let mut alu = Alu::new();
+        alu.run(&program, [7, 2]).unwrap();
+        assert_eq!(alu[RegisterId::X].value(), 1);
+    }
+}
\ No newline at end of file
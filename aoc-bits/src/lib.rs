@@ -0,0 +1,200 @@
+//! Reading a byte stream a few bits at a time, most significant bit first,
+//! the way the d16 transmissions pack their fields
+
+#![no_std]
+
+use core::ops;
+
+/// Cursor over the bits of a byte slice. Reads past the end return `None`
+/// and leave the cursor where it was.
+#[derive(Clone, Debug)]
+pub struct BitsReader<'a> {
+    stream: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BitsReader<'a> {
+    pub fn new(stream: &'a [u8]) -> BitsReader<'a> {
+        BitsReader { stream, pos: 0 }
+    }
+
+    /// Bits read or skipped so far
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Bits in the whole stream
+    pub fn total_bits(&self) -> usize {
+        self.stream.len() * 8
+    }
+
+    /// Bits left to read
+    pub fn remaining(&self) -> usize {
+        self.total_bits().saturating_sub(self.pos)
+    }
+
+    /// Whether the next bit starts a byte
+    pub fn is_aligned(&self) -> bool {
+        self.pos.is_multiple_of(8)
+    }
+
+    /// Bits to skip to reach the start of the next byte, 0 if aligned
+    pub fn bits_to_alignment(&self) -> usize {
+        (8 - self.pos % 8) % 8
+    }
+
+    /// Skips to the start of the next byte, returning how many bits that was
+    pub fn align(&mut self) -> usize {
+        let skipped = self.bits_to_alignment();
+        self.pos += skipped;
+        skipped
+    }
+
+    /// Moves past `bits` bits without reading them, or `None` if there
+    /// aren't that many left
+    pub fn skip(&mut self, bits: usize) -> Option<()> {
+        if bits > self.remaining() {
+            return None;
+        }
+        self.pos += bits;
+        Some(())
+    }
+
+    /// The next `bits` bits (at most 64) as a number, without moving past
+    /// them
+    pub fn peek(&self, bits: u32) -> Option<u64> {
+        assert!(bits <= 64, "can't read more than 64 bits at a time");
+        if bits as usize > self.remaining() {
+            return None;
+        }
+        let mut value = 0u64;
+        let mut pos = self.pos;
+        let mut left = bits;
+        while left > 0 {
+            let offset = (pos % 8) as u32;
+            let take = (8 - offset).min(left);
+            let byte = self.stream[pos / 8] >> (8 - offset - take);
+            value = (value << take) | (byte & (0xff >> (8 - take))) as u64;
+            pos += take as usize;
+            left -= take;
+        }
+        Some(value)
+    }
+
+    /// The next `bits` bits (at most 64) as a number
+    pub fn read(&mut self, bits: u32) -> Option<u64> {
+        let value = self.peek(bits)?;
+        self.pos += bits as usize;
+        Some(value)
+    }
+
+    pub fn read_bit(&mut self) -> Option<bool> {
+        self.read(1).map(|b| b == 1)
+    }
+
+    /// The next `bits` bits, at most 8
+    pub fn read_u8(&mut self, bits: u32) -> Option<u8> {
+        assert!(bits <= 8, "{} bits don't fit in a u8", bits);
+        self.read(bits).map(|v| v as u8)
+    }
+
+    /// The next `bits` bits, at most 16
+    pub fn read_u16(&mut self, bits: u32) -> Option<u16> {
+        assert!(bits <= 16, "{} bits don't fit in a u16", bits);
+        self.read(bits).map(|v| v as u16)
+    }
+
+    /// The next `bits` bits, at most 32
+    pub fn read_u32(&mut self, bits: u32) -> Option<u32> {
+        assert!(bits <= 32, "{} bits don't fit in a u32", bits);
+        self.read(bits).map(|v| v as u32)
+    }
+}
+
+/// Skips bits like `skip`, but may go past the end, after which every read
+/// returns `None`
+impl ops::AddAssign<usize> for BitsReader<'_> {
+    fn add_assign(&mut self, bits: usize) {
+        self.pos += bits;
+    }
+}
+
+pub trait IntoBitsReader {
+    fn read_bits(&self) -> BitsReader<'_>;
+}
+
+impl<B> IntoBitsReader for B where B: AsRef<[u8]> {
+    fn read_bits(&self) -> BitsReader<'_> {
+        BitsReader::new(self.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_across_byte_boundaries() {
+        let data = [0b1101_0010, 0b1111_1110, 0b0010_1000];
+        let mut reader = data.read_bits();
+        assert_eq!(reader.read_u8(3), Some(0b110));
+        assert_eq!(reader.read_u8(3), Some(0b100));
+        assert_eq!(reader.read_bit(), Some(true));
+        assert_eq!(reader.read_u16(11), Some(0b011_1111_1000));
+        assert_eq!(reader.position(), 18);
+        assert_eq!(reader.read_u32(6), Some(0b10_1000));
+        assert_eq!(reader.remaining(), 0);
+        assert_eq!(reader.read_bit(), None);
+    }
+
+    #[test]
+    fn peeking_leaves_the_position() {
+        let data = [0xab, 0xcd];
+        let mut reader = data.read_bits();
+        reader += 4;
+        assert_eq!(reader.peek(8), Some(0xbc));
+        assert_eq!(reader.peek(8), Some(0xbc));
+        assert_eq!(reader.position(), 4);
+        assert_eq!(reader.peek(13), None);
+        assert_eq!(reader.peek(12), Some(0xbcd));
+        assert_eq!(reader.peek(0), Some(0));
+    }
+
+    #[test]
+    fn wide_reads() {
+        let data = [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0xff];
+        let mut reader = data.read_bits();
+        assert_eq!(reader.read(64), Some(0x0123_4567_89ab_cdef));
+        let mut reader = data.read_bits();
+        reader += 4;
+        assert_eq!(reader.read(64), Some(0x1234_5678_9abc_deff));
+        assert_eq!(reader.read(5), None);
+        assert_eq!(reader.read(4), Some(0xf));
+    }
+
+    #[test]
+    fn alignment() {
+        let data = [0u8; 3];
+        let mut reader = data.read_bits();
+        assert!(reader.is_aligned());
+        assert_eq!(reader.align(), 0);
+        reader.skip(3).unwrap();
+        assert!(!reader.is_aligned());
+        assert_eq!(reader.bits_to_alignment(), 5);
+        assert_eq!(reader.align(), 5);
+        assert_eq!(reader.position(), 8);
+        assert_eq!(reader.skip(17), None);
+        assert_eq!(reader.position(), 8);
+        assert_eq!(reader.skip(16), Some(()));
+
+        reader += 10;
+        assert_eq!(reader.remaining(), 0);
+        assert_eq!(reader.read_bit(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "9 bits don't fit in a u8")]
+    fn narrow_reads_check_their_width() {
+        [0u8; 2].read_bits().read_u8(9);
+    }
+}
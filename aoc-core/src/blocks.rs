@@ -0,0 +1,76 @@
+//! Inputs made of blocks of lines with blank lines between them, like d4's
+//! boards or d19's scanner reports, split up without each day tracking
+//! where one block ends.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// `blocks()` on any iterator over lines, as `&str` or `String`
+pub trait LineBlocks: Iterator + Sized {
+    /// The runs of non-blank lines, each line with its trailing whitespace
+    /// (and any `\r`) trimmed. Several blank lines in a row, or blank lines
+    /// at the start or end, don't make empty blocks.
+    fn blocks(self) -> Blocks<Self> {
+        Blocks { lines: self, read: 0, start: 0 }
+    }
+}
+
+impl<I> LineBlocks for I where I: Iterator, I::Item: Into<String> {}
+
+pub struct Blocks<I> {
+    lines: I,
+    read: usize,
+    start: usize,
+}
+
+impl<I> Blocks<I> {
+    /// Line number, from 1, of the first line of the block last returned
+    pub fn start(&self) -> usize {
+        self.start
+    }
+}
+
+impl<I> Iterator for Blocks<I> where I: Iterator, I::Item: Into<String> {
+    type Item = Vec<String>;
+
+    fn next(&mut self) -> Option<Vec<String>> {
+        let mut block = Vec::new();
+        for line in self.lines.by_ref() {
+            self.read += 1;
+            let mut line = line.into();
+            line.truncate(line.trim_end().len());
+            match (line.trim_start().is_empty(), block.is_empty()) {
+                (true, true) => continue,
+                (true, false) => break,
+                (false, true) => {
+                    self.start = self.read;
+                    block.push(line);
+                },
+                (false, false) => block.push(line),
+            }
+        }
+        (!block.is_empty()).then_some(block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_on_blank_lines() {
+        let blocks: Vec<Vec<String>> = "\na\nb \r\n\n \n\nc\n\n".lines().blocks().collect();
+        assert_eq!(blocks, [vec!["a", "b"], vec!["c"]]);
+
+        let mut lines = ["x".to_string(), String::new(), "y".to_string()].into_iter();
+        assert_eq!(lines.by_ref().blocks().next(), Some(vec!["x".to_string()]));
+        assert_eq!(lines.next().as_deref(), Some("y"));
+        assert_eq!("".lines().blocks().next(), None);
+
+        let mut blocks = "\n\na\n\nb\nc\n".lines().blocks();
+        blocks.next();
+        assert_eq!(blocks.start(), 3);
+        blocks.next();
+        assert_eq!(blocks.start(), 5);
+    }
+}
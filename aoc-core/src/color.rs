@@ -0,0 +1,104 @@
+//! ANSI colours for the grids and maps the days draw. Library code paints
+//! what it draws whether or not anyone wants colour; nothing but the plain
+//! text comes out unless the binary turned colour on with `enable`, for
+//! its `--color` flag, so tests, golden files and answers stay plain.
+
+use core::fmt;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Paint from now on, or not
+pub fn enable(on: bool) {
+    ENABLED.store(on, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Color {
+    /// Colours that stand apart on a dark or light terminal, for telling
+    /// things apart by index
+    pub const CYCLE: [Color; 6] = [Color::Red, Color::Green, Color::Yellow, Color::Blue, Color::Magenta, Color::Cyan];
+
+    /// The `n`th colour of `CYCLE`, round again past the end
+    pub fn nth(n: usize) -> Color {
+        Color::CYCLE[n % Color::CYCLE.len()]
+    }
+
+    /// SGR foreground code
+    fn code(self) -> u8 {
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+            Color::White => 37,
+        }
+    }
+}
+
+/// `value` in `color` when colour is on
+pub fn paint<T: fmt::Display>(value: T, color: Color) -> Painted<T> {
+    Painted { value, color, bold: false }
+}
+
+/// A value drawn in a colour, made by `paint`
+#[derive(Clone, Copy, Debug)]
+pub struct Painted<T> {
+    value: T,
+    color: Color,
+    bold: bool,
+}
+
+impl<T> Painted<T> {
+    pub fn bold(self) -> Painted<T> {
+        Painted { bold: true, ..self }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Painted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !enabled() {
+            return self.value.fmt(f);
+        }
+        // the value through `f` itself, so width and the like pad it the same
+        // either way
+        let bold = if self.bold { "1;" } else { "" };
+        write!(f, "\x1b[{}{}m", bold, self.color.code())?;
+        self.value.fmt(f)?;
+        write!(f, "\x1b[0m")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn paints_only_when_enabled() {
+        assert_eq!(format!("{}", paint('#', Color::Red)), "#");
+        enable(true);
+        assert_eq!(format!("{}", paint('#', Color::Red)), "\x1b[31m#\x1b[0m");
+        assert_eq!(format!("{}", paint(7, Color::nth(8)).bold()), "\x1b[1;33m7\x1b[0m");
+        assert_eq!(format!("{:>3}", paint(7, Color::Blue)), "\x1b[34m  7\x1b[0m");
+        enable(false);
+        assert_eq!(format!("{}", paint("A", Color::Cyan)), "A");
+        assert_eq!(format!("{:<3}|", paint("A", Color::Cyan)), "A  |");
+    }
+}
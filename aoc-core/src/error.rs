@@ -0,0 +1,120 @@
+//! One error type for every day's input parsing, so a malformed line is
+//! reported with its line number instead of a panic backtrace. Only needs
+//! `alloc`, apart from wrapping IO errors.
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+use core::num::ParseIntError;
+#[cfg(feature = "std")]
+use std::io;
+
+#[derive(Debug)]
+pub enum AocError {
+    #[cfg(feature = "std")]
+    Io(io::Error),
+    Int(ParseIntError),
+    /// Input that doesn't have the expected shape
+    Invalid(String),
+    /// Another error, on this (1-based) line of the input
+    Line(usize, Box<AocError>),
+}
+
+impl AocError {
+    pub fn invalid(msg: impl Into<String>) -> AocError {
+        AocError::Invalid(msg.into())
+    }
+
+    /// Tag with the line it came from, unless it's already tagged
+    pub fn at_line(self, line: usize) -> AocError {
+        match self {
+            AocError::Line(..) => self,
+            e => AocError::Line(line, Box::new(e)),
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            #[cfg(feature = "std")]
+            AocError::Io(e) => write!(f, "{}", e),
+            AocError::Int(e) => write!(f, "bad number: {}", e),
+            AocError::Invalid(msg) => write!(f, "{}", msg),
+            AocError::Line(n, e) => write!(f, "line {}: {}", n, e),
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            #[cfg(feature = "std")]
+            AocError::Io(e) => Some(e),
+            AocError::Int(e) => Some(e),
+            AocError::Invalid(_) => None,
+            AocError::Line(_, e) => Some(e.as_ref()),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for AocError {
+    fn from(e: io::Error) -> AocError {
+        AocError::Io(e)
+    }
+}
+
+impl From<ParseIntError> for AocError {
+    fn from(e: ParseIntError) -> AocError {
+        AocError::Int(e)
+    }
+}
+
+impl From<&str> for AocError {
+    fn from(msg: &str) -> AocError {
+        AocError::invalid(msg)
+    }
+}
+
+impl From<String> for AocError {
+    fn from(msg: String) -> AocError {
+        AocError::Invalid(msg)
+    }
+}
+
+/// Parse every line of `input` with `f`, stopping at the first failure and
+/// reporting which line it was
+pub fn parse_lines<T, E: Into<AocError>>(input: &str, mut f: impl FnMut(&str) -> Result<T, E>) -> Result<Vec<T>, AocError> {
+    input.lines()
+        .enumerate()
+        .map(|(i, line)| f(line).map_err(|e| e.into().at_line(i + 1)))
+        .collect()
+}
+
+/// The value, or the error printed to stderr and the process exited, for
+/// binaries that have nothing better to do with bad input
+#[cfg(feature = "std")]
+pub fn or_exit<T>(result: Result<T, AocError>) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_line_numbers() {
+        let err = parse_lines("1\n2\nx\n4", |l| l.parse::<u32>()).unwrap_err();
+        assert!(matches!(err, AocError::Line(3, _)));
+        assert_eq!(err.to_string(), "line 3: bad number: invalid digit found in string");
+
+        assert_eq!(parse_lines("1\n2", |l| l.parse::<u32>()).unwrap(), [1, 2]);
+        assert_eq!(AocError::from("nope").at_line(2).at_line(5).to_string(), "line 2: nope");
+    }
+}
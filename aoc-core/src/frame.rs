@@ -0,0 +1,73 @@
+//! Boxed blocks of ASCII art laid out side by side, so explain and
+//! visualization modes can show before/after states together
+
+/// A block of text with a titled border around it
+pub struct Frame {
+    title: String,
+    lines: Vec<String>,
+}
+
+fn width_of(s: &str) -> usize {
+    s.chars().count()
+}
+
+impl Frame {
+    pub fn new(title: &str, content: &str) -> Frame {
+        Frame { title: title.to_string(), lines: content.lines().map(String::from).collect() }
+    }
+
+    /// Width inside the border, wide enough for the content and the title
+    fn inner_width(&self) -> usize {
+        self.lines.iter()
+            .map(|l| width_of(l))
+            .chain(std::iter::once(width_of(&self.title) + 2))
+            .max()
+            .unwrap()
+    }
+
+    /// Bordered rows, padded with blank lines to `height` content rows
+    fn rows(&self, height: usize) -> Vec<String> {
+        let w = self.inner_width();
+        let title = if self.title.is_empty() { String::new() } else { format!("-{}-", self.title) };
+
+        let mut rows = Vec::with_capacity(height + 2);
+        rows.push(format!("+{}{}+", title, "-".repeat(w - width_of(&title))));
+        for r in 0..height {
+            let line = self.lines.get(r).map_or("", String::as_str);
+            rows.push(format!("|{}{}|", line, " ".repeat(w - width_of(line))));
+        }
+        rows.push(format!("+{}+", "-".repeat(w)));
+        rows
+    }
+}
+
+/// Frames next to each other, tops aligned, two spaces apart
+pub fn compose(frames: &[Frame]) -> String {
+    let height = frames.iter().map(|f| f.lines.len()).max().unwrap_or(0);
+    let columns: Vec<Vec<String>> = frames.iter().map(|f| f.rows(height)).collect();
+
+    let mut out = String::new();
+    for r in 0..height + 2 {
+        let row: Vec<&str> = columns.iter().map(|c| c[r].as_str()).collect();
+        out.push_str(&row.join("  "));
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn side_by_side() {
+        let before = Frame::new("before", "#.\n.#\n");
+        let after = Frame::new("", "##\n");
+        assert_eq!(compose(&[before, after]), concat!(
+            "+-before-+  +--+\n",
+            "|#.      |  |##|\n",
+            "|.#      |  |  |\n",
+            "+--------+  +--+\n",
+        ));
+    }
+}
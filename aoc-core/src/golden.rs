@@ -0,0 +1,67 @@
+//! Golden files: how a map or board is drawn, pinned by a copy kept next to
+//! the day's inputs, so a change to the drawing fails its test and shows up
+//! as a diff in review. Run the tests with `UPDATE_GOLDEN=1` to write the
+//! copies afresh after a change that was meant.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where golden file `name` lives for the crate in `dir`, which days give
+/// as `env!("CARGO_MANIFEST_DIR")`
+pub fn path(dir: &str, name: &str) -> PathBuf {
+    Path::new(dir).join("golden").join(name).with_extension("txt")
+}
+
+/// Panics unless `actual` matches golden file `name`, naming the first line
+/// that differs, or writes it as the golden file with `UPDATE_GOLDEN` set
+pub fn check(dir: &str, name: &str, actual: &str) {
+    let path = path(dir, name);
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| fs::write(&path, actual))
+            .unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+        return;
+    }
+
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("{}: {}, UPDATE_GOLDEN=1 writes it", path.display(), e));
+    if expected == actual {
+        return;
+    }
+    let (want, got) = (expected.lines().count(), actual.lines().count());
+    let differs = match expected.lines().zip(actual.lines()).position(|(a, b)| a != b) {
+        Some(i) => format!("differs from line {}", i + 1),
+        None if want != got => format!("differs from line {}", want.min(got) + 1),
+        // lines() drops the `\r`s and a last `\n`, so those are all that's left
+        None => "differs in line endings".to_string(),
+    };
+    panic!("{} {}, UPDATE_GOLDEN=1 rewrites it\n--- expected\n{}--- got\n{}",
+        path.display(), differs, expected, actual);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic;
+
+    #[test]
+    fn compares_with_the_file() {
+        let dir = env::temp_dir().join(format!("aoc-golden-{}", std::process::id()));
+        let dir = dir.to_str().unwrap();
+        fs::create_dir_all(path(dir, "map").parent().unwrap()).unwrap();
+        fs::write(path(dir, "map"), "#.\n.#\n").unwrap();
+
+        check(dir, "map", "#.\n.#\n");
+        let err = panic::catch_unwind(|| check(dir, "map", "#.\n##\n")).err().unwrap();
+        assert!(err.downcast_ref::<String>().unwrap().contains("differs from line 2"));
+        let err = panic::catch_unwind(|| check(dir, "map", "#.\n.#\n.#\n")).err().unwrap();
+        assert!(err.downcast_ref::<String>().unwrap().contains("differs from line 3"));
+        for endings in ["#.\r\n.#\r\n", "#.\n.#"] {
+            let err = panic::catch_unwind(|| check(dir, "map", endings)).err().unwrap();
+            assert!(err.downcast_ref::<String>().unwrap().contains("differs in line endings"));
+        }
+        assert!(panic::catch_unwind(|| check(dir, "missing", "")).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,112 @@
+//! Names swapped for small numbers. Puzzles that name things (caves,
+//! registers, wires) can intern each name once while parsing and then
+//! compare, hash and index by `Symbol` instead of by string. The text of
+//! every name is kept end to end in one buffer rather than a string each.
+
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::BuildHasher;
+
+/// An interned name, numbered from 0 in the order names were first seen,
+/// so it can index a `Vec` of whatever the puzzle keeps per name
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Interner {
+    /// Every name, one after another
+    text: String,
+    /// Where each symbol's name ends in `text`
+    ends: Vec<u32>,
+    /// Symbols by the hash of their name, colliding ones sharing a bucket
+    buckets: HashMap<u64, Vec<Symbol>>,
+    hasher: RandomState,
+}
+
+impl Interner {
+    pub fn new() -> Interner {
+        Interner::default()
+    }
+
+    /// The symbol for `name`, the same one every time it's given
+    pub fn intern(&mut self, name: &str) -> Symbol {
+        let hash = self.hasher.hash_one(name);
+        if let Some(&symbol) = self.bucket(hash).iter().find(|&&s| self.resolve(s) == name) {
+            return symbol;
+        }
+        let symbol = Symbol(self.ends.len().try_into().expect("too many names to intern"));
+        self.text.push_str(name);
+        self.ends.push(self.text.len().try_into().expect("too much text to intern"));
+        self.buckets.entry(hash).or_default().push(symbol);
+        symbol
+    }
+
+    /// The symbol for `name` if it has been interned
+    pub fn get(&self, name: &str) -> Option<Symbol> {
+        let hash = self.hasher.hash_one(name);
+        self.bucket(hash).iter().copied().find(|&s| self.resolve(s) == name)
+    }
+
+    fn bucket(&self, hash: u64) -> &[Symbol] {
+        self.buckets.get(&hash).map_or(&[], Vec::as_slice)
+    }
+
+    /// The name `symbol` stands for. Panics if it came from another
+    /// interner with more names.
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        let i = symbol.index();
+        let start = if i == 0 { 0 } else { self.ends[i - 1] as usize };
+        &self.text[start..self.ends[i] as usize]
+    }
+
+    /// Number of distinct names
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// Every symbol and its name, in the order they were interned
+    pub fn iter(&self) -> impl Iterator<Item = (Symbol, &str)> {
+        (0..self.ends.len() as u32).map(|i| (Symbol(i), self.resolve(Symbol(i))))
+    }
+}
+
+impl fmt::Debug for Interner {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter().map(|(_, name)| name)).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_round_trip() {
+        let mut names = Interner::new();
+        let start = names.intern("start");
+        let a = names.intern("A");
+        assert_eq!(names.intern("start"), start);
+        assert_ne!(a, start);
+        assert_eq!(names.intern(""), Symbol(2));
+        assert_eq!((start.index(), a.index()), (0, 1));
+
+        assert_eq!(names.resolve(a), "A");
+        assert_eq!(names.resolve(Symbol(2)), "");
+        assert_eq!(names.get("A"), Some(a));
+        assert_eq!(names.get("end"), None);
+        assert_eq!(names.len(), 3);
+        assert_eq!(names.iter().map(|(_, n)| n).collect::<Vec<_>>(), ["start", "A", ""]);
+        assert_eq!(format!("{:?}", names), r#"["start", "A", ""]"#);
+    }
+}
@@ -0,0 +1,338 @@
+//! What every day shares: the `Solver` trait the runner drives, finding and
+//! reading inputs, and one error type. Built without the default `std`
+//! feature it's `no_std`, keeping only what the days' algorithms use.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::format;
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::io::BufRead;
+#[cfg(feature = "std")]
+use std::path::{Path, PathBuf};
+
+pub mod blocks;
+pub mod color;
+pub mod error;
+#[cfg(feature = "std")]
+pub mod frame;
+#[cfg(feature = "std")]
+pub mod golden;
+#[cfg(feature = "std")]
+pub mod intern;
+#[cfg(feature = "std")]
+pub mod logging;
+#[cfg(feature = "std")]
+pub mod mem;
+#[cfg(feature = "std")]
+pub mod profile;
+#[cfg(feature = "std")]
+pub mod progress;
+pub mod rng;
+#[cfg(feature = "std")]
+pub mod source;
+pub mod union_find;
+
+pub use blocks::LineBlocks;
+#[cfg(feature = "std")]
+pub use error::or_exit;
+pub use error::{parse_lines, AocError};
+#[cfg(feature = "std")]
+pub use source::InputSource;
+pub use union_find::UnionFind;
+
+/// A day's puzzle, parsed once from the input text and then asked for the
+/// answer to each part
+#[cfg(feature = "std")]
+pub trait Solver {
+    /// Parse, reporting malformed input rather than panicking
+    fn try_parse(input: &str) -> Result<Self, AocError> where Self: Sized;
+
+    /// `try_parse` for input known to be good, panicking if it isn't
+    fn parse(input: &str) -> Self where Self: Sized {
+        Self::try_parse(input).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn part1(&self) -> String;
+
+    fn part2(&self) -> String;
+
+    /// Changes one of the day's settings, like how many days d6 simulates,
+    /// before any part is asked for. Days without settings reject them all.
+    fn set_option(&mut self, name: &str, _value: &str) -> Result<(), AocError> {
+        Err(AocError::invalid(format!("no option {}", name)))
+    }
+
+    /// A directory the day may keep expensive intermediate results in
+    /// between runs, set before any part is asked for. Most days have
+    /// nothing worth keeping and ignore it.
+    fn use_cache(&mut self, _dir: &Path) {}
+
+    /// More about the input than the answers tell, as JSON text of an
+    /// object, for the runner's `--format json` to include. Most days have
+    /// nothing to add.
+    fn report(&self) -> Option<String> {
+        None
+    }
+
+    /// What the `algo` and `impl` options can choose between, for the
+    /// runner to check and list in its help without parsing an input
+    fn strategies() -> Strategies where Self: Sized {
+        Strategies::default()
+    }
+
+    /// Answer to part `n`, which must be 1 or 2
+    fn part(&self, n: usize) -> String {
+        match n {
+            1 => self.part1(),
+            2 => self.part2(),
+            _ => panic!("no part {}", n),
+        }
+    }
+}
+
+/// Which parts of a puzzle to run, as chosen with `--part 1|2|both`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Parts {
+    One,
+    Two,
+    Both,
+}
+
+impl Parts {
+    /// Part numbers to run, in order
+    pub fn numbers(self) -> RangeInclusive<usize> {
+        match self {
+            Parts::One => 1..=1,
+            Parts::Two => 2..=2,
+            Parts::Both => 1..=2,
+        }
+    }
+
+    pub fn has(self, n: usize) -> bool {
+        self.numbers().contains(&n)
+    }
+}
+
+impl FromStr for Parts {
+    type Err = AocError;
+    fn from_str(s: &str) -> Result<Parts, AocError> {
+        match s {
+            "1" => Ok(Parts::One),
+            "2" => Ok(Parts::Two),
+            "both" => Ok(Parts::Both),
+            _ => Err(AocError::invalid(format!("no part {:?}, expected 1, 2 or both", s))),
+        }
+    }
+}
+
+/// One way a day can work out its answers, picked by name with the
+/// runner's `--algo` or `--impl`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Strategy {
+    pub name: &'static str,
+    /// What it does, in a few words for `aoc help`
+    pub about: &'static str,
+}
+
+/// The algorithms a day can use and the implementations of them (with or
+/// without SIMD, say) it can be built with, each the default first. Days
+/// are switched between them with their `algo` and `impl` options.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Strategies {
+    pub algos: &'static [Strategy],
+    pub impls: &'static [Strategy],
+}
+
+impl Strategies {
+    /// The choices for option `algo` or `impl`
+    pub fn of(&self, option: &str) -> &'static [Strategy] {
+        match option {
+            "algo" => self.algos,
+            "impl" => self.impls,
+            _ => &[],
+        }
+    }
+
+    /// Whether `name` is one of the choices for option `algo` or `impl`,
+    /// listing the choices there are if not
+    pub fn check(&self, option: &str, name: &str) -> Result<(), AocError> {
+        let choices = self.of(option);
+        if choices.iter().any(|s| s.name == name) {
+            return Ok(());
+        }
+        let names: Vec<&str> = choices.iter().map(|s| s.name).collect();
+        Err(AocError::invalid(match names.as_slice() {
+            [] => format!("no --{} choices, only the one way", option),
+            _ => format!("no {} {:?}, expected one of {}", option, name, names.join(", ")),
+        }))
+    }
+}
+
+/// The value given for a solver option, parsed, with the option named in
+/// the error if it doesn't parse
+pub fn option_value<T: FromStr>(name: &str, value: &str) -> Result<T, AocError> {
+    value.parse().map_err(|_| AocError::invalid(format!("bad value {:?} for option {}", value, name)))
+}
+
+/// Parse into a boxed solver, so days with different puzzle types can share
+/// a dispatch table
+#[cfg(feature = "std")]
+pub fn parse_boxed<S: Solver + 'static>(input: &str) -> Result<Box<dyn Solver>, AocError> {
+    Ok(Box::new(S::try_parse(input)?))
+}
+
+/// Takes `--input <path>` out of a binary's arguments, returning where to
+/// read the input from (stdin without it, or for `-`) and the arguments
+/// left over for the binary to handle itself
+#[cfg(feature = "std")]
+pub fn split_input_arg(args: impl Iterator<Item = String>) -> (InputSource, Vec<String>) {
+    let mut path = None;
+    let mut rest = vec!();
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => path = Some(args.next().expect("--input needs a path")),
+            _ => rest.push(arg),
+        }
+    }
+    (InputSource::path_or_stdin(path.as_deref()), rest)
+}
+
+/// Takes `--part 1|2|both` out of a binary's arguments, returning the parts
+/// to run (both unless told otherwise) and the arguments left over
+#[cfg(feature = "std")]
+pub fn split_part_arg(args: impl Iterator<Item = String>) -> (Parts, Vec<String>) {
+    let mut parts = Parts::Both;
+    let mut rest = vec!();
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => parts = or_exit(args.next().expect("--part needs 1, 2 or both").parse()),
+            _ => rest.push(arg),
+        }
+    }
+    (parts, rest)
+}
+
+/// This process's arguments, after handling the ones every binary shares:
+/// `--verbose` turns on log output
+#[cfg(feature = "std")]
+pub fn args() -> Vec<String> {
+    let (verbosity, rest) = logging::split_verbosity(std::env::args().skip(1));
+    logging::init(verbosity);
+    rest
+}
+
+/// `split_input_arg` applied to this process's command line
+#[cfg(feature = "std")]
+pub fn input_arg() -> (InputSource, Vec<String>) {
+    split_input_arg(args().into_iter())
+}
+
+/// `input_arg`, with `--part` taken out as well
+#[cfg(feature = "std")]
+pub fn input_and_parts() -> (InputSource, Parts, Vec<String>) {
+    let (input, rest) = input_arg();
+    let (parts, rest) = split_part_arg(rest.into_iter());
+    (input, parts, rest)
+}
+
+/// Reader over `source`, panicking if it can't be opened
+#[cfg(feature = "std")]
+pub fn open_input(source: &InputSource) -> Box<dyn BufRead + '_> {
+    source.open().unwrap_or_else(|e| panic!("{}: {}", source, e))
+}
+
+/// Puzzle input for a day binary: the file given with `--input <path>`,
+/// falling back to stdin
+#[cfg(feature = "std")]
+pub fn input() -> Box<dyn BufRead> {
+    let source = input_arg().0;
+    source.clone().into_reader().unwrap_or_else(|e| panic!("{}: {}", source, e))
+}
+
+/// Where `aoc fetch` keeps inputs unless configured otherwise, relative to
+/// the workspace root
+#[cfg(feature = "std")]
+pub const INPUT_DIR: &str = "inputs";
+
+/// The directory of `year`'s crate for `day`, relative to the workspace root
+#[cfg(feature = "std")]
+pub fn day_dir(year: u32, day: usize) -> PathBuf {
+    format!("{}/d{}", year, day).into()
+}
+
+/// Where `aoc fetch` keeps a day's input in `dir`
+#[cfg(feature = "std")]
+pub fn fetched_input(dir: &Path, year: u32, day: usize) -> PathBuf {
+    dir.join(year.to_string()).join(format!("d{}.txt", day))
+}
+
+/// The input for `year`'s `day` under the workspace `root`: a fetched one
+/// if there is one, else whatever is checked in next to the day's sources
+#[cfg(feature = "std")]
+pub fn locate_input(root: &Path, year: u32, day: usize) -> Option<PathBuf> {
+    locate_input_in(root, Path::new(INPUT_DIR), year, day)
+}
+
+/// `locate_input`, with fetched inputs kept in `input_dir`
+#[cfg(feature = "std")]
+pub fn locate_input_in(root: &Path, input_dir: &Path, year: u32, day: usize) -> Option<PathBuf> {
+    InputSource::locate(root, input_dir, year, day).and_then(|source| source.path())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_arg_is_split_out() {
+        let args = ["--explain", "--input", "2021/d8/input", "x"].map(String::from);
+        let (source, rest) = split_input_arg(args.into_iter());
+        assert_eq!(source, InputSource::File("2021/d8/input".into()));
+        assert_eq!(rest, ["--explain", "x"]);
+
+        let (source, rest) = split_input_arg(std::iter::empty());
+        assert_eq!(source, InputSource::Stdin);
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn part_arg_is_split_out() {
+        let args = ["--part", "1", "--explain"].map(String::from);
+        let (parts, rest) = split_part_arg(args.into_iter());
+        assert_eq!(parts, Parts::One);
+        assert_eq!(rest, ["--explain"]);
+
+        let (parts, _) = split_part_arg(std::iter::empty());
+        assert_eq!(parts.numbers(), 1..=2);
+        assert!(!"2".parse::<Parts>().unwrap().has(1));
+        assert_eq!("3".parse::<Parts>().unwrap_err().to_string(), "no part \"3\", expected 1, 2 or both");
+    }
+
+    #[test]
+    fn strategies_are_checked_by_name() {
+        let strategies = Strategies {
+            algos: &[Strategy { name: "astar", about: "" }, Strategy { name: "dijkstra", about: "" }],
+            impls: &[],
+        };
+        assert!(strategies.check("algo", "dijkstra").is_ok());
+        assert_eq!(strategies.check("algo", "bfs").err().unwrap().to_string(), "no algo \"bfs\", expected one of astar, dijkstra");
+        assert_eq!(strategies.check("impl", "simd").err().unwrap().to_string(), "no --impl choices, only the one way");
+    }
+
+    #[test]
+    fn locates_checked_in_inputs() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        assert!(locate_input(root, 2021, 1).is_some());
+        assert!(locate_input(root, 2021, 15).is_some());
+        assert_eq!(locate_input(root, 2021, 26), None);
+        assert_eq!(locate_input(root, 2022, 1), None);
+        assert_eq!(fetched_input(Path::new("inputs"), 2021, 5), Path::new("inputs/2021/d5.txt"));
+    }
+}
@@ -0,0 +1,67 @@
+//! Diagnostics through the `log` crate, printed to stderr only when asked
+//! for with `--verbose`, so answers are all a run prints by default
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+struct Stderr;
+
+impl Log for Stderr {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("[{} {}] {}", record.level(), record.target(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: Stderr = Stderr;
+
+/// Warnings always, debug output from one `--verbose` and trace from two
+pub fn level(verbosity: u8) -> LevelFilter {
+    match verbosity {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
+
+/// Send log records at or above `verbosity`'s level to stderr. Only the
+/// first call installs the logger; later ones just change the level.
+pub fn init(verbosity: u8) {
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(level(verbosity));
+}
+
+/// Takes `--verbose`/`-v` (any number of times) out of a binary's arguments,
+/// returning how many there were and the arguments left over
+pub fn split_verbosity(args: impl Iterator<Item = String>) -> (u8, Vec<String>) {
+    let mut verbosity = 0u8;
+    let rest = args
+        .filter(|arg| match arg.as_str() {
+            "--verbose" | "-v" => { verbosity = verbosity.saturating_add(1); false },
+            "-vv" => { verbosity = verbosity.saturating_add(2); false },
+            _ => true,
+        })
+        .collect();
+    (verbosity, rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verbose_flags_are_counted() {
+        let args = ["--verbose", "--input", "x", "-v"].map(String::from);
+        let (verbosity, rest) = split_verbosity(args.into_iter());
+        assert_eq!(verbosity, 2);
+        assert_eq!(rest, ["--input", "x"]);
+        assert_eq!(level(verbosity), LevelFilter::Trace);
+        assert_eq!(level(0), LevelFilter::Warn);
+    }
+}
@@ -0,0 +1,157 @@
+//! Best effort memory reporting: peak resident set size from the OS where it
+//! can be read, and heap allocation counts when a binary installs
+//! `CountingAlloc` as its global allocator (`count-alloc` feature)
+
+use std::fs;
+
+#[cfg(feature = "count-alloc")]
+pub use counting::CountingAlloc;
+
+/// Memory used between a `Tracker` starting and finishing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Usage {
+    /// Heap allocations made, if they were being counted
+    pub allocations: Option<u64>,
+    /// Most heap bytes live at once, if they were being counted
+    pub peak_heap: Option<usize>,
+    /// Peak resident set size of the whole process, if the OS reports it
+    pub peak_rss: Option<u64>,
+}
+
+/// Measures memory use from `start` until `finish`
+pub struct Tracker {
+    allocations: u64,
+}
+
+impl Tracker {
+    pub fn start() -> Self {
+        reset_peaks();
+        Tracker { allocations: counters::allocations() }
+    }
+
+    pub fn finish(&self) -> Usage {
+        let counting = counters::counting();
+        Usage {
+            allocations: counting.then(|| counters::allocations() - self.allocations),
+            peak_heap: counting.then(counters::peak_heap),
+            peak_rss: peak_rss(),
+        }
+    }
+}
+
+/// Peak resident set size in bytes, read from `/proc/self/status` (Linux only)
+pub fn peak_rss() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|l| l.starts_with("VmHWM:"))?;
+    let kb = line["VmHWM:".len()..].trim().strip_suffix("kB")?;
+    kb.trim().parse::<u64>().ok().map(|kb| kb * 1024)
+}
+
+/// Start the heap and RSS peaks again from what's in use now, so the next
+/// day isn't charged for an earlier one's high water mark
+pub fn reset_peaks() {
+    counters::reset_peak_heap();
+    // Linux resets VmHWM when "5" is written here; elsewhere this just fails
+    let _ = fs::write("/proc/self/clear_refs", "5");
+}
+
+/// `1536` as `1.5 KiB`, and so on up to GiB
+pub fn human_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+mod counters {
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering::Relaxed};
+
+    pub(super) static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+    pub(super) static LIVE: AtomicUsize = AtomicUsize::new(0);
+    pub(super) static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+    /// Anything that runs has allocated by the time it asks, so a zero count
+    /// means `CountingAlloc` isn't installed
+    pub fn counting() -> bool {
+        allocations() > 0
+    }
+
+    pub fn allocations() -> u64 {
+        ALLOCATIONS.load(Relaxed)
+    }
+
+    pub fn peak_heap() -> usize {
+        PEAK.load(Relaxed)
+    }
+
+    pub fn reset_peak_heap() {
+        PEAK.store(LIVE.load(Relaxed), Relaxed);
+    }
+}
+
+#[cfg(feature = "count-alloc")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::Ordering::Relaxed;
+
+    use super::counters::{ALLOCATIONS, LIVE, PEAK};
+
+    /// The system allocator, counting allocations and live bytes as it goes
+    pub struct CountingAlloc;
+
+    fn grew(size: usize) {
+        ALLOCATIONS.fetch_add(1, Relaxed);
+        let live = LIVE.fetch_add(size, Relaxed) + size;
+        PEAK.fetch_max(live, Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for CountingAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            grew(layout.size());
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            grew(layout.size());
+            unsafe { System.alloc_zeroed(layout) }
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            LIVE.fetch_sub(layout.size(), Relaxed);
+            grew(new_size);
+            unsafe { System.realloc(ptr, layout, new_size) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            LIVE.fetch_sub(layout.size(), Relaxed);
+            unsafe { System.dealloc(ptr, layout) }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_sizes() {
+        assert_eq!(human_bytes(512), "512 B");
+        assert_eq!(human_bytes(1536), "1.5 KiB");
+        assert_eq!(human_bytes(3 << 30), "3.0 GiB");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn reads_peak_rss() {
+        let usage = Tracker::start().finish();
+        assert!(usage.peak_rss.unwrap() > 0);
+    }
+}
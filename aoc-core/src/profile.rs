@@ -0,0 +1,219 @@
+//! Where the time goes, from the `tracing` spans the runner opens around
+//! parsing and each part and the days open around their slower phases.
+//! Nothing is measured unless a `Profiler` is installed; then each span's
+//! time adds up into a tree keyed by the chain of spans it ran inside.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+thread_local! {
+    /// Spans entered on this thread, innermost last
+    static ENTERED: RefCell<Vec<u64>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Time spent in every span with the same label under the same parent
+struct Node {
+    label: String,
+    children: Vec<usize>,
+    busy: Duration,
+    spans: usize,
+}
+
+/// A span that hasn't closed yet
+struct Open {
+    node: usize,
+    entered: Option<Instant>,
+    handles: usize,
+}
+
+#[derive(Default)]
+struct Tree {
+    /// Spans opened outside any other
+    roots: Vec<usize>,
+    nodes: Vec<Node>,
+    open: HashMap<u64, Open>,
+}
+
+impl Tree {
+    fn child(&mut self, parent: Option<usize>, label: String) -> usize {
+        let siblings = match parent {
+            Some(p) => &self.nodes[p].children,
+            None => &self.roots,
+        };
+        if let Some(&n) = siblings.iter().find(|&&n| self.nodes[n].label == label) {
+            return n;
+        }
+        let n = self.nodes.len();
+        self.nodes.push(Node { label, children: vec!(), busy: Duration::ZERO, spans: 0 });
+        match parent {
+            Some(p) => self.nodes[p].children.push(n),
+            None => self.roots.push(n),
+        }
+        n
+    }
+
+    fn write(&self, out: &mut String, nodes: &[usize], depth: usize) {
+        for &n in nodes {
+            let node = &self.nodes[n];
+            let label = format!("{:indent$}{}", "", node.label, indent = depth * 2);
+            write!(out, "{:<40} {:>10.1?}", label, node.busy).unwrap();
+            if node.spans > 1 {
+                write!(out, " ({} spans)", node.spans).unwrap();
+            }
+            out.push('\n');
+            self.write(out, &node.children, depth + 1);
+        }
+    }
+}
+
+/// A span's name followed by its fields' values, like `part 1`
+struct Label(String);
+
+impl Visit for Label {
+    fn record_debug(&mut self, _field: &Field, value: &dyn fmt::Debug) {
+        write!(self.0, " {:?}", value).unwrap();
+    }
+
+    fn record_str(&mut self, _field: &Field, value: &str) {
+        write!(self.0, " {}", value).unwrap();
+    }
+}
+
+/// Collects span timings. Clones share the same tree, so one can be
+/// installed as the subscriber and another kept to print the `report`.
+#[derive(Clone, Default)]
+pub struct Profiler {
+    tree: Arc<Mutex<Tree>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl Profiler {
+    /// A profiler receiving every span from now on, in every thread. Only
+    /// the first call installs one; later ones get a profiler that sees
+    /// nothing.
+    pub fn install() -> Profiler {
+        let profiler = Profiler::default();
+        let _ = tracing::subscriber::set_global_default(profiler.clone());
+        profiler
+    }
+
+    /// Each label with the time spent inside it, indented under the span it
+    /// ran in, in the order they first started
+    pub fn report(&self) -> String {
+        let tree = self.tree.lock().unwrap();
+        let mut out = String::new();
+        tree.write(&mut out, &tree.roots, 0);
+        out
+    }
+}
+
+impl Subscriber for Profiler {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut label = Label(span.metadata().name().to_string());
+        span.record(&mut label);
+
+        let parent = match span.parent() {
+            Some(id) => Some(id.into_u64()),
+            None if span.is_contextual() => ENTERED.with(|e| e.borrow().last().copied()),
+            None => None,
+        };
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+
+        let mut tree = self.tree.lock().unwrap();
+        let parent = parent.and_then(|p| tree.open.get(&p)).map(|o| o.node);
+        let node = tree.child(parent, label.0);
+        tree.nodes[node].spans += 1;
+        tree.open.insert(id, Open { node, entered: None, handles: 1 });
+        Id::from_u64(id)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event<'_>) {}
+
+    fn enter(&self, span: &Id) {
+        ENTERED.with(|e| e.borrow_mut().push(span.into_u64()));
+        if let Some(open) = self.tree.lock().unwrap().open.get_mut(&span.into_u64()) {
+            open.entered = Some(Instant::now());
+        }
+    }
+
+    fn exit(&self, span: &Id) {
+        ENTERED.with(|e| {
+            let mut entered = e.borrow_mut();
+            if let Some(i) = entered.iter().rposition(|&id| id == span.into_u64()) {
+                entered.remove(i);
+            }
+        });
+        let mut tree = self.tree.lock().unwrap();
+        let Some(open) = tree.open.get_mut(&span.into_u64()) else {
+            return;
+        };
+        if let Some(entered) = open.entered.take() {
+            let node = open.node;
+            tree.nodes[node].busy += entered.elapsed();
+        }
+    }
+
+    fn clone_span(&self, span: &Id) -> Id {
+        if let Some(open) = self.tree.lock().unwrap().open.get_mut(&span.into_u64()) {
+            open.handles += 1;
+        }
+        span.clone()
+    }
+
+    fn try_close(&self, span: Id) -> bool {
+        let mut tree = self.tree.lock().unwrap();
+        let Some(open) = tree.open.get_mut(&span.into_u64()) else {
+            return false;
+        };
+        open.handles -= 1;
+        if open.handles > 0 {
+            return false;
+        }
+        tree.open.remove(&span.into_u64());
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spans_nest_into_a_tree() {
+        let profiler = Profiler::default();
+        tracing::subscriber::with_default(profiler.clone(), || {
+            let _day = tracing::info_span!("day", day = 19).entered();
+            tracing::info_span!("parse").in_scope(|| {});
+            for part in 1..=2 {
+                let _part = tracing::info_span!("part", part).entered();
+                for scanner in [1, 2, 1] {
+                    tracing::info_span!("match scanner", scanner).in_scope(|| {});
+                }
+            }
+        });
+
+        let report = profiler.report();
+        let labels: Vec<&str> = report.lines().map(|l| l.trim_start().split("  ").next().unwrap()).collect();
+        assert_eq!(labels, ["day 19", "parse", "part 1", "match scanner 1", "match scanner 2", "part 2", "match scanner 1", "match scanner 2"]);
+        let indents: Vec<usize> = report.lines().map(|l| l.len() - l.trim_start().len()).collect();
+        assert_eq!(indents, [0, 2, 2, 4, 4, 2, 4, 4]);
+        assert!(report.lines().nth(3).unwrap().ends_with("(2 spans)"));
+        assert!(!report.lines().nth(4).unwrap().contains("spans"));
+    }
+}
@@ -0,0 +1,129 @@
+//! Progress bars on stderr for the days that run for minutes. Library code
+//! reports progress whether or not anyone is watching; nothing is drawn
+//! unless the binary turned bars on with `enable`, so tests and piped
+//! answers stay quiet.
+
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Draw bars made from now on, or not
+pub fn enable(on: bool) {
+    ENABLED.store(on, Ordering::Relaxed);
+}
+
+/// Characters between the brackets of a bar
+const WIDTH: usize = 30;
+/// Least time between redraws, so tight loops can report every iteration
+const REDRAW: Duration = Duration::from_millis(100);
+
+/// A bar towards a known total, or a plain counter without one, redrawn in
+/// place. The last state is drawn when it's finished or dropped.
+pub struct Progress {
+    label: String,
+    total: Option<u64>,
+    done: u64,
+    /// Only when visible: `Instant::now` panics on wasm32, where nothing is
+    /// ever drawn
+    started: Option<Instant>,
+    drawn: Option<Instant>,
+    visible: bool,
+}
+
+impl Progress {
+    fn new(label: &str, total: Option<u64>) -> Progress {
+        let visible = ENABLED.load(Ordering::Relaxed);
+        Progress {
+            label: label.to_string(),
+            total,
+            done: 0,
+            started: visible.then(Instant::now),
+            drawn: None,
+            visible,
+        }
+    }
+
+    pub fn bar(label: &str, total: u64) -> Progress {
+        Progress::new(label, Some(total))
+    }
+
+    pub fn counter(label: &str) -> Progress {
+        Progress::new(label, None)
+    }
+
+    pub fn inc(&mut self, n: u64) {
+        self.set(self.done + n);
+    }
+
+    pub fn set(&mut self, done: u64) {
+        self.done = done;
+        if !self.visible {
+            return;
+        }
+        let now = Instant::now();
+        if self.drawn.is_some_and(|t| now - t < REDRAW) {
+            return;
+        }
+        self.drawn = Some(now);
+        self.draw();
+    }
+
+    /// Draws the final state and moves on to a new line
+    pub fn finish(&mut self) {
+        if self.visible {
+            self.draw();
+            eprintln!();
+            self.visible = false;
+        }
+    }
+
+    fn draw(&self) {
+        // back to the start of the line, and clear what's left of the last draw
+        eprint!("\r{}\x1b[K", self.line(self.started.map_or(Duration::ZERO, |t| t.elapsed())));
+        let _ = io::stderr().flush();
+    }
+
+    /// The bar as drawn `elapsed` after it started
+    fn line(&self, elapsed: Duration) -> String {
+        let secs = elapsed.as_secs_f64();
+        match self.total {
+            Some(total) => {
+                let fraction = if total == 0 { 1.0 } else { (self.done as f64 / total as f64).min(1.0) };
+                let filled = (fraction * WIDTH as f64) as usize;
+                let head = if filled < WIDTH { ">" } else { "" };
+                format!("{} [{}{}{}] {}/{} {:.1}s", self.label,
+                    "=".repeat(filled), head, " ".repeat(WIDTH - filled - head.len()),
+                    self.done, total, secs)
+            },
+            None => format!("{} {} {:.1}s", self.label, self.done, secs),
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bars_and_counters() {
+        let mut bar = Progress::bar("scanners", 4);
+        assert_eq!(bar.line(Duration::ZERO), format!("scanners [>{}] 0/4 0.0s", " ".repeat(29)));
+        bar.inc(1);
+        bar.inc(1);
+        assert_eq!(bar.line(Duration::from_millis(1500)), format!("scanners [{}>{}] 2/4 1.5s", "=".repeat(15), " ".repeat(14)));
+        bar.set(4);
+        assert_eq!(bar.line(Duration::ZERO), format!("scanners [{}] 4/4 0.0s", "=".repeat(30)));
+
+        let mut counter = Progress::counter("expanded");
+        counter.inc(12345);
+        assert_eq!(counter.line(Duration::from_secs(3)), "expanded 12345 3.0s");
+    }
+}
@@ -0,0 +1,132 @@
+//! Seedable, reproducible randomness for simulations and input generators:
+//! xoshiro256** seeded through splitmix64. Not for anything that needs to
+//! be unpredictable.
+
+use core::ops::Range;
+
+/// splitmix64, used to spread a single seed over the xoshiro state
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[derive(Clone, Debug)]
+pub struct Rng([u64; 4]);
+
+impl Rng {
+    /// The same seed always gives the same sequence
+    pub fn seed_from(seed: u64) -> Rng {
+        let mut sm = seed;
+        Rng([splitmix64(&mut sm), splitmix64(&mut sm), splitmix64(&mut sm), splitmix64(&mut sm)])
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let s = &mut self.0;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// Independent generator for a sub-task, so adding draws to one part of
+    /// a simulation doesn't shift the numbers seen by another
+    pub fn split(&mut self) -> Rng {
+        Rng::seed_from(self.next_u64())
+    }
+
+    /// Uniform in `0..n`; `n` must not be 0
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "empty range");
+        // reject the top sliver that would bias the modulo
+        let zone = u64::MAX - (u64::MAX - n + 1) % n;
+        loop {
+            let x = self.next_u64();
+            if x <= zone {
+                return x % n;
+            }
+        }
+    }
+
+    /// Uniform in `range`, which must not be empty
+    pub fn range(&mut self, range: Range<i64>) -> i64 {
+        assert!(range.start < range.end, "empty range");
+        let span = range.end.wrapping_sub(range.start) as u64;
+        range.start.wrapping_add(self.below(span) as i64)
+    }
+
+    /// Uniform in `[0, 1)`
+    pub fn f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            return None;
+        }
+        Some(&items[self.below(items.len() as u64) as usize])
+    }
+
+    /// Index picked with probability proportional to its weight, or `None`
+    /// if every weight is zero
+    pub fn weighted_index(&mut self, weights: &[u64]) -> Option<usize> {
+        let total: u64 = weights.iter().sum();
+        if total == 0 {
+            return None;
+        }
+        let mut pick = self.below(total);
+        for (i, &w) in weights.iter().enumerate() {
+            if pick < w {
+                return Some(i);
+            }
+            pick -= w;
+        }
+        unreachable!()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reproducible() {
+        let a: Vec<u64> = (0..8).scan(Rng::seed_from(42), |r, _| Some(r.next_u64())).collect();
+        let b: Vec<u64> = (0..8).scan(Rng::seed_from(42), |r, _| Some(r.next_u64())).collect();
+        let c: Vec<u64> = (0..8).scan(Rng::seed_from(43), |r, _| Some(r.next_u64())).collect();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn helpers_stay_in_bounds() {
+        let mut rng = Rng::seed_from(7);
+        for _ in 0..1000 {
+            assert!((-3..5).contains(&rng.range(-3..5)));
+            assert!((0.0..1.0).contains(&rng.f64()));
+            assert_ne!(rng.weighted_index(&[0, 3, 0, 1]), Some(0));
+        }
+        assert_eq!(rng.weighted_index(&[0, 0]), None);
+        assert_eq!(rng.choose::<u8>(&[]), None);
+
+        let mut items: Vec<u32> = (0..50).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..50).collect::<Vec<_>>());
+        items.sort();
+        assert_eq!(items, (0..50).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,143 @@
+//! Where a day's input comes from. Binaries get one from `--input`, the
+//! runner from what it finds, and tests hand text straight in rather than
+//! faking stdin or writing files.
+
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use crate::{day_dir, fetched_input};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+    /// The input itself, mostly for tests
+    Text(String),
+    /// What `aoc fetch` downloaded for a day into `dir`, the configured
+    /// `input_dir`
+    Fetched { dir: PathBuf, year: u32, day: usize },
+}
+
+impl InputSource {
+    /// The file at `path`, or stdin without one or for `-`
+    pub fn path_or_stdin(path: Option<&str>) -> InputSource {
+        match path {
+            None | Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::File(path.into()),
+        }
+    }
+
+    /// The input for `year`'s `day` under the workspace `root`: what `aoc
+    /// fetch` downloaded into `input_dir` if it has, else whatever is
+    /// checked in next to the day's sources
+    pub fn locate(root: &Path, input_dir: &Path, year: u32, day: usize) -> Option<InputSource> {
+        let fetched = InputSource::Fetched { dir: root.join(input_dir), year, day };
+        if fetched.path().is_some_and(|p| p.is_file()) {
+            return Some(fetched);
+        }
+        ["input", "input.txt"].into_iter()
+            .map(|name| root.join(day_dir(year, day)).join(name))
+            .find(|p| p.is_file())
+            .map(InputSource::File)
+    }
+
+    /// The file the input is read from, if it's in one
+    pub fn path(&self) -> Option<PathBuf> {
+        match self {
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Fetched { dir, year, day } => Some(fetched_input(dir, *year, *day)),
+            InputSource::Stdin | InputSource::Text(_) => None,
+        }
+    }
+
+    /// A reader over the input, borrowing it if it's `Text`
+    pub fn open(&self) -> io::Result<Box<dyn BufRead + '_>> {
+        match self {
+            InputSource::Text(text) => Ok(Box::new(text.as_bytes())),
+            source => source.clone().into_reader(),
+        }
+    }
+
+    /// A reader over the input that owns it, to hand on
+    pub fn into_reader(self) -> io::Result<Box<dyn BufRead>> {
+        Ok(match self {
+            InputSource::Stdin => Box::new(io::stdin().lock()),
+            InputSource::Text(text) => Box::new(io::Cursor::new(text)),
+            source => Box::new(BufReader::new(File::open(source.path().unwrap())?)),
+        })
+    }
+
+    /// The whole input
+    pub fn read_to_string(&self) -> io::Result<String> {
+        match self {
+            InputSource::Stdin => {
+                let mut text = String::new();
+                io::stdin().lock().read_to_string(&mut text)?;
+                Ok(text)
+            },
+            InputSource::Text(text) => Ok(text.clone()),
+            source => fs::read_to_string(source.path().unwrap()),
+        }
+    }
+}
+
+impl From<&str> for InputSource {
+    fn from(text: &str) -> InputSource {
+        InputSource::Text(text.to_string())
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "stdin"),
+            InputSource::Text(_) => write!(f, "input text"),
+            source => write!(f, "{}", source.path().unwrap().display()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_each_source() {
+        let text = InputSource::from("1\n2\n");
+        assert_eq!(text.open().unwrap().lines().count(), 2);
+        assert_eq!(text.read_to_string().unwrap(), "1\n2\n");
+        assert_eq!(text.clone().into_reader().unwrap().lines().next().unwrap().unwrap(), "1");
+
+        let file = InputSource::path_or_stdin(Some(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml")));
+        assert!(file.read_to_string().unwrap().starts_with("[package]"));
+        assert!(file.open().unwrap().lines().next().is_some());
+        let missing = InputSource::path_or_stdin(Some("no/such/input"));
+        assert!(missing.open().is_err());
+        assert_eq!(missing.to_string(), "no/such/input");
+
+        assert_eq!(InputSource::path_or_stdin(Some("-")), InputSource::Stdin);
+    }
+
+    #[test]
+    fn locates_fetched_inputs_first() {
+        let root = std::env::temp_dir().join(format!("aoc-source-{}", std::process::id()));
+        fs::create_dir_all(root.join(day_dir(2021, 5))).unwrap();
+        fs::write(root.join(day_dir(2021, 5)).join("input"), "checked in").unwrap();
+        let checked_in = InputSource::locate(&root, Path::new("cache"), 2021, 5);
+        let fetched = InputSource::Fetched { dir: root.join("cache"), year: 2021, day: 5 };
+        fs::create_dir_all(root.join("cache/2021")).unwrap();
+        fs::write(fetched.path().unwrap(), "fetched").unwrap();
+        let found = InputSource::locate(&root, Path::new("cache"), 2021, 5);
+        let missing = InputSource::locate(&root, Path::new("cache"), 2021, 6);
+        let text = found.as_ref().map(|s| s.read_to_string().unwrap());
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(checked_in, Some(InputSource::File(root.join("2021/d5/input"))));
+        assert_eq!(fetched.path(), Some(root.join("cache/2021/d5.txt")));
+        assert_eq!(found, Some(fetched));
+        assert_eq!(text.as_deref(), Some("fetched"));
+        assert_eq!(missing, None);
+    }
+}
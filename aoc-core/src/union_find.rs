@@ -0,0 +1,119 @@
+//! Disjoint sets over `0..n`, for puzzles that ask which things end up
+//! connected: basins, regions, clusters of beacons. Joining sets and finding
+//! the set an element is in are both close to constant time, with path
+//! compression on `find` and union by rank.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug)]
+pub struct UnionFind {
+    /// Each element's parent, roots being their own
+    parent: Vec<usize>,
+    /// Upper bound on the height of each root's tree
+    rank: Vec<u8>,
+    /// Number of elements in each root's set
+    size: Vec<usize>,
+    sets: usize,
+}
+
+impl UnionFind {
+    /// `n` elements, each in a set of its own
+    pub fn new(n: usize) -> UnionFind {
+        UnionFind { parent: (0..n).collect(), rank: vec![0; n], size: vec![1; n], sets: n }
+    }
+
+    /// Number of elements
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Number of disjoint sets
+    pub fn set_count(&self) -> usize {
+        self.sets
+    }
+
+    /// The element standing for `x`'s set, the same for every element in it
+    /// until the set is next joined to another
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        // point everything on the way straight at the root
+        let mut x = x;
+        while self.parent[x] != root {
+            x = core::mem::replace(&mut self.parent[x], root);
+        }
+        root
+    }
+
+    /// Join the sets `a` and `b` are in, returning false if they already
+    /// were the same set
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        // hang the shallower tree under the deeper one
+        let (root, child) = if self.rank[a] < self.rank[b] { (b, a) } else { (a, b) };
+        if self.rank[root] == self.rank[child] {
+            self.rank[root] += 1;
+        }
+        self.parent[child] = root;
+        self.size[root] += self.size[child];
+        self.sets -= 1;
+        true
+    }
+
+    pub fn same_set(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Number of elements in `x`'s set, `x` included
+    pub fn set_size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn joins_sets() {
+        let mut sets = UnionFind::new(6);
+        assert_eq!((sets.len(), sets.set_count()), (6, 6));
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2));
+
+        assert!(sets.same_set(0, 3));
+        assert!(!sets.same_set(0, 4));
+        assert_eq!(sets.set_size(2), 4);
+        assert_eq!(sets.set_size(5), 1);
+        assert_eq!(sets.set_count(), 3);
+        assert!(UnionFind::new(0).is_empty());
+    }
+
+    #[test]
+    fn find_compresses_paths() {
+        let mut sets = UnionFind::new(5);
+        // equal ranks hang the second root under the first, leaving 4 two
+        // steps from the root, through 2
+        sets.union(0, 1);
+        sets.union(2, 3);
+        sets.union(3, 4);
+        sets.union(0, 2);
+        assert_eq!(sets.parent[4], 2);
+        assert_eq!(sets.find(4), 0);
+        assert_eq!(sets.parent[4], 0);
+        assert_eq!(sets.rank[0], 2);
+    }
+}
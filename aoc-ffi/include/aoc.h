@@ -0,0 +1,33 @@
+/* C interface to the Advent of Code 2021 solvers, from the aoc-ffi crate:
+ *
+ *     cargo build -p aoc-ffi --release
+ *     cc tool.c -Iaoc-ffi/include -Ltarget/release -laoc_ffi
+ */
+
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Answer to part 1 or 2 of day 1 to 25 for `len` bytes of UTF-8 input, or
+ * NULL with the reason available from aoc_last_error(). Free the answer
+ * with aoc_string_free(). */
+char *aoc_solve(uint32_t day, uint32_t part, const uint8_t *input, size_t len);
+
+/* Why the last aoc_solve() on this thread failed, or NULL if it didn't.
+ * Free it with aoc_string_free(). */
+char *aoc_last_error(void);
+
+/* Frees a string from aoc_solve() or aoc_last_error(). NULL is ignored. */
+void aoc_string_free(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
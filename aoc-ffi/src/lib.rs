@@ -0,0 +1,136 @@
+//! C interface to the solvers, for embedding them in other tools. Answers
+//! come back as NUL-terminated strings owned by the caller, which hands each
+//! one back to `aoc_string_free` when done with it. `include/aoc.h` declares
+//! the exports.
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+use std::str;
+
+use aoc_core::AocError;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Answer to one part of one day's puzzle for the given input
+fn solve(day: usize, part: usize, input: &str) -> Result<String, AocError> {
+    if !(1..=2).contains(&part) {
+        return Err(AocError::invalid(format!("no part {}", part)));
+    }
+    let parse = day.checked_sub(1)
+        .and_then(|i| aoc::DAYS.get(i))
+        .ok_or_else(|| AocError::invalid(format!("no day {}", day)))?;
+    Ok(parse(input)?.part(part))
+}
+
+fn into_c_string(s: String) -> Result<*mut c_char, AocError> {
+    CString::new(s)
+        .map(CString::into_raw)
+        .map_err(|_| AocError::invalid("answer contains a NUL byte"))
+}
+
+/// Solves one part of a day. Returns the answer, or null with the reason
+/// left for `aoc_last_error`.
+///
+/// # Safety
+///
+/// `input` must point to `len` readable bytes, or be null if `len` is 0
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(day: u32, part: u32, input: *const u8, len: usize) -> *mut c_char {
+    let bytes = if len == 0 { &[] } else { slice::from_raw_parts(input, len) };
+    // days still panic on some bad inputs, and a panic unwinding out of an
+    // extern "C" fn aborts the caller
+    let answer = panic::catch_unwind(AssertUnwindSafe(|| {
+        str::from_utf8(bytes)
+            .map_err(|e| AocError::invalid(format!("input isn't UTF-8: {}", e)))
+            .and_then(|input| solve(day as usize, part as usize, input))
+    }))
+        .unwrap_or_else(|payload| {
+            let msg = payload.downcast_ref::<String>().cloned()
+                .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+                .unwrap_or_default();
+            Err(AocError::invalid(format!("panicked: {}", msg)))
+        })
+        .and_then(into_c_string);
+    match answer {
+        Ok(s) => {
+            LAST_ERROR.with(|e| *e.borrow_mut() = None);
+            s
+        },
+        Err(err) => {
+            LAST_ERROR.with(|e| *e.borrow_mut() = Some(err.to_string()));
+            ptr::null_mut()
+        },
+    }
+}
+
+/// Why the last `aoc_solve` on this thread failed, or null if it didn't.
+/// Free it with `aoc_string_free` like an answer.
+#[no_mangle]
+pub extern "C" fn aoc_last_error() -> *mut c_char {
+    LAST_ERROR.with(|e| e.borrow().clone())
+        .and_then(|msg| CString::new(msg).ok())
+        .map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Frees a string from `aoc_solve` or `aoc_last_error`. Null is ignored.
+///
+/// # Safety
+///
+/// `s` must have come from one of those functions and not been freed yet
+#[no_mangle]
+pub unsafe extern "C" fn aoc_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    /// Takes ownership of a string from the exports, the way a C caller would
+    fn take(s: *mut c_char) -> Option<String> {
+        if s.is_null() {
+            return None;
+        }
+        let owned = unsafe { CStr::from_ptr(s) }.to_str().unwrap().to_string();
+        unsafe { aoc_string_free(s) };
+        Some(owned)
+    }
+
+    fn solve_c(day: u32, part: u32, input: &[u8]) -> Option<String> {
+        take(unsafe { aoc_solve(day, part, input.as_ptr(), input.len()) })
+    }
+
+    #[test]
+    fn answers_and_errors() {
+        let input = include_bytes!("../../2021/d13/input0");
+        assert_eq!(solve_c(13, 1, input).as_deref(), Some("17"));
+        assert_eq!(take(aoc_last_error()), None);
+
+        assert_eq!(solve_c(13, 3, input), None);
+        assert_eq!(take(aoc_last_error()).as_deref(), Some("no part 3"));
+        assert_eq!(solve_c(26, 1, input), None);
+        assert_eq!(take(aoc_last_error()).as_deref(), Some("no day 26"));
+        assert_eq!(solve_c(13, 1, b"\xff"), None);
+        assert!(take(aoc_last_error()).unwrap().starts_with("input isn't UTF-8"));
+    }
+
+    #[test]
+    fn panics_become_errors() {
+        assert_eq!(solve_c(17, 1, b"target area: x=oops\n"), None);
+        assert!(take(aoc_last_error()).unwrap().starts_with("panicked: "));
+    }
+
+    #[test]
+    fn empty_input_may_be_null() {
+        assert_eq!(take(unsafe { aoc_solve(1, 3, ptr::null(), 0) }), None);
+        unsafe { aoc_string_free(ptr::null_mut()) };
+    }
+}
@@ -0,0 +1,256 @@
+//! 2D and 3D points shared by the days that do coordinate math, generic over
+//! the coordinate type so grids can use `usize` and open space `i32`
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// Point or offset in the plane. On grids `x` is the column and `y` the row,
+/// both from the top left.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// Point or offset in space
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// `|a - b|` without going below zero for unsigned coordinates
+fn distance<T: Copy + Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b { a - b } else { b - a }
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
+
+    pub fn map<U>(self, f: impl Fn(T) -> U) -> Point2<U> {
+        Point2 { x: f(self.x), y: f(self.y) }
+    }
+
+    /// The same point with another coordinate type, or `None` if either
+    /// coordinate doesn't fit (such as a negative one as `usize`)
+    pub fn try_convert<U: TryFrom<T>>(self) -> Option<Point2<U>> {
+        Some(Point2 { x: U::try_from(self.x).ok()?, y: U::try_from(self.y).ok()? })
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point2<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        distance(self.x, other.x) + distance(self.y, other.y)
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+
+    pub fn map<U>(self, f: impl Fn(T) -> U) -> Point3<U> {
+        Point3 { x: f(self.x), y: f(self.y), z: f(self.z) }
+    }
+
+    /// The same point with another coordinate type, or `None` if any
+    /// coordinate doesn't fit
+    pub fn try_convert<U: TryFrom<T>>(self) -> Option<Point3<U>> {
+        Some(Point3 {
+            x: U::try_from(self.x).ok()?,
+            y: U::try_from(self.y).ok()?,
+            z: U::try_from(self.z).ok()?,
+        })
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point3<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        distance(self.x, other.x) + distance(self.y, other.y) + distance(self.z, other.z)
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Mul<Output = T>> Point3<T> {
+    pub fn dot(self, other: Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+}
+
+macro_rules! point_ops {
+    ($point:ident { $($c:ident),+ }) => {
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self {
+                $point { $($c: self.$c + rhs.$c),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self {
+                $point { $($c: self.$c - rhs.$c),+ }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $point<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                $(self.$c += rhs.$c;)+
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $point<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                $(self.$c -= rhs.$c;)+
+            }
+        }
+
+        /// Scaling by a constant
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $point<T> {
+            type Output = Self;
+            fn mul(self, k: T) -> Self {
+                $point { $($c: self.$c * k),+ }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+            fn neg(self) -> Self {
+                $point { $($c: -self.$c),+ }
+            }
+        }
+
+        impl<T: fmt::Display> fmt::Display for $point<T> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                let coords = [$(self.$c.to_string()),+];
+                write!(f, "({})", coords.join(","))
+            }
+        }
+
+        impl<T: fmt::Display> fmt::Debug for $point<T> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Display::fmt(self, f)
+            }
+        }
+    };
+}
+
+point_ops!(Point2 { x, y });
+point_ops!(Point3 { x, y, z });
+
+impl<T> From<[T; 2]> for Point2<T> {
+    fn from([x, y]: [T; 2]) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T> From<Point2<T>> for [T; 2] {
+    fn from(p: Point2<T>) -> Self {
+        [p.x, p.y]
+    }
+}
+
+impl<T> From<[T; 3]> for Point3<T> {
+    fn from([x, y, z]: [T; 3]) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T> From<Point3<T>> for [T; 3] {
+    fn from(p: Point3<T>) -> Self {
+        [p.x, p.y, p.z]
+    }
+}
+
+/// Exactly `N` comma separated coordinates, as puzzle inputs write them
+fn parse_coords<T: FromStr, const N: usize>(s: &str) -> Result<[T; N], &'static str> {
+    let mut parts = s.split(',');
+    let mut coords = Vec::with_capacity(N);
+    for _ in 0..N {
+        let part = parts.next().ok_or("too few coordinates")?;
+        coords.push(part.trim().parse().map_err(|_| "invalid coordinate")?);
+    }
+    if parts.next().is_some() {
+        return Err("too many coordinates");
+    }
+    coords.try_into().map_err(|_| "too few coordinates")
+}
+
+/// `x,y`
+impl<T: FromStr> FromStr for Point2<T> {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_coords::<T, 2>(s).map(Point2::from)
+    }
+}
+
+/// `x,y,z`
+impl<T: FromStr> FromStr for Point3<T> {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_coords::<T, 3>(s).map(Point3::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point2::new(3, -2);
+        let b = Point2::from([1, 4]);
+        assert_eq!(a + b, Point2::new(4, 2));
+        assert_eq!(a - b, Point2::new(2, -6));
+        assert_eq!(-a * 2, Point2::new(-6, 4));
+
+        let mut c = Point3::new(1, 2, 3);
+        c += Point3::new(1, 1, 1);
+        assert_eq!(c, Point3::from((2, 3, 4)));
+        assert_eq!(c.dot(Point3::new(1, 0, -1)), -2);
+    }
+
+    #[test]
+    fn manhattan_distance() {
+        assert_eq!(Point2::new(0usize, 5).manhattan(Point2::new(3, 1)), 7);
+        assert_eq!(Point3::new(1105, -1205, 1229).manhattan(Point3::new(-92, -2380, -20)), 3621);
+    }
+
+    #[test]
+    fn conversions() {
+        let p = Point2::new(2i32, 7);
+        assert_eq!(p.try_convert::<usize>(), Some(Point2::new(2usize, 7)));
+        assert_eq!(Point2::new(-1i32, 0).try_convert::<usize>(), None);
+        assert_eq!(<[i32; 2]>::from(p), [2, 7]);
+        assert_eq!(p.map(|c| c * 10), Point2::new(20, 70));
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let p: Point3<i32> = "-618,-824,-621".parse().unwrap();
+        assert_eq!(p, Point3::new(-618, -824, -621));
+        assert_eq!(p.to_string(), "(-618,-824,-621)");
+        assert_eq!("6,10".parse::<Point2<usize>>(), Ok(Point2::new(6, 10)));
+        assert!("1,2,3".parse::<Point2<i32>>().is_err());
+        assert!("1".parse::<Point2<i32>>().is_err());
+    }
+}
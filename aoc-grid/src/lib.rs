@@ -0,0 +1,225 @@
+//! Row-major 2D grid shared by the days that work on maps of cells
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+pub use aoc_geometry::Point2;
+
+/// Cell position: `x` is the column, `y` the row, both from the top left
+pub type Pos = Point2<usize>;
+
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const ALL_AROUND: [(isize, isize); 8] = [(-1, -1), (0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0)];
+
+/// In-bounds neighbours of a position, clockwise starting from above (4
+/// connected) or from the top left (8 connected)
+pub struct Neighbors {
+    origin: Pos,
+    width: usize,
+    height: usize,
+    offsets: &'static [(isize, isize)],
+}
+
+impl Neighbors {
+    /// Up, right, down, left of `p` within a `width` x `height` area. Works
+    /// without a `Grid` for areas that are computed rather than stored.
+    pub fn orthogonal(p: Pos, width: usize, height: usize) -> Neighbors {
+        Neighbors { origin: p, width, height, offsets: &ORTHOGONAL }
+    }
+
+    /// All eight cells surrounding `p` within a `width` x `height` area
+    pub fn all_around(p: Pos, width: usize, height: usize) -> Neighbors {
+        Neighbors { origin: p, width, height, offsets: &ALL_AROUND }
+    }
+}
+
+impl Iterator for Neighbors {
+    type Item = Pos;
+
+    fn next(&mut self) -> Option<Pos> {
+        while let Some((&(dx, dy), rest)) = self.offsets.split_first() {
+            self.offsets = rest;
+            let x = self.origin.x.checked_add_signed(dx).filter(|&x| x < self.width);
+            let y = self.origin.y.checked_add_signed(dy).filter(|&y| y < self.height);
+            if let (Some(x), Some(y)) = (x, y) {
+                return Some(Pos { x, y });
+            }
+        }
+        None
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Grid over `cells` in row-major order; the length must be a multiple
+    /// of `width`
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Grid<T> {
+        if cells.is_empty() {
+            return Grid { cells, width, height: 0 };
+        }
+        assert!(width > 0 && cells.len().is_multiple_of(width), "{} cells can't be {} wide", cells.len(), width);
+        let height = cells.len() / width;
+        Grid { cells, width, height }
+    }
+
+    /// Grid of one cell per character, rows taken from lines. `None` if any
+    /// character is rejected by `cell`, or the rows differ in length.
+    pub fn parse(text: &str, mut cell: impl FnMut(char) -> Option<T>) -> Option<Grid<T>> {
+        let mut cells = Vec::with_capacity(text.len());
+        let mut width = None;
+        for line in text.lines() {
+            let before = cells.len();
+            for c in line.trim_end().chars() {
+                cells.push(cell(c)?);
+            }
+            let w = cells.len() - before;
+            if *width.get_or_insert(w) != w {
+                return None;
+            }
+        }
+        Some(Grid::from_vec(width.filter(|&w| w > 0)?, cells))
+    }
+
+    pub fn width(&self) -> usize { self.width }
+    pub fn height(&self) -> usize { self.height }
+
+    pub fn contains(&self, p: Pos) -> bool {
+        p.x < self.width && p.y < self.height
+    }
+
+    pub fn get(&self, p: Pos) -> Option<&T> {
+        if self.contains(p) { Some(&self[p]) } else { None }
+    }
+
+    pub fn get_mut(&mut self, p: Pos) -> Option<&mut T> {
+        if self.contains(p) { Some(&mut self[p]) } else { None }
+    }
+
+    /// Every position in row-major order
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| Pos { x: i % width, y: i / width })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    /// The cells, to reuse the allocation
+    pub fn into_vec(self) -> Vec<T> {
+        self.cells
+    }
+
+    pub fn neighbors4(&self, p: Pos) -> Neighbors {
+        Neighbors::orthogonal(p, self.width, self.height)
+    }
+
+    pub fn neighbors8(&self, p: Pos) -> Neighbors {
+        Neighbors::all_around(p, self.width, self.height)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { cells: self.cells.iter().map(f).collect(), width: self.width, height: self.height }
+    }
+
+    fn offset(&self, p: Pos) -> usize {
+        assert!(self.contains(p), "{} outside {}x{} grid", p, self.width, self.height);
+        p.y * self.width + p.x
+    }
+}
+
+impl<T> Default for Grid<T> {
+    fn default() -> Grid<T> {
+        Grid { cells: Vec::new(), width: 0, height: 0 }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid::from_vec(width, vec![value; width * height])
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+    fn index(&self, p: Pos) -> &T {
+        &self.cells[self.offset(p)]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, p: Pos) -> &mut T {
+        let i = self.offset(p);
+        &mut self.cells[i]
+    }
+}
+
+/// Each row on its own line, cells written back to back
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // by the height rather than `chunks`, which can't take a width of 0
+        for y in 0..self.height {
+            for cell in &self.cells[y * self.width..(y + 1) * self.width] {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: fmt::Display> fmt::Debug for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_index() {
+        let grid = Grid::parse("123\n456\n", |c| c.to_digit(10)).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Pos::new(2, 1)], 6);
+        assert_eq!(grid.get(Pos::new(3, 0)), None);
+        assert_eq!(grid.to_string(), "123\n456\n");
+
+        assert!(Grid::parse("12\n345\n", |c| c.to_digit(10)).is_none());
+        assert!(Grid::parse("1x\n", |c| c.to_digit(10)).is_none());
+        assert!(Grid::<u32>::parse("", |c| c.to_digit(10)).is_none());
+    }
+
+    #[test]
+    fn display_empty() {
+        assert_eq!(Grid::<u32>::default().to_string(), "");
+        assert_eq!(Grid::filled(0, 2, 0).to_string(), "");
+        assert_eq!(Grid::filled(2, 0, 0).to_string(), "");
+    }
+
+    #[test]
+    fn neighbors_stay_in_bounds() {
+        let grid = Grid::filled(3, 3, 0);
+        let corner: Vec<Pos> = grid.neighbors4(Pos::new(0, 0)).collect();
+        assert_eq!(corner, [Pos::new(1, 0), Pos::new(0, 1)]);
+        assert_eq!(grid.neighbors4(Pos::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbors8(Pos::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbors8(Pos::new(2, 2)).collect::<Vec<_>>(),
+            [Pos::new(1, 1), Pos::new(2, 1), Pos::new(1, 2)]);
+    }
+}
@@ -0,0 +1,133 @@
+//! Typed parsing for the shapes most inputs come in: a value per line,
+//! blocks of lines between blank lines, and a line of comma separated
+//! values. Malformed input comes back as an `AocError` naming the line
+//! it's on, for the days to return from `Solver::try_parse`.
+
+use std::io::BufRead;
+use std::str::FromStr;
+
+use aoc_core::{AocError, LineBlocks};
+
+/// Every line of `reader` parsed as a `T`, with trailing whitespace (and
+/// any `\r`) trimmed first
+pub fn parse_lines<T, R>(reader: R) -> Result<Vec<T>, AocError>
+where
+    T: FromStr,
+    T::Err: Into<AocError>,
+    R: BufRead,
+{
+    stream_lines(reader).collect()
+}
+
+/// `parse_lines` a line at a time as `reader` is read, for days that can
+/// use each value and let it go rather than keep the whole input
+pub fn stream_lines<T, R>(reader: R) -> impl Iterator<Item = Result<T, AocError>>
+where
+    T: FromStr,
+    T::Err: Into<AocError>,
+    R: BufRead,
+{
+    reader.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let line = line.map_err(|e| AocError::from(e).at_line(i + 1))?;
+            line.trim_end().parse().map_err(|e: T::Err| e.into().at_line(i + 1))
+        })
+}
+
+/// A run of non-blank lines, and where in the input it starts
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Block {
+    /// Line number of the first line, from 1
+    pub line: usize,
+    pub lines: Vec<String>,
+}
+
+impl Block {
+    /// Each line parsed with `f`, with failures tagged with their line
+    pub fn parse_each<T, E: Into<AocError>>(&self, mut f: impl FnMut(&str) -> Result<T, E>) -> Result<Vec<T>, AocError> {
+        self.lines.iter()
+            .enumerate()
+            .map(|(i, line)| f(line).map_err(|e| e.into().at_line(self.line + i)))
+            .collect()
+    }
+}
+
+/// `LineBlocks::blocks` over the lines of `reader`, each with the line it
+/// starts on
+pub fn split_blank_blocks<R: BufRead>(reader: R) -> Result<Vec<Block>, AocError> {
+    let lines = reader.lines()
+        .enumerate()
+        .map(|(i, line)| line.map_err(|e| AocError::from(e).at_line(i + 1)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut blocks = lines.into_iter().blocks();
+    let mut found = vec!();
+    while let Some(lines) = blocks.next() {
+        found.push(Block { line: blocks.start(), lines });
+    }
+    Ok(found)
+}
+
+/// The comma separated values on `line`, each trimmed of whitespace, with
+/// failures naming which value it was
+pub fn parse_csv_line<T>(line: &str) -> Result<Vec<T>, AocError>
+where
+    T: FromStr,
+    T::Err: Into<AocError>,
+{
+    line.trim()
+        .split(',')
+        .enumerate()
+        .map(|(i, s)| s.trim().parse().map_err(|e: T::Err| AocError::invalid(format!("value {}: {}", i + 1, e.into()))))
+        .collect()
+}
+
+/// `parse_csv_line` on the first line of `reader`, for inputs that are a
+/// single line of values
+pub fn read_csv_line<T, R>(mut reader: R) -> Result<Vec<T>, AocError>
+where
+    T: FromStr,
+    T::Err: Into<AocError>,
+    R: BufRead,
+{
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    parse_csv_line(&line).map_err(|e| e.at_line(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_lines_with_line_numbers() {
+        assert_eq!(parse_lines::<u32, _>("199\n200\r\n208\n".as_bytes()).unwrap(), [199, 200, 208]);
+        let err = parse_lines::<u32, _>("1\n2\nx\n".as_bytes()).err().unwrap();
+        assert_eq!(err.to_string(), "line 3: bad number: invalid digit found in string");
+
+        let mut values = stream_lines::<u32, _>("7\ny\n".as_bytes());
+        assert_eq!(values.next().unwrap().unwrap(), 7);
+        assert_eq!(values.next().unwrap().err().unwrap().to_string(), "line 2: bad number: invalid digit found in string");
+    }
+
+    #[test]
+    fn splits_blocks_where_they_start() {
+        let blocks = split_blank_blocks("7,4,9\n\n22 13\n 8  2\n\n\n1 2 \n".as_bytes()).unwrap();
+        assert_eq!(blocks.iter().map(|b| b.line).collect::<Vec<_>>(), [1, 3, 7]);
+        assert_eq!(blocks[1].lines, ["22 13", " 8  2"]);
+        assert_eq!(blocks[2].lines, ["1 2"]);
+
+        let rows = blocks[1].parse_each(|l| l.split_whitespace().map(str::parse::<u8>).collect::<Result<Vec<_>, _>>()).unwrap();
+        assert_eq!(rows, [vec![22, 13], vec![8, 2]]);
+        let err = blocks[0].parse_each(|l| l.parse::<u8>()).err().unwrap();
+        assert_eq!(err.to_string(), "line 1: bad number: invalid digit found in string");
+        assert!(split_blank_blocks("".as_bytes()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn parses_csv() {
+        assert_eq!(parse_csv_line::<u8>("3,4, 3,1,2\n").unwrap(), [3, 4, 3, 1, 2]);
+        assert_eq!(parse_csv_line::<u8>("3,,1").err().unwrap().to_string(), "value 2: bad number: cannot parse integer from empty string");
+        assert_eq!(read_csv_line::<u8, _>("16,1,x\n".as_bytes()).err().unwrap().to_string(), "line 1: value 3: bad number: invalid digit found in string");
+    }
+}
@@ -0,0 +1,433 @@
+//! Axis-aligned boxes of whole-number cells: intervals, rectangles and
+//! cuboids given as one inclusive range per axis, with the overlap and
+//! boolean operations the reactor reboot needed, for any puzzle that lights
+//! up or carves out blocks of cells. It needs only `alloc`, and is built
+//! `no_std` without the default `std` feature.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::{max, min};
+use core::fmt;
+use core::num::ParseIntError;
+use core::ops::{Add, RangeInclusive, Sub};
+use core::str::FromStr;
+
+use aoc_core::AocError;
+
+/// Coordinate types a region can be indexed by
+pub trait Coord: Copy + Ord + fmt::Debug + fmt::Display + FromStr<Err = ParseIntError>
+    + Add<Output = Self> + Sub<Output = Self>
+{
+    const ONE: Self;
+
+    /// Number of cells from `start` to `end` inclusive, if it fits in a u64
+    fn span(start: Self, end: Self) -> Option<u64>;
+
+    /// The next coordinate up, unless this is the largest
+    fn checked_succ(self) -> Option<Self>;
+}
+
+macro_rules! impl_coord {
+    ($($t:ty),*) => {$(
+        impl Coord for $t {
+            const ONE: $t = 1;
+
+            fn span(start: $t, end: $t) -> Option<u64> {
+                u64::try_from((end as i128 - start as i128 + 1).max(0)).ok()
+            }
+
+            fn checked_succ(self) -> Option<$t> {
+                self.checked_add(1)
+            }
+        }
+    )*};
+}
+
+impl_coord!(i32, i64);
+
+/// The cells from one coordinate to another along every one of `N` axes
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Region<T, const N: usize> {
+    ranges: [RangeInclusive<T>; N],
+}
+
+pub type Interval<T> = Region<T, 1>;
+pub type Rect<T> = Region<T, 2>;
+pub type Cuboid<T> = Region<T, 3>;
+
+/// How one region lies relative to another
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Overlap<T, const N: usize> {
+    Same,
+    /// They share the cells of this region, and each has cells the other
+    /// doesn't
+    Intersection(Region<T, N>),
+    /// The first has every cell of the second and more
+    Enclosing,
+    /// The second has every cell of the first and more
+    Enclosed,
+    None,
+}
+
+/// How two ranges lie along one axis
+enum AxisOverlap {
+    Same,
+    Enclosing,
+    Enclosed,
+    Intersecting,
+    None,
+}
+
+fn axis_overlap<T: Coord>(a: &RangeInclusive<T>, b: &RangeInclusive<T>) -> AxisOverlap {
+    match [a.contains(b.start()), a.contains(b.end()), b.contains(a.start()), b.contains(a.end())] {
+        [true, true, true, true] => AxisOverlap::Same,
+        [false, false, false, false] => AxisOverlap::None,
+        [true, true, _, _] => AxisOverlap::Enclosing,
+        [_, _, true, true] => AxisOverlap::Enclosed,
+        _ => AxisOverlap::Intersecting,
+    }
+}
+
+/// Whether `a` starts no later than just after `b` ends
+fn starts_by_end_of<T: Coord>(a: &RangeInclusive<T>, b: &RangeInclusive<T>) -> bool {
+    b.end().checked_succ().is_none_or(|after| *a.start() <= after)
+}
+
+/// Names the axes go by in text, `x=..` and so on
+const AXIS_NAMES: [&str; 3] = ["x", "y", "z"];
+
+/// Axis `i`'s name in text: past `z`, `x3`, `x4` and on, by number
+fn axis_name(i: usize) -> Cow<'static, str> {
+    match AXIS_NAMES.get(i) {
+        Some(&name) => Cow::Borrowed(name),
+        None => Cow::Owned(format!("x{}", i)),
+    }
+}
+
+impl<T, const N: usize> Region<T, N> {
+    pub const fn new(ranges: [RangeInclusive<T>; N]) -> Region<T, N> {
+        Region { ranges }
+    }
+
+    pub fn ranges(&self) -> &[RangeInclusive<T>; N] {
+        &self.ranges
+    }
+}
+
+impl<T: Coord, const N: usize> Region<T, N> {
+    /// Range along axis `i`, counting from 0
+    pub fn axis(&self, i: usize) -> RangeInclusive<T> {
+        self.ranges[i].clone()
+    }
+
+    /// Number of cells inside, or an error if that doesn't fit in a u64
+    pub fn volume(&self) -> Result<u64, AocError> {
+        self.ranges.iter()
+            .try_fold(1u64, |v, r| T::span(*r.start(), *r.end()).and_then(|span| v.checked_mul(span)))
+            .ok_or_else(|| AocError::invalid(format!("volume of {} overflows u64", self)))
+    }
+
+    pub fn overlaps(&self, other: &Region<T, N>) -> Overlap<T, N> {
+        let axes: [AxisOverlap; N] = core::array::from_fn(|i| axis_overlap(&self.ranges[i], &other.ranges[i]));
+        let all = |f: fn(&AxisOverlap) -> bool| axes.iter().all(f);
+        if axes.iter().any(|o| matches!(o, AxisOverlap::None)) {
+            Overlap::None
+        } else if all(|o| matches!(o, AxisOverlap::Same)) {
+            Overlap::Same
+        } else if all(|o| matches!(o, AxisOverlap::Same | AxisOverlap::Enclosing)) {
+            Overlap::Enclosing
+        } else if all(|o| matches!(o, AxisOverlap::Same | AxisOverlap::Enclosed)) {
+            Overlap::Enclosed
+        } else {
+            Overlap::Intersection(self.intersection(other).unwrap())
+        }
+    }
+
+    /// This region cut into pieces around `hole`, which must lie inside it:
+    /// up to two slabs per axis, above and below the hole
+    pub fn sub_into_parts(&self, hole: &Region<T, N>) -> Vec<Region<T, N>> {
+        let mut parts = Vec::with_capacity(2 * N);
+        for i in 0..N {
+            // axes already sliced take the hole's range, the rest all of ours
+            let slab = |range: RangeInclusive<T>| {
+                let mut ranges = self.ranges.clone();
+                ranges[..i].clone_from_slice(&hole.ranges[..i]);
+                ranges[i] = range;
+                Region { ranges }
+            };
+            let (outer, inner) = (&self.ranges[i], &hole.ranges[i]);
+            if outer.end() > inner.end() {
+                parts.push(slab(*inner.end() + T::ONE..=*outer.end()));
+            }
+            if outer.start() < inner.start() {
+                parts.push(slab(*outer.start()..=*inner.start() - T::ONE));
+            }
+        }
+        parts
+    }
+
+    /// Cells in both regions, if there are any
+    pub fn intersection(&self, other: &Region<T, N>) -> Option<Region<T, N>> {
+        let mut ranges = self.ranges.clone();
+        for (r, o) in ranges.iter_mut().zip(&other.ranges) {
+            *r = max(*r.start(), *o.start())..=min(*r.end(), *o.end());
+            if r.is_empty() {
+                return None;
+            }
+        }
+        Some(Region { ranges })
+    }
+
+    /// Cells of this region that aren't in `other`, as regions that don't
+    /// overlap
+    pub fn difference(&self, other: &Region<T, N>) -> Vec<Region<T, N>> {
+        match self.intersection(other) {
+            Some(common) => self.sub_into_parts(&common),
+            None => vec!(self.clone()),
+        }
+    }
+
+    /// Cells in either region, as regions that don't overlap
+    pub fn union(&self, other: &Region<T, N>) -> Vec<Region<T, N>> {
+        let mut parts = vec!(self.clone());
+        parts.extend(other.difference(self));
+        parts
+    }
+
+    pub fn contains(&self, other: &Region<T, N>) -> bool {
+        self.ranges.iter().zip(&other.ranges).all(|(r, o)| r.contains(o.start()) && r.contains(o.end()))
+    }
+
+    /// The region covering exactly this one and `other`, when they line up
+    /// on every axis but one and touch or overlap on that one
+    pub fn merge(&self, other: &Region<T, N>) -> Option<Region<T, N>> {
+        let differing: Vec<usize> = (0..N).filter(|&i| self.ranges[i] != other.ranges[i]).collect();
+        match differing[..] {
+            [] => Some(self.clone()),
+            [i] => {
+                let (a, b) = (&self.ranges[i], &other.ranges[i]);
+                if !(starts_by_end_of(a, b) && starts_by_end_of(b, a)) {
+                    return None;
+                }
+                let mut merged = self.clone();
+                merged.ranges[i] = min(*a.start(), *b.start())..=max(*a.end(), *b.end());
+                Some(merged)
+            },
+            _ => None,
+        }
+    }
+}
+
+impl<T: Coord> Region<T, 3> {
+    pub fn x(&self) -> RangeInclusive<T> { self.axis(0) }
+    pub fn y(&self) -> RangeInclusive<T> { self.axis(1) }
+    pub fn z(&self) -> RangeInclusive<T> { self.axis(2) }
+}
+
+impl<T, const N: usize> From<[RangeInclusive<T>; N]> for Region<T, N> {
+    fn from(ranges: [RangeInclusive<T>; N]) -> Region<T, N> {
+        Region { ranges }
+    }
+}
+
+/// `start..end`
+pub fn parse_range<T: Coord>(s: &str) -> Result<RangeInclusive<T>, AocError> {
+    let (start, end) = s.split_once("..").ok_or("failed to find \"..\"")?;
+    Ok(start.parse::<T>()?..=end.parse::<T>()?)
+}
+
+/// `x=1..2,y=3..4,z=5..6`, with as many axes as the region has
+impl<T: fmt::Display, const N: usize> fmt::Display for Region<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, r) in self.ranges.iter().enumerate() {
+            let sep = if i == 0 { "" } else { "," };
+            write!(f, "{}{}={}..{}", sep, axis_name(i), r.start(), r.end())?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Coord, const N: usize> TryFrom<&str> for Region<T, N> {
+    type Error = AocError;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let mut parts = s.split(',');
+        let mut ranges = Vec::with_capacity(N);
+        for name in (0..N).map(axis_name) {
+            let range = parts.next()
+                .and_then(|p| p.strip_prefix(&*name)?.strip_prefix('='))
+                .ok_or_else(|| AocError::invalid(format!("failed to find {}=", name)))?;
+            ranges.push(parse_range(range)?);
+        }
+        if parts.next().is_some() {
+            return Err(AocError::invalid(format!("more than {} ranges", N)));
+        }
+        Ok(Region { ranges: ranges.try_into().unwrap_or_else(|_| unreachable!()) })
+    }
+}
+
+/// A list of ranges, one per axis
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize, const N: usize> serde::Serialize for Region<T, N> {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_seq(self.ranges.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>, const N: usize> serde::Deserialize<'de> for Region<T, N> {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let ranges: Vec<RangeInclusive<T>> = serde::Deserialize::deserialize(d)?;
+        let len = ranges.len();
+        ranges.try_into()
+            .map(|ranges| Region { ranges })
+            .map_err(|_| serde::de::Error::invalid_length(len, &"one range per axis"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    /// Every region with each axis somewhere in `0..side`
+    fn all_regions<const N: usize>(side: i32) -> Vec<Region<i32, N>> {
+        let ranges: Vec<RangeInclusive<i32>> = (0..side).flat_map(|a| (a..side).map(move |b| a..=b)).collect();
+        let mut regions = vec!(Vec::new());
+        for _ in 0..N {
+            regions = regions.into_iter()
+                .flat_map(|prefix| ranges.iter().map(move |r| {
+                    let mut p = prefix.clone();
+                    p.push(r.clone());
+                    p
+                }))
+                .collect();
+        }
+        regions.into_iter().map(|r| Region::from(<[_; N]>::try_from(r).unwrap())).collect()
+    }
+
+    fn cells<const N: usize>(r: &Region<i32, N>) -> BTreeSet<Vec<i32>> {
+        let mut cells = BTreeSet::from([vec!()]);
+        for range in r.ranges() {
+            cells = cells.into_iter()
+                .flat_map(|c| range.clone().map(move |v| {
+                    let mut c = c.clone();
+                    c.push(v);
+                    c
+                }))
+                .collect();
+        }
+        cells
+    }
+
+    fn cells_of<const N: usize>(regions: &[Region<i32, N>]) -> BTreeSet<Vec<i32>> {
+        regions.iter().flat_map(cells).collect()
+    }
+
+    /// Checks every pair of regions against their cells
+    fn check_every_pair<const N: usize>(side: i32) {
+        let regions = all_regions::<N>(side);
+        for a in &regions {
+            let a_cells = cells(a);
+            assert_eq!(a.volume().unwrap(), a_cells.len() as u64);
+            for b in &regions {
+                let b_cells = cells(b);
+                let common: BTreeSet<_> = a_cells.intersection(&b_cells).cloned().collect();
+                let expected = if common.is_empty() {
+                    Overlap::None
+                } else if a_cells == b_cells {
+                    Overlap::Same
+                } else if common == b_cells {
+                    Overlap::Enclosing
+                } else if common == a_cells {
+                    Overlap::Enclosed
+                } else {
+                    Overlap::Intersection(a.intersection(b).unwrap())
+                };
+                assert_eq!(a.overlaps(b), expected, "{} vs {}", a, b);
+                assert_eq!(a.intersection(b).map(|r| cells(&r)).unwrap_or_default(), common);
+                assert_eq!(a.contains(b), common == b_cells);
+
+                let difference = a.difference(b);
+                assert_eq!(cells_of(&difference), &a_cells - &b_cells, "{} - {}", a, b);
+                let union = a.union(b);
+                assert_eq!(cells_of(&union), &a_cells | &b_cells, "{} | {}", a, b);
+                // the pieces don't overlap, so their volumes add up
+                let volume: u64 = union.iter().map(|r| r.volume().unwrap()).sum();
+                assert_eq!(volume, (&a_cells | &b_cells).len() as u64);
+
+                match a.merge(b) {
+                    Some(m) => assert_eq!(cells(&m), &a_cells | &b_cells),
+                    None => assert!(union.len() > 1 || a.ranges().iter().zip(b.ranges()).filter(|(x, y)| x != y).count() > 1),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn intervals_against_their_cells() {
+        check_every_pair::<1>(6);
+    }
+
+    #[test]
+    fn rects_against_their_cells() {
+        check_every_pair::<2>(4);
+    }
+
+    #[test]
+    fn cuboids_against_their_cells() {
+        check_every_pair::<3>(3);
+    }
+
+    #[test]
+    fn sub_into_parts_surrounds_the_hole() {
+        let outer = Cuboid::from([0..=4, 0..=4, 0..=4]);
+        let parts = outer.sub_into_parts(&Cuboid::from([1..=3, 1..=3, 1..=3]));
+        assert_eq!(parts.len(), 6);
+        assert_eq!(parts.iter().map(|p| p.volume().unwrap()).sum::<u64>(), 125 - 27);
+        assert!(outer.sub_into_parts(&outer).is_empty());
+    }
+
+    #[test]
+    fn text_round_trip() {
+        let c: Cuboid<i64> = Cuboid::try_from("x=-5..47,y=-31..22,z=-19..33").unwrap();
+        assert_eq!((c.x(), c.y(), c.z()), (-5..=47, -31..=22, -19..=33));
+        assert_eq!(c.to_string(), "x=-5..47,y=-31..22,z=-19..33");
+        let r: Rect<i32> = Rect::try_from("x=1..2,y=3..4").unwrap();
+        assert_eq!(r, Rect::new([1..=2, 3..=4]));
+        assert_eq!(Interval::<i32>::new([7..=9]).to_string(), "x=7..9");
+
+        assert_eq!(Rect::<i32>::try_from("x=1..2,z=3..4").unwrap_err().to_string(), "failed to find y=");
+        assert!(Rect::<i32>::try_from("x=1..2,y=3..4,z=5..6").is_err());
+        assert!(Interval::<i32>::try_from("x=1..b").is_err());
+        assert!(Interval::<i32>::try_from("x=0..3000000000").is_err());
+
+        let tesseract: Region<i32, 4> = Region::try_from("x=0..1,y=2..3,z=4..5,x3=6..7").unwrap();
+        assert_eq!(tesseract, Region::new([0..=1, 2..=3, 4..=5, 6..=7]));
+        assert_eq!(tesseract.to_string(), "x=0..1,y=2..3,z=4..5,x3=6..7");
+    }
+
+    #[test]
+    fn volume_overflow() {
+        let line = Interval::new([i64::MIN..=i64::MAX]);
+        assert_eq!(line.volume().unwrap_err().to_string(), format!("volume of {} overflows u64", line));
+        assert_eq!(Interval::new([i64::MIN..=-1]).volume().unwrap(), 1 << 63);
+        assert!(Cuboid::new([i32::MIN..=i32::MAX, i32::MIN..=i32::MAX, 0..=0]).volume().is_err());
+        // an interval with its ends the wrong way round is empty
+        assert_eq!(Interval::new([RangeInclusive::new(3, 1)]).volume().unwrap(), 0);
+    }
+
+    #[test]
+    fn merges_at_the_edges_of_the_coordinates() {
+        let low = Interval::new([i64::MIN..=-1]);
+        let high = Interval::new([0..=i64::MAX]);
+        assert_eq!(low.merge(&high), Some(Interval::new([i64::MIN..=i64::MAX])));
+        assert_eq!(high.merge(&low), low.merge(&high));
+        assert_eq!(Interval::new([0..=1]).merge(&Interval::new([3..=4])), None);
+    }
+}
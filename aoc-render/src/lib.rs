@@ -0,0 +1,175 @@
+//! Rasterizes lit points and grids to PNG, and sequences of them to
+//! animated GIF, for the days' `--render` flags
+
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::Path;
+
+use aoc_geometry::Point2;
+
+/// Background, then colours for increasing cell values
+pub const PALETTE: [[u8; 3]; 4] = [
+    [15, 15, 35],
+    [255, 255, 102],
+    [255, 153, 51],
+    [230, 65, 65],
+];
+
+/// Cells of an image, each an index into `PALETTE`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Raster {
+    width: usize,
+    height: usize,
+    cells: Vec<u8>,
+}
+
+impl Raster {
+    pub fn new(width: usize, height: usize) -> Raster {
+        Raster { width, height, cells: vec![0; width * height] }
+    }
+
+    pub fn width(&self) -> usize { self.width }
+    pub fn height(&self) -> usize { self.height }
+
+    /// Cell values from `f(x, y)`, clamped to the palette
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> u8) -> Raster {
+        let mut raster = Raster::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                raster.set(x, y, f(x, y));
+            }
+        }
+        raster
+    }
+
+    /// The points lit on a background just big enough to hold them, with
+    /// the smallest x and y at the top left
+    pub fn from_points(points: impl IntoIterator<Item = Point2<i32>>) -> Raster {
+        let points: Vec<_> = points.into_iter().collect();
+        let Some(min_x) = points.iter().map(|p| p.x).min() else { return Raster::new(0, 0) };
+        let min_y = points.iter().map(|p| p.y).min().unwrap();
+        let width = (points.iter().map(|p| p.x).max().unwrap() - min_x + 1) as usize;
+        let height = (points.iter().map(|p| p.y).max().unwrap() - min_y + 1) as usize;
+
+        let mut raster = Raster::new(width, height);
+        for p in points {
+            raster.set((p.x - min_x) as usize, (p.y - min_y) as usize, 1);
+        }
+        raster
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> u8 {
+        self.cells[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: u8) {
+        self.cells[y * self.width + x] = value.min(PALETTE.len() as u8 - 1);
+    }
+
+    /// Palette indices, each cell a `scale` pixel square, in a
+    /// `width` x `height` canvas padded with background
+    fn pixels(&self, scale: usize, width: usize, height: usize) -> Vec<u8> {
+        let mut pixels = vec![0; width * scale * height * scale];
+        let row_len = width * scale;
+        for y in 0..self.height * scale {
+            for x in 0..self.width * scale {
+                pixels[y * row_len + x] = self.get(x / scale, y / scale);
+            }
+        }
+        pixels
+    }
+}
+
+fn palette_bytes() -> Vec<u8> {
+    PALETTE.concat()
+}
+
+fn create(path: &Path) -> io::Result<BufWriter<File>> {
+    Ok(BufWriter::new(File::create(path)?))
+}
+
+/// Writes `raster` as an indexed-colour PNG, each cell `scale` pixels square
+pub fn write_png(path: &Path, raster: &Raster, scale: usize) -> io::Result<()> {
+    let (width, height) = (raster.width.max(1), raster.height.max(1));
+    let mut encoder = png::Encoder::new(create(path)?, (width * scale) as u32, (height * scale) as u32);
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(palette_bytes());
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer.write_image_data(&raster.pixels(scale, width, height)).map_err(io::Error::other)?;
+    writer.finish().map_err(io::Error::other)
+}
+
+/// Writes `frames` as a looping GIF, `delay` hundredths of a second apart.
+/// Frames are drawn from the top left of a canvas big enough for the
+/// largest of them.
+pub fn write_gif(path: &Path, frames: &[Raster], scale: usize, delay: u16) -> io::Result<()> {
+    let width = frames.iter().map(|f| f.width).max().unwrap_or(0).max(1);
+    let height = frames.iter().map(|f| f.height).max().unwrap_or(0).max(1);
+    let (w, h) = ((width * scale) as u16, (height * scale) as u16);
+
+    let mut encoder = gif::Encoder::new(create(path)?, w, h, &palette_bytes()).map_err(io::Error::other)?;
+    encoder.set_repeat(gif::Repeat::Infinite).map_err(io::Error::other)?;
+    for raster in frames {
+        let mut frame = gif::Frame::from_indexed_pixels(w, h, raster.pixels(scale, width, height), None);
+        frame.delay = delay;
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+    Ok(())
+}
+
+/// Writes a PNG of the last frame, or all of them as a GIF if `path` ends
+/// in `.gif`
+pub fn write(path: &Path, frames: &[Raster], scale: usize) -> io::Result<()> {
+    if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("gif")) {
+        write_gif(path, frames, scale, 20)
+    } else {
+        let last = frames.last().cloned().unwrap_or_else(|| Raster::new(0, 0));
+        write_png(path, &last, scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_fit_their_bounds() {
+        let r = Raster::from_points([Point2::new(-1, 2), Point2::new(1, 3)]);
+        assert_eq!((r.width(), r.height()), (3, 2));
+        assert_eq!(r.get(0, 0), 1);
+        assert_eq!(r.get(2, 1), 1);
+        assert_eq!(r.get(1, 0), 0);
+    }
+
+    #[test]
+    fn values_clamp_to_palette() {
+        let r = Raster::from_fn(2, 1, |x, _| x as u8 * 9);
+        assert_eq!(r.get(1, 0), PALETTE.len() as u8 - 1);
+    }
+
+    #[test]
+    fn scaled_and_padded() {
+        let r = Raster::from_fn(1, 1, |_, _| 1);
+        assert_eq!(r.pixels(2, 2, 1), [1, 1, 0, 0, 1, 1, 0, 0]);
+    }
+
+    #[test]
+    fn writes_png_and_gif() {
+        let dir = std::env::temp_dir();
+        let frames = [Raster::from_fn(3, 2, |x, y| (x + y) as u8), Raster::new(1, 1)];
+
+        let png = dir.join(format!("aoc-render-{}.png", std::process::id()));
+        write(&png, &frames, 2).unwrap();
+        let bytes = std::fs::read(&png).unwrap();
+        assert_eq!(&bytes[1..4], b"PNG");
+
+        let gif = png.with_extension("gif");
+        write(&gif, &frames, 2).unwrap();
+        let bytes = std::fs::read(&gif).unwrap();
+        assert_eq!(&bytes[..6], b"GIF89a");
+
+        std::fs::remove_file(png).unwrap();
+        std::fs::remove_file(gif).unwrap();
+    }
+}
@@ -0,0 +1,257 @@
+//! Shortest paths over graphs given implicitly: a start node and a closure
+//! listing the successors of any node, so days can search state spaces
+//! without building the graph first
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Path costs: `Default` is zero
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<C: Copy + Ord + Add<Output = C> + Default> Cost for C {}
+
+/// Nodes reached so far, each with the index of the node it was reached from
+struct Visited<N> {
+    nodes: Vec<(N, usize)>,
+    index: HashMap<N, usize>,
+}
+
+impl<N: Eq + Hash + Clone> Visited<N> {
+    fn new(start: &N) -> Visited<N> {
+        Visited { nodes: vec!((start.clone(), usize::MAX)), index: HashMap::from([(start.clone(), 0)]) }
+    }
+
+    /// Path from the start to node `i`
+    fn path_to(&self, mut i: usize) -> Vec<N> {
+        let mut path = vec!();
+        while i != usize::MAX {
+            let (node, parent) = &self.nodes[i];
+            path.push(node.clone());
+            i = *parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Fewest steps from `start` to a node where `success` holds, as the path
+/// including both ends
+pub fn bfs<N, I>(start: &N, mut successors: impl FnMut(&N) -> I, mut success: impl FnMut(&N) -> bool) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let _span = tracing::info_span!("bfs").entered();
+    let mut visited = Visited::new(start);
+    let mut queue = VecDeque::from([0]);
+    while let Some(i) = queue.pop_front() {
+        if success(&visited.nodes[i].0) {
+            return Some(visited.path_to(i));
+        }
+        for next in successors(&visited.nodes[i].0.clone()) {
+            if let Entry::Vacant(e) = visited.index.entry(next.clone()) {
+                e.insert(visited.nodes.len());
+                queue.push_back(visited.nodes.len());
+                visited.nodes.push((next, i));
+            }
+        }
+    }
+    None
+}
+
+/// Cheapest path from `start` to a node where `success` holds, with its
+/// cost. `heuristic` must never overestimate the remaining cost.
+pub fn astar<N, C, I>(
+    start: &N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut success: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let _span = tracing::info_span!("astar").entered();
+    let mut visited = Visited::new(start);
+    // best known cost to each visited node, by index
+    let mut costs = vec!(C::default());
+    let mut queue = BinaryHeap::from([Reverse((heuristic(start), C::default(), 0))]);
+
+    while let Some(Reverse((_, cost, i))) = queue.pop() {
+        if cost > costs[i] {
+            continue;
+        }
+        let node = visited.nodes[i].0.clone();
+        if success(&node) {
+            return Some((visited.path_to(i), cost));
+        }
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            let j = match visited.index.entry(next) {
+                Entry::Vacant(e) => {
+                    let j = visited.nodes.len();
+                    visited.nodes.push((e.key().clone(), i));
+                    costs.push(next_cost);
+                    e.insert(j);
+                    j
+                },
+                Entry::Occupied(e) => {
+                    let j = *e.get();
+                    if next_cost >= costs[j] {
+                        continue;
+                    }
+                    visited.nodes[j].1 = i;
+                    costs[j] = next_cost;
+                    j
+                },
+            };
+            queue.push(Reverse((next_cost + heuristic(&visited.nodes[j].0), next_cost, j)));
+        }
+    }
+    None
+}
+
+/// Cheapest path from `start` to a node where `success` holds, with its cost
+pub fn dijkstra<N, C, I>(start: &N, successors: impl FnMut(&N) -> I, success: impl FnMut(&N) -> bool) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::default(), success)
+}
+
+/// How a pass of `ida_star` ended
+enum Pass<C> {
+    Found(C),
+    /// Cut off by the bound, with the least estimate that was over it, if any
+    Over(Option<C>),
+}
+
+/// Cheapest path like `astar`, found by depth-first passes under a bound on
+/// cost plus `heuristic` that each pass raises to the least estimate the
+/// last one cut off. A pass remembers the cheapest cost it reached each node
+/// at, and goes no further from a node reached again for no less, which
+/// keeps it from searching every order of independent steps over again.
+pub fn ida_star<N, C, I>(
+    start: &N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut success: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let _span = tracing::info_span!("ida_star").entered();
+    let mut pass = Deepening { path: vec!(start.clone()), reached: HashMap::new(), bound: heuristic(start) };
+    loop {
+        pass.reached.clear();
+        pass.reached.insert(start.clone(), C::default());
+        match pass.deepen(C::default(), &mut successors, &mut heuristic, &mut success) {
+            Pass::Found(cost) => return Some((pass.path, cost)),
+            Pass::Over(Some(next)) => pass.bound = next,
+            Pass::Over(None) => return None,
+        }
+    }
+}
+
+/// One pass of `ida_star`
+struct Deepening<N, C> {
+    /// From the start to the node being searched from
+    path: Vec<N>,
+    /// Cheapest cost each node has been reached at in this pass
+    reached: HashMap<N, C>,
+    bound: C,
+}
+
+impl<N: Eq + Hash + Clone, C: Cost> Deepening<N, C> {
+    /// Searches on from the last node of the path, reached at `cost`
+    fn deepen<I: IntoIterator<Item = (N, C)>>(
+        &mut self,
+        cost: C,
+        successors: &mut impl FnMut(&N) -> I,
+        heuristic: &mut impl FnMut(&N) -> C,
+        success: &mut impl FnMut(&N) -> bool,
+    ) -> Pass<C> {
+        let node = self.path[self.path.len() - 1].clone();
+        let estimate = cost + heuristic(&node);
+        if estimate > self.bound {
+            return Pass::Over(Some(estimate));
+        }
+        if success(&node) {
+            return Pass::Found(cost);
+        }
+        let mut over: Option<C> = None;
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            match self.reached.entry(next.clone()) {
+                Entry::Occupied(e) if *e.get() <= next_cost => continue,
+                Entry::Occupied(mut e) => { e.insert(next_cost); },
+                Entry::Vacant(e) => { e.insert(next_cost); },
+            }
+            self.path.push(next);
+            match self.deepen(next_cost, successors, heuristic, success) {
+                Pass::Found(cost) => return Pass::Found(cost),
+                Pass::Over(Some(estimate)) => over = Some(over.map_or(estimate, |o| o.min(estimate))),
+                Pass::Over(None) => {},
+            }
+            self.path.pop();
+        }
+        Pass::Over(over)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 5x5 grid, moving right or down, with entering (x, y) costing
+    /// `COSTS[y][x]`
+    const COSTS: [[u32; 5]; 5] = [
+        [0, 1, 9, 9, 9],
+        [9, 1, 1, 1, 9],
+        [9, 9, 9, 1, 9],
+        [1, 1, 1, 1, 1],
+        [9, 9, 9, 9, 1],
+    ];
+
+    fn right_or_down(&(x, y): &(usize, usize)) -> Vec<((usize, usize), u32)> {
+        [(x + 1, y), (x, y + 1)].into_iter()
+            .filter(|&(x, y)| x < 5 && y < 5)
+            .map(|(x, y)| ((x, y), COSTS[y][x]))
+            .collect()
+    }
+
+    #[test]
+    fn dijkstra_and_astar_agree() {
+        let goal = |&p: &(usize, usize)| p == (4, 4);
+        let (path, cost) = dijkstra(&(0, 0), right_or_down, goal).unwrap();
+        assert_eq!(cost, 8);
+        assert_eq!(path, [(0, 0), (1, 0), (1, 1), (2, 1), (3, 1), (3, 2), (3, 3), (4, 3), (4, 4)]);
+
+        let manhattan = |&(x, y): &(usize, usize)| (8 - x - y) as u32;
+        assert_eq!(astar(&(0, 0), right_or_down, manhattan, goal), Some((path.clone(), cost)));
+        assert_eq!(ida_star(&(0, 0), right_or_down, manhattan, goal), Some((path, cost)));
+    }
+
+    #[test]
+    fn bfs_counts_steps() {
+        // the integers, stepping by +1 or *2
+        let path = bfs(&1u32, |&n| [n + 1, n * 2], |&n| n == 10).unwrap();
+        assert_eq!(path, [1, 2, 4, 5, 10]);
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        assert_eq!(bfs(&0u8, |&n| (n < 3).then_some(n + 1), |&n| n == 5), None);
+        assert_eq!(dijkstra(&0u8, |&n| (n < 3).then_some((n + 1, 1u32)), |&n| n == 5), None);
+        // going round in a cycle never gets there either
+        assert_eq!(ida_star(&0u8, |&n| [((n + 1) % 3, 1u32)], |_| 0, |&n| n == 5), None);
+    }
+}
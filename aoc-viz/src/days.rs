@@ -0,0 +1,214 @@
+//! `Visualize` for d11, d15 and d25
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use ratatui::style::Color;
+
+use aoc_grid::{Grid, Pos};
+use d11::OctoMap;
+use d15::{Cave, ChitonCave, Risk};
+use d25::{Cucumber, Map};
+
+use crate::Visualize;
+
+/// Day 11: octopus energy levels, one step at a time until they all flash
+/// together
+pub struct Octopuses {
+    map: OctoMap,
+    flashes: u32,
+}
+
+impl Octopuses {
+    pub fn new(input: &str) -> Octopuses {
+        Octopuses { map: OctoMap::from_str(input.lines().map(String::from)), flashes: 0 }
+    }
+}
+
+impl Visualize for Octopuses {
+    fn size(&self) -> (usize, usize) {
+        (self.map.width(), self.map.height())
+    }
+
+    fn cell(&self, p: Pos) -> (char, Color) {
+        let o = self.map[p];
+        let colour = match o.energy() {
+            0 => Color::White,
+            e => Color::Rgb(0, 40 + 20 * e, 60 + 15 * e),
+        };
+        (if o.flashed() { '*' } else { (b'0' + o.energy()) as char }, colour)
+    }
+
+    fn status(&self) -> String {
+        format!("step {}: {} flashes", self.map.steps(), self.flashes)
+    }
+
+    fn step(&mut self) -> bool {
+        if self.flashes as usize == self.map.width() * self.map.height() {
+            return false;
+        }
+        self.flashes = self.map.step();
+        true
+    }
+}
+
+/// Day 15: Dijkstra's search spreading out from the entrance, a batch of
+/// cells per step, then the lowest risk path once it reaches the exit
+pub struct Frontier {
+    cave: Cave,
+    risk: Grid<Option<Risk>>,
+    came_from: Grid<Option<Pos>>,
+    settled: Grid<bool>,
+    queue: BinaryHeap<Reverse<(Risk, Pos)>>,
+    path: Vec<Pos>,
+}
+
+impl Frontier {
+    pub fn new(input: &str) -> Frontier {
+        let cave = Cave::from_reader(&mut input.lines());
+        let dim = cave.dim();
+        let mut risk = Grid::filled(dim, dim, None);
+        risk[cave.entrance()] = Some(0);
+        let queue = BinaryHeap::from([Reverse((0, cave.entrance()))]);
+        Frontier {
+            cave,
+            risk,
+            came_from: Grid::filled(dim, dim, None),
+            settled: Grid::filled(dim, dim, false),
+            queue,
+            path: vec!(),
+        }
+    }
+
+    /// Settles the nearest cell on the frontier, returning false if the
+    /// search is over
+    fn settle_one(&mut self) -> bool {
+        let Some(Reverse((risk, p))) = self.queue.pop() else { return false };
+        if self.settled[p] {
+            return true;
+        }
+        self.settled[p] = true;
+        if p == self.cave.exit() {
+            self.path = std::iter::successors(Some(p), |&p| self.came_from[p]).collect();
+            return false;
+        }
+        for n in self.settled.neighbors4(p) {
+            if let Some(r) = self.cave.risk(n) {
+                let r = risk + r;
+                if self.risk[n].is_none_or(|old| r < old) {
+                    self.risk[n] = Some(r);
+                    self.came_from[n] = Some(p);
+                    self.queue.push(Reverse((r, n)));
+                }
+            }
+        }
+        true
+    }
+}
+
+impl Visualize for Frontier {
+    fn size(&self) -> (usize, usize) {
+        (self.cave.dim(), self.cave.dim())
+    }
+
+    fn cell(&self, p: Pos) -> (char, Color) {
+        let ch = self.cave.risk(p).map_or('#', |r| char::from_digit(r, 10).unwrap_or('+'));
+        let colour = if self.path.contains(&p) {
+            Color::Yellow
+        } else if self.settled[p] {
+            Color::Blue
+        } else if self.risk[p].is_some() {
+            Color::Green
+        } else {
+            Color::DarkGray
+        };
+        (ch, colour)
+    }
+
+    fn status(&self) -> String {
+        let settled = self.settled.cells().iter().filter(|&&s| s).count();
+        match self.path.first() {
+            Some(&exit) => format!("lowest risk {} ({} cells settled)", self.risk[exit].unwrap(), settled),
+            None => format!("{} cells settled, {} on the frontier", settled, self.queue.len()),
+        }
+    }
+
+    fn step(&mut self) -> bool {
+        // about one row's worth per frame, so big caves don't take forever
+        (0..self.cave.dim()).all(|_| self.settle_one())
+    }
+}
+
+/// Day 25: both herds of sea cucumbers, until neither can move
+pub struct Cucumbers {
+    map: Map,
+    steps: usize,
+    moved: usize,
+}
+
+impl Cucumbers {
+    pub fn new(input: &str) -> Option<Cucumbers> {
+        let map = Map::try_from_lines(input.lines()).ok()?;
+        Some(Cucumbers { map, steps: 0, moved: 0 })
+    }
+}
+
+impl Visualize for Cucumbers {
+    fn size(&self) -> (usize, usize) {
+        (self.map.width(), self.map.height())
+    }
+
+    fn cell(&self, p: Pos) -> (char, Color) {
+        match self.map.get(p) {
+            Some(Cucumber::Easterly) => ('>', Color::LightCyan),
+            Some(Cucumber::Southerly) => ('v', Color::LightGreen),
+            None => ('.', Color::DarkGray),
+        }
+    }
+
+    fn status(&self) -> String {
+        format!("step {}: {} moved", self.steps, self.moved)
+    }
+
+    fn step(&mut self) -> bool {
+        self.steps += 1;
+        self.moved = self.map.step();
+        self.moved > 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(v: &mut dyn Visualize) -> usize {
+        let mut steps = 0;
+        while v.step() {
+            steps += 1;
+        }
+        steps
+    }
+
+    #[test]
+    fn octopuses_until_synchronized() {
+        let mut v = Octopuses::new(include_str!("../../2021/d11/test_input"));
+        assert_eq!(run(&mut v), 195);
+        assert_eq!(v.cell(Pos::new(0, 0)), ('*', Color::White));
+    }
+
+    #[test]
+    fn frontier_finds_lowest_risk() {
+        let mut v = Frontier::new(include_str!("../../2021/d15/input_test"));
+        run(&mut v);
+        assert!(v.status().starts_with("lowest risk 40 "));
+        assert_eq!(v.path.first(), Some(&Pos::new(9, 9)));
+        assert_eq!(v.path.last(), Some(&Pos::new(0, 0)));
+    }
+
+    #[test]
+    fn cucumbers_stop() {
+        let mut v = Cucumbers::new(include_str!("../../2021/d25/input_sample")).unwrap();
+        assert_eq!(run(&mut v), 57);
+        assert_eq!(v.status(), "step 58: 0 moved");
+    }
+}
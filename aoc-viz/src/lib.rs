@@ -0,0 +1,92 @@
+//! Animated terminal views of the days whose state is worth watching, for
+//! `aoc run --viz`
+
+use std::io;
+use std::time::Duration;
+
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Block, Widget};
+
+use aoc_grid::Pos;
+
+mod days;
+
+pub use days::{Cucumbers, Frontier, Octopuses};
+
+/// A puzzle state that can be drawn cell by cell and moved on a step at a time
+pub trait Visualize {
+    /// Width and height of the drawing, in cells
+    fn size(&self) -> (usize, usize);
+
+    /// Character and colour to draw at `p`
+    fn cell(&self, p: Pos) -> (char, Color);
+
+    /// One line about the current state, shown above the drawing
+    fn status(&self) -> String;
+
+    /// Moves on a step, returning false once there's nothing left to show
+    fn step(&mut self) -> bool;
+}
+
+/// The visualization for `day`, starting from its input
+pub fn for_day(day: usize, input: &str) -> Option<Box<dyn Visualize>> {
+    Some(match day {
+        11 => Box::new(Octopuses::new(input)),
+        15 => Box::new(Frontier::new(input)),
+        25 => Box::new(Cucumbers::new(input)?),
+        _ => return None,
+    })
+}
+
+/// Draws as much of the state as fits, from the top left
+struct View<'a>(&'a dyn Visualize);
+
+impl Widget for View<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered().title(self.0.status());
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let (width, height) = self.0.size();
+        for y in 0..height.min(inner.height as usize) {
+            for x in 0..width.min(inner.width as usize) {
+                let (ch, colour) = self.0.cell(Pos::new(x, y));
+                buf[(inner.x + x as u16, inner.y + y as u16)]
+                    .set_char(ch)
+                    .set_style(Style::default().fg(colour));
+            }
+        }
+    }
+}
+
+/// Animates `v` in the terminal, a step every `delay`, until `q` or Esc.
+/// Space pauses, and the last state stays up once it stops changing.
+pub fn animate(v: &mut dyn Visualize, delay: Duration) -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    let result = (|| {
+        let mut running = true;
+        loop {
+            terminal.draw(|f| f.render_widget(View(v), f.area()))?;
+            if event::poll(delay)? {
+                if let Event::Key(key) = event::read()? {
+                    if key.kind != KeyEventKind::Press {
+                        continue;
+                    }
+                    match key.code {
+                        KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                        KeyCode::Char(' ') => running = !running,
+                        _ => {},
+                    }
+                }
+            }
+            if running {
+                running = v.step();
+            }
+        }
+    })();
+    ratatui::restore();
+    result
+}
@@ -0,0 +1,184 @@
+//! The exports the JS shim calls. Strings cross the boundary as bytes in
+//! this module's memory: the shim copies input into a buffer from
+//! `aoc_alloc`, and reads results back through `aoc_output`, which holds
+//! the last answer, frame or error message.
+
+use std::cell::RefCell;
+use std::slice;
+use std::str;
+
+use aoc_core::AocError;
+
+use crate::visual::{visual, Visual};
+
+thread_local! {
+    static OUTPUT: RefCell<String> = const { RefCell::new(String::new()) };
+    static VISUALS: RefCell<Vec<Option<Box<dyn Visual>>>> = const { RefCell::new(Vec::new()) };
+}
+
+fn set_output(s: String) {
+    OUTPUT.with(|o| *o.borrow_mut() = s);
+}
+
+/// Keeps the answer, or the error for the shim to show, returning 0 or -1
+fn report(result: Result<String, AocError>) -> i32 {
+    match result {
+        Ok(s) => {
+            set_output(s);
+            0
+        },
+        Err(e) => {
+            set_output(e.to_string());
+            -1
+        },
+    }
+}
+
+/// # Safety
+///
+/// `ptr` must point to `len` readable bytes, such as a buffer from
+/// `aoc_alloc`
+unsafe fn input<'a>(ptr: *const u8, len: usize) -> Result<&'a str, AocError> {
+    let bytes = if len == 0 { &[] } else { slice::from_raw_parts(ptr, len) };
+    str::from_utf8(bytes).map_err(|e| AocError::invalid(format!("input isn't UTF-8: {}", e)))
+}
+
+/// A buffer of `len` bytes for the shim to copy input into
+#[no_mangle]
+pub extern "C" fn aoc_alloc(len: usize) -> *mut u8 {
+    let mut buf = Vec::<u8>::with_capacity(len);
+    let ptr = buf.as_mut_ptr();
+    std::mem::forget(buf);
+    ptr
+}
+
+/// # Safety
+///
+/// `ptr` and `len` must be exactly as given to and returned by `aoc_alloc`,
+/// and the buffer not freed already
+#[no_mangle]
+pub unsafe extern "C" fn aoc_free(ptr: *mut u8, len: usize) {
+    drop(Vec::from_raw_parts(ptr, 0, len));
+}
+
+/// Start of the last output, valid until the next call into this module
+#[no_mangle]
+pub extern "C" fn aoc_output() -> *const u8 {
+    OUTPUT.with(|o| o.borrow().as_ptr())
+}
+
+#[no_mangle]
+pub extern "C" fn aoc_output_len() -> usize {
+    OUTPUT.with(|o| o.borrow().len())
+}
+
+/// Solves one part of a day, leaving the answer in the output. Returns 0,
+/// or -1 with an error message in the output instead.
+///
+/// # Safety
+///
+/// The input at `ptr` must be `len` readable bytes
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(day: usize, part: usize, ptr: *const u8, len: usize) -> i32 {
+    report(input(ptr, len).and_then(|input| crate::solve(day, part, input)))
+}
+
+/// Starts a visualization of `day`, returning a handle for the other
+/// `aoc_visual_` calls, or -1 with an error message in the output
+///
+/// # Safety
+///
+/// The input at `ptr` must be `len` readable bytes
+#[no_mangle]
+pub unsafe extern "C" fn aoc_visual_new(day: usize, ptr: *const u8, len: usize) -> i32 {
+    match input(ptr, len).and_then(|input| visual(day, input)) {
+        Ok(v) => VISUALS.with(|vs| {
+            let mut vs = vs.borrow_mut();
+            vs.push(Some(v));
+            vs.len() as i32 - 1
+        }),
+        Err(e) => report(Err(e)),
+    }
+}
+
+fn with_visual<T>(handle: i32, f: impl FnOnce(&mut dyn Visual) -> T) -> Option<T> {
+    VISUALS.with(|vs| {
+        let mut vs = vs.borrow_mut();
+        let v = vs.get_mut(usize::try_from(handle).ok()?)?.as_mut()?;
+        Some(f(v.as_mut()))
+    })
+}
+
+/// Draws the visualization's current state into the output. Returns 0, or
+/// -1 for a bad handle.
+#[no_mangle]
+pub extern "C" fn aoc_visual_frame(handle: i32) -> i32 {
+    let frame = with_visual(handle, |v| v.frame())
+        .ok_or_else(|| AocError::invalid(format!("no visual {}", handle)));
+    report(frame)
+}
+
+/// Moves the visualization on a step. Returns 1 if it moved, 0 once it's
+/// finished, and -1 for a bad handle.
+#[no_mangle]
+pub extern "C" fn aoc_visual_step(handle: i32) -> i32 {
+    with_visual(handle, |v| v.step() as i32).unwrap_or(-1)
+}
+
+#[no_mangle]
+pub extern "C" fn aoc_visual_free(handle: i32) {
+    VISUALS.with(|vs| {
+        let mut vs = vs.borrow_mut();
+        if let Some(v) = usize::try_from(handle).ok().and_then(|i| vs.get_mut(i)) {
+            *v = None;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output() -> String {
+        let bytes = unsafe { slice::from_raw_parts(aoc_output(), aoc_output_len()) };
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    /// Calls `f` with `s` copied into a buffer from `aoc_alloc`, the way the
+    /// shim passes input
+    fn with_input<T>(s: &str, f: impl FnOnce(*const u8, usize) -> T) -> T {
+        let ptr = aoc_alloc(s.len());
+        unsafe {
+            std::ptr::copy_nonoverlapping(s.as_ptr(), ptr, s.len());
+            let result = f(ptr, s.len());
+            aoc_free(ptr, s.len());
+            result
+        }
+    }
+
+    #[test]
+    fn solve_through_output() {
+        let input = include_str!("../../2021/d13/input0");
+        assert_eq!(with_input(input, |p, n| unsafe { aoc_solve(13, 1, p, n) }), 0);
+        assert_eq!(output(), "17");
+
+        assert_eq!(with_input(input, |p, n| unsafe { aoc_solve(13, 3, p, n) }), -1);
+        assert_eq!(output(), "no part 3");
+    }
+
+    #[test]
+    fn visual_handles() {
+        let input = include_str!("../../2021/d13/input0");
+        let h = with_input(input, |p, n| unsafe { aoc_visual_new(13, p, n) });
+        assert!(h >= 0);
+        assert_eq!(aoc_visual_step(h), 1);
+        assert_eq!(aoc_visual_step(h), 1);
+        assert_eq!(aoc_visual_step(h), 0);
+        assert_eq!(aoc_visual_frame(h), 0);
+        assert_eq!(output().lines().count(), 5);
+
+        aoc_visual_free(h);
+        assert_eq!(aoc_visual_step(h), -1);
+        assert_eq!(aoc_visual_frame(h), -1);
+    }
+}
@@ -0,0 +1,78 @@
+//! Browser facade over the solvers. A `wasm32-unknown-unknown` build exports
+//! the functions in `ffi` for the JS shim in `www/`, which answers puzzles
+//! and animates the days that have something to look at:
+//!
+//! ```text
+//! cargo build -p aoc-wasm --release --target wasm32-unknown-unknown
+//! cp target/wasm32-unknown-unknown/release/aoc_wasm.wasm aoc-wasm/www/
+//! ```
+//!
+//! Nothing here touches files or the network, unlike the `aoc` runner, so
+//! none of its dependencies need to build for the web.
+
+use aoc_core::{parse_boxed, AocError, Solver};
+
+pub mod ffi;
+pub mod visual;
+
+pub use visual::{visual, Visual};
+
+type Parse = fn(&str) -> Result<Box<dyn Solver>, AocError>;
+
+/// Parser for each day, indexed by day - 1
+const DAYS: [Parse; 25] = [
+    parse_boxed::<d1::Puzzle>,
+    parse_boxed::<d2::Puzzle>,
+    parse_boxed::<d3::Puzzle>,
+    parse_boxed::<d4::Puzzle>,
+    parse_boxed::<d5::Puzzle>,
+    parse_boxed::<d6::Puzzle>,
+    parse_boxed::<d7::Puzzle>,
+    parse_boxed::<d8::Puzzle>,
+    parse_boxed::<d9::Puzzle>,
+    parse_boxed::<d10::Puzzle>,
+    parse_boxed::<d11::Puzzle>,
+    parse_boxed::<d12::Puzzle>,
+    parse_boxed::<d13::Puzzle>,
+    parse_boxed::<d14::Puzzle>,
+    parse_boxed::<d15::Puzzle>,
+    parse_boxed::<d16::Puzzle>,
+    parse_boxed::<d17::Puzzle>,
+    parse_boxed::<d18::Puzzle>,
+    parse_boxed::<d19::Puzzle>,
+    parse_boxed::<d20::Puzzle>,
+    parse_boxed::<d21::Puzzle>,
+    parse_boxed::<d22::Puzzle>,
+    parse_boxed::<d23::Puzzle>,
+    parse_boxed::<d24::Puzzle>,
+    parse_boxed::<d25::Puzzle>,
+];
+
+/// Answer to one part of one day's puzzle for the given input
+pub fn solve(day: usize, part: usize, input: &str) -> Result<String, AocError> {
+    if !(1..=2).contains(&part) {
+        return Err(AocError::invalid(format!("no part {}", part)));
+    }
+    let parse = day.checked_sub(1)
+        .and_then(|i| DAYS.get(i))
+        .ok_or_else(|| AocError::invalid(format!("no day {}", day)))?;
+    Ok(parse(input)?.part(part))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves_example() {
+        let input = include_str!("../../2021/d13/input0");
+        assert_eq!(solve(13, 1, input).unwrap(), "17");
+    }
+
+    #[test]
+    fn rejects_unknown_day_and_part() {
+        assert_eq!(solve(26, 1, "").unwrap_err().to_string(), "no day 26");
+        assert_eq!(solve(0, 1, "").unwrap_err().to_string(), "no day 0");
+        assert_eq!(solve(1, 3, "").unwrap_err().to_string(), "no part 3");
+    }
+}
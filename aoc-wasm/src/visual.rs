@@ -0,0 +1,130 @@
+//! Days whose state is worth watching, drawn one character per cell so the
+//! shim can paint each character as a coloured square
+
+use aoc_core::AocError;
+use d13::{Fold, Paper};
+use d20::{Enhancer, Image};
+use d25::Map;
+
+/// A puzzle state that can be drawn and then moved on a step at a time
+pub trait Visual {
+    /// Current state as lines of text, one character per cell
+    fn frame(&self) -> String;
+
+    /// Moves on a step, returning false once there's nothing left to show
+    fn step(&mut self) -> bool;
+}
+
+/// Day 13: the transparent paper, folded one instruction per step
+pub struct Origami {
+    paper: Paper,
+    folds: Vec<Fold>,
+    folded: usize,
+}
+
+impl Visual for Origami {
+    fn frame(&self) -> String {
+        self.paper.render()
+    }
+
+    fn step(&mut self) -> bool {
+        match self.folds.get(self.folded) {
+            Some(fold) => {
+                self.paper.fold(fold);
+                self.folded += 1;
+                true
+            },
+            None => false,
+        }
+    }
+}
+
+/// Day 20: the trench map image, enhanced once per step for part 2's rounds
+pub struct TrenchMap {
+    enhancer: Enhancer,
+    image: Image,
+    rounds: usize,
+}
+
+impl TrenchMap {
+    const ROUNDS: usize = 50;
+}
+
+impl Visual for TrenchMap {
+    fn frame(&self) -> String {
+        self.image.to_string()
+    }
+
+    fn step(&mut self) -> bool {
+        if self.rounds == Self::ROUNDS {
+            return false;
+        }
+        self.image.enhance(&self.enhancer);
+        self.rounds += 1;
+        true
+    }
+}
+
+/// Day 25: both herds of sea cucumbers, until neither can move
+pub struct Cucumbers {
+    map: Map,
+}
+
+impl Visual for Cucumbers {
+    fn frame(&self) -> String {
+        format!("{:?}", self.map)
+    }
+
+    fn step(&mut self) -> bool {
+        self.map.step() > 0
+    }
+}
+
+/// The visualization for `day`, starting from its input
+pub fn visual(day: usize, input: &str) -> Result<Box<dyn Visual>, AocError> {
+    Ok(match day {
+        13 => {
+            let (paper, folds) = d13::parse_input(input)?;
+            Box::new(Origami { paper, folds, folded: 0 })
+        },
+        20 => {
+            let (enhancer, mut image) = d20::read_input(&mut input.lines().map(String::from));
+            image.reserve_rounds(TrenchMap::ROUNDS);
+            Box::new(TrenchMap { enhancer, image, rounds: 0 })
+        },
+        25 => Box::new(Cucumbers { map: Map::try_from_lines(input.lines())? }),
+        _ => return Err(AocError::invalid(format!("day {} has nothing to show", day))),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn steps(v: &mut dyn Visual) -> usize {
+        let mut n = 0;
+        while v.step() {
+            n += 1;
+        }
+        n
+    }
+
+    #[test]
+    fn origami_folds_to_a_square() {
+        let mut v = visual(13, include_str!("../../2021/d13/input0")).unwrap();
+        assert_eq!(steps(v.as_mut()), 2);
+        assert_eq!(v.frame(), "#####\n#...#\n#...#\n#...#\n#####\n");
+    }
+
+    #[test]
+    fn cucumbers_stop_moving() {
+        let mut v = visual(25, include_str!("../../2021/d25/input_sample")).unwrap();
+        assert_eq!(steps(v.as_mut()), 57);
+        assert!(!v.step());
+    }
+
+    #[test]
+    fn other_days_have_no_visual() {
+        assert!(visual(1, "").is_err());
+    }
+}
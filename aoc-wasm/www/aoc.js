@@ -0,0 +1,98 @@
+// Thin wrapper over the exports in aoc-wasm/src/ffi.rs: strings go in
+// through a buffer from aoc_alloc and come back through aoc_output.
+
+const encoder = new TextEncoder();
+const decoder = new TextDecoder();
+
+export async function load(url = "aoc_wasm.wasm") {
+  const { instance } = await WebAssembly.instantiateStreaming(fetch(url));
+  return new Aoc(instance.exports);
+}
+
+export class Aoc {
+  constructor(exports) {
+    this.wasm = exports;
+  }
+
+  output() {
+    const { memory, aoc_output, aoc_output_len } = this.wasm;
+    return decoder.decode(new Uint8Array(memory.buffer, aoc_output(), aoc_output_len()));
+  }
+
+  // Calls f(ptr, len) with the input copied into wasm memory
+  withInput(input, f) {
+    const bytes = encoder.encode(input);
+    const ptr = this.wasm.aoc_alloc(bytes.length);
+    new Uint8Array(this.wasm.memory.buffer, ptr, bytes.length).set(bytes);
+    try {
+      return f(ptr, bytes.length);
+    } finally {
+      this.wasm.aoc_free(ptr, bytes.length);
+    }
+  }
+
+  check(status) {
+    if (status < 0) {
+      throw new Error(this.output());
+    }
+    return status;
+  }
+
+  solve(day, part, input) {
+    this.check(this.withInput(input, (p, n) => this.wasm.aoc_solve(day, part, p, n)));
+    return this.output();
+  }
+
+  visual(day, input) {
+    const handle = this.check(this.withInput(input, (p, n) => this.wasm.aoc_visual_new(day, p, n)));
+    return new Visual(this, handle);
+  }
+}
+
+export class Visual {
+  constructor(aoc, handle) {
+    this.aoc = aoc;
+    this.handle = handle;
+  }
+
+  frame() {
+    this.aoc.check(this.aoc.wasm.aoc_visual_frame(this.handle));
+    return this.aoc.output();
+  }
+
+  // True while the state is still changing
+  step() {
+    return this.aoc.check(this.aoc.wasm.aoc_visual_step(this.handle)) === 1;
+  }
+
+  free() {
+    this.aoc.wasm.aoc_visual_free(this.handle);
+  }
+}
+
+// Colour for each character the visuals draw; anything else is background
+const COLOURS = {
+  "#": "#ffff66",
+  ">": "#4fc3f7",
+  "v": "#81c784",
+};
+
+// Paints a frame onto the canvas, one square per character, scaled to fit
+export function draw(canvas, frame) {
+  const rows = frame.split("\n").filter(r => r.length > 0);
+  const width = Math.max(1, ...rows.map(r => r.length));
+  const cell = Math.max(1, Math.floor(Math.min(canvas.width / width, canvas.height / Math.max(1, rows.length))));
+
+  const ctx = canvas.getContext("2d");
+  ctx.fillStyle = "#0f0f23";
+  ctx.fillRect(0, 0, canvas.width, canvas.height);
+  rows.forEach((row, y) => {
+    for (let x = 0; x < row.length; x++) {
+      const colour = COLOURS[row[x]];
+      if (colour) {
+        ctx.fillStyle = colour;
+        ctx.fillRect(x * cell, y * cell, cell, cell);
+      }
+    }
+  });
+}
@@ -0,0 +1,50 @@
+//! Parse and solve timings for every day against the real inputs:
+//! `cargo bench -p aoc --bench days`, with `-- d19` to pick out one day.
+//! Days without an input file on disk are skipped.
+
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use aoc_core::Solver;
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+fn read_input(day: &str) -> Option<String> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let path = aoc_core::locate_input(root, 2021, day[1..].parse().unwrap())?;
+    fs::read_to_string(path).ok()
+}
+
+fn bench_day<S: Solver>(c: &mut Criterion, day: &str) {
+    let input = match read_input(day) {
+        Some(input) => input,
+        None => return eprintln!("{}: no input, skipping", day),
+    };
+
+    let mut group = c.benchmark_group(day);
+    group.sample_size(10).warm_up_time(Duration::from_millis(500));
+
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(&input))));
+    // each iteration solves a fresh parse, so nothing cached by an earlier
+    // part (or iteration) is counted
+    group.bench_function("part1", |b| {
+        b.iter_batched(|| S::parse(&input), |s| s.part1(), BatchSize::LargeInput)
+    });
+    group.bench_function("part2", |b| {
+        b.iter_batched(|| S::parse(&input), |s| s.part2(), BatchSize::LargeInput)
+    });
+    group.finish();
+}
+
+macro_rules! days {
+    ($($day:ident),*) => {
+        fn all_days(c: &mut Criterion) {
+            $(bench_day::<$day::Puzzle>(c, stringify!($day));)*
+        }
+    };
+}
+
+days!(d1, d2, d3, d4, d5, d6, d7, d8, d9, d10, d11, d12, d13, d14, d15, d16, d17, d18, d19, d20, d21, d22, d23, d24, d25);
+
+criterion_group!(benches, all_days);
+criterion_main!(benches);
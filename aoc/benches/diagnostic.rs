@@ -0,0 +1,79 @@
+//! d3's life-support search on a million-line report, against the way it
+//! used to go with every number a `Vec<char>` and the survivors filtered
+//! down a bit at a time. Heap use is printed first when counting allocations:
+//! `cargo bench -p aoc --bench diagnostic --features count-alloc`
+
+use std::time::Duration;
+
+use aoc_core::mem::{self, Tracker};
+use aoc_core::rng::Rng;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOC: mem::CountingAlloc = mem::CountingAlloc;
+
+const WIDTH: usize = 24;
+
+/// `lines` random `WIDTH`-bit numbers, one per line
+fn report(lines: usize) -> String {
+    let mut rng = Rng::seed_from(3);
+    let mut text = String::with_capacity(lines * (WIDTH + 1));
+    for _ in 0..lines {
+        text += &format!("{:0width$b}\n", rng.range(0..1 << WIDTH), width = WIDTH);
+    }
+    text
+}
+
+/// The search as it was, as the baseline
+fn chars_rating(input: &str, most_common: bool) -> u32 {
+    let values: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+    let mut f: Vec<&Vec<char>> = values.iter().collect();
+    for i in 0..WIDTH {
+        if f.len() == 1 {
+            break;
+        }
+        let ones = f.iter().filter(|v| v[i] == '1').count();
+        let zeros = f.len() - ones;
+        let pick = if (ones >= zeros) == most_common { '1' } else { '0' };
+        if f.iter().any(|v| v[i] == pick) {
+            f.retain(|v| v[i] == pick);
+        }
+    }
+    f[0].iter().fold(0, |a, &c| a << 1 | (c == '1') as u32)
+}
+
+fn life_support(c: &mut Criterion) {
+    let input = report(1_000_000);
+
+    let chars = Tracker::start();
+    let baseline = chars_rating(&input, true) * chars_rating(&input, false);
+    let chars = chars.finish();
+    let bits = Tracker::start();
+    let report = d3::read_report(input.as_bytes()).unwrap();
+    let rating = report.oxygen_rating() * report.co2_rating();
+    let bits = bits.finish();
+    assert_eq!(rating, baseline);
+    drop(report);
+    match (chars.peak_heap, bits.peak_heap) {
+        (Some(chars), Some(bits)) => eprintln!(
+            "peak heap with the {} input: chars {}, bits {}",
+            mem::human_bytes(input.len() as u64),
+            mem::human_bytes(chars as u64),
+            mem::human_bytes(bits as u64)
+        ),
+        _ => eprintln!("build with --features count-alloc to see heap use"),
+    }
+
+    let mut group = c.benchmark_group("d3 life support");
+    group.sample_size(10).warm_up_time(Duration::from_millis(500));
+    group.bench_function("chars", |b| b.iter(|| chars_rating(black_box(&input), true) * chars_rating(black_box(&input), false)));
+    group.bench_function("bits", |b| b.iter(|| {
+        let report = d3::read_report(black_box(input.as_bytes())).unwrap();
+        report.oxygen_rating() * report.co2_rating()
+    }));
+    group.finish();
+}
+
+criterion_group!(benches, life_support);
+criterion_main!(benches);
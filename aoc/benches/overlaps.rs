@@ -0,0 +1,51 @@
+//! d5's ways of counting overlaps side by side: the dense map and the
+//! hashed cells on the real input, then the hashed cells against the sweep
+//! on vents spread over millions of cells, too many for a dense map:
+//! `cargo bench -p aoc --bench overlaps`
+
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use aoc_core::rng::Rng;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// `vents` vents of up to 2000 cells each, anywhere on a floor `size`
+/// across
+fn sparse_vents(vents: usize, size: i64) -> Vec<d5::VentInput> {
+    let mut rng = Rng::seed_from(5);
+    let mut text = String::new();
+    let mut made = 0;
+    while made < vents {
+        let (x, y) = (rng.range(0..size), rng.range(0..size));
+        let len = rng.range(0..2000);
+        let (dx, dy) = *rng.choose(&[(1, 0), (0, 1), (1, 1), (1, -1)]).unwrap();
+        let (x2, y2) = (x + dx * len, y + dy * len);
+        if (0..size).contains(&x2) && (0..size).contains(&y2) {
+            text += &format!("{},{} -> {},{}\n", x, y, x2, y2);
+            made += 1;
+        }
+    }
+    d5::read_input(text.as_bytes()).unwrap().0
+}
+
+fn backends(c: &mut Criterion) {
+    let mut group = c.benchmark_group("d5 overlaps");
+    group.sample_size(10).warm_up_time(Duration::from_millis(500));
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    if let Some(input) = aoc_core::locate_input(root, 2021, 5).and_then(|p| fs::read_to_string(p).ok()) {
+        let (vents, x_dim, y_dim) = d5::read_input(input.as_bytes()).unwrap();
+        group.bench_function("input dense", |b| b.iter(|| d5::SeaFloor::from_lines(black_box(&vents), (x_dim, y_dim)).count_overlaps()));
+        group.bench_function("input hashed", |b| b.iter(|| d5::count_overlaps_hashed(black_box(&vents))));
+        group.bench_function("input sweep", |b| b.iter(|| d5::count_overlaps_sweep(black_box(&vents))));
+    }
+
+    let vents = sparse_vents(2000, 5_000_000);
+    group.bench_function("sparse hashed", |b| b.iter(|| d5::count_overlaps_hashed(black_box(&vents))));
+    group.bench_function("sparse sweep", |b| b.iter(|| d5::count_overlaps_sweep(black_box(&vents))));
+    group.finish();
+}
+
+criterion_group!(benches, backends);
+criterion_main!(benches);
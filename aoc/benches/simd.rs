@@ -0,0 +1,56 @@
+//! The inner loops the `simd` feature speeds up, on the real inputs. To see
+//! the difference, save a baseline without the feature and compare against
+//! it with:
+//!
+//! ```text
+//! cargo +nightly bench -p aoc --bench simd -- --save-baseline scalar
+//! cargo +nightly bench -p aoc --bench simd --features simd -- --baseline scalar
+//! ```
+
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn read_input(day: usize) -> Option<String> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    fs::read_to_string(aoc_core::locate_input(root, 2021, day)?).ok()
+}
+
+fn inner_loops(c: &mut Criterion) {
+    let mut group = c.benchmark_group("simd");
+    group.sample_size(20).warm_up_time(Duration::from_millis(500));
+
+    if let Some(input) = read_input(6) {
+        let population = d6::Population::parse_ages(&input);
+        group.bench_function("d6 tick_day", |b| b.iter(|| {
+            let mut p = population.clone();
+            for _ in 0..256 {
+                black_box(&mut p).tick_day();
+            }
+            p
+        }));
+    }
+
+    if let Some(input) = read_input(7) {
+        let positions = d7::parse_positions(&input).unwrap();
+        group.bench_function("d7 get_true_fuel_cost", |b| b.iter(|| d7::get_true_fuel_cost(black_box(&positions), 480)));
+        group.bench_function("d7 get_optimal_pos", |b| b.iter(|| d7::get_optimal_pos(black_box(&positions), d7::get_fuel_cost)));
+    }
+
+    if let Some(input) = read_input(20) {
+        let (enhancer, image) = d20::read_input(&mut input.lines().map(String::from));
+        group.bench_function("d20 enhance x10", |b| b.iter(|| {
+            let mut image = image.clone();
+            for _ in 0..10 {
+                image.enhance(&enhancer);
+            }
+            image.count_lit()
+        }));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, inner_loops);
+criterion_main!(benches);
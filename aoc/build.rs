@@ -0,0 +1,53 @@
+//! Gives the answer cache an identity for this build without reading the
+//! executable: a hash of every file in the workspace's crates, the
+//! top-level manifest, lock file and `answers.toml`, with the profile and
+//! features, as `AOC_BUILD_ID`.
+
+use std::collections::hash_map::DefaultHasher;
+use std::env;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// Loose files at the top of the workspace that go into a build or its
+/// answers; the rest there, like `aoc.toml` or saved output, don't
+const TOP_LEVEL: [&str; 3] = ["Cargo.toml", "Cargo.lock", "answers.toml"];
+
+/// Every file under `dir`, sources and the inputs and fixtures they
+/// `include_str!` alike, leaving out build output and hidden directories
+/// like `.git` and the cache itself. At the `top` only `TOP_LEVEL`'s.
+fn inputs(dir: &Path, top: bool, found: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if path.is_dir() {
+            if name != "target" && !name.starts_with('.') {
+                inputs(&path, false, found);
+            }
+        } else if !top || TOP_LEVEL.contains(&&*name) {
+            found.push(path);
+        }
+    }
+}
+
+fn main() {
+    let root = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).parent().unwrap().to_path_buf();
+    let mut files = vec!();
+    inputs(&root, true, &mut files);
+    files.sort();
+
+    let mut hasher = DefaultHasher::new();
+    for path in &files {
+        println!("cargo:rerun-if-changed={}", path.display());
+        path.strip_prefix(&root).unwrap().hash(&mut hasher);
+        fs::read(path).unwrap_or_default().hash(&mut hasher);
+    }
+    env::var("PROFILE").unwrap_or_default().hash(&mut hasher);
+    let mut features: Vec<String> = env::vars().map(|(k, _)| k).filter(|k| k.starts_with("CARGO_FEATURE_")).collect();
+    features.sort();
+    features.hash(&mut hasher);
+
+    println!("cargo:rustc-env=AOC_BUILD_ID={:016x}", hasher.finish());
+}
@@ -0,0 +1,219 @@
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc_core::mem::{self, Usage};
+
+use crate::cache::Cache;
+use crate::config::Config;
+use crate::{run_day, DayRun};
+
+/// How one day went when running the whole season
+pub enum Outcome {
+    Ran(DayRun),
+    NoInput,
+    Failed(String),
+}
+
+/// Every one of `year`'s days with an input under `root`, spread over
+/// `threads` workers,
+/// with each day's wall time and its options from `config`. Results come
+/// back in day order. Memory is measured for the whole process, so
+/// tracking it runs one day at a time, and skips the answers in `cache`.
+pub fn run_all(root: &Path, config: &Config, year: u32, days: usize, threads: usize, track_memory: bool, cache: Option<&Cache>) -> Vec<(usize, Outcome, Duration)> {
+    let threads = workers(threads, track_memory);
+    let next = AtomicUsize::new(1);
+    let results = Mutex::new(Vec::with_capacity(days));
+
+    thread::scope(|s| {
+        for _ in 0..threads {
+            s.spawn(|| loop {
+                let day = next.fetch_add(1, Ordering::Relaxed);
+                if day > days {
+                    break;
+                }
+                let t = Instant::now();
+                let outcome = run_one(root, config, year, day, track_memory, cache);
+                results.lock().unwrap().push((day, outcome, t.elapsed()));
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(day, ..)| *day);
+    results
+}
+
+/// Days `run_all` runs at once when asked for `threads`
+pub fn workers(threads: usize, track_memory: bool) -> usize {
+    if track_memory { 1 } else { threads.max(1) }
+}
+
+fn run_one(root: &Path, config: &Config, year: u32, day: usize, track_memory: bool, cache: Option<&Cache>) -> Outcome {
+    let input = match aoc_core::locate_input_in(root, config.input_dir(), year, day) {
+        Some(path) => match fs::read_to_string(&path) {
+            Ok(input) => input,
+            Err(e) => return Outcome::Failed(format!("{}: {}", path.display(), e)),
+        },
+        None => return Outcome::NoInput,
+    };
+
+    let options = config.options(year, day);
+    let key = cache.map(|c| c.key(day, &input, &options));
+    if let (Some(cache), Some(key), false) = (cache, key, track_memory) {
+        if let Some(answers) = cache.answers(year, day, key, 1..=2) {
+            let answers = answers.into_iter().map(|(part, a)| (part, a, Duration::ZERO)).collect();
+            return Outcome::Ran(DayRun { year, day, parse: Duration::ZERO, answers, memory: None, threads: None, cached: true, report: None });
+        }
+    }
+
+    // a panicking day shouldn't take the rest of the season down with it
+    match panic::catch_unwind(AssertUnwindSafe(|| run_day(year, day, 1..=2, &input, &options, track_memory, cache))) {
+        Ok(Ok(run)) => {
+            if let (Some(cache), Some(key)) = (cache, key) {
+                let answers: Vec<(usize, String)> = run.answers.iter().map(|(part, a, _)| (*part, a.clone())).collect();
+                if let Err(e) = cache.store(year, day, key, &answers) {
+                    log::warn!("caching day {}: {}", day, e);
+                }
+            }
+            Outcome::Ran(run)
+        },
+        Ok(Err(e)) => Outcome::Failed(e.to_string()),
+        Err(payload) => {
+            let msg = payload.downcast_ref::<String>().cloned()
+                .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+                .unwrap_or_default();
+            Outcome::Failed(format!("panicked: {}", msg))
+        },
+    }
+}
+
+/// Multi-line answers (drawn letters) don't fit in a table cell
+pub fn cell(answer: &str) -> String {
+    match answer.lines().count() {
+        0 | 1 => answer.to_string(),
+        n => format!("<{} line drawing>", n),
+    }
+}
+
+/// Peak heap if allocations were counted, or else peak RSS
+fn peak_memory(usage: &Usage) -> String {
+    match (usage.peak_heap, usage.peak_rss) {
+        (Some(heap), _) => mem::human_bytes(heap as u64),
+        (None, Some(rss)) => format!("{} RSS", mem::human_bytes(rss)),
+        (None, None) => "?".to_string(),
+    }
+}
+
+/// `day | part1 | part2 | time` table, with the total wall time and the
+/// threads used underneath, and a `peak memory` column if it was tracked.
+/// Days answered from the cache show that as their time.
+pub fn summary(results: &[(usize, Outcome, Duration)], total: Duration, workers: usize) -> String {
+    let with_memory = results.iter().any(|(_, o, _)| matches!(o, Outcome::Ran(DayRun { memory: Some(_), .. })));
+    let mut rows = vec![vec!["day".to_string(), "part1".to_string(), "part2".to_string(), "time".to_string()]];
+    if with_memory {
+        rows[0].push("peak memory".to_string());
+    }
+    for (day, outcome, elapsed) in results {
+        let (part1, part2, memory) = match outcome {
+            Outcome::Ran(run) => {
+                let answer = |part| run.answers.iter()
+                    .find(|(p, ..)| *p == part)
+                    .map_or(String::new(), |(_, a, _)| cell(a));
+                (answer(1), answer(2), run.memory.as_ref().map_or(String::new(), peak_memory))
+            },
+            Outcome::NoInput => ("(no input)".to_string(), String::new(), String::new()),
+            Outcome::Failed(e) => (format!("error: {}", e), String::new(), String::new()),
+        };
+        let time = match outcome {
+            Outcome::Ran(DayRun { cached: true, .. }) => "cached".to_string(),
+            _ => format!("{:.1?}", elapsed),
+        };
+        let mut row = vec![day.to_string(), part1, part2, time];
+        if with_memory {
+            row.push(memory);
+        }
+        rows.push(row);
+    }
+
+    let mut widths = vec![0; rows[0].len()];
+    for row in &rows {
+        for (w, c) in widths.iter_mut().zip(row) {
+            *w = (*w).max(c.chars().count());
+        }
+    }
+
+    let mut out = String::new();
+    for (i, row) in rows.iter().enumerate() {
+        let line: Vec<String> = row.iter().zip(&widths).map(|(c, &w)| format!("{:<w$}", c, w = w)).collect();
+        out += line.join(" | ").trim_end();
+        out.push('\n');
+        if i == 0 {
+            let rule: Vec<String> = widths.iter().map(|&w| "-".repeat(w)).collect();
+            out += &rule.join("-+-");
+            out.push('\n');
+        }
+    }
+    let ran = results.iter().filter(|(_, o, _)| matches!(o, Outcome::Ran(_))).count();
+    out += &format!("{} days in {:.1?} on {} thread{}", ran, total, workers, if workers == 1 { "" } else { "s" });
+    if let Some(pool) = aoc::parallel_threads() {
+        out += &format!(", with {} more shared by the parallel days", pool);
+    }
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarizes_days() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let results = run_all(root, &Config::default(), 2021, 2, 2, false, None);
+        assert_eq!(results.len(), 2);
+        assert!(matches!(results[0], (1, Outcome::Ran(_), _)));
+
+        let table = summary(&results, Duration::from_millis(5), 2);
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[0].starts_with("day | part1"));
+        assert!(lines[2].starts_with("1   | "));
+        assert!(lines.last().unwrap().starts_with("2 days in 5.0ms on 2 threads"));
+        assert_eq!(cell("#..\n.#.\n"), "<2 line drawing>");
+    }
+
+    #[test]
+    fn reruns_come_from_the_cache() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let cache_root = std::env::temp_dir().join(format!("aoc-all-cache-{}", std::process::id()));
+        let cache = Cache::new(&cache_root);
+        let first = run_all(root, &Config::default(), 2021, 2, 2, false, Some(&cache));
+        let second = run_all(root, &Config::default(), 2021, 2, 2, false, Some(&cache));
+        fs::remove_dir_all(&cache_root).unwrap();
+
+        for ((_, first, _), (_, second, _)) in first.iter().zip(&second) {
+            let (Outcome::Ran(first), Outcome::Ran(second)) = (first, second) else {
+                panic!("day didn't run");
+            };
+            assert!(!first.cached && second.cached);
+            let answers = |run: &DayRun| run.answers.iter().map(|(_, a, _)| a.clone()).collect::<Vec<_>>();
+            assert_eq!(answers(first), answers(second));
+        }
+        assert!(summary(&second, Duration::from_millis(5), 2).lines().nth(2).unwrap().ends_with("| cached"));
+    }
+
+    #[test]
+    fn summarizes_memory() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let results = run_all(root, &Config::default(), 2021, 1, 4, true, None);
+        let table = summary(&results, Duration::from_millis(5), workers(4, true));
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[0].ends_with("| peak memory"));
+        assert_eq!(peak_memory(&Usage { allocations: Some(3), peak_heap: Some(1536), peak_rss: Some(1 << 20) }), "1.5 KiB");
+        assert_eq!(peak_memory(&Usage { allocations: None, peak_heap: None, peak_rss: Some(1 << 20) }), "1.0 MiB RSS");
+    }
+}
@@ -0,0 +1,114 @@
+//! `.aoc-cache/` under the root: answers from earlier runs of `aoc all`, in
+//! a directory per year and keyed by a hash of the day's input, its options and the `aoc` build that
+//! worked them out, so days that haven't changed since are skipped. Days
+//! keep their own expensive intermediates in there too, through
+//! `Solver::use_cache`.
+
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+pub const DIR: &str = ".aoc-cache";
+
+pub struct Cache {
+    dir: PathBuf,
+    /// `AOC_BUILD_ID`
+    build: u64,
+}
+
+/// Hash of the workspace's sources, profile and features, from `build.rs`,
+/// so that rebuilding with a change to any solver makes every cached answer
+/// stale
+fn build_hash() -> u64 {
+    u64::from_str_radix(env!("AOC_BUILD_ID"), 16).unwrap()
+}
+
+impl Cache {
+    /// The cache under `root`, for answers from this build of `aoc`
+    pub fn new(root: &Path) -> Cache {
+        Cache { dir: root.join(DIR), build: build_hash() }
+    }
+
+    /// Where days keep their intermediates, created if need be
+    pub fn dir(&self) -> io::Result<&Path> {
+        fs::create_dir_all(&self.dir)?;
+        Ok(&self.dir)
+    }
+
+    /// What `day`'s answers from `input` with `options` are stored under
+    pub fn key(&self, day: usize, input: &str, options: &[(String, String)]) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        (self.build, day, input, options).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn answers_path(&self, year: u32, day: usize) -> PathBuf {
+        self.dir.join(year.to_string()).join(format!("day{}.json", day))
+    }
+
+    /// Stored answers to each of `parts`, or `None` unless all of them were
+    /// stored under `key`
+    pub fn answers(&self, year: u32, day: usize, key: u64, parts: RangeInclusive<usize>) -> Option<Vec<(usize, String)>> {
+        let stored: Value = serde_json::from_slice(&fs::read(self.answers_path(year, day)).ok()?).ok()?;
+        if stored["key"] != format!("{:016x}", key) {
+            return None;
+        }
+        parts
+            .map(|part| stored[format!("part{}", part)].as_str().map(|a| (part, a.to_string())))
+            .collect()
+    }
+
+    /// Stores `answers` under `key`, keeping any other parts already stored
+    /// under the same key
+    pub fn store(&self, year: u32, day: usize, key: u64, answers: &[(usize, String)]) -> io::Result<()> {
+        let path = self.answers_path(year, day);
+        let key = format!("{:016x}", key);
+        let mut stored = match fs::read(&path).ok().and_then(|b| serde_json::from_slice::<Value>(&b).ok()) {
+            Some(Value::Object(stored)) if stored.get("key") == Some(&Value::from(key.as_str())) => stored,
+            _ => serde_json::Map::from_iter([("key".to_string(), Value::from(key))]),
+        };
+        for (part, answer) in answers {
+            stored.insert(format!("part{}", part), answer.as_str().into());
+        }
+
+        fs::create_dir_all(path.parent().unwrap())?;
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, Value::from(stored).to_string())?;
+        fs::rename(tmp, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_come_back_under_their_key() {
+        let root = std::env::temp_dir().join(format!("aoc-cache-{}", std::process::id()));
+        let cache = Cache::new(&root);
+        let key = cache.key(1, "199\n200\n", &[]);
+        assert_ne!(key, cache.key(1, "199\n201\n", &[]));
+        assert_ne!(key, cache.key(1, "199\n200\n", &[("window".to_string(), "2".to_string())]));
+        assert_eq!(cache.answers(2021, 1, key, 1..=2), None);
+
+        cache.store(2021, 1, key, &[(1, "7".to_string())]).unwrap();
+        assert_eq!(cache.answers(2021, 1, key, 1..=1), Some(vec![(1, "7".to_string())]));
+        assert_eq!(cache.answers(2021, 1, key, 1..=2), None);
+        cache.store(2021, 1, key, &[(2, "#.\n.#\n".to_string())]).unwrap();
+        let both = cache.answers(2021, 1, key, 1..=2);
+        assert_eq!(cache.answers(2022, 1, key, 1..=2), None);
+
+        // a new key replaces everything stored under the old one
+        cache.store(2021, 1, key + 1, &[(2, "5".to_string())]).unwrap();
+        let replaced = (cache.answers(2021, 1, key, 2..=2), cache.answers(2021, 1, key + 1, 1..=2));
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(both, Some(vec![(1, "7".to_string()), (2, "#.\n.#\n".to_string())]));
+        assert_eq!(replaced, (None, None));
+    }
+}
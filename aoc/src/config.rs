@@ -0,0 +1,171 @@
+//! `aoc.toml` at the workspace root, for settings that would otherwise need
+//! passing on every run:
+//!
+//! ```toml
+//! session = "53616c7465645f5f..."
+//! input_dir = "inputs"
+//! format = "json"
+//!
+//! [day.6]
+//! part2_days = 300
+//!
+//! [day.20]
+//! part2_rounds = 100
+//!
+//! [day.22]
+//! algo = "signed-volume"
+//!
+//! [year.2021.day.7]
+//! algo = "analytic"
+//! ```
+//!
+//! `[day.N]` tables are for 2021's days, which had them before there were
+//! other years; `[year.Y.day.N]` tables only for years aoc has solvers for.
+//! Everything is optional, and command line flags win over the file.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use aoc_core::AocError;
+use serde::Deserialize;
+
+pub const FILE_NAME: &str = "aoc.toml";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Session cookie for `aoc fetch`
+    pub session: Option<String>,
+    /// Where fetched inputs go and are looked for, relative to the root
+    pub input_dir: Option<PathBuf>,
+    /// Output of `run` and `all` when `--format` isn't given
+    pub format: Option<Format>,
+    /// Solver options for each of 2021's days, passed to
+    /// `Solver::set_option`
+    day: DayOptions,
+    /// Solver options for the days of any year
+    year: BTreeMap<String, YearConfig>,
+}
+
+type DayOptions = BTreeMap<String, BTreeMap<String, toml::Value>>;
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct YearConfig {
+    day: DayOptions,
+}
+
+/// The first day in `options` that `year` doesn't have
+fn unknown_day(options: &DayOptions, days: &[aoc::Parse]) -> Option<String> {
+    options.keys().find(|d| !d.parse().is_ok_and(|d| (1..=days.len()).contains(&d))).cloned()
+}
+
+impl Config {
+    /// The config in `root`, or the defaults if there isn't one
+    pub fn load(root: &Path) -> Result<Config, AocError> {
+        let path = root.join(FILE_NAME);
+        match fs::read_to_string(&path) {
+            Ok(text) => Config::parse(&text).map_err(|e| AocError::invalid(format!("{}: {}", path.display(), e))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(AocError::invalid(format!("{}: {}", path.display(), e))),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Config, AocError> {
+        let config: Config = toml::from_str(text).map_err(|e| AocError::invalid(e.message()))?;
+        if let Some(day) = unknown_day(&config.day, &aoc::DAYS) {
+            return Err(AocError::invalid(format!("no day {} for [day.{}]", day, day)));
+        }
+        for (year, options) in &config.year {
+            let days = year.parse().ok().and_then(aoc::days)
+                .ok_or_else(|| AocError::invalid(format!("no year {} for [year.{}]", year, year)))?;
+            if let Some(day) = unknown_day(&options.day, days) {
+                return Err(AocError::invalid(format!("no day {} for [year.{}.day.{}]", day, year, day)));
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn input_dir(&self) -> &Path {
+        self.input_dir.as_deref().unwrap_or(Path::new(aoc_core::INPUT_DIR))
+    }
+
+    /// Options for `year`'s `day` as names and values, with values in the
+    /// text form `set_option` takes
+    pub fn options(&self, year: u32, day: usize) -> Vec<(String, String)> {
+        let day = day.to_string();
+        let options = self.year.get(&year.to_string())
+            .and_then(|y| y.day.get(&day))
+            .or_else(|| self.day.get(&day).filter(|_| year == 2021));
+        let Some(options) = options else {
+            return vec!();
+        };
+        options.iter()
+            .map(|(name, value)| {
+                let value = match value {
+                    toml::Value::String(s) => s.clone(),
+                    v => v.to_string(),
+                };
+                (name.clone(), value)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_settings_and_day_options() {
+        let config = Config::parse(concat!(
+            "session = \"abc\"\n",
+            "format = \"json\"\n",
+            "[day.6]\npart2_days = 300\n",
+            "[day.20]\npart1_rounds = \"4\"\n",
+            "[year.2021.day.7]\ncost = \"linear\"\n",
+        )).unwrap();
+        assert_eq!(config.session.as_deref(), Some("abc"));
+        assert_eq!(config.format, Some(Format::Json));
+        assert_eq!(config.input_dir(), Path::new("inputs"));
+        assert_eq!(config.options(2021, 6), [("part2_days".to_string(), "300".to_string())]);
+        assert_eq!(config.options(2021, 20), [("part1_rounds".to_string(), "4".to_string())]);
+        assert_eq!(config.options(2021, 7), [("cost".to_string(), "linear".to_string())]);
+        assert!(config.options(2021, 1).is_empty());
+        assert!(config.options(2022, 6).is_empty());
+
+        assert!(Config::parse("").unwrap().format.is_none());
+        assert!(Config::parse("format = \"yaml\"").is_err());
+        assert!(Config::parse("sesion = \"typo\"").is_err());
+        assert_eq!(Config::parse("[day.26]\nx = 1").unwrap_err().to_string(), "no day 26 for [day.26]");
+        assert_eq!(Config::parse("[year.2021.day.0]\nx = 1").unwrap_err().to_string(), "no day 0 for [year.2021.day.0]");
+        assert_eq!(Config::parse("[year.1999.day.1]\nx = 1").unwrap_err().to_string(), "no year 1999 for [year.1999]");
+    }
+
+    #[test]
+    fn documented_example_parses() {
+        let doc: String = include_str!("config.rs").lines()
+            .map_while(|l| l.strip_prefix("//!"))
+            .map(|l| format!("{}\n", l.strip_prefix(' ').unwrap_or(l)))
+            .collect();
+        let example = doc.split_once("```toml\n").unwrap().1.split_once("```").unwrap().0;
+        let config = Config::parse(example).unwrap();
+        assert_eq!(config.options(2021, 7), [("algo".to_string(), "analytic".to_string())]);
+    }
+
+    #[test]
+    fn missing_file_is_the_defaults() {
+        let config = Config::load(Path::new("no/such/dir")).unwrap();
+        assert!(config.session.is_none());
+        assert!(config.options(2021, 6).is_empty());
+    }
+}
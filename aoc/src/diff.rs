@@ -0,0 +1,194 @@
+//! `aoc diff`: two dumps from `aoc run` or `aoc all` with `--format json`,
+//! compared day by day for answers that changed and days that got slower.
+//! Handy for checking a refactor of shared code didn't break or slow down
+//! the days built on it.
+
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::all::cell;
+
+/// Slowdowns smaller than this are timer noise, whatever the percentage
+const NOISE_MS: f64 = 1.0;
+
+/// One dump's runs, from either a single `run` or a whole `all`
+pub fn load(path: &Path) -> Result<Vec<Value>, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    parse_dump(&text).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn parse_dump(text: &str) -> Result<Vec<Value>, String> {
+    let runs = match serde_json::from_str(text).map_err(|e| e.to_string())? {
+        Value::Array(runs) => runs,
+        run => vec![run],
+    };
+    if let Some(bad) = runs.iter().find(|r| r["day"].as_u64().is_none()) {
+        return Err(format!("not a day's run: {}", bad));
+    }
+    Ok(runs)
+}
+
+/// Year and day of a run, counting dumps from before `year` was recorded
+/// as 2021's
+fn key(run: &Value) -> (u64, u64) {
+    (run["year"].as_u64().unwrap_or(2021), run["day"].as_u64().unwrap_or_default())
+}
+
+/// Total time of a run, unless its answers came from the cache
+fn elapsed_ms(run: &Value) -> Option<f64> {
+    match run["cached"].as_bool() {
+        Some(true) => None,
+        _ => run["elapsed_ms"].as_f64(),
+    }
+}
+
+fn show(answer: &Value) -> String {
+    match answer {
+        Value::String(s) => cell(s),
+        Value::Null => "(none)".to_string(),
+        v => v.to_string(),
+    }
+}
+
+/// How one day differs between the two dumps
+#[derive(Debug, PartialEq)]
+pub struct DayDiff {
+    pub year: u64,
+    pub day: u64,
+    /// Parts whose answers differ, before then after
+    pub changed: Vec<(usize, String, String)>,
+    /// Time before and after, where both were timed
+    pub times: Option<(f64, f64)>,
+    /// Whether the day is only in one dump, the first or the second
+    pub only_in: Option<usize>,
+}
+
+impl DayDiff {
+    /// Slower by more than `threshold` percent, and by more than noise
+    pub fn regressed(&self, threshold: f64) -> bool {
+        self.times.is_some_and(|(before, after)| after - before > NOISE_MS && after > before * (1.0 + threshold / 100.0))
+    }
+}
+
+/// Every day in either dump, in order
+pub fn compare(before: &[Value], after: &[Value]) -> Vec<DayDiff> {
+    let mut keys: Vec<(u64, u64)> = before.iter().chain(after).map(key).collect();
+    keys.sort_unstable();
+    keys.dedup();
+
+    keys.into_iter()
+        .map(|(year, day)| {
+            let find = |runs: &[Value]| runs.iter().find(|r| key(r) == (year, day)).cloned();
+            let (a, b) = match (find(before), find(after)) {
+                (Some(a), Some(b)) => (a, b),
+                (a, _) => {
+                    let only_in = Some(if a.is_some() { 0 } else { 1 });
+                    return DayDiff { year, day, changed: vec!(), times: None, only_in };
+                },
+            };
+            let changed = (1..=2)
+                .map(|part| (part, &a[format!("part{}", part)], &b[format!("part{}", part)]))
+                .filter(|(_, x, y)| x != y)
+                .map(|(part, x, y)| (part, show(x), show(y)))
+                .collect();
+            let times = elapsed_ms(&a).zip(elapsed_ms(&b));
+            DayDiff { year, day, changed, times, only_in: None }
+        })
+        .collect()
+}
+
+/// A line per day, marking changed answers and slowdowns past `threshold`
+/// percent, then a count of each. `names` are what to call the two dumps.
+pub fn report(diffs: &[DayDiff], names: [&str; 2], threshold: f64) -> String {
+    let years = diffs.iter().map(|d| d.year).min() != diffs.iter().map(|d| d.year).max();
+    let mut out = String::new();
+    for d in diffs {
+        let label = if years { format!("{} day {}", d.year, d.day) } else { format!("day {}", d.day) };
+        if let Some(i) = d.only_in {
+            out += &format!("{}: only in {}\n", label, names[i]);
+            continue;
+        }
+
+        let mut notes = vec!();
+        if d.changed.is_empty() {
+            notes.push("same answers".to_string());
+        }
+        for (part, a, b) in &d.changed {
+            notes.push(format!("part{} {} -> {} MISMATCH", part, a, b));
+        }
+        notes.push(match d.times {
+            Some((a, b)) => {
+                let change = if a > 0.0 { format!(" ({:+.1}%)", (b - a) / a * 100.0) } else { String::new() };
+                let slower = if d.regressed(threshold) { " SLOWER" } else { "" };
+                format!("{:.3}ms -> {:.3}ms{}{}", a, b, change, slower)
+            },
+            None => "cached".to_string(),
+        });
+        out += &format!("{}: {}\n", label, notes.join(", "));
+    }
+
+    let mismatched = diffs.iter().filter(|d| !d.changed.is_empty()).count();
+    let slower = diffs.iter().filter(|d| d.regressed(threshold)).count();
+    out += &format!("{} with different answers, {} slower by more than {}%\n", days(mismatched), days(slower), threshold);
+    out
+}
+
+fn days(n: usize) -> String {
+    format!("{} day{}", n, if n == 1 { "" } else { "s" })
+}
+
+/// Whether the second dump has any changed answer or slowdown
+pub fn worse(diffs: &[DayDiff], threshold: f64) -> bool {
+    diffs.iter().any(|d| !d.changed.is_empty() || d.regressed(threshold))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marks_mismatches_and_slowdowns() {
+        let before = parse_dump(r#"[
+            {"year": 2021, "day": 1, "part1": 7, "part2": 5, "elapsed_ms": 2.0},
+            {"year": 2021, "day": 2, "part1": 150, "part2": 900, "elapsed_ms": 10.0},
+            {"year": 2021, "day": 3, "part1": 198, "part2": 230, "elapsed_ms": 0.2},
+            {"day": 4, "part1": 4512, "part2": 1924, "elapsed_ms": 30.0}
+        ]"#).unwrap();
+        let after = parse_dump(r##"[
+            {"year": 2021, "day": 1, "part1": 7, "part2": 6, "elapsed_ms": 1.0},
+            {"year": 2021, "day": 2, "part1": 150, "part2": 900, "elapsed_ms": 15.0},
+            {"year": 2021, "day": 3, "part1": 198, "part2": 230, "elapsed_ms": 0.9},
+            {"year": 2021, "day": 4, "part1": 4512, "part2": 1924, "elapsed_ms": 0.0, "cached": true},
+            {"year": 2021, "day": 13, "part1": 17, "part2": "#.#\n.#.\n", "elapsed_ms": 1.0}
+        ]"##).unwrap();
+
+        let diffs = compare(&before, &after);
+        assert_eq!(diffs.len(), 5);
+        assert_eq!(diffs[0].changed, [(2, "5".to_string(), "6".to_string())]);
+        assert!(!diffs[0].regressed(20.0));
+        assert!(diffs[1].regressed(20.0) && !diffs[1].regressed(60.0));
+        // over 300% slower, but by less than the noise
+        assert!(!diffs[2].regressed(20.0));
+        assert_eq!(diffs[3].times, None);
+        assert_eq!(diffs[4].only_in, Some(1));
+        assert!(worse(&diffs, 20.0));
+        assert!(!worse(&diffs[2..], 20.0));
+
+        let report = report(&diffs, ["a.json", "b.json"], 20.0);
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines[0], "day 1: part2 5 -> 6 MISMATCH, 2.000ms -> 1.000ms (-50.0%)");
+        assert_eq!(lines[1], "day 2: same answers, 10.000ms -> 15.000ms (+50.0%) SLOWER");
+        assert_eq!(lines[3], "day 4: same answers, cached");
+        assert_eq!(lines[4], "day 13: only in b.json");
+        assert_eq!(lines[5], "1 day with different answers, 1 day slower by more than 20%");
+    }
+
+    #[test]
+    fn single_runs_and_bad_dumps() {
+        assert_eq!(parse_dump(r#"{"year": 2021, "day": 5, "part1": 5}"#).unwrap().len(), 1);
+        assert!(parse_dump("[1, 2]").is_err());
+        assert!(parse_dump("day 5").is_err());
+    }
+}
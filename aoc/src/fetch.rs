@@ -0,0 +1,57 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+
+fn input_url(year: u32, day: usize) -> String {
+    format!("https://adventofcode.com/{}/day/{}/input", year, day)
+}
+
+/// Session cookie from `AOC_SESSION`, or failing that from `aoc.toml` or
+/// `~/.config/aoc/session`
+fn session_token(config: &Config) -> Result<String, String> {
+    if let Ok(token) = env::var("AOC_SESSION") {
+        return Ok(token.trim().to_string());
+    }
+    if let Some(token) = &config.session {
+        return Ok(token.trim().to_string());
+    }
+    let home = env::var("HOME").map_err(|_| "AOC_SESSION is not set".to_string())?;
+    let path = Path::new(&home).join(".config/aoc/session");
+    fs::read_to_string(&path)
+        .map(|token| token.trim().to_string())
+        .map_err(|e| format!("AOC_SESSION is not set and {}: {}", path.display(), e))
+}
+
+/// Downloads the input for `year`'s `day` into the configured input
+/// directory unless it's already there, returning the path it was saved to
+pub fn fetch(year: u32, day: usize, config: &Config) -> Result<PathBuf, String> {
+    let path = aoc_core::fetched_input(config.input_dir(), year, day);
+    if path.exists() {
+        return Ok(path);
+    }
+
+    let token = session_token(config)?;
+    let input = ureq::get(&input_url(year, day))
+        .set("Cookie", &format!("session={}", token))
+        .set("User-Agent", "github.com/ggazebo/aoc2021")
+        .call()
+        .map_err(|e| e.to_string())?
+        .into_string()
+        .map_err(|e| e.to_string())?;
+
+    fs::create_dir_all(path.parent().unwrap()).map_err(|e| e.to_string())?;
+    fs::write(&path, input).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_locations() {
+        assert_eq!(input_url(2021, 15), "https://adventofcode.com/2021/day/15/input");
+    }
+}
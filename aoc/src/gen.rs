@@ -0,0 +1,159 @@
+//! `aoc gen`: synthetic inputs that are valid for a day's puzzle but as big
+//! as asked for, to benchmark solvers well past the size of the real ones
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use aoc_core::rng::Rng;
+use aoc_core::AocError;
+use d19::{Pos, Rotation};
+
+/// Input for `day` at roughly `size`: the side of a d9 height map, the
+/// number of d22 reboot steps or the number of d19 scanners
+pub fn generate(day: usize, size: usize, rng: &mut Rng) -> Result<String, AocError> {
+    match day {
+        9 => Ok(height_map(size, rng)),
+        19 => Ok(scanners(size, rng)),
+        22 => Ok(reboot_steps(size, rng)),
+        _ => Err(AocError::invalid(format!("no generator for day {}", day))),
+    }
+}
+
+fn height_map(side: usize, rng: &mut Rng) -> String {
+    let mut out = String::with_capacity((side + 1) * side);
+    for _ in 0..side {
+        out.extend((0..side).map(|_| char::from(b'0' + rng.below(10) as u8)));
+        out.push('\n');
+    }
+    out
+}
+
+fn random_range(rng: &mut Rng, reach: i64, max_len: i64) -> (i64, i64) {
+    let start = rng.range(-reach..reach);
+    (start, (start + rng.range(0..max_len)).min(reach))
+}
+
+/// A fifth of the steps inside the initialization region and the rest spread
+/// over the whole reactor without touching it, like the real inputs
+fn reboot_steps(steps: usize, rng: &mut Rng) -> String {
+    let mut out = String::new();
+    for i in 0..steps {
+        let state = if i == 0 || rng.below(4) > 0 { "on" } else { "off" };
+        let ranges = if i < steps / 5 {
+            [(); 3].map(|_| random_range(rng, 50, 50))
+        } else {
+            loop {
+                let ranges = [(); 3].map(|_| random_range(rng, 100_000, 50_000));
+                if ranges.iter().any(|&(start, end)| end < -50 || start > 50) {
+                    break ranges;
+                }
+            }
+        };
+        let [x, y, z] = ranges.map(|(start, end)| format!("{}..{}", start, end));
+        writeln!(out, "{} x={},y={},z={}", state, x, y, z).unwrap();
+    }
+    out
+}
+
+/// How far a scanner sees along each axis
+const SCANNER_RANGE: i32 = 1000;
+
+fn random_pos(rng: &mut Rng, centre: Pos, reach: i32) -> Pos {
+    let mut coord = || rng.range(-reach as i64..reach as i64 + 1) as i32;
+    centre + Pos::new(coord(), coord(), coord())
+}
+
+/// 600 to 800 either way
+fn step(rng: &mut Rng) -> i32 {
+    let d = rng.range(600..801) as i32;
+    if rng.below(2) == 0 { d } else { -d }
+}
+
+/// Scanners each placed near an earlier one, sharing at least 12 beacons
+/// with it so the whole set can be pieced together, and each reporting
+/// every beacon in range in its own random orientation
+fn scanners(count: usize, rng: &mut Rng) -> String {
+    let mut centres = vec!(Pos::new(0, 0, 0));
+    let mut beacons = HashSet::new();
+    for i in 0..count.max(1) {
+        let centre = if i == 0 {
+            centres[0]
+        } else {
+            // 600 to 800 away on each axis leaves a shared region at least
+            // 1200 wide for the common beacons. Of a few candidates, take
+            // the one with the fewest scanners nearby, so they spread out
+            // rather than crowd together each seeing hundreds of beacons.
+            let crowding = |c: Pos| centres.iter().filter(|o| o.manhattan(c) < 3000).count();
+            let mut best: Option<(usize, Pos, Pos)> = None;
+            for _ in 0..20 {
+                let parent = centres[rng.below(centres.len() as u64) as usize];
+                let centre = parent + Pos::new(step(rng), step(rng), step(rng));
+                let crowd = crowding(centre);
+                if best.is_none_or(|(least, ..)| crowd < least) {
+                    best = Some((crowd, parent, centre));
+                }
+            }
+            let (_, parent, centre) = best.unwrap();
+            let shared = Pos::new((parent.x + centre.x) / 2, (parent.y + centre.y) / 2, (parent.z + centre.z) / 2);
+            let before = beacons.len();
+            while beacons.len() < before + 12 {
+                beacons.insert(random_pos(rng, shared, 200));
+            }
+            centres.push(centre);
+            centre
+        };
+        for _ in 0..14 {
+            beacons.insert(random_pos(rng, centre, SCANNER_RANGE));
+        }
+    }
+
+    let rotations: Vec<Rotation> = Rotation::all().collect();
+    let mut beacons: Vec<Pos> = beacons.into_iter().collect();
+    beacons.sort();
+    let mut out = String::new();
+    for (n, &centre) in centres.iter().enumerate() {
+        let rotation = *rng.choose(&rotations).unwrap();
+        let mut seen: Vec<Pos> = beacons.iter()
+            .map(|&b| b - centre)
+            .filter(|p| [p.x, p.y, p.z].iter().all(|c| c.abs() <= SCANNER_RANGE))
+            .map(|p| rotation.apply(p))
+            .collect();
+        rng.shuffle(&mut seen);
+        writeln!(out, "--- scanner {} ---", n).unwrap();
+        for p in seen {
+            writeln!(out, "{},{},{}", p.x, p.y, p.z).unwrap();
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::DAYS;
+
+    #[test]
+    fn generated_inputs_solve() {
+        let mut rng = Rng::seed_from(1278);
+        for (day, size) in [(9, 20), (22, 30), (19, 4)] {
+            let input = generate(day, size, &mut rng).unwrap();
+            let solver = DAYS[day - 1](&input).unwrap();
+            assert!(solver.part1().parse::<u64>().is_ok(), "day {}", day);
+        }
+        assert_eq!(generate(9, 3, &mut rng).unwrap().lines().count(), 3);
+        assert_eq!(generate(22, 7, &mut rng).unwrap().lines().count(), 7);
+        assert!(generate(1, 10, &mut rng).is_err());
+    }
+
+    #[test]
+    fn scanners_piece_together() {
+        let input = generate(19, 5, &mut Rng::seed_from(19)).unwrap();
+        let scans = d19::read_input(&mut input.lines().map(String::from));
+        assert_eq!(scans.len(), 5);
+        let (beacons, located) = d19::locate_scanners(&scans);
+        assert_eq!(located.len(), 4);
+        // every beacon generated is in range of the scanner it was made for
+        assert_eq!(beacons.len(), 5 * 14 + 4 * 12);
+    }
+}
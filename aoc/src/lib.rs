@@ -0,0 +1,160 @@
+//! Every day's solver behind one dispatch table per year, shared by the
+//! `aoc` runner and the regression tests
+
+use std::io::BufRead;
+
+use aoc_core::{parse_boxed, AocError, Solver, Strategies};
+
+pub type Parse = fn(&str) -> Result<Box<dyn Solver>, AocError>;
+
+/// A day's `Solver::strategies`, which can't be asked of a boxed solver
+pub type Describe = fn() -> Strategies;
+
+/// Answers worked out in one pass as the input is read, without holding it
+/// all, for the days that can: `None` for a part that needs the whole input
+pub type Stream = fn(&mut dyn BufRead) -> Result<[Option<String>; 2], AocError>;
+
+/// A season of puzzles, with the parser for each of its days
+pub struct Year {
+    pub year: u32,
+    /// Indexed by day - 1
+    pub days: &'static [Parse],
+    /// What each day's `algo` and `impl` options choose between, likewise
+    pub strategies: &'static [Describe],
+    /// Each day's streaming pass, for the days with one, likewise
+    pub streams: &'static [Option<Stream>],
+}
+
+/// Every year there are solvers for, oldest first. A year's day crates live
+/// in a directory named for it, like `2021/d5`.
+pub const YEARS: &[Year] = &[Year { year: 2021, days: &DAYS, strategies: &STRATEGIES, streams: &STREAMS }];
+
+fn find_year(year: u32) -> Option<&'static Year> {
+    YEARS.iter().find(|y| y.year == year)
+}
+
+/// The days of `year`, if there are solvers for it
+pub fn days(year: u32) -> Option<&'static [Parse]> {
+    find_year(year).map(|y| y.days)
+}
+
+/// What `day` of `year` lets `--algo` and `--impl` choose between, if there
+/// is such a day
+pub fn strategies(year: u32, day: usize) -> Option<Strategies> {
+    find_year(year)?.strategies.get(day.checked_sub(1)?).map(|describe| describe())
+}
+
+/// How `day` of `year` streams its input, if it can
+pub fn stream(year: u32, day: usize) -> Option<Stream> {
+    *find_year(year)?.streams.get(day.checked_sub(1)?)?
+}
+
+/// The year `aoc` works on unless told otherwise
+pub fn latest_year() -> u32 {
+    YEARS[YEARS.len() - 1].year
+}
+
+/// Sets each of `options` on `day`'s solver, checking any `algo` or `impl`
+/// against the day's strategies first, so a bad one is reported along with
+/// the choices there are
+pub fn set_options(solver: &mut dyn Solver, year: u32, day: usize, options: &[(String, String)]) -> Result<(), AocError> {
+    let strategies = strategies(year, day).unwrap_or_default();
+    for (name, value) in options {
+        if name == "algo" || name == "impl" {
+            strategies.check(name, value)?;
+        }
+        solver.set_option(name, value)?;
+    }
+    Ok(())
+}
+
+/// Parser for each of 2021's days, indexed by day - 1
+pub const DAYS: [Parse; 25] = [
+    parse_boxed::<d1::Puzzle>,
+    parse_boxed::<d2::Puzzle>,
+    parse_boxed::<d3::Puzzle>,
+    parse_boxed::<d4::Puzzle>,
+    parse_boxed::<d5::Puzzle>,
+    parse_boxed::<d6::Puzzle>,
+    parse_boxed::<d7::Puzzle>,
+    parse_boxed::<d8::Puzzle>,
+    parse_boxed::<d9::Puzzle>,
+    parse_boxed::<d10::Puzzle>,
+    parse_boxed::<d11::Puzzle>,
+    parse_boxed::<d12::Puzzle>,
+    parse_boxed::<d13::Puzzle>,
+    parse_boxed::<d14::Puzzle>,
+    parse_boxed::<d15::Puzzle>,
+    parse_boxed::<d16::Puzzle>,
+    parse_boxed::<d17::Puzzle>,
+    parse_boxed::<d18::Puzzle>,
+    parse_boxed::<d19::Puzzle>,
+    parse_boxed::<d20::Puzzle>,
+    parse_boxed::<d21::Puzzle>,
+    parse_boxed::<d22::Puzzle>,
+    parse_boxed::<d23::Puzzle>,
+    parse_boxed::<d24::Puzzle>,
+    parse_boxed::<d25::Puzzle>,
+];
+
+/// Strategies for each of 2021's days, indexed by day - 1
+pub const STRATEGIES: [Describe; 25] = [
+    <d1::Puzzle as Solver>::strategies,
+    <d2::Puzzle as Solver>::strategies,
+    <d3::Puzzle as Solver>::strategies,
+    <d4::Puzzle as Solver>::strategies,
+    <d5::Puzzle as Solver>::strategies,
+    <d6::Puzzle as Solver>::strategies,
+    <d7::Puzzle as Solver>::strategies,
+    <d8::Puzzle as Solver>::strategies,
+    <d9::Puzzle as Solver>::strategies,
+    <d10::Puzzle as Solver>::strategies,
+    <d11::Puzzle as Solver>::strategies,
+    <d12::Puzzle as Solver>::strategies,
+    <d13::Puzzle as Solver>::strategies,
+    <d14::Puzzle as Solver>::strategies,
+    <d15::Puzzle as Solver>::strategies,
+    <d16::Puzzle as Solver>::strategies,
+    <d17::Puzzle as Solver>::strategies,
+    <d18::Puzzle as Solver>::strategies,
+    <d19::Puzzle as Solver>::strategies,
+    <d20::Puzzle as Solver>::strategies,
+    <d21::Puzzle as Solver>::strategies,
+    <d22::Puzzle as Solver>::strategies,
+    <d23::Puzzle as Solver>::strategies,
+    <d24::Puzzle as Solver>::strategies,
+    <d25::Puzzle as Solver>::strategies,
+];
+
+/// Streaming passes for 2021's days that have them, indexed by day - 1
+pub const STREAMS: [Option<Stream>; 25] = [
+    Some(|r| d1::stream(r)),
+    Some(|r| d2::stream(r)),
+    Some(|r| d3::stream(r)),
+    None,
+    Some(|r| d5::stream(r)),
+    None, None, None, None, None,
+    None, None, None, None, None,
+    None, None, None, None, None,
+    None, None, None, None, None,
+];
+
+/// Threads in the pool the `parallel` days share, or `None` if aoc was
+/// built without the `parallel` feature
+pub fn parallel_threads() -> Option<usize> {
+    #[cfg(feature = "parallel")]
+    return Some(rayon::current_num_threads());
+    #[cfg(not(feature = "parallel"))]
+    None
+}
+
+/// Sizes the pool the `parallel` days share, if there is one. Only the
+/// first call, made before any day runs, has any effect.
+pub fn set_parallel_threads(threads: usize) {
+    #[cfg(feature = "parallel")]
+    {
+        let _ = rayon::ThreadPoolBuilder::new().num_threads(threads).build_global();
+    }
+    #[cfg(not(feature = "parallel"))]
+    let _ = threads;
+}
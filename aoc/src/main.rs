@@ -0,0 +1,553 @@
+use std::io::BufRead;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc_core::mem::{self, Tracker, Usage};
+use aoc_core::profile::Profiler;
+use aoc_core::rng::Rng;
+use aoc_core::{AocError, InputSource, Parts};
+use cache::Cache;
+use config::{Config, Format};
+
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOC: mem::CountingAlloc = mem::CountingAlloc;
+
+mod all;
+mod cache;
+mod config;
+mod diff;
+mod fetch;
+mod gen;
+mod repl;
+mod scaffold;
+mod watch;
+
+const USAGE: &str = "\
+usage: aoc run [--year <y>] --day <1-25> [--part <1|2|both>] [--input <path>] [--algo <name>] [--impl <name>] [--threads <n>] [--format text|json] [--stats] [--profile] [--verbose] [--viz] [--no-cache] [--stream]
+       aoc all [--year <y>] [--threads <n>] [--format text|json] [--stats] [--profile] [--no-cache]
+       aoc fetch [--year <y>] --day <1-25>
+       aoc watch [--year <y>] --day <1-25> [--input <path>]
+       aoc new-day [--year <y>] --day <n>
+       aoc repl [--year <y>] --day <1-25> [--input <path>] [--algo <name>] [--impl <name>]
+       aoc diff <a.json> <b.json> [--threshold <percent>]
+       aoc gen --day <9|19|22> --size <n> [--seed <n>]
+       aoc help [--year <y>] [--day <1-25>]
+";
+
+fn usage() -> ! {
+    eprint!("{}", USAGE);
+    std::process::exit(2);
+}
+
+/// The usage, then what `--algo` and `--impl` can pick between for `day`,
+/// or for every day of `year` that has a choice
+fn help(year: u32, day: Option<usize>) -> String {
+    let mut out = format!("{}\nstrategies for {}, each day's default first:\n", USAGE, year);
+    let days = match day {
+        Some(day) => day..=day,
+        None => 1..=aoc::days(year).map_or(0, |days| days.len()),
+    };
+    let mut any = false;
+    for day in days {
+        let strategies = aoc::strategies(year, day).unwrap_or_default();
+        for option in ["algo", "impl"] {
+            for s in strategies.of(option) {
+                out += &format!("  day {:<2} --{} {:<15} {}\n", day, option, s.name, s.about);
+                any = true;
+            }
+        }
+    }
+    match (any, day) {
+        (true, _) => {},
+        (false, Some(day)) => out += &format!("  none, day {} has only the one way\n", day),
+        (false, None) => out += "  none, every day has only the one way\n",
+    }
+    out
+}
+
+fn read_input(source: &InputSource) -> String {
+    source.read_to_string().unwrap_or_else(|e| panic!("{}: {}", source, e))
+}
+
+/// Answers for one day, with how long parsing and each part took, the
+/// memory it used if that was asked for, and the threads the parallel days
+/// had if aoc was built with them. Answers from the cache took no time.
+struct DayRun {
+    year: u32,
+    day: usize,
+    parse: Duration,
+    answers: Vec<(usize, String, Duration)>,
+    memory: Option<Usage>,
+    threads: Option<usize>,
+    cached: bool,
+    /// `Solver::report`, parsed
+    report: Option<serde_json::Value>,
+}
+
+/// `options` are names and values for `Solver::set_option`, set before
+/// any part runs, and the day may keep intermediates in `cache`. Parsing
+/// and each part run in spans of their own, inside one for the day.
+fn run_day(year: u32, day: usize, parts: RangeInclusive<usize>, input: &str, options: &[(String, String)], track_memory: bool, cache: Option<&Cache>) -> Result<DayRun, AocError> {
+    let parse = aoc::days(year)
+        .and_then(|days| days.get(day.checked_sub(1)?))
+        .ok_or_else(|| AocError::invalid(format!("no day {} in {}", day, year)))?;
+    let _span = tracing::info_span!("day", day).entered();
+    let tracker = track_memory.then(Tracker::start);
+    let t = Instant::now();
+    let parse_span = tracing::info_span!("parse").entered();
+    let mut solver = parse(input)?;
+    aoc::set_options(solver.as_mut(), year, day, options)?;
+    if let Some(cache) = cache {
+        solver.use_cache(cache.dir()?);
+    }
+    parse_span.exit();
+    let parse = t.elapsed();
+
+    let answers = parts
+        .map(|part| {
+            let _span = tracing::info_span!("part", part).entered();
+            let t = Instant::now();
+            let answer = solver.part(part);
+            (part, answer, t.elapsed())
+        })
+        .collect();
+    let report = solver.report()
+        .map(|r| serde_json::from_str(&r).map_err(|e| AocError::invalid(format!("day {} report: {}", day, e))))
+        .transpose()?;
+
+    let memory = tracker.map(|t| t.finish());
+    Ok(DayRun { year, day, parse, answers, memory, threads: aoc::parallel_threads(), cached: false, report })
+}
+
+/// Answers for one day from its streaming pass over `reader`, which reads
+/// and solves in one go and so is timed as the parse
+fn stream_day(year: u32, day: usize, parts: RangeInclusive<usize>, reader: &mut dyn BufRead, track_memory: bool) -> Result<DayRun, AocError> {
+    let stream = aoc::stream(year, day).ok_or(AocError::invalid("no streaming pass, run it without --stream"))?;
+    let _span = tracing::info_span!("day", day).entered();
+    let tracker = track_memory.then(Tracker::start);
+    let t = Instant::now();
+    let streamed = tracing::info_span!("stream").in_scope(|| stream(reader))?;
+    let parse = t.elapsed();
+
+    let answers = parts
+        .map(|part| match &streamed[part - 1] {
+            Some(answer) => Ok((part, answer.clone(), Duration::ZERO)),
+            None => Err(AocError::invalid(format!("part {} needs the whole input, run it without --stream", part))),
+        })
+        .collect::<Result<_, _>>()?;
+
+    let memory = tracker.map(|t| t.finish());
+    Ok(DayRun { year, day, parse, answers, memory, threads: aoc::parallel_threads(), cached: false, report: None })
+}
+
+fn print_text(run: &DayRun) {
+    println!("day {} parse ({:?})", run.day, run.parse);
+    for (part, answer, elapsed) in &run.answers {
+        if answer.contains('\n') {
+            println!("day {} part {} ({:?}):", run.day, part, elapsed);
+            print!("{}", answer);
+        } else {
+            println!("day {} part {}: {} ({:?})", run.day, part, answer, elapsed);
+        }
+    }
+    if let Some(usage) = &run.memory {
+        println!("day {} memory: {}", run.day, describe_usage(usage));
+    }
+    if let Some(threads) = run.threads {
+        println!("day {} threads: {}", run.day, threads);
+    }
+}
+
+fn describe_usage(usage: &Usage) -> String {
+    let mut parts = vec!();
+    if let Some(n) = usage.allocations {
+        parts.push(format!("{} allocations", n));
+    }
+    if let Some(bytes) = usage.peak_heap {
+        parts.push(format!("peak heap {}", mem::human_bytes(bytes as u64)));
+    }
+    if let Some(bytes) = usage.peak_rss {
+        parts.push(format!("peak RSS {}", mem::human_bytes(bytes)));
+    }
+    if parts.is_empty() {
+        "not available on this platform".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
+/// `{"year": Y, "day": N, "part1": ..., "part2": ..., "elapsed_ms": ...}`, with numeric
+/// answers as JSON numbers and the total time including parsing, plus
+/// `allocations`, `peak_heap_bytes` and `peak_rss_bytes` for whichever of
+/// those were measured, `threads` if aoc was built with `parallel`,
+/// `cached` if the answers came from the cache, and `report` for days with
+/// more to say than their answers when they weren't cached
+fn to_json(run: &DayRun) -> serde_json::Value {
+    let mut json = serde_json::Map::new();
+    json.insert("year".to_string(), run.year.into());
+    json.insert("day".to_string(), run.day.into());
+    let mut elapsed = run.parse;
+    for (part, answer, part_elapsed) in &run.answers {
+        let value = match answer.parse::<i64>() {
+            Ok(n) => n.into(),
+            Err(_) => answer.as_str().into(),
+        };
+        json.insert(format!("part{}", part), value);
+        elapsed += *part_elapsed;
+    }
+    json.insert("elapsed_ms".to_string(), (elapsed.as_secs_f64() * 1000.0).into());
+    if let Some(usage) = &run.memory {
+        let measured = [
+            ("allocations", usage.allocations),
+            ("peak_heap_bytes", usage.peak_heap.map(|b| b as u64)),
+            ("peak_rss_bytes", usage.peak_rss),
+        ];
+        for (key, value) in measured {
+            if let Some(value) = value {
+                json.insert(key.to_string(), value.into());
+            }
+        }
+    }
+    if let Some(threads) = run.threads {
+        json.insert("threads".to_string(), threads.into());
+    }
+    if run.cached {
+        json.insert("cached".to_string(), true.into());
+    }
+    if let Some(report) = &run.report {
+        json.insert("report".to_string(), report.clone());
+    }
+    json.into()
+}
+
+#[cfg(feature = "viz")]
+fn visualize(year: u32, day: usize, input: &str) {
+    // the visualizations are all of 2021's puzzles
+    let Some(mut v) = aoc_viz::for_day(day, input).filter(|_| year == 2021) else {
+        eprintln!("day {} has no visualization", day);
+        std::process::exit(1);
+    };
+    if let Err(e) = aoc_viz::animate(v.as_mut(), Duration::from_millis(50)) {
+        eprintln!("visualizing day {}: {}", day, e);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(not(feature = "viz"))]
+fn visualize(_year: u32, _day: usize, _input: &str) {
+    eprintln!("--viz needs aoc built with the viz feature");
+    std::process::exit(2);
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let command = match args.next() {
+        Some(c) if ["run", "all", "fetch", "watch", "new-day", "gen", "repl", "diff", "help"].contains(&c.as_str()) => c,
+        _ => usage(),
+    };
+
+    let mut year = None;
+    let mut day = None;
+    let mut parts = Parts::Both;
+    let mut input = None;
+    let mut json = None;
+    let mut track_memory = false;
+    let mut verbosity = 0;
+    let mut threads = None;
+    let mut viz = false;
+    let mut size = None;
+    let mut seed = 2021;
+    let mut use_cache = true;
+    let mut profile = false;
+    let mut dumps = vec!();
+    let mut threshold = 10.0;
+    let mut stream = false;
+    // `algo` and `impl` options for the one day being run
+    let mut choices = vec!();
+    let mut show_help = command == "help";
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => year = Some(args.next().and_then(|y| y.parse::<u32>().ok()).unwrap_or_else(|| usage())),
+            "--day" => day = args.next().and_then(|d| d.parse::<usize>().ok()),
+            "--part" => parts = args.next().and_then(|p| p.parse().ok()).unwrap_or_else(|| usage()),
+            "--input" => input = args.next(),
+            "--format" => json = match args.next().as_deref() {
+                Some("json") => Some(true),
+                Some("text") => Some(false),
+                _ => usage(),
+            },
+            "--stats" | "--mem" => track_memory = true,
+            "--verbose" | "-v" => verbosity += 1,
+            "--threads" => threads = Some(args.next().and_then(|n| n.parse::<usize>().ok()).unwrap_or_else(|| usage())),
+            "--viz" => viz = true,
+            "--size" => size = Some(args.next().and_then(|n| n.parse::<usize>().ok()).unwrap_or_else(|| usage())),
+            "--seed" => seed = args.next().and_then(|n| n.parse::<u64>().ok()).unwrap_or_else(|| usage()),
+            "--no-cache" => use_cache = false,
+            "--profile" => profile = true,
+            "--stream" => stream = true,
+            "--algo" | "--impl" => {
+                let value = args.next().unwrap_or_else(|| usage());
+                choices.push((arg[2..].to_string(), value));
+            },
+            "--help" | "-h" => show_help = true,
+            "--threshold" => threshold = args.next().and_then(|t| t.parse::<f64>().ok()).unwrap_or_else(|| usage()),
+            _ if command == "diff" && !arg.starts_with("--") => dumps.push(arg),
+            _ => panic!("unknown argument: {}", arg),
+        }
+    }
+
+    aoc_core::logging::init(verbosity);
+
+    if show_help {
+        print!("{}", help(year.unwrap_or_else(aoc::latest_year), day));
+        return;
+    }
+    if !choices.is_empty() && command != "run" && command != "repl" {
+        eprintln!("--algo and --impl pick how a single day is solved, with run or repl");
+        std::process::exit(2);
+    }
+    if stream && (command != "run" || !choices.is_empty() || viz) {
+        eprintln!("--stream runs a single day's streaming pass, with run and without --algo, --impl or --viz");
+        std::process::exit(2);
+    }
+
+    // `diff` only reads the two dumps, and fails if the second has answers
+    // that changed or days slower by more than --threshold percent
+    if command == "diff" {
+        let [a, b] = <[String; 2]>::try_from(dumps).unwrap_or_else(|_| usage());
+        let runs = [&a, &b].map(|path| diff::load(Path::new(path)).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }));
+        let diffs = diff::compare(&runs[0], &runs[1]);
+        print!("{}", diff::report(&diffs, [&a, &b], threshold));
+        if diff::worse(&diffs, threshold) {
+            std::process::exit(1);
+        }
+        return;
+    }
+    // --threads sizes the parallel days' pool too, as well as how many
+    // days `all` runs at once
+    if let Some(threads) = threads {
+        aoc::set_parallel_threads(threads);
+    }
+
+    let root = Path::new(".");
+    let config = Config::load(root).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(2);
+    });
+    let json = json.unwrap_or(config.format == Some(Format::Json));
+    // `all` takes answers from the cache for days that haven't changed, and
+    // any day can keep intermediates there
+    let cache = use_cache.then(|| Cache::new(root));
+    // the span timing tree goes to stderr once the answers are out
+    let profiler = profile.then(Profiler::install);
+
+    let year = year.unwrap_or_else(aoc::latest_year);
+
+    if command == "new-day" {
+        let day = day.filter(|&d| d > 0).unwrap_or_else(|| usage());
+        match scaffold::create(root, year, day) {
+            Ok(dir) => println!("{}", dir.display()),
+            Err(e) => {
+                eprintln!("new day {}: {}", day, e);
+                std::process::exit(1);
+            },
+        }
+        return;
+    }
+
+    if command == "fetch" {
+        let day = day.filter(|d| (1..=25).contains(d)).unwrap_or_else(|| usage());
+        match fetch::fetch(year, day, &config) {
+            Ok(path) => println!("{}", path.display()),
+            Err(e) => {
+                eprintln!("fetching day {}: {}", day, e);
+                std::process::exit(1);
+            },
+        }
+        return;
+    }
+
+    let Some(days) = aoc::days(year) else {
+        eprintln!("no solvers for {}", year);
+        std::process::exit(2);
+    };
+
+    if command == "all" {
+        let threads = threads.unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()));
+        let t = Instant::now();
+        let results = all::run_all(root, &config, year, days.len(), threads, track_memory, cache.as_ref());
+        if json {
+            let runs: Vec<serde_json::Value> = results.iter()
+                .filter_map(|(_, outcome, _)| match outcome {
+                    all::Outcome::Ran(run) => Some(to_json(run)),
+                    _ => None,
+                })
+                .collect();
+            println!("{}", serde_json::Value::from(runs));
+        } else {
+            print!("{}", all::summary(&results, t.elapsed(), all::workers(threads, track_memory)));
+        }
+        if let Some(profiler) = &profiler {
+            eprint!("{}", profiler.report());
+        }
+        if results.iter().any(|(_, outcome, _)| matches!(outcome, all::Outcome::Failed(_))) {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let day = match day {
+        Some(d) if (1..=days.len()).contains(&d) => d,
+        _ => usage(),
+    };
+
+    if command == "gen" {
+        let size = size.unwrap_or_else(|| usage());
+        if year != 2021 {
+            eprintln!("gen only makes inputs for 2021's puzzles");
+            std::process::exit(2);
+        }
+        match gen::generate(day, size, &mut Rng::seed_from(seed)) {
+            Ok(input) => print!("{}", input),
+            Err(e) => {
+                eprintln!("gen: {}", e);
+                std::process::exit(1);
+            },
+        }
+        return;
+    }
+
+    // without --input, use a fetched or checked-in input if there is one
+    let source = match input {
+        Some(path) => InputSource::path_or_stdin(Some(&path)),
+        None => InputSource::locate(root, config.input_dir(), year, day).unwrap_or(InputSource::Stdin),
+    };
+
+    if command == "watch" {
+        let input = source.path().unwrap_or_else(|| {
+            eprintln!("watch needs --input or an input it can find");
+            std::process::exit(2);
+        });
+        if let Err(e) = watch::watch(year, day, input) {
+            eprintln!("watching day {}: {}", day, e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // --stream hands the day a reader rather than the whole input
+    let run = if stream {
+        stream_day(year, day, parts.numbers(), &mut aoc_core::open_input(&source), track_memory)
+    } else {
+        let input = read_input(&source);
+        // --algo and --impl win over the config's options for the day
+        let mut options = config.options(year, day);
+        options.extend(choices);
+        if command == "repl" {
+            if let Err(e) = repl::run(year, day, input, options) {
+                eprintln!("day {}: {}", day, e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        if viz {
+            visualize(year, day, &input);
+            return;
+        }
+        run_day(year, day, parts.numbers(), &input, &options, track_memory, cache.as_ref())
+    };
+    let run = match run {
+        Ok(run) => run,
+        Err(e) => {
+            eprintln!("day {}: {}", day, e);
+            std::process::exit(1);
+        },
+    };
+    if json {
+        println!("{}", to_json(&run));
+    } else {
+        print_text(&run);
+    }
+    if let Some(profiler) = &profiler {
+        eprint!("{}", profiler.report());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dispatches_to_day_and_part() {
+        let d1 = aoc::DAYS[0](include_str!("../../2021/d1/test_input")).unwrap();
+        assert_eq!(d1.part1(), "7");
+        assert_eq!(d1.part2(), "5");
+        let d6 = aoc::days(2021).unwrap()[5](include_str!("../../2021/d6/test_input")).unwrap();
+        assert_eq!(d6.part(1), "5934");
+        assert_eq!(d6.part(2), "26984457539");
+    }
+
+    #[test]
+    fn json_report() {
+        let run = run_day(2021, 13, 1..=2, include_str!("../../2021/d13/input0"), &[], false, None).unwrap();
+        let json = to_json(&run);
+        assert_eq!(json["year"], 2021);
+        assert_eq!(json["day"], 13);
+        assert_eq!(json["part1"], 17);
+        assert!(json["part2"].as_str().unwrap().contains('#'));
+        assert!(json["elapsed_ms"].is_f64());
+        assert_eq!(json.get("threads").is_some(), cfg!(feature = "parallel"));
+
+        let run = run_day(2021, 1, 2..=2, include_str!("../../2021/d1/test_input"), &[], false, None).unwrap();
+        let json = to_json(&run);
+        assert_eq!(json["part2"], 5);
+        assert!(json.get("part1").is_none());
+        assert!(json.get("report").is_none());
+
+        let run = run_day(2021, 10, 1..=2, include_str!("../../2021/d10/test_input"), &[], false, None).unwrap();
+        let json = to_json(&run);
+        assert_eq!(json["report"]["syntax_error_score"], json["part1"]);
+        assert_eq!(json["report"]["per_line"].as_array().unwrap().len(), 10);
+        assert!(json.get("peak_rss_bytes").is_none());
+        assert!(run_day(2021, 26, 1..=2, "", &[], false, None).is_err());
+        assert!(run_day(1999, 1, 1..=2, "", &[], false, None).is_err());
+    }
+
+    #[test]
+    fn algos_are_checked_and_listed() {
+        let option = |name: &str, value: &str| [(name.to_string(), value.to_string())];
+        let input = include_str!("../../2021/d15/input_test");
+        let run = run_day(2021, 15, 1..=1, input, &option("algo", "dijkstra"), false, None).unwrap();
+        assert_eq!(run.answers[0].1, "40");
+        let err = run_day(2021, 15, 1..=1, input, &option("algo", "bfs"), false, None).err().unwrap();
+        assert_eq!(err.to_string(), "no algo \"bfs\", expected one of astar, dijkstra, corridor");
+        let err = run_day(2021, 1, 1..=1, "1\n", &option("impl", "simd"), false, None).err().unwrap();
+        assert_eq!(err.to_string(), "no --impl choices, only the one way");
+
+        let all = help(2021, None);
+        assert!(all.starts_with(USAGE));
+        assert!(all.contains("  day 23 --algo ida "));
+        assert!(all.contains("  day 22 --algo signed-volume "));
+        assert!(help(2021, Some(1)).ends_with("only the one way\n"));
+    }
+
+    #[test]
+    fn streams_days_that_can() {
+        let source = InputSource::from(include_str!("../../2021/d5/test_input"));
+        assert_eq!(read_input(&source).lines().count(), 10);
+        let run = stream_day(2021, 5, 1..=2, &mut aoc_core::open_input(&source), false).unwrap();
+        assert_eq!(run.answers.iter().map(|(_, a, _)| a.as_str()).collect::<Vec<_>>(), ["5", "12"]);
+
+        let sample = InputSource::from("00100\n11110\n10110\n");
+        assert_eq!(stream_day(2021, 3, 1..=1, &mut sample.open().unwrap(), false).unwrap().answers[0].1, "198");
+        let err = stream_day(2021, 3, 1..=2, &mut sample.open().unwrap(), false).err().unwrap();
+        assert_eq!(err.to_string(), "part 2 needs the whole input, run it without --stream");
+        assert!(stream_day(2021, 4, 1..=2, &mut InputSource::from("").open().unwrap(), false).is_err());
+    }
+}
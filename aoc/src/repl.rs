@@ -0,0 +1,301 @@
+//! `aoc repl`: a day's input parsed once and kept in memory, with commands
+//! that call into the day's library a step at a time, to look at the state
+//! part way through a solve
+
+use std::io::{self, BufRead, Write};
+
+use aoc::Parse;
+use aoc_core::AocError;
+
+/// What a day adds to the prompt, on top of the commands every day has
+trait Session {
+    /// Output of one of the day's commands, or `None` if it isn't one
+    fn run(&mut self, command: &str, args: &[&str]) -> Option<Result<String, String>>;
+
+    /// Usage and description of each of the day's commands, for `help`
+    fn commands(&self) -> &'static [(&'static str, &'static str)];
+}
+
+/// How many times to repeat a command, 1 if not given
+fn count(args: &[&str]) -> Result<usize, String> {
+    match args {
+        [] => Ok(1),
+        [n] => n.parse().map_err(|_| format!("not a count: {}", n)),
+        _ => Err("expected at most one count".to_string()),
+    }
+}
+
+struct D13 {
+    paper: d13::Paper,
+    folds: Vec<d13::Fold>,
+    /// Folds made so far
+    done: usize,
+}
+
+impl Session for D13 {
+    fn run(&mut self, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        Some(match command {
+            "fold" => count(args).and_then(|n| {
+                if self.done + n > self.folds.len() {
+                    return Err(format!("only {} folds left", self.folds.len() - self.done));
+                }
+                let lines: Vec<String> = self.folds[self.done..self.done + n].iter()
+                    .map(|fold| format!("{}: {} dots", fold, self.paper.fold(fold)))
+                    .collect();
+                self.done += n;
+                Ok(lines.join("\n"))
+            }),
+            "folds" => Ok(self.folds.iter().enumerate()
+                .map(|(i, fold)| format!("{} {}", if i == self.done { ">" } else { " " }, fold))
+                .collect::<Vec<_>>()
+                .join("\n")),
+            "dots" => Ok(self.paper.len().to_string()),
+            "show" => Ok(self.paper.render().trim_end().to_string()),
+            _ => return None,
+        })
+    }
+
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("fold [n]", "make the next n folds"),
+            ("folds", "list the folds, marking the next"),
+            ("dots", "count the dots"),
+            ("show", "draw the paper"),
+        ]
+    }
+}
+
+struct D16 {
+    packet: d16::Packet,
+}
+
+impl D16 {
+    /// The packet at a path of sub-packet indexes like `0.2.1`, or the
+    /// outermost one without a path
+    fn at(&self, args: &[&str]) -> Result<&d16::Packet, String> {
+        let path = match args {
+            [] => return Ok(&self.packet),
+            [path] => path,
+            _ => return Err("expected at most one path".to_string()),
+        };
+        path.split('.').try_fold(&self.packet, |packet, i| {
+            let i: usize = i.parse().map_err(|_| format!("not a sub-packet index: {}", i))?;
+            packet.sub_packets().get(i)
+                .ok_or_else(|| format!("{:?} has no sub-packet {}", packet.data(), i))
+        })
+    }
+}
+
+impl Session for D16 {
+    fn run(&mut self, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        Some(match command {
+            "show" => self.at(args).map(|packet| {
+                let mut lines = vec!(format!("{:?}", packet.data()));
+                lines.extend(packet.sub_packets().iter().enumerate().map(|(i, p)| format!("  {}: {:?}", i, p.data())));
+                lines.join("\n")
+            }),
+            "eval" => self.at(args).and_then(|packet| {
+                packet.evaluate(d16::Arithmetic::Checked).map(|v| v.to_string()).map_err(|e| e.to_string())
+            }),
+            "sum" => self.at(args).map(|packet| packet.version_sum().to_string()),
+            _ => return None,
+        })
+    }
+
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("show [path]", "a packet and its sub-packets, with paths like 0.2.1"),
+            ("eval [path]", "value of a packet"),
+            ("sum [path]", "sum of the versions inside a packet"),
+        ]
+    }
+}
+
+struct D25 {
+    map: d25::Map,
+    /// Steps taken so far
+    steps: usize,
+}
+
+impl Session for D25 {
+    fn run(&mut self, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        Some(match command {
+            "step" => count(args).map(|n| {
+                let mut moved = 0;
+                for _ in 0..n {
+                    moved = self.map.step();
+                    self.steps += 1;
+                    if moved == 0 {
+                        break;
+                    }
+                }
+                let stopped = if moved == 0 { ", herds have stopped" } else { "" };
+                format!("step {}: {} moved{}", self.steps, moved, stopped)
+            }),
+            "show" => Ok(self.map.to_ascii().trim_end().to_string()),
+            _ => return None,
+        })
+    }
+
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("step [n]", "move the herds n times, stopping early if they stop"),
+            ("show", "draw the map"),
+        ]
+    }
+}
+
+/// The day's commands with its input loaded, for the days that have any
+fn session(year: u32, day: usize, input: &str) -> Result<Option<Box<dyn Session>>, AocError> {
+    Ok(match (year, day) {
+        (2021, 13) => {
+            let (paper, folds) = d13::parse_input(input)?;
+            Some(Box::new(D13 { paper, folds, done: 0 }))
+        },
+        (2021, 16) => Some(Box::new(D16 { packet: d16::parse_packet(input)? })),
+        (2021, 25) => {
+            let map = d25::Map::try_from_lines(input.lines().map(str::trim_end)).map_err(AocError::invalid)?;
+            Some(Box::new(D25 { map, steps: 0 }))
+        },
+        _ => None,
+    })
+}
+
+const COMMANDS: &[(&str, &str)] = &[
+    ("part <1|2>", "solve a part from the input"),
+    ("reset", "back to the input as it was loaded"),
+    ("help", "list the commands"),
+    ("quit", "leave"),
+];
+
+pub struct Repl {
+    year: u32,
+    day: usize,
+    parse: Parse,
+    input: String,
+    /// Solver options for `part`
+    options: Vec<(String, String)>,
+    session: Option<Box<dyn Session>>,
+}
+
+impl Repl {
+    pub fn new(year: u32, day: usize, input: String, options: Vec<(String, String)>) -> Result<Repl, AocError> {
+        let parse = *aoc::days(year)
+            .and_then(|days| days.get(day.checked_sub(1)?))
+            .ok_or_else(|| AocError::invalid(format!("no day {} in {}", day, year)))?;
+        let mut repl = Repl { year, day, parse, input, options, session: None };
+        // set the solver up first, for its errors on bad input or options
+        repl.solver()?;
+        repl.session = session(year, day, &repl.input)?;
+        Ok(repl)
+    }
+
+    fn solver(&self) -> Result<Box<dyn aoc_core::Solver>, AocError> {
+        let mut solver = (self.parse)(&self.input)?;
+        aoc::set_options(solver.as_mut(), self.year, self.day, &self.options)?;
+        Ok(solver)
+    }
+
+    fn help(&self) -> String {
+        let day_commands = self.session.as_ref().map_or(&[][..], |s| s.commands());
+        let width = COMMANDS.iter().chain(day_commands).map(|(c, _)| c.len()).max().unwrap_or(0);
+        COMMANDS.iter().chain(day_commands)
+            .map(|(c, about)| format!("{:width$}  {}", c, about, width = width))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Output of a line typed at the prompt, or `None` to leave
+    pub fn eval(&mut self, line: &str) -> Option<Result<String, String>> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some((&command, args)) = words.split_first() else {
+            return Some(Ok(String::new()));
+        };
+        Some(match command {
+            "quit" | "exit" => return None,
+            "help" => Ok(self.help()),
+            "part" => match args {
+                [p @ ("1" | "2")] => self.solver()
+                    .map(|solver| solver.part(p.parse().unwrap()))
+                    .map_err(|e| e.to_string()),
+                _ => Err("expected part 1 or 2".to_string()),
+            },
+            "reset" => session(self.year, self.day, &self.input)
+                .map(|s| {
+                    self.session = s;
+                    "reset".to_string()
+                })
+                .map_err(|e| e.to_string()),
+            _ => self.session.as_mut()
+                .and_then(|s| s.run(command, args))
+                .unwrap_or_else(|| Err(format!("unknown command {}, try help", command))),
+        })
+    }
+}
+
+/// Reads commands from stdin until it ends or `quit`
+pub fn run(year: u32, day: usize, input: String, options: Vec<(String, String)>) -> Result<(), AocError> {
+    let mut repl = Repl::new(year, day, input, options)?;
+    if repl.session.is_none() {
+        eprintln!("day {} has no commands of its own, only part and reset", day);
+    }
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("d{}> ", day);
+        io::stdout().flush()?;
+        let Some(line) = lines.next() else {
+            println!();
+            return Ok(());
+        };
+        match repl.eval(&line?) {
+            None => return Ok(()),
+            Some(Ok(out)) if out.is_empty() => {},
+            Some(Ok(out)) => println!("{}", out),
+            Some(Err(e)) => eprintln!("error: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(repl: &mut Repl, line: &str) -> Result<String, String> {
+        repl.eval(line).unwrap()
+    }
+
+    #[test]
+    fn folds_one_at_a_time() {
+        let mut repl = Repl::new(2021, 13, include_str!("../../2021/d13/input0").to_string(), vec!()).unwrap();
+        assert_eq!(eval(&mut repl, "folds"), Ok("> fold along y=7\n  fold along x=5".to_string()));
+        assert_eq!(eval(&mut repl, "fold"), Ok("fold along y=7: 17 dots".to_string()));
+        assert_eq!(eval(&mut repl, "fold 2"), Err("only 1 folds left".to_string()));
+        assert_eq!(eval(&mut repl, "fold"), Ok("fold along x=5: 16 dots".to_string()));
+        assert_eq!(eval(&mut repl, "show").unwrap().lines().next(), Some("#####"));
+        assert_eq!(eval(&mut repl, "reset"), Ok("reset".to_string()));
+        assert_eq!(eval(&mut repl, "dots"), Ok("18".to_string()));
+        assert_eq!(eval(&mut repl, "part 1"), Ok("17".to_string()));
+        assert!(eval(&mut repl, "unfold").is_err());
+        assert!(repl.eval("quit").is_none());
+    }
+
+    #[test]
+    fn walks_into_sub_packets() {
+        let mut repl = Repl::new(2021, 16, "9C0141080250320F1802104A08".to_string(), vec!()).unwrap();
+        assert_eq!(eval(&mut repl, "eval"), Ok("1".to_string()));
+        assert_eq!(eval(&mut repl, "show").unwrap().lines().count(), 3);
+        assert_eq!(eval(&mut repl, "eval 0"), Ok("4".to_string()));
+        assert_eq!(eval(&mut repl, "eval 1.1"), Ok("2".to_string()));
+        assert_eq!(eval(&mut repl, "sum 1.1").map(|s| s.parse::<u32>().is_ok()), Ok(true));
+        assert!(eval(&mut repl, "eval 0.5").is_err());
+    }
+
+    #[test]
+    fn steps_the_herds() {
+        let mut repl = Repl::new(2021, 25, include_str!("../../2021/d25/input_sample").to_string(), vec!()).unwrap();
+        assert!(eval(&mut repl, "step").unwrap().starts_with("step 1: "));
+        assert_eq!(eval(&mut repl, "step 100"), Ok("step 58: 0 moved, herds have stopped".to_string()));
+        assert!(eval(&mut repl, "help").unwrap().contains("step [n]"));
+    }
+}
@@ -0,0 +1,152 @@
+//! `aoc new-day`: a fresh crate for a day under its year's directory, with
+//! the boilerplate every day shares, added to the workspace
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CARGO_TOML: &str = r#"[package]
+name = "{crate}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+aoc-core = { path = "../../aoc-core" }
+"#;
+
+const LIB_RS: &str = r#"use aoc_core::{AocError, Solver};
+
+pub struct Puzzle {
+    lines: Vec<String>,
+}
+
+impl Solver for Puzzle {
+    fn try_parse(input: &str) -> Result<Self, AocError> {
+        Ok(Puzzle { lines: input.lines().map(String::from).collect() })
+    }
+
+    fn part1(&self) -> String {
+        todo!("day {day} part 1 from {} lines", self.lines.len())
+    }
+
+    fn part2(&self) -> String {
+        todo!("day {day} part 2 from {} lines", self.lines.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test_input");
+
+    #[test]
+    #[ignore = "needs the example input and answer"]
+    fn example_part1() {
+        assert_eq!(Puzzle::try_parse(EXAMPLE).unwrap().part1(), "");
+    }
+
+    #[test]
+    #[ignore = "needs the example input and answer"]
+    fn example_part2() {
+        assert_eq!(Puzzle::try_parse(EXAMPLE).unwrap().part2(), "");
+    }
+}
+"#;
+
+const MAIN_RS: &str = r#"use aoc_core::Solver;
+use {lib}::Puzzle;
+
+fn main() {
+    let (source, _args) = aoc_core::input_arg();
+    let input = source.read_to_string().unwrap_or_else(|e| panic!("{}: {}", source, e));
+
+    let puzzle = aoc_core::or_exit(Puzzle::try_parse(&input));
+    println!("part 1: {}", puzzle.part1());
+    println!("part 2: {}", puzzle.part2());
+}
+"#;
+
+/// Package name of `year`'s crate for `day`. 2021's crates keep the plain
+/// `dN` names they had before there were other years; everyone else's say
+/// which year they're from, so they don't clash.
+pub fn crate_name(year: u32, day: usize) -> String {
+    match year {
+        2021 => format!("d{}", day),
+        _ => format!("y{}-d{}", year, day),
+    }
+}
+
+/// Files of the new crate, relative to its directory
+pub fn files(year: u32, day: usize) -> Vec<(&'static str, String)> {
+    let name = crate_name(year, day);
+    let fill = |template: &str| template
+        .replace("{crate}", &name)
+        .replace("{lib}", &name.replace('-', "_"))
+        .replace("{day}", &day.to_string());
+    vec!(
+        ("Cargo.toml", fill(CARGO_TOML)),
+        ("src/lib.rs", fill(LIB_RS)),
+        ("src/main.rs", fill(MAIN_RS)),
+        ("test_input", String::new()),
+    )
+}
+
+/// The workspace manifest with `member` added at the end of its members
+pub fn add_member(manifest: &str, member: &str) -> Result<String, String> {
+    let start = manifest.find("members = [").ok_or("no workspace members in Cargo.toml")?;
+    let end = start + manifest[start..].find("\n]").ok_or("unterminated members list in Cargo.toml")?;
+    let quoted = format!("\"{}\"", member);
+    if manifest[start..end].contains(&quoted) {
+        return Err(format!("{} is already a workspace member", member));
+    }
+    Ok(format!("{}\n    {},{}", &manifest[..end], quoted, &manifest[end..]))
+}
+
+/// Writes the crate for `year`'s `day` under `root` and adds it to the
+/// workspace, returning its directory
+pub fn create(root: &Path, year: u32, day: usize) -> Result<PathBuf, String> {
+    let member = aoc_core::day_dir(year, day).display().to_string();
+    let dir = root.join(&member);
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+
+    let manifest_path = root.join("Cargo.toml");
+    let manifest = fs::read_to_string(&manifest_path)
+        .map_err(|e| format!("{}: {}", manifest_path.display(), e))?;
+    let manifest = add_member(&manifest, &member)?;
+
+    for (file, contents) in files(year, day) {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| fs::write(&path, contents))
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+    fs::write(&manifest_path, manifest).map_err(|e| format!("{}: {}", manifest_path.display(), e))?;
+    Ok(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn member_goes_at_the_end() {
+        let manifest = "[workspace]\nmembers = [\n    \"aoc\",\n    \"2021/d1\", \"2021/d2\",\n]\n";
+        assert_eq!(add_member(manifest, "2022/d1").unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"2021/d1\", \"2021/d2\",\n    \"2022/d1\",\n]\n");
+        assert!(add_member(manifest, "2021/d2").is_err());
+        assert!(add_member("[package]\n", "2022/d1").is_err());
+    }
+
+    #[test]
+    fn templates_are_filled_in() {
+        let files = files(2022, 3);
+        let (_, main) = files.iter().find(|(f, _)| *f == "src/main.rs").unwrap();
+        assert!(main.contains("use y2022_d3::Puzzle;"));
+        let (_, manifest) = files.iter().find(|(f, _)| *f == "Cargo.toml").unwrap();
+        assert!(manifest.contains("name = \"y2022-d3\""));
+        assert!(files.iter().all(|(_, text)| !text.contains("{crate}") && !text.contains("{lib}") && !text.contains("{day}")));
+        assert_eq!(crate_name(2021, 26), "d26");
+    }
+}
@@ -0,0 +1,109 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use notify::{RecursiveMode, Watcher};
+use serde_json::Value;
+
+/// Workspace checkout this runner was built from
+fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
+/// Builds and runs the day through `aoc run --format json`, so edits to the
+/// solver are picked up. Build and runtime failures come back as the error.
+fn run_once(year: u32, day: usize, input: &Path) -> Result<Value, String> {
+    let output = Command::new(env!("CARGO"))
+        .current_dir(workspace_root())
+        .args(["run", "--release", "--quiet", "-p", "aoc", "--", "run", "--format", "json"])
+        .args(["--year", &year.to_string(), "--day", &day.to_string()])
+        .arg("--input").arg(input)
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())
+}
+
+fn show_answer(answer: &Value) -> String {
+    match answer {
+        Value::String(s) if s.contains('\n') => format!("\n{}", s.trim_end()),
+        Value::String(s) => s.clone(),
+        _ => answer.to_string(),
+    }
+}
+
+/// One line per part, noting answers that changed since `prev`, then the
+/// timing and how it moved
+fn diff(prev: Option<&Value>, next: &Value) -> Vec<String> {
+    let mut lines = vec!();
+    for part in ["part1", "part2"] {
+        let answer = &next[part];
+        if answer.is_null() {
+            continue;
+        }
+        let line = match prev.map(|p| &p[part]) {
+            Some(old) if old == answer => format!("{}: {}", part, show_answer(answer)),
+            Some(old) if !old.is_null() => format!("{}: {} -> {}", part, show_answer(old), show_answer(answer)),
+            _ => format!("{}: {} (new)", part, show_answer(answer)),
+        };
+        lines.push(line);
+    }
+
+    let elapsed = next["elapsed_ms"].as_f64().unwrap_or_default();
+    lines.push(match prev.and_then(|p| p["elapsed_ms"].as_f64()) {
+        Some(old) => format!("{:.3}ms ({:+.3}ms)", elapsed, elapsed - old),
+        None => format!("{:.3}ms", elapsed),
+    });
+    lines
+}
+
+/// Re-runs `year`'s `day` whenever its sources or the input change, until
+/// killed
+pub fn watch(year: u32, day: usize, input: PathBuf) -> Result<(), String> {
+    let input = input.canonicalize().map_err(|e| format!("{}: {}", input.display(), e))?;
+    let root = workspace_root();
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).map_err(|e| e.to_string())?;
+    for path in [root.join(aoc_core::day_dir(year, day)).join("src"), root.join("aoc-core/src")] {
+        watcher.watch(&path, RecursiveMode::Recursive).map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+    watcher.watch(&input, RecursiveMode::NonRecursive).map_err(|e| e.to_string())?;
+
+    let mut prev = None;
+    loop {
+        println!("-- day {}", day);
+        match run_once(year, day, &input) {
+            Ok(next) => {
+                for line in diff(prev.as_ref(), &next) {
+                    println!("{}", line);
+                }
+                prev = Some(next);
+            },
+            Err(e) => print!("{}", e),
+        }
+
+        // wait for a change, then let the burst of events from one save settle
+        rx.recv().map_err(|e| e.to_string())?.map_err(|e| e.to_string())?;
+        while rx.recv_timeout(Duration::from_millis(200)).is_ok() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diff_against_previous_run() {
+        let first = json!({"day": 1, "part1": 7, "part2": 5, "elapsed_ms": 2.0});
+        assert_eq!(diff(None, &first), ["part1: 7 (new)", "part2: 5 (new)", "2.000ms"]);
+
+        let second = json!({"day": 1, "part1": 7, "part2": 6, "elapsed_ms": 1.5});
+        assert_eq!(diff(Some(&first), &second), ["part1: 7", "part2: 5 -> 6", "1.500ms (-0.500ms)"]);
+    }
+}
@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use d16::{Arithmetic, IntoBitsReader, Packet};
+
+// the raw bits of a transmission, as the hex decodes to
+fuzz_target!(|data: &[u8]| {
+    if let Some(packet) = Packet::from_bits(&mut data.read_bits()) {
+        let _ = packet.version_sum();
+        let _ = packet.evaluate(Arithmetic::Checked);
+    }
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use d18::SnailfishNumber;
+
+fuzz_target!(|s: &str| {
+    if let Ok(n) = SnailfishNumber::try_from(s) {
+        let _ = n.to_string();
+        let _ = n.magnitude();
+    }
+});
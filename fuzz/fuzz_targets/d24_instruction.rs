@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use d24::Instruction;
+
+fuzz_target!(|s: &str| {
+    let _ = Instruction::try_from(s);
+});
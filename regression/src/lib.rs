@@ -0,0 +1,4 @@
+//! Regression tests only: `tests/answers.rs` checks every day against the
+//! expected answers in the workspace `answers.toml`, and with the
+//! `differential` feature `tests/differential.rs` checks that the days with
+//! more than one algorithm get the same answers from each.
@@ -0,0 +1,53 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Expected {
+    part1: String,
+    part2: String,
+    /// Too slow to run in a debug build; checked with `cargo test --release`
+    #[serde(default)]
+    slow: bool,
+}
+
+fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
+fn manifest() -> BTreeMap<String, Expected> {
+    let text = fs::read_to_string(workspace_root().join("answers.toml")).unwrap();
+    toml::from_str(&text).unwrap()
+}
+
+#[test]
+fn every_day_matches_answers() {
+    let mut failures = vec!();
+    for (name, expected) in manifest() {
+        let day: usize = name.strip_prefix('d').and_then(|d| d.parse().ok())
+            .unwrap_or_else(|| panic!("bad day in answers.toml: {}", name));
+
+        if expected.slow && cfg!(debug_assertions) {
+            eprintln!("{}: slow, skipped in debug builds", name);
+            continue;
+        }
+        let input = match aoc_core::locate_input(workspace_root(), 2021, day) {
+            Some(path) => fs::read_to_string(path).unwrap(),
+            None => {
+                failures.push(format!("{}: has answers but no input", name));
+                continue;
+            },
+        };
+
+        let solver = aoc::DAYS[day - 1](&input).unwrap_or_else(|e| panic!("{}: {}", name, e));
+        for (part, want) in [(1, &expected.part1), (2, &expected.part2)] {
+            let got = solver.part(part);
+            if &got != want {
+                failures.push(format!("{} part {}: expected {:?}, got {:?}", name, part, want, got));
+            }
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
@@ -0,0 +1,114 @@
+//! Every algorithm of a day run on the same small random inputs, which they
+//! should all get the same answers for. The slow simple algorithms check
+//! the fast clever ones on far more cases than the examples give.
+#![cfg(feature = "differential")]
+
+use std::fmt::Write;
+
+use aoc_core::rng::Rng;
+
+/// Random inputs tried for each day
+const ROUNDS: usize = 40;
+
+/// A day whose `algo` choices are checked against each other
+struct Differential {
+    day: usize,
+    /// A random input small enough for the slowest algorithm
+    input: fn(&mut Rng) -> String,
+    /// Set along with each `algo`, to keep the slowest one quick
+    options: &'static [(&'static str, &'static str)],
+}
+
+const DAYS: &[Differential] = &[
+    Differential { day: 7, input: crab_positions, options: &[] },
+    Differential { day: 14, input: polymer_rules, options: &[("part1_steps", "4"), ("part2_steps", "9")] },
+    Differential { day: 15, input: risk_levels, options: &[] },
+    Differential { day: 22, input: reboot_steps, options: &[] },
+];
+
+fn crab_positions(rng: &mut Rng) -> String {
+    let count = rng.range(1..20);
+    let positions: Vec<String> = (0..count).map(|_| rng.range(0..50).to_string()).collect();
+    positions.join(",")
+}
+
+/// A template of up to 8 elements out of 4, with a rule for most pairs
+fn polymer_rules(rng: &mut Rng) -> String {
+    const ELEMENTS: &[u8] = b"BCHN";
+    let element = |rng: &mut Rng| ELEMENTS[rng.below(ELEMENTS.len() as u64) as usize] as char;
+    let mut out: String = (0..rng.range(2..9)).map(|_| element(rng)).collect();
+    out.push_str("\n\n");
+    for &a in ELEMENTS {
+        for &b in ELEMENTS {
+            if rng.below(4) > 0 {
+                writeln!(out, "{}{} -> {}", a as char, b as char, element(rng)).unwrap();
+            }
+        }
+    }
+    out
+}
+
+fn risk_levels(rng: &mut Rng) -> String {
+    let side = rng.range(2..10);
+    let mut out = String::new();
+    for _ in 0..side {
+        out.extend((0..side).map(|_| char::from(b'1' + rng.below(9) as u8)));
+        out.push('\n');
+    }
+    out
+}
+
+/// Overlapping steps, each either inside the initialization region or
+/// wholly outside it like the real inputs
+fn reboot_steps(rng: &mut Rng) -> String {
+    let mut out = String::new();
+    for i in 0..rng.range(1..10) {
+        let state = if i == 0 || rng.below(3) > 0 { "on" } else { "off" };
+        // moving one axis past the region is enough to leave it
+        let outside = match rng.below(3) {
+            0 => Some(rng.below(3) as usize),
+            _ => None,
+        };
+        let [x, y, z] = [0, 1, 2].map(|axis| {
+            let start = rng.range(-50..40);
+            let end = (start + rng.range(0..20)).min(50);
+            let shift = if outside == Some(axis) { 101 } else { 0 };
+            format!("{}..{}", start + shift, end + shift)
+        });
+        writeln!(out, "{} x={},y={},z={}", state, x, y, z).unwrap();
+    }
+    out
+}
+
+/// Each part's answer from `day` with `algo`
+fn answers(day: usize, input: &str, algo: &str, options: &[(&str, &str)]) -> [String; 2] {
+    let mut solver = aoc::DAYS[day - 1](input).unwrap_or_else(|e| panic!("d{}: {}\n{}", day, e, input));
+    let options: Vec<(String, String)> = options.iter().chain([&("algo", algo)])
+        .map(|&(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    aoc::set_options(solver.as_mut(), 2021, day, &options).unwrap();
+    [solver.part1(), solver.part2()]
+}
+
+#[test]
+fn algorithms_agree() {
+    let mut failures = vec!();
+    for d in DAYS {
+        let algos = aoc::strategies(2021, d.day).unwrap().algos;
+        assert!(algos.len() > 1, "d{} has only the one algorithm", d.day);
+
+        let mut rng = Rng::seed_from(d.day as u64);
+        for _ in 0..ROUNDS {
+            let input = (d.input)(&mut rng);
+            let expected = answers(d.day, &input, algos[0].name, d.options);
+            for algo in &algos[1..] {
+                let got = answers(d.day, &input, algo.name, d.options);
+                if got != expected {
+                    failures.push(format!("d{} {} got {:?}, {} got {:?}, for\n{}",
+                        d.day, algos[0].name, expected, algo.name, got, input));
+                }
+            }
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}